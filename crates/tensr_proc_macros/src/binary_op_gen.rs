@@ -45,6 +45,12 @@ pub enum BinaryOperation {
     Sub,
     Mul,
     Div,
+    Rem,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
 }
 
 impl BinaryOperation {
@@ -56,6 +62,12 @@ impl BinaryOperation {
             BinaryOperation::Sub => quote::quote! { Sub },
             BinaryOperation::Mul => quote::quote! { Mul },
             BinaryOperation::Div => quote::quote! { Div },
+            BinaryOperation::Rem => quote::quote! { Rem },
+            BinaryOperation::BitAnd => quote::quote! { BitAnd },
+            BinaryOperation::BitOr => quote::quote! { BitOr },
+            BinaryOperation::BitXor => quote::quote! { BitXor },
+            BinaryOperation::Shl => quote::quote! { Shl },
+            BinaryOperation::Shr => quote::quote! { Shr },
         }
     }
 
@@ -67,6 +79,12 @@ impl BinaryOperation {
             BinaryOperation::Sub => quote::quote! { sub },
             BinaryOperation::Mul => quote::quote! { mul },
             BinaryOperation::Div => quote::quote! { div },
+            BinaryOperation::Rem => quote::quote! { rem },
+            BinaryOperation::BitAnd => quote::quote! { bitand },
+            BinaryOperation::BitOr => quote::quote! { bitor },
+            BinaryOperation::BitXor => quote::quote! { bitxor },
+            BinaryOperation::Shl => quote::quote! { shl },
+            BinaryOperation::Shr => quote::quote! { shr },
         }
     }
 
@@ -79,6 +97,12 @@ impl BinaryOperation {
             BinaryOperation::Sub => quote::quote! { SubKernel },
             BinaryOperation::Mul => quote::quote! { MulKernel },
             BinaryOperation::Div => quote::quote! { DivKernel },
+            BinaryOperation::Rem => quote::quote! { RemKernel },
+            BinaryOperation::BitAnd => quote::quote! { BitAndKernel },
+            BinaryOperation::BitOr => quote::quote! { BitOrKernel },
+            BinaryOperation::BitXor => quote::quote! { BitXorKernel },
+            BinaryOperation::Shl => quote::quote! { ShlKernel },
+            BinaryOperation::Shr => quote::quote! { ShrKernel },
         }
     }
 }
@@ -92,6 +116,12 @@ impl Parse for BinaryOperation {
             "Sub" => Ok(BinaryOperation::Sub),
             "Mul" => Ok(BinaryOperation::Mul),
             "Div" => Ok(BinaryOperation::Div),
+            "Rem" => Ok(BinaryOperation::Rem),
+            "BitAnd" => Ok(BinaryOperation::BitAnd),
+            "BitOr" => Ok(BinaryOperation::BitOr),
+            "BitXor" => Ok(BinaryOperation::BitXor),
+            "Shl" => Ok(BinaryOperation::Shl),
+            "Shr" => Ok(BinaryOperation::Shr),
             _ => Err(syn::Error::new(op.span(), "Invalid operation")),
         }
     }
@@ -137,11 +167,15 @@ impl Parse for RefType {
 /// Enum representing the type of argument a function takes.
 ///
 /// An [`ArrayBase`] argument is a Tensr array.
+/// A [`TensrFn1`] argument is a unary Tensr function.
 /// A [`TensrFn2`] argument is a binary Tensr function.
+/// A [`TensrFn3`] argument is a ternary Tensr function.
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum ArgumentType {
     ArrayBase,
+    TensrFn1,
     TensrFn2,
+    TensrFn3,
 }
 
 impl Parse for ArgumentType {
@@ -150,7 +184,9 @@ impl Parse for ArgumentType {
 
         match arg_type.to_string().as_ref() {
             "ArrayBase" => Ok(ArgumentType::ArrayBase),
+            "TensrFn1" => Ok(ArgumentType::TensrFn1),
             "TensrFn2" => Ok(ArgumentType::TensrFn2),
+            "TensrFn3" => Ok(ArgumentType::TensrFn3),
             _ => Err(syn::Error::new(arg_type.span(), "Invalid argument type")),
         }
     }
@@ -160,7 +196,9 @@ impl ToTokens for ArgumentType {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         *tokens = match self {
             ArgumentType::ArrayBase => quote::quote! { ArrayBase },
+            ArgumentType::TensrFn1 => quote::quote! { TensrFn1 },
             ArgumentType::TensrFn2 => quote::quote! { TensrFn2 },
+            ArgumentType::TensrFn3 => quote::quote! { TensrFn3 },
         }
     }
 }
@@ -199,7 +237,9 @@ impl Parse for Argument {
 }
 
 /// Implements a binary operation for a given pair of arguments. The return
-/// type is a lazily evaluated function object.
+/// type is a lazily evaluated function object. The operands are broadcast
+/// together, so both must have a shape, and the output dimension type is
+/// computed through the `BroadcastShape` trait.
 ///
 /// # Arguments
 /// * `op_type` - The binary operation to implement
@@ -219,11 +259,19 @@ pub fn gen_binary_op(
     /// * `StorageType${name}`
     /// * `NDims${name}`
     ///
+    /// ## `TensrFn1`
+    /// * `Op${name}`
+    /// * `ArgType${name}`
+    ///
     /// ## `TensrFn2`
     /// * `Op${name}`
     /// * `LhsType${name}`
     /// * `RhsType${name}`
     ///
+    /// ## `TensrFn3`
+    /// * `Op${name}`
+    /// * `Arg0Type${name}`, `Arg1Type${name}` and `Arg2Type${name}`
+    ///
     /// # Arguments
     /// * `arg` - The type to generate the generics for
     /// * `name` - The name variant of the argument
@@ -241,6 +289,15 @@ pub fn gen_binary_op(
 
                 quote::quote! { #storage_type, #ndims_type }
             }
+            ArgumentType::TensrFn1 => {
+                let op_type: syn::Type =
+                    syn::parse_str(&format!("Op{}", name)).unwrap();
+
+                let arg_type: syn::Type =
+                    syn::parse_str(&format!("ArgType{}", name)).unwrap();
+
+                quote::quote! { #op_type, #arg_type }
+            }
             ArgumentType::TensrFn2 => {
                 let op_type: syn::Type =
                     syn::parse_str(&format!("Op{}", name)).unwrap();
@@ -253,6 +310,17 @@ pub fn gen_binary_op(
 
                 quote::quote! { #op_type, #lhs_type, #rhs_type }
             }
+            ArgumentType::TensrFn3 => {
+                let op_type: syn::Type =
+                    syn::parse_str(&format!("Op{}", name)).unwrap();
+
+                let arg_types = (0..3).map(|i| {
+                    syn::parse_str::<syn::Type>(&format!("Arg{i}Type{name}"))
+                        .unwrap()
+                });
+
+                quote::quote! { #op_type, #(#arg_types),* }
+            }
         }
     }
 
@@ -263,11 +331,18 @@ pub fn gen_binary_op(
     /// * `StorageType${name}: traits::Storage`
     /// * `NDims${name}: Dimension`
     ///
+    /// ## `TensrFn1`
+    /// * `Op${name}: op_traits::UnaryOp`
+    /// * `ArgType${name}: GetWriteableBuffer`
+    ///
     /// ## `TensrFn2`
     /// * `Op${name}: op_traits::BinaryOp`
     /// * `LhsType${name}: GetWriteableBuffer`
     /// * `RhsType${name}: GetWriteableBuffer<Buffer = LhsType${name}::Buffer>`
     ///
+    /// ## `TensrFn3`
+    /// * `Op${name}: op_traits::TernaryOp`
+    ///
     /// # Arguments
     /// * `arg` - The type to generate the generic bounds for
     /// * `name` - The name variant of the argument
@@ -291,6 +366,18 @@ pub fn gen_binary_op(
                     #ndims_type: Dimension,
                 }
             }
+            ArgumentType::TensrFn1 => {
+                let op_type: syn::Type =
+                    syn::parse_str(&format!("Op{}", name)).unwrap();
+
+                let arg_type: syn::Type =
+                    syn::parse_str(&format!("ArgType{}", name)).unwrap();
+
+                quote::quote! {
+                    #op_type: op_traits::UnaryOp,
+                    #arg_type: GetWriteableBuffer,
+                }
+            }
             ArgumentType::TensrFn2 => {
                 let op_type: syn::Type =
                     syn::parse_str(&format!("Op{}", name)).unwrap();
@@ -307,6 +394,14 @@ pub fn gen_binary_op(
                     #rhs_type: GetWriteableBuffer<Buffer = #lhs_type::Buffer>,
                 }
             }
+            ArgumentType::TensrFn3 => {
+                let op_type: syn::Type =
+                    syn::parse_str(&format!("Op{}", name)).unwrap();
+
+                quote::quote! {
+                    #op_type: op_traits::TernaryOp,
+                }
+            }
         }
     }
 
@@ -332,9 +427,15 @@ pub fn gen_binary_op(
             ArgumentType::ArrayBase => {
                 quote::quote! { #ref_type ArrayBase<Backend, #generic> }
             }
+            ArgumentType::TensrFn1 => {
+                quote::quote! { #ref_type TensrFn1<'a, Backend, #generic> }
+            }
             ArgumentType::TensrFn2 => {
                 quote::quote! { #ref_type TensrFn2<'a, Backend, #generic> }
             }
+            ArgumentType::TensrFn3 => {
+                quote::quote! { #ref_type TensrFn3<'a, Backend, #generic> }
+            }
         }
     }
 
@@ -344,7 +445,9 @@ pub fn gen_binary_op(
     let requires_lifetime = arguments.iter().any(|a| {
         a.ref_type == RefType::Ref
             || a.ref_type == RefType::RefMut
+            || a.arg_type == ArgumentType::TensrFn1
             || a.arg_type == ArgumentType::TensrFn2
+            || a.arg_type == ArgumentType::TensrFn3
     });
 
     // If one of the arguments requires a lifetime, we use 'a. If not, we don't
@@ -389,6 +492,10 @@ pub fn gen_binary_op(
             Backend: traits::Backend,
             #lhs_generic_bounds
             #rhs_generic_bounds
+            #lhs_type: traits::ContainerShape,
+            #rhs_type: traits::ContainerShape,
+            <#lhs_type as traits::ContainerShape>::NDims:
+                BroadcastShape<<#rhs_type as traits::ContainerShape>::NDims>,
         {
             type Output = TensrFn2<
                 #output_lifetime,
@@ -411,10 +518,10 @@ pub fn gen_binary_op(
     pretty_print(result)
 }
 
-/// Generate all possible combinations from a pair of argument types,
+/// Generate all possible combinations from a set of argument types,
 /// including owned, reference and mutable reference types.
 pub fn gen_type_pairs(
-    types: [ArgumentType; 2],
+    types: &[ArgumentType],
 ) -> Vec<((RefType, ArgumentType), (RefType, ArgumentType))> {
     // f(["A", "B"]) => [
     //      [(Own, "A"), (Own, "A")],
@@ -502,8 +609,12 @@ pub fn gen_all(tok: TokenStream) -> TokenStream {
     let stream = input.to_token_stream().into();
     let op = parse_macro_input!(stream as BinaryOperation);
 
-    let perms =
-        gen_type_pairs([ArgumentType::ArrayBase, ArgumentType::TensrFn2]);
+    let perms = gen_type_pairs(&[
+        ArgumentType::ArrayBase,
+        ArgumentType::TensrFn1,
+        ArgumentType::TensrFn2,
+        ArgumentType::TensrFn3,
+    ]);
 
     let mut result = String::new();
 
@@ -1,203 +1,322 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, ToTokens};
-use syn::{
-    parse::{Parse, ParseStream},
-    parse_macro_input, Expr,
-};
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Ownership {
-    Ref,
-    Own,
-}
-
-impl Parse for Ownership {
-    fn parse(input: ParseStream) -> syn::Result<Self> {
-        let ownership = input.parse::<syn::Ident>()?;
-
-        match ownership.to_string().as_str() {
-            "Ref" => Ok(Ownership::Ref),
-            "Own" => Ok(Ownership::Own),
-            _ => Err(syn::Error::new(
-                ownership.span(),
-                "Invalid ownership specifier",
-            )),
-        }
-    }
-}
+use syn::{parse_macro_input, LitInt};
 
+/// Generate a lazily evaluated function object with `arity` operands, named
+/// `TensrFn{arity}`, along with its `Function{arity}` evaluation trait.
+///
+/// The generated type mirrors `TensrFn2`. The operands are stored as a tuple
+/// and broadcast together, the scalar type of the result is given by the
+/// `op_traits::FunctionOp` implementation of the operation, and the operation
+/// decides which operand buffers (if any) may be reused through
+/// `op_traits::ReuseOperandBuffer`.
+///
+/// The following items must be in scope where the macro is invoked:
+/// `PhantomData`, `ArrayBase`, `Axes`, `DimDyn`, `Dimension`,
+/// `BroadcastShape`, `broadcast_shapes`, `GetWriteableBuffer`, `ShapeError`,
+/// `traits` and `op_traits`.
+///
+/// # Panics
+/// * If the generated code is not valid Rust
 pub fn function_gen(tok: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(tok as Expr);
-
-    // INFO: [ Ownership::Ref, Ownership::Own, ... ]
-    let mut owner_types = Vec::new();
-    if let Expr::Array(arr) = input {
-        for elem in arr.elems.into_iter() {
-            let stream = elem.to_token_stream().into();
-            let ownership = parse_macro_input!(stream as Ownership);
-            owner_types.push(ownership);
+    let arity = parse_macro_input!(tok as LitInt);
+    let arity: usize = match arity.base10_parse() {
+        Ok(n) if n >= 1 => n,
+        Ok(_) => {
+            return syn::Error::new(arity.span(), "Arity must be at least 1")
+                .to_compile_error()
+                .into()
         }
-    }
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let type_name = format_ident!("TensrFn{arity}");
+    let trait_name = format_ident!("Function{arity}");
 
-    let fn_dimensions = owner_types.len();
+    // INFO: [ Arg0, Arg1, ... ] and [ arg0, arg1, ... ]
+    let arg_types: Vec<_> =
+        (0..arity).map(|i| format_ident!("Arg{i}")).collect();
+    let arg_names: Vec<_> =
+        (0..arity).map(|i| format_ident!("arg{i}")).collect();
 
-    // INFO: [ "Arg0", "Arg1", ... ]
-    let trait_types = owner_types
+    // INFO: <Arg0 as traits::ContainerScalarType>::Scalar, ...
+    let scalars: Vec<_> = arg_types
         .iter()
-        .enumerate()
-        .map(|(i, _)| {
-            let name = format_ident!("Arg{i}");
-            quote::quote! { #name }
+        .map(|t| quote::quote! { <#t as traits::ContainerScalarType>::Scalar })
+        .collect();
+
+    // INFO: The broadcast dimension type of every operand, and the bounds
+    // required to compute it, folding from the left
+    let mut ndims = quote::quote! {
+        <Arg0 as traits::ContainerShape>::NDims
+    };
+    let mut ndims_bounds = Vec::new();
+    for t in arg_types.iter().skip(1) {
+        let rhs = quote::quote! { <#t as traits::ContainerShape>::NDims };
+        ndims_bounds.push(quote::quote! { #ndims: BroadcastShape<#rhs>, });
+        ndims = quote::quote! { <#ndims as BroadcastShape<#rhs>>::Output };
+    }
+
+    // INFO: The broadcast shape of the operands, computed at runtime
+    let first_arg = &arg_names[0];
+    let shape_steps: Vec<_> = arg_names
+        .iter()
+        .skip(1)
+        .map(|name| {
+            quote::quote! {
+                let shape: DimDyn = broadcast_shapes(
+                    shape.as_slice(),
+                    #name.get_shape().as_slice(),
+                )?;
+            }
         })
-        .collect::<Vec<_>>();
+        .collect();
 
-    // INFO: <'a, Backend, Op, Arg0, Arg1, ...>
-    let trait_types = if owner_types.iter().any(|t| t == &Ownership::Ref) {
-        // Type requires a lifetime parameter
+    let ref_types =
+        [quote::quote! {}, quote::quote! { &'a }, quote::quote! { &'a mut }];
+
+    let scalar_type_impls = ref_types.iter().map(|ref_type| {
         quote::quote! {
-            <'a, Backend, Op, #(#trait_types),*>
+            impl<'a, Backend, Op, #(#arg_types),*> traits::ContainerScalarType
+                for #ref_type #type_name<'a, Backend, Op, #(#arg_types),*>
+            where
+                Backend: traits::Backend,
+                #(#arg_types: traits::ContainerScalarType,)*
+                Op: op_traits::FunctionOp<(#(#scalars,)*)>,
+            {
+                type Scalar = Op::Output;
+            }
         }
-    } else {
+    });
+
+    let shape_impls = ref_types.iter().map(|ref_type| {
         quote::quote! {
-            <Backend, Op, #(#trait_types),*>
-        }
-    };
+            impl<'a, Backend, Op, #(#arg_types),*> traits::ContainerShape
+                for #ref_type #type_name<'a, Backend, Op, #(#arg_types),*>
+            where
+                Backend: traits::Backend,
+                #(#arg_types: traits::ContainerShape,)*
+                #(#ndims_bounds)*
+            {
+                type NDims = #ndims;
 
-    // INFO: Function2RefRef or Function2OwnOwn or ...
-    let mut type_name = format!("Function{}", owner_types.len());
-    for v in owner_types.iter() {
-        match v {
-            Ownership::Ref => type_name.push_str("Ref"),
-            Ownership::Own => type_name.push_str("Own"),
+                fn get_shape(&self) -> Self::NDims {
+                    Self::NDims::from_slice(self.shape.as_slice())
+                        .expect("broadcast shape has an invalid number of axes")
+                }
+            }
         }
-    }
-    let type_name = format_ident!("{}", type_name);
-
-    // INFO: Trait bounds (including "where")
-    let mut trait_bounds = String::new();
-    trait_bounds.push_str("where Backend: traits::Backend, ");
-
-    // TODO: Make binary op more generic
-    trait_bounds.push_str("Op: op_traits::BinaryOp, ");
-    for (i, _) in owner_types.iter().enumerate() {
-        trait_bounds.push_str(&format!("Arg{i}: traits::LazyArrayObject, "));
-    }
+    });
 
-    // INFO: Trait bounds (parsed)
-    let trait_bounds = syn::parse_str::<syn::WhereClause>(&trait_bounds)
-        .map_err(|e| e.to_compile_error())
-        .unwrap();
+    let ref_buffer_impls = ref_types.iter().skip(1).map(|ref_type| {
+        quote::quote! {
+            impl<'a, Backend, Op, #(#arg_types),*> GetWriteableBuffer
+                for #ref_type #type_name<'a, Backend, Op, #(#arg_types),*>
+            where
+                Backend: traits::Backend,
+                Op: op_traits::ReuseOperandBuffer<(#(#arg_types,)*)>,
+            {
+                type Buffer = Op::Buffer;
 
-    // INFO: arg0: Arg0, arg1: Arg1, ...
-    let argument_attributes: Vec<_> = owner_types
-        .iter()
-        .enumerate()
-        .map(|(n, o)| {
-            let name = format_ident!("arg{}", n);
-            let dtype = format_ident!("Arg{}", n);
-            match o {
-                Ownership::Ref => {
-                    quote::quote! { #name: &'a #dtype }
+                unsafe fn get_buffer_and_set_no_free(
+                    &mut self,
+                    _len: usize,
+                ) -> Option<Self::Buffer> {
+                    // The operands are still owned by the borrowed
+                    // expression, so none of their buffers can be taken
+                    None
                 }
-                Ownership::Own => quote::quote! { #name: #dtype },
             }
-        })
-        .collect();
+        }
+    });
 
-    // INFO: arg0: &'a Arg0, arg1: &'a Arg1, ...
-    let arg_params: Vec<_> = owner_types
-        .iter()
-        .enumerate()
-        .map(|(i, v)| {
-            let name = format_ident!("arg{}", i);
-            let dtype = format_ident!("Arg{}", i);
-            match v {
-                Ownership::Ref => quote::quote! { #name: &'a #dtype },
-                Ownership::Own => quote::quote! { #name: #dtype },
-            }
-        })
-        .collect();
+    let doc = format!(
+        " A lazily-evaluated element-wise operation with {arity} operands. \
+         The operands are broadcast together, so the result has their \
+         broadcast shape."
+    );
 
-    let args: Vec<_> = (0..fn_dimensions)
-        .map(|n| {
-            let name = format_ident!("arg{}", n);
-            quote::quote! { #name }
-        })
-        .collect();
+    let trait_doc = format!(
+        " A lazily-evaluated operation with {arity} operands, which can be \
+         evaluated into an output container of type `Out`"
+    );
 
-    // INFO: Struct definition
     let out = quote::quote! {
-        pub struct #type_name #trait_types // Type name and trait types
-            #trait_bounds                  // Trait bounds
-        {
-            #(pub(crate) #argument_attributes,)*
-            pub(crate) backend: PhantomData<Backend>,
-            pub(crate) op: PhantomData<Op>,
+        #[doc = #trait_doc]
+        pub trait #trait_name<Out> {
+            /// Evaluate the operation, writing each element of the result
+            /// into `out`
+            fn apply(&self, out: &mut Out);
+        }
+
+        #[doc = #doc]
+        pub struct #type_name<'a, Backend, Op, #(#arg_types),*> {
+            pub(crate) args: (#(#arg_types,)*),
+
+            /// The broadcast shape of the operands
+            pub(crate) shape: DimDyn,
+
+            op: PhantomData<Op>,
+            backend: PhantomData<Backend>,
+            lifetime: PhantomData<&'a ()>,
         }
 
-        impl #trait_types traits::ContainerLength for #type_name #trait_types
-            #trait_bounds
+        #(#scalar_type_impls)*
+
+        #(#shape_impls)*
+
+        impl<Backend, Op, #(#arg_types),*> traits::ContainerLength
+            for #type_name<'_, Backend, Op, #(#arg_types),*>
+        where
+            Backend: traits::Backend,
         {
             fn len(&self) -> usize {
-                self.arg0.len()
+                self.shape.len()
             }
         }
 
-        impl #trait_types traits::ContainerScalarType for #type_name #trait_types
-            #trait_bounds
+        impl<Backend, Op, #(#arg_types),*>
+            #type_name<'_, Backend, Op, #(#arg_types),*>
+        where
+            #(#arg_types: traits::ContainerShape,)*
         {
-            type Scalar = Arg0::Scalar;
-        }
+            /// Create a new function object operating on the given operands.
+            ///
+            /// # Panics
+            /// Panics if the shapes of the operands cannot be broadcast
+            /// together.
+            #[track_caller]
+            pub fn new(#(#arg_names: #arg_types),*) -> Self {
+                Self::try_new(#(#arg_names),*)
+                    .unwrap_or_else(|err| panic!("{err}"))
+            }
 
-        impl #trait_types traits::ContainerStorageType for #type_name #trait_types
-            #trait_bounds
-        {
-            type Storage = Arg0::Storage;
+            /// Create a new function object operating on the given operands.
+            ///
+            /// # Errors
+            /// Returns [`ShapeError::BroadcastMismatch`] if the shapes of the
+            /// operands cannot be broadcast together.
+            pub fn try_new(
+                #(#arg_names: #arg_types),*
+            ) -> Result<Self, ShapeError> {
+                let shape =
+                    DimDyn::new_from(#first_arg.get_shape().as_slice());
+                #(#shape_steps)*
+
+                Ok(Self {
+                    args: (#(#arg_names,)*),
+                    shape,
+                    op: PhantomData,
+                    backend: PhantomData,
+                    lifetime: PhantomData,
+                })
+            }
         }
 
-        impl #trait_types traits::ContainerBackendType for #type_name #trait_types
-            #trait_bounds
+        impl<Backend, Op, #(#arg_types),*>
+            #type_name<'_, Backend, Op, #(#arg_types),*>
+        where
+            Backend: traits::Backend,
         {
-            type Backend = Backend;
+            /// Evaluate the expression, returning a new owned array
+            /// containing the result. If the operation allows it, the buffer
+            /// of an owned, contiguous operand with the same shape as the
+            /// result is reused. This is equivalent to calling `.into()`.
+            #[allow(clippy::type_complexity)]
+            pub fn eval(
+                self,
+            ) -> ArrayBase<
+                Backend,
+                Backend::OwnedStorage<
+                    <Self as traits::ContainerScalarType>::Scalar
+                >,
+                <Self as traits::ContainerShape>::NDims,
+            >
+            where
+                Self: traits::ContainerScalarType + traits::ContainerShape,
+                ArrayBase<
+                    Backend,
+                    Backend::OwnedStorage<
+                        <Self as traits::ContainerScalarType>::Scalar
+                    >,
+                    <Self as traits::ContainerShape>::NDims,
+                >: From<Self>,
+            {
+                self.into()
+            }
+
+            /// Evaluate the expression, writing the result into `out`. The
+            /// output may be an owned array or a mutable view, but must have
+            /// the same shape as the expression.
+            ///
+            /// # Panics
+            /// Panics if the shape of `out` does not match the shape of the
+            /// expression.
+            #[track_caller]
+            pub fn eval_into<Out>(self, out: &mut Out)
+            where
+                Self: #trait_name<Out>,
+            {
+                self.apply(out);
+            }
         }
 
-        impl #trait_types traits::LazyArrayObject for #type_name #trait_types
-            #trait_bounds
+        impl<'a, Backend, Op, #(#arg_types,)* StorageType, NDims>
+            From<#type_name<'a, Backend, Op, #(#arg_types),*>>
+            for ArrayBase<Backend, StorageType, NDims>
+        where
+            Backend: traits::Backend,
+            StorageType: traits::OwnedStorage,
+            NDims: Dimension,
+            #type_name<'a, Backend, Op, #(#arg_types),*>:
+                traits::ContainerShape<NDims = NDims>
+                + GetWriteableBuffer<Buffer = StorageType::Raw>
+                + #trait_name<Self>,
         {
+            fn from(
+                mut expr: #type_name<'a, Backend, Op, #(#arg_types),*>,
+            ) -> Self {
+                let shape = traits::ContainerShape::get_shape(&expr);
+                let len = shape.len();
+
+                // Safety: The buffer is taken from an operand which is
+                // consumed by this function, and every element of that
+                // operand is read before the same element of the output is
+                // written
+                let storage = unsafe {
+                    expr.get_buffer_and_set_no_free(len).map_or_else(
+                        || StorageType::new_from_shape_uninit(&shape),
+                        |buffer| StorageType::from_raw(buffer, len),
+                    )
+                };
+
+                let mut out =
+                    Self::new(Axes::new_with_default_stride(shape), storage);
+                expr.apply(&mut out);
+                out
+            }
         }
 
-        impl #trait_types #type_name #trait_types
-            #trait_bounds
+        impl<Backend, Op, #(#arg_types),*> GetWriteableBuffer
+            for #type_name<'_, Backend, Op, #(#arg_types),*>
+        where
+            Backend: traits::Backend,
+            Op: op_traits::ReuseOperandBuffer<(#(#arg_types,)*)>,
         {
-            #[inline(always)]
-            pub fn new( #(#arg_params),* ) -> Self
-                #trait_bounds
-            {
-                Self { #(#args),*, backend: PhantomData, op: PhantomData }
+            type Buffer = Op::Buffer;
+
+            unsafe fn get_buffer_and_set_no_free(
+                &mut self,
+                len: usize,
+            ) -> Option<Self::Buffer> {
+                Op::reuse_buffer(&mut self.args, len)
             }
         }
 
-        // unsafe impl #trait_types HasWriteableBuffer
-        // for #type_name #trait_types
-        //     #trait_bounds
-        // {
-        //     type Buffer = Arg0::Buffer;
-        //
-        //     unsafe fn get_buffer(&self) -> (Self::Buffer, usize) {
-        //         self.arg0.get_buffer()
-        //     }
-        //
-        //     unsafe fn get_buffer_checked(&self, len: usize) -> Option<Self::Buffer> {
-        //         self.arg0.get_buffer_checked(len)
-        //     }
-        //
-        //     unsafe fn set_buffer_no_free(&mut self) {
-        //         self.arg0.set_buffer_no_free();
-        //     }
-        // }
+        #(#ref_buffer_impls)*
     };
 
     // We pretty-print the output for better error messages and debugging
-    let stream = syn::parse_file(&out.to_string()).unwrap();
+    let stream = syn::parse2::<syn::File>(out).unwrap();
     stream.to_token_stream().into()
 }
@@ -15,3 +15,11 @@ pub fn generate_binary_op(tok: TokenStream) -> TokenStream {
 pub fn generate_all_binary_ops(tok: TokenStream) -> TokenStream {
     binary_op_gen::gen_all(tok)
 }
+
+/// Generate a lazily evaluated function object with the given number of
+/// operands, such as `generate_function!(3)`, which defines `TensrFn3` and
+/// `Function3`.
+#[proc_macro]
+pub fn generate_function(tok: TokenStream) -> TokenStream {
+    function_gen::function_gen(tok)
+}
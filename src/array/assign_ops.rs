@@ -0,0 +1,299 @@
+//! In-place compound assignment operators, such as `+=`, for arrays and
+//! mutable views.
+
+use crate::{
+    array::{base::ArrayBase, scalar::TensrScalar},
+    backend::{
+        host::{host_backend::HostBackend, host_kernels::HostBinaryOp},
+        op_traits,
+        traits::{
+            self, ContainerShape, ScalarAccessor, ScalarWriter, StorageMut,
+        },
+    },
+    dimension::{
+        broadcast::check_broadcast_to, dim::Dimension, index::next_index,
+    },
+};
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: StorageMut,
+    NDims: Dimension,
+{
+    /// Apply `Op` element-wise to `self` and `rhs`, writing the result back
+    /// into `self`. `rhs` is broadcast to the shape of `self`.
+    ///
+    /// Each element of `self` is read immediately before it is overwritten,
+    /// and the borrow checker guarantees that `rhs` cannot refer to the data
+    /// in `self`, so no temporary storage is needed. An expression which reads
+    /// from `self`, such as `a += &a * 2.0`, is rejected at compile time and
+    /// must be evaluated into a temporary first with `a += (&a * 2.0).eval()`.
+    #[track_caller]
+    fn apply_assign<Op, Rhs>(&mut self, rhs: &Rhs)
+    where
+        Op: HostBinaryOp<StorageType::Scalar>
+            + op_traits::BinaryOp<
+                Output<StorageType::Scalar> = StorageType::Scalar,
+            >,
+        Rhs: ScalarAccessor<Scalar = StorageType::Scalar> + ContainerShape,
+    {
+        let shape = self.axes.shape.clone();
+        if let Err(err) =
+            check_broadcast_to(rhs.get_shape().as_slice(), shape.as_slice())
+        {
+            panic!("{err}");
+        }
+
+        let len = shape.len();
+        if self.is_contiguous() && rhs.supports_flat_index(len) {
+            for i in 0..len {
+                let value =
+                    Op::apply_scalar(self.get_scalar(i), rhs.get_scalar(i));
+                self.write_scalar(value, i);
+            }
+        } else if !shape.is_empty() {
+            let mut index = vec![0; shape.ndim() as usize];
+            loop {
+                let value = Op::apply_scalar(
+                    self.get_scalar_nd(&index),
+                    rhs.get_scalar_nd(&index),
+                );
+                self.write_scalar_nd(value, &index);
+
+                if !next_index(&mut index, shape.as_slice()) {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// Implement an in-place binary operation, such as `+=`, for arrays and
+/// mutable views. The right-hand side may be any array, view or lazily
+/// evaluated function object whose shape can be broadcast to the shape of the
+/// left-hand side. The result is written directly into the left operand.
+///
+/// A lazy expression which reads from the left operand cannot borrow it while
+/// it is being written, so it must be evaluated into a temporary array first:
+///
+/// ```rust
+/// use tensr::array::type_remap::Array1;
+/// use tensr::dimension::dim::Dim1;
+///
+/// let mut a =
+///     Array1::from_vec(Dim1::new([3]), vec![1.0_f64, 2.0, 3.0]).unwrap();
+/// a += (&a * 2.0).eval();
+/// assert_eq!(a[2], 9.0);
+/// ```
+macro_rules! assign_op {
+    ($op_name: ident, $op_fn: ident, $_op: tt) => {
+        paste::paste! {
+            impl<StorageType, NDims, Rhs> std::ops::[< $op_name Assign >]<Rhs>
+                for ArrayBase<HostBackend, StorageType, NDims>
+            where
+                StorageType: StorageMut,
+                NDims: Dimension,
+                Rhs: ScalarAccessor<Scalar = StorageType::Scalar>
+                    + ContainerShape,
+                <HostBackend as traits::Backend>::[< $op_name Kernel >]:
+                    HostBinaryOp<StorageType::Scalar>
+                    + op_traits::BinaryOp<
+                        Output<StorageType::Scalar> = StorageType::Scalar,
+                    >,
+            {
+                #[track_caller]
+                fn [< $op_fn _assign >](&mut self, rhs: Rhs) {
+                    self.apply_assign::<
+                        <HostBackend as traits::Backend>::[< $op_name Kernel >],
+                        Rhs,
+                    >(&rhs);
+                }
+            }
+        }
+    };
+}
+
+/// Implement an in-place binary operation with a scalar right-hand side, such
+/// as `array *= 2.0`. The scalar is wrapped in a [`TensrScalar`] and broadcast
+/// to the shape of the array.
+macro_rules! scalar_assign_op {
+    ($scalar: ty, $op_name: ident, $op_fn: ident) => {
+        paste::paste! {
+            impl<StorageType, NDims> std::ops::[< $op_name Assign >]<$scalar>
+                for ArrayBase<HostBackend, StorageType, NDims>
+            where
+                StorageType: StorageMut<Scalar = $scalar>,
+                NDims: Dimension,
+            {
+                #[inline(always)]
+                fn [< $op_fn _assign >](&mut self, rhs: $scalar) {
+                    std::ops::[< $op_name Assign >]::[< $op_fn _assign >](
+                        self,
+                        TensrScalar::<HostBackend, $scalar>::new(rhs),
+                    );
+                }
+            }
+        }
+    };
+}
+
+macro_rules! scalar_assign_ops {
+    ($op_name: ident, $op_fn: ident, $_op: tt) => {
+        crate::repeat_tensr_types!(scalar_assign_op, $op_name, $op_fn);
+    };
+}
+
+macro_rules! integer_scalar_assign_ops {
+    ($op_name: ident, $op_fn: ident, $_op: tt) => {
+        crate::repeat_integer_types!(scalar_assign_op, $op_name, $op_fn);
+    };
+}
+
+crate::repeat_binary_ops!(assign_op);
+crate::repeat_arithmetic_ops!(scalar_assign_ops);
+crate::repeat_bitwise_ops!(integer_scalar_assign_ops);
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use crate::{
+        array::type_remap::{Array1, Array2, ArrayView2, ArrayViewMut2},
+        dimension::dim::{Dim1, Dim2},
+        s,
+    };
+
+    #[test]
+    fn test_assign_array() {
+        let mut a =
+            Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+        let b = Array2::from_shape_fn(Dim2::new([2, 3]), |_| 2);
+        let ptr = a.storage.ptr.0;
+
+        a += &b;
+        assert_eq!(a[[1, 2]], 7);
+
+        a *= &b;
+        assert_eq!(a[[0, 1]], 6);
+
+        a -= b.view();
+        assert_eq!(a[[1, 0]], 8);
+
+        a /= b;
+        assert_eq!(a[[1, 1]], 5);
+
+        // The update happens in place
+        assert_eq!(a.storage.ptr.0, ptr);
+    }
+
+    #[test]
+    fn test_assign_scalar() {
+        let mut a =
+            Array1::from_vec(Dim1::new([3]), vec![1.0_f32, 2.0, 4.0]).unwrap();
+
+        a *= 3.0;
+        a -= 1.0;
+        a /= 2.0;
+        a += 0.5;
+        assert_eq!(a[0], 1.5);
+        assert_eq!(a[2], 6.0);
+    }
+
+    #[test]
+    fn test_assign_integer_ops() {
+        let mut a =
+            Array1::from_vec(Dim1::new([3]), vec![13_u16, 6, 255]).unwrap();
+        let b = Array1::from_vec(Dim1::new([3]), vec![5_u16, 4, 16]).unwrap();
+
+        a %= &b;
+        assert_eq!(a[0], 3);
+        a |= 8;
+        assert_eq!(a[1], 10);
+        a &= &b;
+        assert_eq!(a[0], 1);
+        assert_eq!(a[2], 0);
+        a ^= 1;
+        a <<= 2;
+        a >>= 1;
+        assert_eq!(a[0], 0);
+        assert_eq!(a[1], 2);
+        assert_eq!(a[2], 2);
+    }
+
+    #[test]
+    fn test_assign_broadcast() {
+        let mut a = Array2::from_shape_fn(Dim2::new([3, 2]), |i| i[0] * 10);
+        let row = Array1::from_vec(Dim1::new([2]), vec![1, 2]).unwrap();
+        let column =
+            Array2::from_vec(Dim2::new([3, 1]), vec![0, 1, 2]).unwrap();
+
+        a += &row;
+        a -= &column;
+        assert_eq!(a[[0, 1]], 2);
+        assert_eq!(a[[2, 0]], 19);
+    }
+
+    #[test]
+    fn test_assign_expression() {
+        let mut a = Array1::from_vec(Dim1::new([4]), vec![1, 2, 3, 4]).unwrap();
+        let b = Array1::from_vec(Dim1::new([4]), vec![4, 3, 2, 1]).unwrap();
+
+        a += &b * 2_i32 - &b;
+        assert_eq!(a[0], 5);
+        assert_eq!(a[3], 5);
+
+        // Operands with different numbers of dimensions are broadcast
+        let ones = Array2::from_vec(Dim2::new([1, 4]), vec![1; 4]).unwrap();
+        let mut out = Array2::from_vec(Dim2::new([1, 4]), vec![0; 4]).unwrap();
+        out -= &ones * &a;
+        assert_eq!(out[[0, 1]], -5);
+    }
+
+    #[test]
+    fn test_assign_self_referencing() {
+        let mut a = Array2::from_vec(
+            Dim2::new([2, 3]),
+            vec![0.0_f64, 1.0, 2.0, 3.0, 4.0, 5.0],
+        )
+        .unwrap();
+
+        // The expression is evaluated into a temporary before `a` is written
+        a += (&a * 2.0).eval();
+        assert_eq!(a[[1, 2]], 15.0);
+
+        // Reading elements in a different order from the one they are written
+        // must still see the original values
+        a -= a.slice(s![..;-1, ..;-1]).to_owned();
+        assert_eq!(a[[0, 0]], -15.0);
+        assert_eq!(a[[1, 2]], 15.0);
+
+        let b = (&a * &a).eval();
+        a *= (&a + &b).eval();
+        assert_eq!(a[[0, 1]], -9.0 * (-9.0 + 81.0));
+    }
+
+    #[test]
+    fn test_assign_views() {
+        let mut data = Array2::from_shape_fn(Dim2::new([4, 4]), |i| {
+            u64::try_from(i[0] * 4 + i[1]).unwrap()
+        });
+        let other = Array2::from_shape_fn(Dim2::new([4, 4]), |_| 100_u64);
+
+        // Update every other column, reading from a reversed view
+        let source: ArrayView2<'_, u64> = other.slice(s![..;-1, ..2]);
+        let mut target: ArrayViewMut2<'_, u64> = data.slice_mut(s![.., ..;2]);
+        target += &source;
+        target *= 2;
+
+        assert_eq!(data[[0, 0]], 200);
+        assert_eq!(data[[3, 2]], 228);
+        assert_eq!(data[[3, 3]], 15);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be broadcast to shape [3, 1]")]
+    fn test_assign_broadcast_mismatch() {
+        let mut a = Array2::<f32>::zeros(Dim2::new([3, 1]));
+        let b = Array2::<f32>::zeros(Dim2::new([3, 2]));
+        a += &b;
+    }
+}
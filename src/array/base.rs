@@ -1,9 +1,12 @@
+//! The [`ArrayBase`] type underlying every array and view.
+
 use num_traits::{One, Zero};
 
 use crate::{
     array::traits::GetWriteableBuffer,
     backend::{host::host_backend::HostBackend, traits},
-    dimension::{axes::Axes, dim::Dimension},
+    dimension::{axes::Axes, dim::Dimension, index::next_index},
+    types::UDim,
 };
 
 /// The base type for all arrays. This type should not be used directly -- it is
@@ -62,6 +65,7 @@ where
         Self::new(Axes::<NDims>::new_with_default_stride(shape), storage)
     }
 
+    /// Create an array of the given shape, filled with zeros
     pub fn zeros(shape: NDims) -> Self
     where
         StorageType: traits::OwnedStorage,
@@ -72,6 +76,7 @@ where
         Self::new(Axes::<NDims>::new_with_default_stride(shape), storage)
     }
 
+    /// Create an array of the given shape, filled with ones
     pub fn ones(shape: NDims) -> Self
     where
         StorageType: traits::OwnedStorage,
@@ -82,6 +87,7 @@ where
         Self::new(Axes::<NDims>::new_with_default_stride(shape), storage)
     }
 
+    /// Create an array of the given shape, with every element set to `value`
     pub fn new_with(shape: NDims, value: StorageType::Scalar) -> Self
     where
         StorageType: traits::OwnedStorage,
@@ -91,8 +97,26 @@ where
         Self::new(Axes::<NDims>::new_with_default_stride(shape), storage)
     }
 
-    pub fn fill(&mut self, value: StorageType::Scalar) {
-        self.storage.fill(value);
+    /// Set every element of the array to `value`. For views, only the
+    /// elements which are part of the view are modified.
+    pub fn fill(&mut self, value: StorageType::Scalar)
+    where
+        StorageType: traits::StorageMut,
+    {
+        if self.is_contiguous() {
+            let offset = self.axes.stride.offset;
+            (offset..offset + self.axes.shape.len())
+                .for_each(|i| self.storage[i] = value);
+        } else if !self.axes.shape.is_empty() {
+            let mut index = vec![0; self.axes.shape.ndim() as usize];
+            loop {
+                self.storage[self.axes.offset_of(&index)] = value;
+
+                if !next_index(&mut index, self.axes.shape.as_slice()) {
+                    break;
+                }
+            }
+        }
     }
 
     /// Get the dimensions of the array
@@ -100,9 +124,26 @@ where
         &self.axes.shape
     }
 
-    /// Get the strides of the array
+    /// Get the strides of the array. Strides are measured in elements, and
+    /// are interpreted as signed values (see [`Stride`]).
+    ///
+    /// [`Stride`]: crate::dimension::stride::Stride
     pub const fn strides(&self) -> &NDims {
-        &self.axes.stride
+        &self.axes.stride.strides
+    }
+
+    /// Get the index of the first element of the array within its storage.
+    /// This is always zero for arrays which own their data, but may be
+    /// non-zero for views.
+    pub const fn offset(&self) -> usize {
+        self.axes.stride.offset
+    }
+
+    /// Returns `true` if the elements of the array are laid out contiguously
+    /// in row-major order. Newly-created arrays are always contiguous, but
+    /// views may not be.
+    pub fn is_contiguous(&self) -> bool {
+        self.axes.is_contiguous()
     }
 }
 
@@ -114,7 +155,7 @@ where
     NDims: Dimension,
 {
     fn len(&self) -> usize {
-        self.storage.len()
+        self.axes.shape.len()
     }
 }
 
@@ -126,7 +167,7 @@ where
     NDims: Dimension,
 {
     fn len(&self) -> usize {
-        self.storage.len()
+        self.axes.shape.len()
     }
 }
 
@@ -138,7 +179,7 @@ where
     NDims: Dimension,
 {
     fn len(&self) -> usize {
-        self.storage.len()
+        self.axes.shape.len()
     }
 }
 
@@ -172,6 +213,48 @@ where
     type Scalar = StorageType::Scalar;
 }
 
+impl<Backend, StorageType, NDims> traits::ContainerShape
+    for ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    type NDims = NDims;
+
+    fn get_shape(&self) -> Self::NDims {
+        self.axes.shape.clone()
+    }
+}
+
+impl<Backend, StorageType, NDims> traits::ContainerShape
+    for &ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    type NDims = NDims;
+
+    fn get_shape(&self) -> Self::NDims {
+        self.axes.shape.clone()
+    }
+}
+
+impl<Backend, StorageType, NDims> traits::ContainerShape
+    for &mut ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    type NDims = NDims;
+
+    fn get_shape(&self) -> Self::NDims {
+        self.axes.shape.clone()
+    }
+}
+
 impl<Backend, StorageType, NDims> traits::ContainerStorageType
     for ArrayBase<Backend, StorageType, NDims>
 where
@@ -311,8 +394,19 @@ where
         &mut self,
         len: usize,
     ) -> Option<Self::Buffer> {
-        // If we own the storage, we can do whatever we want (within reason)
-        self.storage.get_buffer_and_set_no_free(len)
+        // If we own the storage, we can do whatever we want (within reason).
+        // The buffer can only be reused if the result will have the same
+        // layout, otherwise elements could be overwritten before being read.
+        // The allocation must also be exactly the right size, so it can be
+        // freed correctly by whichever object takes ownership of it
+        if self.axes.stride.offset == 0
+            && self.is_contiguous()
+            && self.storage.len() == len
+        {
+            self.storage.get_buffer_and_set_no_free(len)
+        } else {
+            None
+        }
     }
 }
 
@@ -350,8 +444,9 @@ where
         &mut self,
         _: usize,
     ) -> Option<Self::Buffer> {
-        // We don't own it, but we can mutate it, so this is fine
-        self.storage.get_buffer_and_set_no_free(self.storage.len())
+        // The array is only borrowed, so it is still used (and will free its
+        // data) after the expression has been evaluated
+        None
     }
 }
 
@@ -363,19 +458,35 @@ where
 {
     #[inline(always)]
     fn get_scalar(&self, index: usize) -> Self::Scalar {
-        self.storage[index]
+        self.storage[self.axes.stride.offset + index]
+    }
+
+    #[inline(always)]
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar {
+        self.storage[self.axes.broadcast_offset_of(index)]
+    }
+
+    #[inline(always)]
+    fn is_contiguous(&self) -> bool {
+        self.axes.is_contiguous()
     }
 }
 
 impl<StorageType, NDims> traits::ScalarWriter
     for ArrayBase<HostBackend, StorageType, NDims>
 where
-    StorageType: traits::Storage,
+    StorageType: traits::StorageMut,
     NDims: Dimension,
 {
     #[inline(always)]
     fn write_scalar(&mut self, value: Self::Scalar, index: usize) {
-        self.storage[index] = value;
+        self.storage[self.axes.stride.offset + index] = value;
+    }
+
+    #[inline(always)]
+    fn write_scalar_nd(&mut self, value: Self::Scalar, index: &[UDim]) {
+        let offset = self.axes.offset_of(index);
+        self.storage[offset] = value;
     }
 }
 
@@ -387,7 +498,17 @@ where
 {
     #[inline(always)]
     fn get_scalar(&self, index: usize) -> Self::Scalar {
-        self.storage[index]
+        self.storage[self.axes.stride.offset + index]
+    }
+
+    #[inline(always)]
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar {
+        self.storage[self.axes.broadcast_offset_of(index)]
+    }
+
+    #[inline(always)]
+    fn is_contiguous(&self) -> bool {
+        self.axes.is_contiguous()
     }
 }
 
@@ -399,18 +520,34 @@ where
 {
     #[inline(always)]
     fn get_scalar(&self, index: usize) -> Self::Scalar {
-        self.storage[index]
+        self.storage[self.axes.stride.offset + index]
+    }
+
+    #[inline(always)]
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar {
+        self.storage[self.axes.broadcast_offset_of(index)]
+    }
+
+    #[inline(always)]
+    fn is_contiguous(&self) -> bool {
+        self.axes.is_contiguous()
     }
 }
 
 impl<StorageType, NDims> traits::ScalarWriter
     for &mut ArrayBase<HostBackend, StorageType, NDims>
 where
-    StorageType: traits::Storage,
+    StorageType: traits::StorageMut,
     NDims: Dimension,
 {
     #[inline(always)]
     fn write_scalar(&mut self, value: Self::Scalar, index: usize) {
-        self.storage[index] = value;
+        self.storage[self.axes.stride.offset + index] = value;
+    }
+
+    #[inline(always)]
+    fn write_scalar_nd(&mut self, value: Self::Scalar, index: &[UDim]) {
+        let offset = self.axes.offset_of(index);
+        self.storage[offset] = value;
     }
 }
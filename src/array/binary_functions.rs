@@ -0,0 +1,359 @@
+//! Named element-wise functions of two operands, such as `pow`, and
+//! element-wise comparisons and logical operations.
+
+use num_traits::NumCast;
+
+use crate::{
+    array::{
+        base::ArrayBase,
+        function_1::TensrFn1,
+        function_2::TensrFn2,
+        function_3::TensrFn3,
+        scalar::{IntoOperand, TensrScalar},
+    },
+    backend::traits::{self, ContainerScalarType, ContainerShape},
+    dimension::dim::Dimension,
+};
+
+/// Convert an integer exponent into the scalar type of the base.
+#[track_caller]
+fn exponent<T: NumCast>(n: i32) -> T {
+    num_traits::cast(n).unwrap_or_else(|| {
+        panic!("exponent {n} cannot be represented by the scalar type")
+    })
+}
+
+/// Generate a method which lazily applies a named binary function to an array
+/// (borrowing it) and another operand, which may be a scalar.
+macro_rules! array_binary_function {
+    (Powi, $_fn_name: ident) => {
+        /// Lazily raise each element of the array to the integer power `n`.
+        ///
+        /// # Panics
+        /// Panics if `n` cannot be represented by the scalar type.
+        #[track_caller]
+        pub fn powi(
+            &self,
+            n: i32,
+        ) -> TensrFn2<
+            '_,
+            Backend,
+            Backend::PowiKernel,
+            &Self,
+            TensrScalar<Backend, StorageType::Scalar>,
+        >
+        where
+            StorageType::Scalar: NumCast,
+        {
+            TensrFn2::new(self, TensrScalar::new(exponent(n)))
+        }
+    };
+    ($op_name: ident, $fn_name: ident) => {
+        paste::paste! {
+            #[doc = concat!(
+                "Lazily apply `", stringify!($fn_name), "` element-wise to ",
+                "the array and `rhs`, which are broadcast together.\n\n",
+                "# Panics\n",
+                "Panics if the shapes cannot be broadcast together."
+            )]
+            #[track_caller]
+            pub fn $fn_name<Other>(
+                &self,
+                rhs: Other,
+            ) -> TensrFn2<
+                '_,
+                Backend,
+                Backend::[< $op_name Kernel >],
+                &Self,
+                Other::Operand,
+            >
+            where
+                Other: IntoOperand<Backend>,
+                Other::Operand: ContainerShape,
+            {
+                TensrFn2::new(self, rhs.into_operand())
+            }
+        }
+    };
+}
+
+/// Generate a method which lazily applies a named binary function to the
+/// result of a function object (consuming it) and another operand.
+macro_rules! function_binary_function {
+    (Powi, $_fn_name: ident) => {
+        /// Lazily raise each element of the result of this expression to the
+        /// integer power `n`.
+        ///
+        /// # Panics
+        /// Panics if `n` cannot be represented by the scalar type.
+        #[track_caller]
+        pub fn powi(
+            self,
+            n: i32,
+        ) -> TensrFn2<
+            'a,
+            Backend,
+            Backend::PowiKernel,
+            Self,
+            TensrScalar<Backend, <Self as ContainerScalarType>::Scalar>,
+        >
+        where
+            Self: ContainerScalarType + ContainerShape,
+            <Self as ContainerScalarType>::Scalar: NumCast,
+        {
+            TensrFn2::new(self, TensrScalar::new(exponent(n)))
+        }
+    };
+    ($op_name: ident, $fn_name: ident) => {
+        paste::paste! {
+            #[doc = concat!(
+                "Lazily apply `", stringify!($fn_name), "` element-wise to ",
+                "the result of this expression and `rhs`, which are broadcast ",
+                "together.\n\n",
+                "# Panics\n",
+                "Panics if the shapes cannot be broadcast together."
+            )]
+            #[track_caller]
+            pub fn $fn_name<Other>(
+                self,
+                rhs: Other,
+            ) -> TensrFn2<
+                'a,
+                Backend,
+                Backend::[< $op_name Kernel >],
+                Self,
+                Other::Operand,
+            >
+            where
+                Self: ContainerShape,
+                Other: IntoOperand<Backend>,
+                Other::Operand: ContainerShape,
+            {
+                TensrFn2::new(self, rhs.into_operand())
+            }
+        }
+    };
+}
+
+impl<Backend, StorageType, NDims> ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    crate::repeat_binary_functions!(array_binary_function);
+    crate::repeat_comparison_ops!(array_binary_function);
+}
+
+impl<'a, Backend, Op, Arg> TensrFn1<'a, Backend, Op, Arg>
+where
+    Backend: traits::Backend,
+{
+    crate::repeat_binary_functions!(function_binary_function);
+    crate::repeat_comparison_ops!(function_binary_function);
+}
+
+impl<'a, Backend, Op, Lhs, Rhs> TensrFn2<'a, Backend, Op, Lhs, Rhs>
+where
+    Backend: traits::Backend,
+{
+    crate::repeat_binary_functions!(function_binary_function);
+    crate::repeat_comparison_ops!(function_binary_function);
+}
+
+impl<'a, Backend, Op, Arg0, Arg1, Arg2>
+    TensrFn3<'a, Backend, Op, Arg0, Arg1, Arg2>
+where
+    Backend: traits::Backend,
+{
+    crate::repeat_binary_functions!(function_binary_function);
+    crate::repeat_comparison_ops!(function_binary_function);
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use crate::{
+        array::type_remap::{Array1, Array2},
+        backend::{
+            host::{host_backend::HostBackend, host_kernels::HostBinaryOp},
+            traits::Backend,
+        },
+        dimension::dim::{Dim1, Dim2, Dimension},
+    };
+
+    #[test]
+    fn test_pow() {
+        let a =
+            Array1::from_vec(Dim1::new([3]), vec![1.0_f64, 2.0, 9.0]).unwrap();
+        let b = Array1::from_vec(Dim1::new([3]), vec![3.0, 3.0, 0.5]).unwrap();
+
+        let result = a.pow(&b).eval();
+        assert_eq!(result[1], 8.0);
+        assert_eq!(result[2], 3.0);
+
+        let result = a.pow(2.0).eval();
+        assert_eq!(result[2], 81.0);
+
+        let result = a.powi(-1).eval();
+        assert_eq!(result[1], 0.5);
+
+        let result = (&a + &b).powi(2).eval();
+        assert_eq!(result[0], 16.0);
+
+        // `i32::MAX` rounds up to 2^31 as an `f32`, which is not a valid
+        // `i32`, so it must not be mistaken for an exponent of zero
+        let c = Array1::from_vec(Dim1::new([2]), vec![1.0_f32, 0.5]).unwrap();
+        let result = c.powi(i32::MAX).eval();
+        assert_eq!(result[0], 1.0);
+        assert_eq!(result[1], 0.0);
+    }
+
+    #[test]
+    fn test_powi_kernel_exponents() {
+        type Kernel = <HostBackend as Backend>::PowiKernel;
+
+        assert_eq!(Kernel::apply_scalar(2.0_f64, 10.0), 1024.0);
+        assert_eq!(Kernel::apply_scalar(4.0_f64, 0.5), 2.0);
+        assert_eq!(Kernel::apply_scalar(2.0_f64, 1e10), f64::INFINITY);
+        assert!(Kernel::apply_scalar(2.0_f64, f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn test_minimum_maximum() {
+        let a = Array1::from_vec(Dim1::new([4]), vec![1.0_f32, 5.0, -2.0, 0.0])
+            .unwrap();
+        let b = Array1::from_vec(Dim1::new([4]), vec![2.0, 3.0, f32::NAN, 0.0])
+            .unwrap();
+
+        let min = a.minimum(&b).eval();
+        assert_eq!(min[0], 1.0);
+        assert_eq!(min[1], 3.0);
+        assert!(min[2].is_nan());
+
+        let max = a.maximum(&b).eval();
+        assert_eq!(max[0], 2.0);
+        assert_eq!(max[1], 5.0);
+        assert!(max[2].is_nan());
+
+        // NaN is propagated from the left-hand side too
+        let max = b.maximum(&a).eval();
+        assert!(max[2].is_nan());
+
+        // Integers and scalars work too, as does broadcasting
+        let ints = Array2::from_shape_fn(Dim2::new([2, 3]), |i| {
+            u32::try_from(i[0] * 3 + i[1]).unwrap()
+        });
+        let clamped = ints.maximum(2_u32).minimum(4_u32).eval();
+        assert_eq!(clamped[[0, 0]], 2);
+        assert_eq!(clamped[[1, 0]], 3);
+        assert_eq!(clamped[[1, 2]], 4);
+    }
+
+    #[test]
+    fn test_float_functions() {
+        let y =
+            Array1::from_vec(Dim1::new([3]), vec![1.0_f64, -3.0, 0.0]).unwrap();
+        let x = Array1::from_vec(Dim1::new([3]), vec![1.0, 4.0, -1.0]).unwrap();
+
+        let atan2 = y.atan2(&x).eval();
+        assert_eq!(atan2[0], 1.0_f64.atan2(1.0));
+        assert_eq!(atan2[2], std::f64::consts::PI);
+
+        let hypot = y.hypot(&x).eval();
+        assert_eq!(hypot[1], 5.0);
+
+        let copysign = x.copysign(&y).eval();
+        assert_eq!(copysign[0], 1.0);
+        assert_eq!(copysign[1], -4.0);
+        assert_eq!(copysign[2], 1.0);
+    }
+
+    #[test]
+    fn test_fused_expression() {
+        let a =
+            Array1::from_slice(Dim1::new([3]), &[3.0_f64, -4.0, 0.5]).unwrap();
+        let ptr = a.storage.ptr.0;
+
+        // Named functions, unary functions and operators can all be combined
+        // into a single expression, reusing the consumed buffer
+        let result = (a * 2.0).abs().hypot(8.0).maximum(9.0).sqrt().eval();
+        assert_eq!(result.storage.ptr.0, ptr);
+        assert_eq!(result[0], 10.0_f64.sqrt());
+        assert_eq!(result[1], 8.0_f64.hypot(8.0).sqrt());
+        assert_eq!(result[2], 3.0);
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let a =
+            Array1::from_vec(Dim1::new([4]), vec![1.0_f64, 2.0, 3.0, f64::NAN])
+                .unwrap();
+        let b =
+            Array1::from_vec(Dim1::new([4]), vec![3.0, 2.0, 1.0, 0.0]).unwrap();
+
+        let check = |result: &Array1<bool>, expected: [bool; 4]| {
+            for (i, value) in expected.into_iter().enumerate() {
+                assert_eq!(result[i], value, "element {i}");
+            }
+        };
+
+        check(&a.eq(&b).eval(), [false, true, false, false]);
+        check(&a.ne(&b).eval(), [true, false, true, true]);
+        check(&a.lt(&b).eval(), [true, false, false, false]);
+        check(&a.le(&b).eval(), [true, true, false, false]);
+        check(&a.gt(&b).eval(), [false, false, true, false]);
+        check(&a.ge(&b).eval(), [false, true, true, false]);
+
+        // Scalars and nested expressions can be compared too
+        check(&a.gt(1.5).eval(), [false, true, true, false]);
+        check(&(&a + &b).eq(4.0).eval(), [true, true, true, false]);
+    }
+
+    #[test]
+    fn test_comparison_broadcast() {
+        let a = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+        let row =
+            Array1::from_vec(Dim1::new([3]), vec![0_usize, 4, 2]).unwrap();
+
+        let mask = a.ge(&row).eval();
+        assert_eq!(mask.shape().as_slice(), &[2, 3]);
+        assert!(mask[[0, 0]]);
+        assert!(!mask[[0, 1]]);
+        assert!(mask[[1, 1]]);
+    }
+
+    #[test]
+    fn test_logical() {
+        let a = Array1::from_vec(Dim1::new([4]), vec![1, 2, 3, 4]).unwrap();
+        let lower = a.gt(1_i32);
+        let upper = a.lt(4_i32);
+
+        let both = lower.logical_and(upper).eval();
+        assert!(!both[0]);
+        assert!(both[1]);
+        assert!(!both[3]);
+
+        let either = a.eq(1_i32).logical_or(a.eq(4_i32)).eval();
+        assert!(either[0]);
+        assert!(!either[2]);
+
+        let xor = a.le(2_i32).logical_xor(true).eval();
+        assert!(!xor[0]);
+        assert!(xor[2]);
+
+        // Masks also support the bitwise operators and `!`
+        let mask = (!(a.ge(2_i32) & a.le(3_i32))).eval();
+        assert!(mask[0]);
+        assert!(!mask[1]);
+        assert!(mask[3]);
+
+        let mask = both.logical_not().logical_and(&either).eval();
+        assert!(mask[0]);
+        assert!(!mask[1]);
+        assert!(mask[3]);
+
+        let mask = (&both | &either).eval();
+        assert!((0..4).all(|i| mask[i]));
+    }
+}
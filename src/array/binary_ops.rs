@@ -1,12 +1,247 @@
+//! Lazily evaluated binary operators, such as `+` and `&`, between arrays,
+//! views, function objects and scalars.
+
 use crate::{
     array::{
-        base::ArrayBase, function_2::TensrFn2, traits::GetWriteableBuffer,
+        base::ArrayBase, function_1::TensrFn1, function_2::TensrFn2,
+        function_3::TensrFn3, scalar::TensrScalar, traits::GetWriteableBuffer,
     },
     backend::{op_traits, traits},
-    dimension::dim::Dimension,
+    dimension::{broadcast::BroadcastShape, dim::Dimension},
 };
 
 tensr_proc_macros::generate_all_binary_ops!(Add);
 tensr_proc_macros::generate_all_binary_ops!(Sub);
 tensr_proc_macros::generate_all_binary_ops!(Mul);
 tensr_proc_macros::generate_all_binary_ops!(Div);
+tensr_proc_macros::generate_all_binary_ops!(Rem);
+tensr_proc_macros::generate_all_binary_ops!(BitAnd);
+tensr_proc_macros::generate_all_binary_ops!(BitOr);
+tensr_proc_macros::generate_all_binary_ops!(BitXor);
+tensr_proc_macros::generate_all_binary_ops!(Shl);
+tensr_proc_macros::generate_all_binary_ops!(Shr);
+
+/// Implement a binary operation between a scalar of type `$scalar` and each
+/// kind of array or function object operand, with the scalar on either side.
+/// The scalar is wrapped in a [`TensrScalar`], so the result stays lazy.
+macro_rules! scalar_binary_op {
+    ($scalar: ty, $op_name: ident, $op_fn: ident) => {
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'static, [],
+            [StorageType: traits::Storage, NDims: Dimension],
+            ArrayBase<Backend, StorageType, NDims>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [StorageType: traits::Storage, NDims: Dimension],
+            &'a ArrayBase<Backend, StorageType, NDims>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [StorageType: traits::Storage, NDims: Dimension],
+            &'a mut ArrayBase<Backend, StorageType, NDims>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [Op: op_traits::UnaryOp, ArgType: GetWriteableBuffer],
+            TensrFn1<'a, Backend, Op, ArgType>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [Op: op_traits::UnaryOp, ArgType: GetWriteableBuffer],
+            &'a TensrFn1<'a, Backend, Op, ArgType>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [Op: op_traits::UnaryOp, ArgType: GetWriteableBuffer],
+            &'a mut TensrFn1<'a, Backend, Op, ArgType>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [
+                Op: op_traits::BinaryOp,
+                LhsType: GetWriteableBuffer,
+                RhsType: GetWriteableBuffer<Buffer = LhsType::Buffer>
+            ],
+            TensrFn2<'a, Backend, Op, LhsType, RhsType>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [
+                Op: op_traits::BinaryOp,
+                LhsType: GetWriteableBuffer,
+                RhsType: GetWriteableBuffer<Buffer = LhsType::Buffer>
+            ],
+            &'a TensrFn2<'a, Backend, Op, LhsType, RhsType>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [
+                Op: op_traits::BinaryOp,
+                LhsType: GetWriteableBuffer,
+                RhsType: GetWriteableBuffer<Buffer = LhsType::Buffer>
+            ],
+            &'a mut TensrFn2<'a, Backend, Op, LhsType, RhsType>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [
+                Op: op_traits::TernaryOp,
+                Arg0Type: Sized,
+                Arg1Type: Sized,
+                Arg2Type: Sized
+            ],
+            TensrFn3<'a, Backend, Op, Arg0Type, Arg1Type, Arg2Type>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [
+                Op: op_traits::TernaryOp,
+                Arg0Type: Sized,
+                Arg1Type: Sized,
+                Arg2Type: Sized
+            ],
+            &'a TensrFn3<'a, Backend, Op, Arg0Type, Arg1Type, Arg2Type>
+        );
+        scalar_binary_op!(
+            @impl $scalar, $op_name, $op_fn, 'a, ['a],
+            [
+                Op: op_traits::TernaryOp,
+                Arg0Type: Sized,
+                Arg1Type: Sized,
+                Arg2Type: Sized
+            ],
+            &'a mut TensrFn3<'a, Backend, Op, Arg0Type, Arg1Type, Arg2Type>
+        );
+    };
+
+    (
+        @impl $scalar: ty, $op_name: ident, $op_fn: ident,
+        $out_lifetime: lifetime, [$($lifetime: lifetime)?],
+        [$($generic: ident: $bound: path),*],
+        $operand: ty
+    ) => {
+        paste::paste! {
+            impl<$($lifetime,)? Backend, $($generic),*>
+                std::ops::$op_name<$scalar> for $operand
+            where
+                Backend: traits::Backend,
+                $($generic: $bound,)*
+                $operand: traits::ContainerScalarType<Scalar = $scalar>
+                    + traits::ContainerShape,
+            {
+                type Output = TensrFn2<
+                    $out_lifetime,
+                    Backend,
+                    Backend::[< $op_name Kernel >],
+                    $operand,
+                    TensrScalar<Backend, $scalar>,
+                >;
+
+                #[inline(always)]
+                fn $op_fn(self, rhs: $scalar) -> Self::Output {
+                    Self::Output::new(self, TensrScalar::new(rhs))
+                }
+            }
+
+            impl<$($lifetime,)? Backend, $($generic),*>
+                std::ops::$op_name<$operand> for $scalar
+            where
+                Backend: traits::Backend,
+                $($generic: $bound,)*
+                $operand: traits::ContainerScalarType<Scalar = $scalar>
+                    + traits::ContainerShape,
+            {
+                type Output = TensrFn2<
+                    $out_lifetime,
+                    Backend,
+                    Backend::[< $op_name Kernel >],
+                    TensrScalar<Backend, $scalar>,
+                    $operand,
+                >;
+
+                #[inline(always)]
+                fn $op_fn(self, rhs: $operand) -> Self::Output {
+                    Self::Output::new(TensrScalar::new(self), rhs)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! scalar_binary_ops {
+    ($op_name: ident, $op_fn: ident, $_op: tt) => {
+        crate::repeat_tensr_types!(scalar_binary_op, $op_name, $op_fn);
+    };
+}
+
+macro_rules! integer_scalar_binary_ops {
+    ($op_name: ident, $op_fn: ident, $_op: tt) => {
+        crate::repeat_integer_types!(scalar_binary_op, $op_name, $op_fn);
+    };
+}
+
+crate::repeat_arithmetic_ops!(scalar_binary_ops);
+crate::repeat_bitwise_ops!(integer_scalar_binary_ops);
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        array::type_remap::{Array1, Array2},
+        dimension::dim::{Dim1, Dim2},
+    };
+
+    #[test]
+    fn test_rem() {
+        let a = Array1::from_vec(Dim1::new([4]), vec![7, -7, 9, 10]).unwrap();
+        let b = Array1::from_vec(Dim1::new([4]), vec![3, 3, 4, 5]).unwrap();
+
+        let result = (&a % &b).eval();
+        assert_eq!(result[0], 1);
+        assert_eq!(result[1], -1);
+        assert_eq!(result[3], 0);
+
+        let result = (&a % 4_i32).eval();
+        assert_eq!(result[2], 1);
+
+        let floats =
+            Array1::from_vec(Dim1::new([2]), vec![5.5_f64, -1.5]).unwrap();
+        let result = (&floats % 2.0).eval();
+        assert!((result[0] - 1.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let a =
+            Array1::from_vec(Dim1::new([3]), vec![0b1100_u16, 0b1010, 0xff])
+                .unwrap();
+        let b =
+            Array1::from_vec(Dim1::new([3]), vec![0b1010_u16, 0b0110, 0x0f])
+                .unwrap();
+
+        assert_eq!((&a & &b).eval()[0], 0b1000);
+        assert_eq!((&a | &b).eval()[1], 0b1110);
+        assert_eq!((&a ^ &b).eval()[2], 0xf0);
+        assert_eq!((0x0f_u16 & &a).eval()[2], 0x0f);
+    }
+
+    #[test]
+    fn test_shifts() {
+        let a = Array2::from_shape_fn(Dim2::new([2, 3]), |i| 1_u32 << i[1]);
+        let shifts =
+            Array1::from_vec(Dim1::new([3]), vec![0_u32, 1, 2]).unwrap();
+
+        // Broadcasting and fusing with other operations
+        let result = ((&a << &shifts) + 1_u32).eval();
+        assert_eq!(result[[1, 2]], 17);
+
+        let result = (&a >> 1_u32).eval();
+        assert_eq!(result[[0, 0]], 0);
+        assert_eq!(result[[0, 2]], 2);
+
+        let signed = Array1::from_vec(Dim1::new([2]), vec![-8_i64, 8]).unwrap();
+        let result = (signed >> 2_i64).eval();
+        assert_eq!(result[0], -2);
+        assert_eq!(result[1], 2);
+    }
+}
@@ -0,0 +1,258 @@
+//! Constructors for owned host arrays, from vectors, slices, iterators and
+//! functions of the index.
+
+use crate::{
+    array::{base::ArrayBase, type_remap::Array1},
+    backend::host::{host_backend::HostBackend, host_storage::HostStorage},
+    dimension::{
+        axes::Axes,
+        dim::{Dim1, Dimension},
+        index::next_index,
+    },
+    error::ShapeError,
+};
+
+impl<T, NDims> ArrayBase<HostBackend, HostStorage<T>, NDims>
+where
+    T: Copy,
+    NDims: Dimension,
+{
+    /// Create a new array with the given shape, taking ownership of the
+    /// elements in `data`. Elements are stored in row-major order.
+    ///
+    /// Where possible, the allocation of `data` is reused directly (see
+    /// [`HostStorage::from_vec`]), so no elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::LengthMismatch`] if the length of `data` is not
+    /// equal to the number of elements in `shape`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_vec(Dim2::new([2, 3]), vec![1, 2, 3, 4, 5, 6]).unwrap();
+    /// assert_eq!(array.get_scalar(4), 5);
+    ///
+    /// assert!(Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3]).is_err());
+    /// ```
+    pub fn from_vec(shape: NDims, data: Vec<T>) -> Result<Self, ShapeError> {
+        Self::check_length(&shape, data.len())?;
+        Ok(Self::from_storage(shape, HostStorage::from_vec(data)))
+    }
+
+    /// Create a new array with the given shape, copying the elements from
+    /// `data`. Elements are read in row-major order.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::LengthMismatch`] if the length of `data` is not
+    /// equal to the number of elements in `shape`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_slice(Dim2::new([2, 2]), &[1.0f32, 2.0, 3.0, 4.0]).unwrap();
+    /// assert_eq!(array.get_scalar(3), 4.0);
+    /// ```
+    pub fn from_slice(shape: NDims, data: &[T]) -> Result<Self, ShapeError> {
+        Self::check_length(&shape, data.len())?;
+        Ok(Self::from_storage(shape, HostStorage::from_slice(data)))
+    }
+
+    /// Create a new array with the given shape, filled with the values
+    /// produced by an iterator in row-major order.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::LengthMismatch`] if the iterator does not yield
+    /// exactly as many elements as there are in `shape`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_shape_iter(Dim2::new([3, 4]), 0..12).unwrap();
+    /// assert_eq!(array.get_scalar(7), 7);
+    /// ```
+    pub fn from_shape_iter<I>(shape: NDims, iter: I) -> Result<Self, ShapeError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let data: Vec<T> = iter.into_iter().collect();
+        Self::from_vec(shape, data)
+    }
+
+    /// Create a new array with the given shape, where each element is the
+    /// result of calling `f` with the multi-dimensional index of that element.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([2, 3]), |index| index[0] * 10 + index[1]);
+    /// assert_eq!(array.get_scalar(0), 0);
+    /// assert_eq!(array.get_scalar(5), 12);
+    /// ```
+    pub fn from_shape_fn<F>(shape: NDims, mut f: F) -> Self
+    where
+        F: FnMut(&[usize]) -> T,
+    {
+        let mut data = Vec::with_capacity(shape.len());
+
+        if !shape.is_empty() {
+            let mut index = vec![0; shape.ndim() as usize];
+            loop {
+                data.push(f(&index));
+
+                if !next_index(&mut index, shape.as_slice()) {
+                    break;
+                }
+            }
+        }
+
+        Self::from_storage(shape, HostStorage::from_vec(data))
+    }
+
+    fn from_storage(shape: NDims, storage: HostStorage<T>) -> Self {
+        Self::new(Axes::new_with_default_stride(shape), storage)
+    }
+
+    fn check_length(shape: &NDims, len: usize) -> Result<(), ShapeError> {
+        let expected = shape.len();
+        if expected == len {
+            Ok(())
+        } else {
+            Err(ShapeError::LengthMismatch { expected, found: len })
+        }
+    }
+}
+
+impl<T> FromIterator<T> for Array1<T>
+where
+    T: Copy,
+{
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let data: Vec<T> = iter.into_iter().collect();
+        Self::from_storage(Dim1::new([data.len()]), HostStorage::from_vec(data))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        backend::{
+            host::host_storage::MEM_ALIGN,
+            traits::{ContainerLength, ScalarAccessor},
+        },
+        dimension::{dim::Dim3, dyn_dim::DimDyn},
+    };
+
+    #[test]
+    fn test_from_vec() {
+        let array =
+            Array1::from_vec(Dim1::new([5]), vec![1, 2, 3, 4, 5]).unwrap();
+        assert_eq!(array.len(), 5);
+
+        for i in 0..5 {
+            assert_eq!(array.get_scalar(i), i + 1);
+        }
+    }
+
+    #[test]
+    fn test_from_vec_length_mismatch() {
+        let result = Array1::from_vec(Dim1::new([4]), vec![1, 2, 3]);
+        assert_eq!(
+            result.err(),
+            Some(ShapeError::LengthMismatch { expected: 4, found: 3 })
+        );
+    }
+
+    #[test]
+    fn test_from_vec_reuses_aligned_allocation() {
+        let mut data = Vec::<u64>::with_capacity(4096);
+        data.extend(0..4096);
+
+        // Allocations this large are typically page-aligned, but this is not
+        // guaranteed, so only check reuse when alignment permits
+        let ptr = data.as_ptr();
+        let array = Array1::from_vec(Dim1::new([4096]), data).unwrap();
+
+        if (ptr as usize).is_multiple_of(MEM_ALIGN) {
+            assert_eq!(array.storage.ptr.0.as_ptr().cast_const(), ptr);
+        }
+
+        assert_eq!(array.get_scalar(4095), 4095);
+    }
+
+    #[test]
+    fn test_from_slice_dyn() {
+        let data: Vec<i32> = (0..24).collect();
+        let array =
+            ArrayBase::<HostBackend, HostStorage<i32>, DimDyn>::from_slice(
+                DimDyn::new_from(vec![2usize, 3, 4]),
+                &data,
+            )
+            .unwrap();
+
+        assert_eq!(array.len(), 24);
+        assert_eq!(array.strides().as_slice(), &[12, 4, 1]);
+        assert_eq!(array.get_scalar(23), 23);
+    }
+
+    #[test]
+    fn test_from_shape_iter() {
+        let array =
+            ArrayBase::<HostBackend, HostStorage<u32>, Dim3>::from_shape_iter(
+                Dim3::new([2, 2, 2]),
+                (0..8).map(|x| x * 2),
+            )
+            .unwrap();
+        assert_eq!(array.get_scalar(7), 14);
+
+        let result =
+            ArrayBase::<HostBackend, HostStorage<u32>, Dim3>::from_shape_iter(
+                Dim3::new([2, 2, 2]),
+                0..9,
+            );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_from_shape_fn() {
+        let array =
+            ArrayBase::<HostBackend, HostStorage<usize>, Dim3>::from_shape_fn(
+                Dim3::new([2, 3, 4]),
+                |index| index[0] * 100 + index[1] * 10 + index[2],
+            );
+
+        let mut i = 0;
+        for a in 0..2 {
+            for b in 0..3 {
+                for c in 0..4 {
+                    assert_eq!(array.get_scalar(i), a * 100 + b * 10 + c);
+                    i += 1;
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_from_iterator() {
+        let array: Array1<f64> = (0..10).map(f64::from).collect();
+        assert_eq!(array.len(), 10);
+        assert_eq!(array.shape().as_slice(), &[10]);
+        assert!((array.get_scalar(9) - 9.0).abs() < f64::EPSILON);
+
+        let empty: Array1<f64> = std::iter::empty().collect();
+        assert!(empty.is_empty());
+    }
+}
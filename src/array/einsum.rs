@@ -0,0 +1,706 @@
+//! Einstein summation over any number of operands.
+
+use std::collections::BTreeMap;
+
+use rayon::prelude::*;
+
+use crate::{
+    array::base::ArrayBase,
+    backend::{
+        host::{
+            host_backend::HostBackend,
+            host_gemm::{host_batched_gemm, GemmScalar},
+            host_storage::HostStorage,
+        },
+        traits::Storage,
+    },
+    dimension::{
+        axes::Axes, dim::Dimension, dyn_dim::DimDyn, index::next_index,
+        stride::advance,
+    },
+    error::ShapeError,
+    types::UDim,
+};
+
+/// An operand of [`einsum`].
+///
+/// This is implemented for every host array and view, so operands with
+/// different storage and dimension types can be passed together, as in
+/// `einsum("ij,j->i", &[&matrix, &vector])`.
+pub trait EinsumOperand<T>: Sync {
+    /// The shape of the operand
+    fn einsum_shape(&self) -> &[UDim];
+
+    /// The strides of the operand, stored as in
+    /// [`Stride`](crate::dimension::stride::Stride)
+    fn einsum_strides(&self) -> &[UDim];
+
+    /// The location of the first element within the underlying storage
+    fn einsum_offset(&self) -> usize;
+
+    /// The element at `offset` within the underlying storage
+    fn einsum_element(&self, offset: usize) -> T;
+}
+
+impl<StorageType, NDims> EinsumOperand<StorageType::Scalar>
+    for ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: Storage + Sync,
+    NDims: Dimension + Sync,
+{
+    fn einsum_shape(&self) -> &[UDim] {
+        self.axes.shape.as_slice()
+    }
+
+    fn einsum_strides(&self) -> &[UDim] {
+        self.axes.stride.strides.as_slice()
+    }
+
+    fn einsum_offset(&self) -> usize {
+        self.axes.stride.offset
+    }
+
+    #[inline(always)]
+    fn einsum_element(&self, offset: usize) -> StorageType::Scalar {
+        self.storage[offset]
+    }
+}
+
+/// The parsed subscripts of an [`einsum`] call
+struct Subscripts {
+    /// The labels of the axes of each operand
+    inputs: Vec<Vec<char>>,
+
+    /// The labels of the axes of the result
+    output: Vec<char>,
+}
+
+/// Parse `subscripts` for a contraction of `operands` operands.
+///
+/// Labels are ASCII letters, and whitespace is ignored. If there is no
+/// `->`, the output contains every label which appears exactly once, in
+/// alphabetical order, as in `NumPy`.
+fn parse_subscripts(
+    subscripts: &str,
+    operands: usize,
+) -> Result<Subscripts, ShapeError> {
+    let invalid = |reason: String| ShapeError::InvalidSubscripts {
+        subscripts: subscripts.to_string(),
+        reason,
+    };
+
+    let compact: String =
+        subscripts.chars().filter(|c| !c.is_whitespace()).collect();
+    let (inputs, output) = match compact.split_once("->") {
+        Some((inputs, output)) => (inputs, Some(output)),
+        None => (compact.as_str(), None),
+    };
+
+    let parse_labels = |term: &str| {
+        term.chars()
+            .map(|c| {
+                if c.is_ascii_alphabetic() {
+                    Ok(c)
+                } else {
+                    Err(invalid(format!("unexpected character '{c}'")))
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()
+    };
+
+    let inputs =
+        inputs.split(',').map(parse_labels).collect::<Result<Vec<_>, _>>()?;
+    if inputs.len() != operands {
+        return Err(invalid(format!(
+            "{} terms were given for {operands} operands",
+            inputs.len()
+        )));
+    }
+
+    let output = if let Some(output) = output {
+        let output = parse_labels(output)?;
+        for (i, label) in output.iter().enumerate() {
+            if output[..i].contains(label) {
+                return Err(invalid(format!(
+                    "output label '{label}' is repeated"
+                )));
+            }
+            if !inputs.iter().any(|term| term.contains(label)) {
+                return Err(invalid(format!(
+                    "output label '{label}' does not appear in any operand"
+                )));
+            }
+        }
+        output
+    } else {
+        let mut counts = BTreeMap::new();
+        for &label in inputs.iter().flatten() {
+            *counts.entry(label).or_insert(0_usize) += 1;
+        }
+        counts
+            .into_iter()
+            .filter(|&(_, count)| count == 1)
+            .map(|(label, _)| label)
+            .collect()
+    };
+
+    Ok(Subscripts { inputs, output })
+}
+
+/// The elements of a [`Term`]
+enum TermData<'a, T> {
+    /// One of the operands of the contraction
+    Operand(&'a dyn EinsumOperand<T>),
+
+    /// An intermediate result, stored contiguously
+    Owned(HostStorage<T>),
+}
+
+/// An operand or intermediate result of a contraction, with a label for each
+/// axis. Labels are unique within a term.
+struct Term<'a, T> {
+    labels: Vec<char>,
+    shape: Vec<UDim>,
+    strides: Vec<UDim>,
+    offset: usize,
+    data: TermData<'a, T>,
+}
+
+impl<'a, T: GemmScalar> Term<'a, T> {
+    /// Label the axes of `operand`, recording the length of each label in
+    /// `sizes`. Axes with the same label are merged into a single axis along
+    /// their diagonal by adding their strides, so no elements are copied.
+    fn from_operand(
+        operand: &'a dyn EinsumOperand<T>,
+        labels: &[char],
+        sizes: &mut BTreeMap<char, usize>,
+        invalid: impl Fn(String) -> ShapeError,
+    ) -> Result<Self, ShapeError> {
+        let shape = operand.einsum_shape();
+        let strides = operand.einsum_strides();
+        if shape.len() != labels.len() {
+            return Err(invalid(format!(
+                "an operand with {} axes has {} labels",
+                shape.len(),
+                labels.len()
+            )));
+        }
+
+        let mut term = Self {
+            labels: Vec::new(),
+            shape: Vec::new(),
+            strides: Vec::new(),
+            offset: operand.einsum_offset(),
+            data: TermData::Operand(operand),
+        };
+
+        for ((&label, &len), &stride) in labels.iter().zip(shape).zip(strides) {
+            let size = *sizes.entry(label).or_insert(len);
+            if size != len {
+                return Err(invalid(format!(
+                    "label '{label}' is used for axes of lengths {size} and \
+                     {len}"
+                )));
+            }
+
+            // A repeated label selects a diagonal, so stepping along it steps
+            // along every axis with that label. The strides are signed, so
+            // they are added with wrapping arithmetic
+            if let Some(axis) = term.labels.iter().position(|&l| l == label) {
+                term.strides[axis] = term.strides[axis].wrapping_add(stride);
+            } else {
+                term.labels.push(label);
+                term.shape.push(len);
+                term.strides.push(stride);
+            }
+        }
+
+        Ok(term)
+    }
+
+    /// Create a term from contiguous, row-major `storage`
+    fn owned(
+        labels: Vec<char>,
+        shape: Vec<UDim>,
+        storage: HostStorage<T>,
+    ) -> Self {
+        let mut strides = vec![1; shape.len()];
+        for axis in (0..shape.len().saturating_sub(1)).rev() {
+            strides[axis] = strides[axis + 1] * shape[axis + 1];
+        }
+
+        Self {
+            labels,
+            shape,
+            strides,
+            offset: 0,
+            data: TermData::Owned(storage),
+        }
+    }
+
+    /// The element at `offset` within the underlying storage
+    #[inline(always)]
+    fn get(&self, offset: usize) -> T {
+        match &self.data {
+            TermData::Operand(operand) => operand.einsum_element(offset),
+            TermData::Owned(storage) => storage[offset],
+        }
+    }
+
+    /// The offset of every element of the sub-array spanned by the axes with
+    /// the given `labels`, relative to the first element, in row-major order
+    fn offsets(&self, labels: &[char]) -> Vec<usize> {
+        let axes: Vec<usize> = labels
+            .iter()
+            .map(|label| {
+                self.labels
+                    .iter()
+                    .position(|l| l == label)
+                    .expect("label is not an axis of the term")
+            })
+            .collect();
+        let shape: Vec<UDim> =
+            axes.iter().map(|&axis| self.shape[axis]).collect();
+
+        let count: usize = shape.iter().product();
+        let mut index: Vec<UDim> = vec![0; shape.len()];
+        let mut offsets = Vec::with_capacity(count);
+        for _ in 0..count {
+            offsets.push(
+                axes.iter().zip(&index).fold(0_usize, |acc, (&axis, &i)| {
+                    advance(acc, i, self.strides[axis])
+                }),
+            );
+            next_index(&mut index, &shape);
+        }
+        offsets
+    }
+
+    /// Sum over every axis whose label is not in `labels`, arranging the
+    /// remaining axes in the order of `labels`. Elements of the result are
+    /// computed in parallel.
+    fn reduce_to(&self, labels: &[char]) -> Self {
+        let summed: Vec<char> = self
+            .labels
+            .iter()
+            .filter(|label| !labels.contains(label))
+            .copied()
+            .collect();
+
+        let outer = self.offsets(labels);
+        let inner = self.offsets(&summed);
+        let data: Vec<T> = outer
+            .par_iter()
+            .map(|&outer| {
+                let base = self.offset.wrapping_add(outer);
+                inner.iter().fold(T::zero(), |acc, &inner| {
+                    acc + self.get(base.wrapping_add(inner))
+                })
+            })
+            .collect();
+
+        let shape = labels.iter().map(|label| self.len_of(*label)).collect();
+        Self::owned(labels.to_vec(), shape, HostStorage::from_vec(data))
+    }
+
+    /// The length of the axis with the given label
+    fn len_of(&self, label: char) -> UDim {
+        let axis = self.labels.iter().position(|&l| l == label);
+        self.shape[axis.expect("label is not an axis of the term")]
+    }
+
+    /// Contract `self` with `other`, keeping the axes whose labels are in
+    /// `keep` and summing over every other label shared by both terms.
+    ///
+    /// Labels in both terms which are kept are batch axes, so this is a
+    /// batched matrix multiplication whose rows are the remaining axes of
+    /// `self` and whose columns are the remaining axes of `other`. Every
+    /// label in only one of the terms must be kept.
+    fn contract(&self, other: &Self, keep: &[char]) -> Self {
+        let shared = |label: &&char| other.labels.contains(label);
+        let batch: Vec<char> = self
+            .labels
+            .iter()
+            .filter(|label| shared(label) && keep.contains(label))
+            .copied()
+            .collect();
+        let contracted: Vec<char> = self
+            .labels
+            .iter()
+            .filter(|label| shared(label) && !keep.contains(label))
+            .copied()
+            .collect();
+        let rows: Vec<char> = self
+            .labels
+            .iter()
+            .filter(|label| !shared(label))
+            .copied()
+            .collect();
+        let columns: Vec<char> = other
+            .labels
+            .iter()
+            .filter(|label| !self.labels.contains(label))
+            .copied()
+            .collect();
+
+        let (a_batch, b_batch) = (self.offsets(&batch), other.offsets(&batch));
+        let (a_rows, a_inner) =
+            (self.offsets(&rows), self.offsets(&contracted));
+        let (b_inner, b_columns) =
+            (other.offsets(&contracted), other.offsets(&columns));
+
+        let storage = host_batched_gemm(
+            a_batch.len(),
+            a_rows.len(),
+            a_inner.len(),
+            b_columns.len(),
+            |index, i, p| {
+                self.get(
+                    self.offset
+                        .wrapping_add(a_batch[index])
+                        .wrapping_add(a_rows[i])
+                        .wrapping_add(a_inner[p]),
+                )
+            },
+            |index, p, j| {
+                other.get(
+                    other
+                        .offset
+                        .wrapping_add(b_batch[index])
+                        .wrapping_add(b_inner[p])
+                        .wrapping_add(b_columns[j]),
+                )
+            },
+        );
+
+        let labels: Vec<char> =
+            batch.into_iter().chain(rows).chain(columns).collect();
+        let shape = labels
+            .iter()
+            .map(|&label| {
+                if self.labels.contains(&label) {
+                    self.len_of(label)
+                } else {
+                    other.len_of(label)
+                }
+            })
+            .collect();
+        Self::owned(labels, shape, storage)
+    }
+}
+
+/// The labels of terms `i` and `j` which are still needed after contracting
+/// them together, since they appear in the output or in another term
+fn needed_labels<T>(
+    terms: &[Term<'_, T>],
+    i: usize,
+    j: usize,
+    output: &[char],
+) -> Vec<char> {
+    let mut labels = terms[i].labels.clone();
+    labels.extend(
+        terms[j].labels.iter().filter(|l| !terms[i].labels.contains(l)),
+    );
+    labels.retain(|label| {
+        output.contains(label)
+            || terms.iter().enumerate().any(|(t, term)| {
+                t != i && t != j && term.labels.contains(label)
+            })
+    });
+    labels
+}
+
+/// Compute a tensor contraction described by Einstein summation subscripts,
+/// with a result of any number of dimensions.
+///
+/// The subscripts label each axis of each operand with a letter, such as
+/// `"bij,bjk->bik"`. Labels which do not appear in the output (after `->`)
+/// are summed over. Repeating a label within one operand takes its diagonal,
+/// so `"ii->"` is the trace and `"ii->i"` the diagonal, and a label in no
+/// other operand is summed alone, so `"ij->i"` sums each row. Without `->`,
+/// the output contains every label which appears exactly once, in
+/// alphabetical order.
+///
+/// Each operand is first reduced by summing labels used by no other operand.
+/// Then operands are contracted in pairs, choosing the pair with the smallest
+/// result at each step, and each pairwise contraction is computed by the
+/// batched host GEMM kernel. Operands are read through their strides, so
+/// views are never copied.
+///
+/// # Errors
+/// Returns [`ShapeError::InvalidSubscripts`] if the subscripts are malformed,
+/// do not match the number of operands or their numbers of axes, or use a
+/// label for axes of different lengths.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::{Array1, Array2};
+/// use tensr::dimension::dim::{Dim1, Dim2};
+///
+/// let a = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+/// let x = Array1::from_vec(Dim1::new([2]), vec![1, -1]).unwrap();
+///
+/// let ax = tensr::einsum("ij,j->i", &[&a, &x]).unwrap();
+/// assert_eq!(ax[[0].as_slice()], -1);
+///
+/// let trace = tensr::einsum("ii", &[&a]).unwrap();
+/// assert_eq!(trace[[].as_slice()], 5);
+/// ```
+pub fn einsum<T: GemmScalar>(
+    subscripts: &str,
+    operands: &[&dyn EinsumOperand<T>],
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, DimDyn>, ShapeError> {
+    einsum_typed(subscripts, operands)
+}
+
+/// Compute a tensor contraction described by Einstein summation subscripts,
+/// with a result of the fixed dimension type `NDims`. See [`einsum`].
+///
+/// # Errors
+/// Returns [`ShapeError::InvalidSubscripts`] under the same conditions as
+/// [`einsum`], or [`ShapeError::DimensionMismatch`] if the result does not
+/// have the number of dimensions of `NDims`.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array3;
+/// use tensr::dimension::dim::{Dim2, Dim3};
+///
+/// let a = Array3::from_shape_fn(Dim3::new([2, 3, 4]), |i| i[0] + i[1] + i[2]);
+/// let sums = tensr::einsum_typed::<Dim2, _>("ijk->ki", &[&a]).unwrap();
+/// assert_eq!(sums[[3, 1]], 3 * 4 + 3);
+/// ```
+pub fn einsum_typed<NDims: Dimension, T: GemmScalar>(
+    subscripts: &str,
+    operands: &[&dyn EinsumOperand<T>],
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, NDims>, ShapeError> {
+    let invalid = |reason: String| ShapeError::InvalidSubscripts {
+        subscripts: subscripts.to_string(),
+        reason,
+    };
+
+    let Subscripts { inputs, output } =
+        parse_subscripts(subscripts, operands.len())?;
+
+    let mut sizes = BTreeMap::new();
+    let mut terms = operands
+        .iter()
+        .zip(&inputs)
+        .map(|(&operand, labels)| {
+            Term::from_operand(operand, labels, &mut sizes, invalid)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let shape: Vec<UDim> = output.iter().map(|label| sizes[label]).collect();
+    let shape = NDims::from_slice(&shape).ok_or_else(|| {
+        ShapeError::DimensionMismatch {
+            expected: NDims::NDIM.unwrap_or_default(),
+            found: shape.len(),
+        }
+    })?;
+
+    // Sum over labels which only appear in a single operand, so every label
+    // of every term is either in the output or shared with another term.
+    // Contracting two terms keeps exactly the labels still needed, so this
+    // remains true until one term is left
+    for i in 0..terms.len() {
+        let needed = needed_labels(&terms, i, i, &output);
+        if needed.len() < terms[i].labels.len() {
+            terms[i] = terms[i].reduce_to(&needed);
+        }
+    }
+
+    while terms.len() > 1 {
+        let mut best = (usize::MAX, 0, 1);
+        for i in 0..terms.len() {
+            for j in i + 1..terms.len() {
+                let size = needed_labels(&terms, i, j, &output)
+                    .iter()
+                    .map(|label| sizes[label])
+                    .product();
+                if size < best.0 {
+                    best = (size, i, j);
+                }
+            }
+        }
+
+        let (_, i, j) = best;
+        let keep = needed_labels(&terms, i, j, &output);
+        let rhs = terms.remove(j);
+        let lhs = terms.remove(i);
+        terms.push(lhs.contract(&rhs, &keep));
+    }
+
+    let result = terms.swap_remove(0);
+    let result = match result.data {
+        TermData::Owned(_) if result.labels == output => result,
+        _ => result.reduce_to(&output),
+    };
+    let TermData::Owned(storage) = result.data else {
+        unreachable!("reduced terms are owned")
+    };
+
+    Ok(ArrayBase::new(Axes::new_with_default_stride(shape), storage))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        array::{
+            einsum::{einsum, einsum_typed},
+            type_remap::{Array1, Array2, Array3, ArrayView2},
+        },
+        dimension::dim::{Dim0, Dim1, Dim2, Dim3, Dimension},
+        error::ShapeError,
+        s,
+    };
+
+    fn matrix(rows: usize, cols: usize, seed: usize) -> Array2<i64> {
+        Array2::from_shape_fn(Dim2::new([rows, cols]), |i| {
+            i64::try_from((i[0] * 7 + i[1] * 3 + seed) % 11).unwrap() - 5
+        })
+    }
+
+    #[test]
+    fn test_matrix_products() {
+        let a = matrix(3, 4, 0);
+        let b = matrix(4, 5, 1);
+
+        let product = einsum_typed::<Dim2, _>("ij,jk->ik", &[&a, &b]).unwrap();
+        let expected = a.matmul(&b);
+        assert_eq!(product.shape().as_slice(), &[3, 5]);
+        for i in 0..3 {
+            for k in 0..5 {
+                assert_eq!(product[[i, k]], expected[[i, k]]);
+            }
+        }
+
+        // The transposed product, and implicit output
+        let transposed =
+            einsum_typed::<Dim2, _>("ij,jk->ki", &[&a, &b]).unwrap();
+        assert_eq!(transposed[[4, 2]], expected[[2, 4]]);
+        let implicit = einsum_typed::<Dim2, _>("ij,jk", &[&a, &b]).unwrap();
+        assert_eq!(implicit[[2, 4]], expected[[2, 4]]);
+
+        let batched = Array3::from_shape_fn(Dim3::new([2, 3, 4]), |i| {
+            i64::try_from(i[0] + i[1] * i[2]).unwrap()
+        });
+        let stacked = Array3::from_shape_fn(Dim3::new([2, 4, 2]), |i| {
+            i64::try_from(i[0] * 2 + i[1]).unwrap()
+                - i64::try_from(i[2]).unwrap()
+        });
+        let product =
+            einsum_typed::<Dim3, _>("bij,bjk->bik", &[&batched, &stacked])
+                .unwrap();
+        let expected = batched.matmul(&stacked);
+        assert_eq!(product[[1, 2, 1]], expected[[1, 2, 1]]);
+        assert_eq!(product[[0, 1, 0]], expected[[0, 1, 0]]);
+    }
+
+    #[test]
+    fn test_traces_and_diagonals() {
+        let a = matrix(4, 4, 2);
+        let expected: i64 = (0..4).map(|i| a[[i, i]]).sum();
+
+        let trace = einsum_typed::<Dim0, _>("ii->", &[&a]).unwrap();
+        assert_eq!(trace[[]], expected);
+
+        let diagonal = einsum_typed::<Dim1, _>("ii->i", &[&a]).unwrap();
+        assert_eq!(diagonal[2], a[[2, 2]]);
+
+        let rows = einsum_typed::<Dim1, _>("ij->i", &[&a]).unwrap();
+        assert_eq!(rows[1], (0..4).map(|j| a[[1, j]]).sum::<i64>());
+
+        // A diagonal of a reversed view
+        let view: ArrayView2<'_, i64> = a.slice(s![..;-1, ..]);
+        let anti = einsum_typed::<Dim1, _>("ii->i", &[&view]).unwrap();
+        assert_eq!(anti[0], a[[3, 0]]);
+        assert_eq!(anti[3], a[[0, 3]]);
+    }
+
+    #[test]
+    fn test_outer_and_mixed_operands() {
+        let x = Array1::from_vec(Dim1::new([3]), vec![1, 2, 3]).unwrap();
+        let y = Array1::from_vec(Dim1::new([2]), vec![10, 20]).unwrap();
+
+        let outer = einsum_typed::<Dim2, _>("i,j->ij", &[&x, &y]).unwrap();
+        assert_eq!(outer.shape().as_slice(), &[3, 2]);
+        assert_eq!(outer[[2, 1]], 60);
+
+        let inner = einsum_typed::<Dim0, _>("i,i", &[&x, &x]).unwrap();
+        assert_eq!(inner[[]], 14);
+
+        // Operands of different dimension types
+        let a = matrix(2, 3, 3);
+        let ax = einsum_typed::<Dim1, _>("ij,j->i", &[&a, &x]).unwrap();
+        assert_eq!(ax[1], a[[1, 0]] + 2 * a[[1, 1]] + 3 * a[[1, 2]]);
+
+        let dynamic = einsum("ij,j->i", &[&a, &x]).unwrap();
+        assert_eq!(dynamic.shape().as_slice(), &[2]);
+    }
+
+    #[test]
+    #[allow(clippy::many_single_char_names)]
+    fn test_many_operands() {
+        let a = matrix(3, 4, 0);
+        let b = matrix(4, 6, 1);
+        let c = matrix(6, 2, 2);
+        let d = matrix(2, 5, 3);
+
+        let chain =
+            einsum_typed::<Dim2, _>("ij,jk,kl,lm->im", &[&a, &b, &c, &d])
+                .unwrap();
+        let expected = a.matmul(&b).matmul(&c).matmul(&d);
+        for i in 0..3 {
+            for m in 0..5 {
+                assert_eq!(chain[[i, m]], expected[[i, m]]);
+            }
+        }
+
+        // A label shared by three operands is kept until the last of them
+        let x = Array1::from_vec(Dim1::new([4]), vec![1, -1, 2, 0]).unwrap();
+        let weighted = einsum_typed::<Dim1, _>(
+            "ij,j,jk->k",
+            &[&a.slice(s![..1, ..]), &x, &b],
+        )
+        .unwrap();
+        let expected: i64 = (0..4).map(|j| a[[0, j]] * x[j] * b[[j, 5]]).sum();
+        assert_eq!(weighted[5], expected);
+    }
+
+    #[test]
+    fn test_errors() {
+        let a = matrix(2, 3, 0);
+        let invalid = |subscripts: &str, reason: &str| {
+            Some(ShapeError::InvalidSubscripts {
+                subscripts: subscripts.to_string(),
+                reason: reason.to_string(),
+            })
+        };
+
+        assert_eq!(
+            einsum("ij,jk", &[&a]).err(),
+            invalid("ij,jk", "2 terms were given for 1 operands")
+        );
+        assert_eq!(
+            einsum("ijk", &[&a]).err(),
+            invalid("ijk", "an operand with 2 axes has 3 labels")
+        );
+        assert_eq!(
+            einsum("ii", &[&a]).err(),
+            invalid("ii", "label 'i' is used for axes of lengths 2 and 3")
+        );
+        assert_eq!(
+            einsum("ij->k", &[&a]).err(),
+            invalid("ij->k", "output label 'k' does not appear in any operand")
+        );
+        assert_eq!(
+            einsum("i1", &[&a]).err(),
+            invalid("i1", "unexpected character '1'")
+        );
+        assert_eq!(
+            einsum_typed::<Dim1, _>("ij", &[&a]).err(),
+            Some(ShapeError::DimensionMismatch { expected: 1, found: 2 })
+        );
+    }
+}
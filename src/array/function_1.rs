@@ -0,0 +1,217 @@
+//! Lazily evaluated element-wise operations with one operand.
+
+use std::marker::PhantomData;
+
+use crate::{
+    array::{base::ArrayBase, traits::GetWriteableBuffer},
+    backend::{
+        op_traits, traits,
+        traits::{
+            ContainerLength, ContainerScalarType, ContainerShape, OwnedStorage,
+        },
+    },
+    dimension::{axes::Axes, dim::Dimension},
+};
+
+/// A lazily-evaluated operation with one operand, which can be evaluated into
+/// an output container of type `Out`
+pub trait Function1<Out> {
+    /// Evaluate the operation, writing each element of the result into `out`
+    fn apply(&self, out: &mut Out);
+}
+
+/// A lazily-evaluated element-wise unary operation, such as `-a` or
+/// `a.sqrt()`. The result has the same shape as the argument.
+pub struct TensrFn1<'a, Backend, Op, Arg> {
+    pub(crate) arg: Arg,
+
+    op: PhantomData<Op>,
+    backend: PhantomData<Backend>,
+    lifetime: PhantomData<&'a ()>,
+}
+
+macro_rules! tensr_fn_1_impl {
+    ($($ref_type: tt)*) => {
+        impl<'a, Backend, Op, Arg> ContainerScalarType
+            for $($ref_type)* TensrFn1<'a, Backend, Op, Arg>
+        where
+            Backend: traits::Backend,
+            Op: op_traits::UnaryOp,
+            Arg: ContainerScalarType,
+        {
+            type Scalar = Arg::Scalar;
+        }
+
+        impl<'a, Backend, Op, Arg> ContainerShape
+            for $($ref_type)* TensrFn1<'a, Backend, Op, Arg>
+        where
+            Backend: traits::Backend,
+            Op: op_traits::UnaryOp,
+            Arg: ContainerShape,
+        {
+            type NDims = Arg::NDims;
+
+            fn get_shape(&self) -> Self::NDims {
+                self.arg.get_shape()
+            }
+        }
+    };
+}
+
+tensr_fn_1_impl!();
+tensr_fn_1_impl!(&'a);
+tensr_fn_1_impl!(&'a mut);
+
+impl<Backend, Op, Arg> ContainerLength for TensrFn1<'_, Backend, Op, Arg>
+where
+    Backend: traits::Backend,
+    Op: op_traits::UnaryOp,
+    Arg: ContainerLength,
+{
+    fn len(&self) -> usize {
+        self.arg.len()
+    }
+}
+
+impl<Backend, Op, Arg> TensrFn1<'_, Backend, Op, Arg> {
+    /// Create a new function object operating on `arg`.
+    pub const fn new(arg: Arg) -> Self {
+        Self {
+            arg,
+            op: PhantomData,
+            backend: PhantomData,
+            lifetime: PhantomData,
+        }
+    }
+}
+
+impl<Backend, Op, Arg> TensrFn1<'_, Backend, Op, Arg>
+where
+    Backend: traits::Backend,
+{
+    /// Evaluate the expression, returning a new owned array containing the
+    /// result.
+    ///
+    /// If the argument is (or contains) an owned, contiguous array of the
+    /// same shape as the result, its buffer is reused for the output. This is
+    /// equivalent to calling `.into()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::dimension::dim::Dim1;
+    ///
+    /// let a = Array1::from_vec(Dim1::new([3]), vec![1.0f64, 4.0, 9.0]).unwrap();
+    ///
+    /// let b = a.sqrt().eval();
+    /// assert_eq!(b[2], 3.0);
+    ///
+    /// let c: Array1<f64> = (-&b).into();
+    /// assert_eq!(c[1], -2.0);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn eval(
+        self,
+    ) -> ArrayBase<
+        Backend,
+        Backend::OwnedStorage<<Self as ContainerScalarType>::Scalar>,
+        <Self as ContainerShape>::NDims,
+    >
+    where
+        Self: ContainerScalarType + ContainerShape,
+        ArrayBase<
+            Backend,
+            Backend::OwnedStorage<<Self as ContainerScalarType>::Scalar>,
+            <Self as ContainerShape>::NDims,
+        >: From<Self>,
+    {
+        self.into()
+    }
+
+    /// Evaluate the expression, writing the result into `out`. The output
+    /// may be an owned array or a mutable view, but must have the same shape
+    /// as the expression.
+    ///
+    /// # Panics
+    /// Panics if the shape of `out` does not match the shape of the
+    /// expression.
+    #[track_caller]
+    pub fn eval_into<Out>(self, out: &mut Out)
+    where
+        Self: Function1<Out>,
+    {
+        self.apply(out);
+    }
+}
+
+impl<'a, Backend, Op, Arg, StorageType, NDims>
+    From<TensrFn1<'a, Backend, Op, Arg>>
+    for ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: OwnedStorage,
+    NDims: Dimension,
+    TensrFn1<'a, Backend, Op, Arg>: ContainerShape<NDims = NDims>
+        + GetWriteableBuffer<Buffer = StorageType::Raw>
+        + Function1<Self>,
+{
+    fn from(mut expr: TensrFn1<'a, Backend, Op, Arg>) -> Self {
+        let shape = expr.get_shape();
+        let len = shape.len();
+
+        // Safety: See the implementation for `TensrFn2`. Each element of the
+        // argument is read before the same element of the output is written
+        let storage = unsafe {
+            expr.get_buffer_and_set_no_free(len).map_or_else(
+                || StorageType::new_from_shape_uninit(&shape),
+                |buffer| StorageType::from_raw(buffer, len),
+            )
+        };
+
+        let mut out = Self::new(Axes::new_with_default_stride(shape), storage);
+        expr.apply(&mut out);
+        out
+    }
+}
+
+impl<Backend, Op, Arg> GetWriteableBuffer for TensrFn1<'_, Backend, Op, Arg>
+where
+    Backend: traits::Backend,
+    Op: op_traits::UnaryOp,
+    Arg: GetWriteableBuffer,
+{
+    type Buffer = Arg::Buffer;
+
+    unsafe fn get_buffer_and_set_no_free(
+        &mut self,
+        len: usize,
+    ) -> Option<Self::Buffer> {
+        self.arg.get_buffer_and_set_no_free(len)
+    }
+}
+
+macro_rules! tensr_fn_1_ref_buffer_impl {
+    ($($ref_type: tt)*) => {
+        impl<'a, Backend, Op, Arg> GetWriteableBuffer
+            for $($ref_type)* TensrFn1<'a, Backend, Op, Arg>
+        where
+            Backend: traits::Backend,
+            Op: op_traits::UnaryOp,
+            Arg: GetWriteableBuffer,
+        {
+            type Buffer = Arg::Buffer;
+
+            unsafe fn get_buffer_and_set_no_free(
+                &mut self,
+                _len: usize,
+            ) -> Option<Self::Buffer> {
+                // The argument is still owned by the borrowed expression, so
+                // its buffer cannot be taken
+                None
+            }
+        }
+    };
+}
+
+tensr_fn_1_ref_buffer_impl!(&'a);
+tensr_fn_1_ref_buffer_impl!(&'a mut);
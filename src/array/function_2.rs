@@ -1,20 +1,42 @@
+//! Lazily evaluated element-wise operations with two operands.
+
 use std::marker::PhantomData;
 
 use crate::{
-    array::traits::GetWriteableBuffer,
+    array::{base::ArrayBase, traits::GetWriteableBuffer},
     backend::{
         op_traits, traits,
-        traits::{ContainerLength, ContainerScalarType},
+        traits::{
+            ContainerLength, ContainerScalarType, ContainerShape, OwnedStorage,
+        },
+    },
+    dimension::{
+        axes::Axes,
+        broadcast::{broadcast_shapes, BroadcastShape},
+        dim::Dimension,
+        dyn_dim::DimDyn,
     },
+    error::ShapeError,
 };
 
+/// A lazily-evaluated operation with two operands, which can be evaluated into
+/// an output container of type `Out`
 pub trait Function2<Out> {
+    /// Evaluate the operation, writing each element of the result into `out`
     fn apply(&self, out: &mut Out);
 }
 
+/// A lazily-evaluated binary operation. The operands are broadcast together,
+/// so the result has the broadcast shape of `Lhs` and `Rhs`, and no broadcast
+/// copies of either operand are ever created.
 pub struct TensrFn2<'a, Backend, Op, Lhs, Rhs> {
     pub(crate) lhs: Lhs,
     pub(crate) rhs: Rhs,
+
+    /// The broadcast shape of the operands. This is converted into the
+    /// correct [`Dimension`] type by [`ContainerShape::get_shape`].
+    pub(crate) shape: DimDyn,
+
     op: PhantomData<Op>,
     backend: PhantomData<Backend>,
     lifetime: PhantomData<&'a ()>,
@@ -28,7 +50,7 @@ where
     Lhs: ContainerScalarType,
     Rhs: ContainerScalarType<Scalar = Lhs::Scalar>,
 {
-    type Scalar = Lhs::Scalar;
+    type Scalar = Op::Output<Lhs::Scalar>;
 }
 
 impl<'a, Backend, Op, Lhs, Rhs> ContainerScalarType
@@ -39,7 +61,18 @@ where
     Lhs: ContainerScalarType,
     Rhs: ContainerScalarType<Scalar = Lhs::Scalar>,
 {
-    type Scalar = Lhs::Scalar;
+    type Scalar = Op::Output<Lhs::Scalar>;
+}
+
+impl<'a, Backend, Op, Lhs, Rhs> ContainerScalarType
+    for &'a mut TensrFn2<'a, Backend, Op, Lhs, Rhs>
+where
+    Backend: traits::Backend,
+    Op: op_traits::BinaryOp,
+    Lhs: ContainerScalarType,
+    Rhs: ContainerScalarType<Scalar = Lhs::Scalar>,
+{
+    type Scalar = Op::Output<Lhs::Scalar>;
 }
 
 impl<Backend, Op, Lhs, Rhs> ContainerLength
@@ -47,23 +80,175 @@ impl<Backend, Op, Lhs, Rhs> ContainerLength
 where
     Backend: traits::Backend,
     Op: op_traits::BinaryOp,
-    Lhs: ContainerLength,
-    Rhs: ContainerLength,
 {
     fn len(&self) -> usize {
-        self.lhs.len()
+        self.shape.len()
     }
 }
 
-impl<Backend, Op, Lhs, Rhs> TensrFn2<'_, Backend, Op, Lhs, Rhs> {
-    pub const fn new(lhs: Lhs, rhs: Rhs) -> Self {
-        Self {
+macro_rules! tensr_fn_2_shape_impl {
+    ($($ref_type: tt)*) => {
+        impl<'a, Backend, Op, Lhs, Rhs> ContainerShape
+            for $($ref_type)* TensrFn2<'a, Backend, Op, Lhs, Rhs>
+        where
+            Backend: traits::Backend,
+            Op: op_traits::BinaryOp,
+            Lhs: ContainerShape,
+            Rhs: ContainerShape,
+            Lhs::NDims: BroadcastShape<Rhs::NDims>,
+        {
+            type NDims = <Lhs::NDims as BroadcastShape<Rhs::NDims>>::Output;
+
+            fn get_shape(&self) -> Self::NDims {
+                Self::NDims::from_slice(self.shape.as_slice())
+                    .expect("broadcast shape has an invalid number of axes")
+            }
+        }
+    };
+}
+
+tensr_fn_2_shape_impl!();
+tensr_fn_2_shape_impl!(&'a);
+tensr_fn_2_shape_impl!(&'a mut);
+
+impl<Backend, Op, Lhs, Rhs> TensrFn2<'_, Backend, Op, Lhs, Rhs>
+where
+    Lhs: ContainerShape,
+    Rhs: ContainerShape,
+{
+    /// Create a new function object operating on `lhs` and `rhs`.
+    ///
+    /// # Panics
+    /// Panics if the shapes of `lhs` and `rhs` cannot be broadcast together.
+    #[track_caller]
+    pub fn new(lhs: Lhs, rhs: Rhs) -> Self {
+        Self::try_new(lhs, rhs).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Create a new function object operating on `lhs` and `rhs`.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::BroadcastMismatch`] if the shapes of `lhs` and
+    /// `rhs` cannot be broadcast together.
+    pub fn try_new(lhs: Lhs, rhs: Rhs) -> Result<Self, ShapeError> {
+        let shape = broadcast_shapes(
+            lhs.get_shape().as_slice(),
+            rhs.get_shape().as_slice(),
+        )?;
+
+        Ok(Self {
             lhs,
             rhs,
+            shape,
             op: PhantomData,
             backend: PhantomData,
             lifetime: PhantomData,
-        }
+        })
+    }
+}
+
+impl<Backend, Op, Lhs, Rhs> TensrFn2<'_, Backend, Op, Lhs, Rhs>
+where
+    Backend: traits::Backend,
+{
+    /// Evaluate the expression, returning a new owned array containing the
+    /// result.
+    ///
+    /// If one of the operands is an owned, contiguous array of the same shape
+    /// as the result, its buffer is reused for the output instead of
+    /// allocating a new one. This is equivalent to calling `.into()`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let a = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+    /// let b = Array2::from_vec(Dim2::new([1, 2]), vec![10, 20]).unwrap();
+    ///
+    /// let c = (&a + &b).eval();
+    /// assert_eq!(c[[1, 1]], 24);
+    ///
+    /// // Conversions work too
+    /// let d: Array2<i32> = (&c + &c).into();
+    /// assert_eq!(d[[0, 0]], 22);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn eval(
+        self,
+    ) -> ArrayBase<
+        Backend,
+        Backend::OwnedStorage<<Self as ContainerScalarType>::Scalar>,
+        <Self as ContainerShape>::NDims,
+    >
+    where
+        Self: ContainerScalarType + ContainerShape,
+        ArrayBase<
+            Backend,
+            Backend::OwnedStorage<<Self as ContainerScalarType>::Scalar>,
+            <Self as ContainerShape>::NDims,
+        >: From<Self>,
+    {
+        self.into()
+    }
+
+    /// Evaluate the expression, writing the result into `out`. The output
+    /// may be an owned array or a mutable view, but must have the same shape
+    /// as the expression.
+    ///
+    /// # Panics
+    /// Panics if the shape of `out` does not match the shape of the
+    /// expression.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::dimension::dim::Dim1;
+    ///
+    /// let a = Array1::from_vec(Dim1::new([3]), vec![1.0f32, 2.0, 3.0]).unwrap();
+    /// let mut out = Array1::zeros(Dim1::new([3]));
+    ///
+    /// (&a * &a).eval_into(&mut out);
+    /// assert_eq!(out[2], 9.0);
+    /// ```
+    #[track_caller]
+    pub fn eval_into<Out>(self, out: &mut Out)
+    where
+        Self: Function2<Out>,
+    {
+        self.apply(out);
+    }
+}
+
+impl<'a, Backend, Op, Lhs, Rhs, StorageType, NDims>
+    From<TensrFn2<'a, Backend, Op, Lhs, Rhs>>
+    for ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: OwnedStorage,
+    NDims: Dimension,
+    TensrFn2<'a, Backend, Op, Lhs, Rhs>: ContainerShape<NDims = NDims>
+        + GetWriteableBuffer<Buffer = StorageType::Raw>
+        + Function2<Self>,
+{
+    fn from(mut expr: TensrFn2<'a, Backend, Op, Lhs, Rhs>) -> Self {
+        let shape = expr.get_shape();
+        let len = shape.len();
+
+        // Safety: The buffer is taken from an operand which is consumed by
+        // this function, so nothing else can use or free it. Every element of
+        // the output is written by `apply` before the storage is read, and
+        // each element of the reused operand is read before it is overwritten
+        let storage = unsafe {
+            expr.get_buffer_and_set_no_free(len).map_or_else(
+                || StorageType::new_from_shape_uninit(&shape),
+                |buffer| StorageType::from_raw(buffer, len),
+            )
+        };
+
+        let mut out = Self::new(Axes::new_with_default_stride(shape), storage);
+        expr.apply(&mut out);
+        out
     }
 }
 
@@ -71,19 +256,23 @@ impl<Backend, Op, Lhs, Rhs> GetWriteableBuffer
     for TensrFn2<'_, Backend, Op, Lhs, Rhs>
 where
     Backend: traits::Backend,
-    Op: op_traits::BinaryOp,
+    Op: op_traits::BinaryOp + op_traits::ReuseBuffer<Lhs::Buffer>,
     Lhs: GetWriteableBuffer,
     Rhs: GetWriteableBuffer<Buffer = Lhs::Buffer>,
 {
-    type Buffer = Lhs::Buffer;
+    type Buffer = <Op as op_traits::ReuseBuffer<Lhs::Buffer>>::Output;
 
     unsafe fn get_buffer_and_set_no_free(
         &mut self,
         len: usize,
     ) -> Option<Self::Buffer> {
-        self.lhs
-            .get_buffer_and_set_no_free(len)
-            .or_else(|| self.rhs.get_buffer_and_set_no_free(len))
+        // The operation decides whether an operand's buffer can hold the
+        // result, which is not the case if the scalar type changes
+        Op::reuse_buffer(|| {
+            self.lhs
+                .get_buffer_and_set_no_free(len)
+                .or_else(|| self.rhs.get_buffer_and_set_no_free(len))
+        })
     }
 }
 
@@ -91,11 +280,11 @@ impl<'a, Backend, Op, Lhs, Rhs> GetWriteableBuffer
     for &'a TensrFn2<'a, Backend, Op, Lhs, Rhs>
 where
     Backend: traits::Backend,
-    Op: op_traits::BinaryOp,
+    Op: op_traits::BinaryOp + op_traits::ReuseBuffer<Lhs::Buffer>,
     Lhs: GetWriteableBuffer,
     Rhs: GetWriteableBuffer<Buffer = Lhs::Buffer>,
 {
-    type Buffer = Lhs::Buffer;
+    type Buffer = <Op as op_traits::ReuseBuffer<Lhs::Buffer>>::Output;
 
     unsafe fn get_buffer_and_set_no_free(
         &mut self,
@@ -109,18 +298,18 @@ impl<'a, Backend, Op, Lhs, Rhs> GetWriteableBuffer
     for &'a mut TensrFn2<'a, Backend, Op, Lhs, Rhs>
 where
     Backend: traits::Backend,
-    Op: op_traits::BinaryOp,
+    Op: op_traits::BinaryOp + op_traits::ReuseBuffer<Lhs::Buffer>,
     Lhs: GetWriteableBuffer,
     Rhs: GetWriteableBuffer<Buffer = Lhs::Buffer>,
 {
-    type Buffer = Lhs::Buffer;
+    type Buffer = <Op as op_traits::ReuseBuffer<Lhs::Buffer>>::Output;
 
     unsafe fn get_buffer_and_set_no_free(
         &mut self,
-        len: usize,
+        _len: usize,
     ) -> Option<Self::Buffer> {
-        self.lhs
-            .get_buffer_and_set_no_free(len)
-            .or_else(|| self.rhs.get_buffer_and_set_no_free(len))
+        // The operands are still owned by the borrowed expression, so none of
+        // their buffers can be taken
+        None
     }
 }
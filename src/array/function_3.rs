@@ -0,0 +1,17 @@
+//! Lazily evaluated element-wise operations with three operands.
+
+use std::marker::PhantomData;
+
+use crate::{
+    array::{base::ArrayBase, traits::GetWriteableBuffer},
+    backend::{op_traits, traits},
+    dimension::{
+        axes::Axes,
+        broadcast::{broadcast_shapes, BroadcastShape},
+        dim::Dimension,
+        dyn_dim::DimDyn,
+    },
+    error::ShapeError,
+};
+
+tensr_proc_macros::generate_function!(3);
@@ -0,0 +1,210 @@
+//! Indexing arrays and views by a multi-index.
+
+use crate::{
+    array::base::ArrayBase,
+    backend::traits,
+    dimension::{dim::Dimension, index::NdIndex},
+};
+
+impl<Backend, StorageType, NDims> ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    /// Return a reference to the element at the multi-dimensional `index`, or
+    /// `None` if the index is out of bounds. The strides and offset of the
+    /// array are taken into account, so this is valid for views.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+    /// assert_eq!(array.get([1, 2]), Some(&5));
+    /// assert_eq!(array.get([2, 0]), None);
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn get<I>(&self, index: I) -> Option<&StorageType::Scalar>
+    where
+        I: NdIndex<NDims>,
+    {
+        self.axes
+            .checked_offset_of(index.index_slice())
+            .map(|offset| &self.storage[offset])
+    }
+
+    /// Return a mutable reference to the element at the multi-dimensional
+    /// `index`, or `None` if the index is out of bounds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let mut array = Array2::from_shape_fn(Dim2::new([2, 3]), |_| 0);
+    /// *array.get_mut([0, 1]).unwrap() = 7;
+    /// assert_eq!(array[[0, 1]], 7);
+    /// ```
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn get_mut<I>(&mut self, index: I) -> Option<&mut StorageType::Scalar>
+    where
+        StorageType: traits::StorageMut,
+        I: NdIndex<NDims>,
+    {
+        self.axes
+            .checked_offset_of(index.index_slice())
+            .map(|offset| &mut self.storage[offset])
+    }
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn index_failed<NDims: Dimension>(index: &[usize], shape: &NDims) -> ! {
+    panic!("index {index:?} is out of bounds for array with shape {shape:?}");
+}
+
+impl<Backend, StorageType, NDims, I> std::ops::Index<I>
+    for ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+    I: NdIndex<NDims>,
+{
+    type Output = StorageType::Scalar;
+
+    #[track_caller]
+    fn index(&self, index: I) -> &Self::Output {
+        let index = index.index_slice();
+        let Some(offset) = self.axes.checked_offset_of(index) else {
+            index_failed(index, &self.axes.shape)
+        };
+
+        &self.storage[offset]
+    }
+}
+
+impl<Backend, StorageType, NDims, I> std::ops::IndexMut<I>
+    for ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::StorageMut,
+    NDims: Dimension,
+    I: NdIndex<NDims>,
+{
+    #[track_caller]
+    fn index_mut(&mut self, index: I) -> &mut Self::Output {
+        let index = index.index_slice();
+        let Some(offset) = self.axes.checked_offset_of(index) else {
+            index_failed(index, &self.axes.shape)
+        };
+
+        &mut self.storage[offset]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        array::{
+            test_fixtures::array_3d,
+            type_remap::{
+                Array1, Array2, Array3, ArrayDyn, ArrayView2, ArrayViewMut2,
+            },
+        },
+        dimension::{
+            dim::{Dim1, Dim2, Dim3},
+            dyn_dim::DimDyn,
+        },
+        s,
+    };
+
+    #[test]
+    fn test_index() {
+        let mut array = array_3d();
+        assert_eq!(array[[0, 0, 0]], 0);
+        assert_eq!(array[[1, 2, 3]], 123);
+
+        array[[1, 0, 2]] = 7;
+        assert_eq!(array[[1, 0, 2]], 7);
+
+        let mut vector = Array1::from_shape_fn(Dim1::new([4]), |i| i[0]);
+        vector[2] += 10;
+        assert_eq!(vector[2], 12);
+        assert_eq!(vector[[3]], 3);
+    }
+
+    #[test]
+    fn test_index_dyn() {
+        let mut array = ArrayDyn::from_shape_fn(
+            DimDyn::new_from(vec![2usize, 3, 4]),
+            |i| i[0] * 100 + i[1] * 10 + i[2],
+        );
+
+        assert_eq!(array[[1, 1, 1]], 111);
+        assert_eq!(array[&[0usize, 2, 3][..]], 23);
+        assert_eq!(array[vec![1, 2, 0]], 120);
+
+        array[[0, 1, 2]] = 0;
+        assert_eq!(array.get([0, 1, 2]), Some(&0));
+
+        assert_eq!(array.get([0, 1]), None);
+        assert_eq!(array.get([0, 1, 2, 3]), None);
+        assert_eq!(array.get([0, 3, 0]), None);
+    }
+
+    #[test]
+    fn test_get() {
+        let mut array = array_3d();
+        assert_eq!(array.get([1, 1, 3]), Some(&113));
+        assert_eq!(array.get([2, 0, 0]), None);
+        assert_eq!(array.get([0, 0, 4]), None);
+
+        *array.get_mut([0, 2, 1]).unwrap() = 5;
+        assert_eq!(array[[0, 2, 1]], 5);
+        assert!(array.get_mut([0, 3, 0]).is_none());
+    }
+
+    #[test]
+    fn test_index_view() {
+        let mut array = array_3d();
+
+        // Non-contiguous, reversed view
+        let view: ArrayView2<'_, usize> = array.slice(s![1, ..;-1, 1..;2]);
+        assert_eq!(view[[0, 0]], 121);
+        assert_eq!(view[[2, 1]], 103);
+        assert_eq!(view.get([3, 0]), None);
+        assert_eq!(view.get([0, 2]), None);
+
+        let mut view: ArrayViewMut2<'_, usize> = array.slice_mut(s![.., 1, ..]);
+        view[[1, 3]] = 0;
+        assert_eq!(view.get_mut([0, 0]).copied(), Some(10));
+        assert_eq!(array[[1, 1, 3]], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for array with shape [2, 3]")]
+    fn test_index_out_of_bounds() {
+        let array = Array3::from_shape_fn(Dim3::new([1, 2, 3]), |_| 0);
+        let view: ArrayView2<'_, i32> = array.slice(s![0, .., ..]);
+        let _ = view[[2, 0]];
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_index_dyn_wrong_ndim() {
+        let array =
+            ArrayDyn::from_shape_fn(DimDyn::new_from(vec![2usize, 2]), |_| 0);
+        let _ = array[[0, 0, 0]];
+    }
+
+    #[test]
+    fn test_index_2d() {
+        let array = Array2::from_shape_fn(Dim2::new([3, 3]), |i| i[0] == i[1]);
+        assert!(array[[1, 1]]);
+        assert!(!array[[1, 2]]);
+    }
+}
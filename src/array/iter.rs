@@ -0,0 +1,490 @@
+//! Iterators over the elements of arrays and views, in row-major order.
+
+use std::{iter::FusedIterator, marker::PhantomData, ptr::NonNull};
+
+use crate::{
+    array::base::ArrayBase,
+    backend::{
+        host::{
+            host_backend::HostBackend,
+            host_storage::{HostData, HostStorage},
+        },
+        traits::StorageMut,
+    },
+    dimension::{
+        axes::Axes,
+        dim::Dimension,
+        index::{next_index, prev_index},
+    },
+};
+
+/// Tracks the multi-indices of the next elements to be visited from the front
+/// and from the back of an array, in row-major order.
+#[derive(Debug, Clone)]
+struct Cursor<D: Dimension> {
+    axes: Axes<D>,
+    front: D,
+    back: D,
+    remaining: usize,
+}
+
+impl<D: Dimension> Cursor<D> {
+    fn new(axes: Axes<D>) -> Self {
+        let remaining = axes.shape.len();
+        let mut front = axes.shape.clone();
+        let mut back = axes.shape.clone();
+
+        // Safety: Only the values of the indices are set, not their number
+        unsafe {
+            front.as_slice_mut().fill(0);
+            if remaining > 0 {
+                back.as_slice_mut().iter_mut().for_each(|i| *i -= 1);
+            }
+        }
+
+        Self { axes, front, back, remaining }
+    }
+
+    /// Return the storage offset of the next element from the front, and
+    /// advance past it
+    fn next(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let offset = self.axes.offset_of(self.front.as_slice());
+        self.remaining -= 1;
+
+        // Safety: The index stays within the shape
+        unsafe {
+            next_index(self.front.as_slice_mut(), self.axes.shape.as_slice());
+        }
+        Some(offset)
+    }
+
+    /// Return the storage offset of the next element from the back, and move
+    /// before it
+    fn next_back(&mut self) -> Option<usize> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let offset = self.axes.offset_of(self.back.as_slice());
+        self.remaining -= 1;
+
+        // Safety: The index stays within the shape
+        unsafe {
+            prev_index(self.back.as_slice_mut(), self.axes.shape.as_slice());
+        }
+        Some(offset)
+    }
+}
+
+/// The elements of an array, either as a contiguous slice or through a
+/// cursor which follows the strides of the array
+enum Elements<Slice, D: Dimension> {
+    Contiguous(Slice),
+    Strided(NonNull<()>, Cursor<D>),
+}
+
+/// Generate the iterator trait implementations for an element iterator,
+/// where `$get` converts a pointer to an element into the item type.
+macro_rules! element_iterator {
+    ($name: ident, $item: ty, $get: expr) => {
+        impl<'a, T, D: Dimension> Iterator for $name<'a, T, D> {
+            type Item = $item;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                match &mut self.elements {
+                    Elements::Contiguous(slice) => slice.next(),
+                    Elements::Strided(ptr, cursor) => {
+                        let offset = cursor.next()?;
+
+                        // Safety: The cursor only visits elements within the
+                        // storage, each exactly once
+                        Some(unsafe {
+                            $get(ptr.cast::<T>().as_ptr().add(offset))
+                        })
+                    }
+                }
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                let len = match &self.elements {
+                    Elements::Contiguous(slice) => slice.len(),
+                    Elements::Strided(_, cursor) => cursor.remaining,
+                };
+                (len, Some(len))
+            }
+        }
+
+        impl<T, D: Dimension> DoubleEndedIterator for $name<'_, T, D> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                match &mut self.elements {
+                    Elements::Contiguous(slice) => slice.next_back(),
+                    Elements::Strided(ptr, cursor) => {
+                        let offset = cursor.next_back()?;
+
+                        // Safety: As for `next`
+                        Some(unsafe {
+                            $get(ptr.cast::<T>().as_ptr().add(offset))
+                        })
+                    }
+                }
+            }
+        }
+
+        impl<T, D: Dimension> ExactSizeIterator for $name<'_, T, D> {}
+        impl<T, D: Dimension> FusedIterator for $name<'_, T, D> {}
+    };
+}
+
+/// An iterator over references to the elements of an array, in row-major
+/// order. Created by [`ArrayBase::iter`].
+pub struct Iter<'a, T, D: Dimension> {
+    elements: Elements<std::slice::Iter<'a, T>, D>,
+}
+
+element_iterator!(Iter, &'a T, |ptr: *mut T| &*ptr);
+
+/// An iterator over mutable references to the elements of an array, in
+/// row-major order. Created by [`ArrayBase::iter_mut`].
+pub struct IterMut<'a, T, D: Dimension> {
+    elements: Elements<std::slice::IterMut<'a, T>, D>,
+}
+
+element_iterator!(IterMut, &'a mut T, |ptr: *mut T| &mut *ptr);
+
+/// An iterator over the elements of an array, in row-major order, together
+/// with their multi-indices. Created by [`ArrayBase::indexed_iter`].
+pub struct IndexedIter<'a, T, D: Dimension> {
+    ptr: NonNull<T>,
+    cursor: Cursor<D>,
+    phantom: PhantomData<&'a T>,
+}
+
+impl<'a, T, D: Dimension> Iterator for IndexedIter<'a, T, D> {
+    type Item = (D, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.cursor.front.clone();
+        let offset = self.cursor.next()?;
+
+        // Safety: The cursor only visits elements within the storage
+        Some((index, unsafe { &*self.ptr.as_ptr().add(offset) }))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<T, D: Dimension> DoubleEndedIterator for IndexedIter<'_, T, D> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let index = self.cursor.back.clone();
+        let offset = self.cursor.next_back()?;
+
+        // Safety: As for `next`
+        Some((index, unsafe { &*self.ptr.as_ptr().add(offset) }))
+    }
+}
+
+impl<T, D: Dimension> ExactSizeIterator for IndexedIter<'_, T, D> {}
+impl<T, D: Dimension> FusedIterator for IndexedIter<'_, T, D> {}
+
+/// An iterator which consumes an array and returns its elements by value, in
+/// row-major order. Created by [`IntoIterator::into_iter`] on an owned array.
+pub struct IntoIter<T: Copy, D: Dimension> {
+    storage: HostStorage<T>,
+    cursor: Cursor<D>,
+}
+
+impl<T: Copy, D: Dimension> Iterator for IntoIter<T, D> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.cursor.next().map(|offset| self.storage[offset])
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.cursor.remaining, Some(self.cursor.remaining))
+    }
+}
+
+impl<T: Copy, D: Dimension> DoubleEndedIterator for IntoIter<T, D> {
+    fn next_back(&mut self) -> Option<T> {
+        self.cursor.next_back().map(|offset| self.storage[offset])
+    }
+}
+
+impl<T: Copy, D: Dimension> ExactSizeIterator for IntoIter<T, D> {}
+impl<T: Copy, D: Dimension> FusedIterator for IntoIter<T, D> {}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    /// Return an iterator over references to the elements of the array, in
+    /// row-major order. Views are traversed through their strides, and
+    /// contiguous arrays are traversed as a slice.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+    ///
+    /// let columns: Vec<i32> = array.t().iter().copied().collect();
+    /// assert_eq!(columns, [1, 3, 2, 4]);
+    ///
+    /// let last = array.iter().rev().next();
+    /// assert_eq!(last, Some(&4));
+    /// ```
+    pub fn iter(&self) -> Iter<'_, StorageType::Scalar, NDims> {
+        let ptr = self.storage.as_non_null().0;
+        let len = self.axes.shape.len();
+
+        let elements = if self.is_contiguous() {
+            // Safety: A contiguous array's elements form a slice, starting at
+            // its offset
+            Elements::Contiguous(if len == 0 {
+                [].iter()
+            } else {
+                unsafe {
+                    std::slice::from_raw_parts(
+                        ptr.as_ptr().add(self.axes.stride.offset),
+                        len,
+                    )
+                }
+                .iter()
+            })
+        } else {
+            Elements::Strided(ptr.cast(), Cursor::new(self.axes.clone()))
+        };
+
+        Iter { elements }
+    }
+
+    /// Return an iterator over mutable references to the elements of the
+    /// array, in row-major order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let mut array = Array2::from_shape_fn(Dim2::new([2, 3]), |_| 0);
+    /// for (i, value) in array.iter_mut().enumerate() {
+    ///     *value = i;
+    /// }
+    /// assert_eq!(array[[1, 0]], 3);
+    /// ```
+    pub fn iter_mut(&mut self) -> IterMut<'_, StorageType::Scalar, NDims>
+    where
+        StorageType: StorageMut,
+    {
+        let ptr = self.storage.as_non_null().0;
+        let len = self.axes.shape.len();
+
+        let elements = if self.is_contiguous() {
+            // Safety: As for `iter`, and the array is borrowed mutably, so
+            // nothing else can access its elements
+            Elements::Contiguous(if len == 0 {
+                [].iter_mut()
+            } else {
+                unsafe {
+                    std::slice::from_raw_parts_mut(
+                        ptr.as_ptr().add(self.axes.stride.offset),
+                        len,
+                    )
+                }
+                .iter_mut()
+            })
+        } else {
+            Elements::Strided(ptr.cast(), Cursor::new(self.axes.clone()))
+        };
+
+        IterMut { elements }
+    }
+
+    /// Return an iterator over the elements of the array and their
+    /// multi-indices, in row-major order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+    /// for (index, &value) in array.indexed_iter() {
+    ///     assert_eq!(array[[index[0], index[1]]], value);
+    /// }
+    ///
+    /// let (index, value) = array.indexed_iter().nth(2).unwrap();
+    /// assert_eq!(index.as_slice(), &[1, 0]);
+    /// assert_eq!(*value, 3);
+    /// ```
+    pub fn indexed_iter(&self) -> IndexedIter<'_, StorageType::Scalar, NDims> {
+        IndexedIter {
+            ptr: self.storage.as_non_null().0,
+            cursor: Cursor::new(self.axes.clone()),
+            phantom: PhantomData,
+        }
+    }
+}
+
+impl<T, NDims> IntoIterator for ArrayBase<HostBackend, HostStorage<T>, NDims>
+where
+    T: Copy,
+    NDims: Dimension,
+{
+    type Item = T;
+    type IntoIter = IntoIter<T, NDims>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { cursor: Cursor::new(self.axes), storage: self.storage }
+    }
+}
+
+impl<'a, StorageType, NDims> IntoIterator
+    for &'a ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    type Item = &'a StorageType::Scalar;
+    type IntoIter = Iter<'a, StorageType::Scalar, NDims>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, StorageType, NDims> IntoIterator
+    for &'a mut ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData + StorageMut,
+    NDims: Dimension,
+{
+    type Item = &'a mut StorageType::Scalar;
+    type IntoIter = IterMut<'a, StorageType::Scalar, NDims>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array::{
+            test_fixtures::array_3d,
+            type_remap::{Array2, ArrayView, ArrayViewMut2},
+        },
+        dimension::{
+            axes::Axis,
+            dim::{Dim2, Dim3},
+        },
+        s,
+    };
+
+    /// The elements of `array` in row-major order, read by indexing
+    fn expected(array: &ArrayView<'_, usize, Dim3>) -> Vec<usize> {
+        array.indexed_iter().map(|(i, _)| array[[i[0], i[1], i[2]]]).collect()
+    }
+
+    #[test]
+    fn test_iter_strided() {
+        let array = array_3d();
+        let views: [ArrayView<'_, usize, Dim3>; 4] = [
+            array.view(),
+            array.slice(s![.., 1.., ..;-2]),
+            array.view().permuted_axes([2, 0, 1]).unwrap(),
+            array.slice(s![.., 3.., ..]),
+        ];
+
+        for view in &views {
+            let values: Vec<usize> = view.iter().copied().collect();
+            assert_eq!(values, expected(view));
+            assert_eq!(view.iter().len(), values.len());
+
+            let mut reversed: Vec<usize> = view.iter().rev().copied().collect();
+            reversed.reverse();
+            assert_eq!(reversed, values);
+
+            let total: usize = view.into_iter().sum();
+            assert_eq!(total, values.iter().sum());
+        }
+
+        let mut iter = views[1].iter();
+        assert_eq!(iter.len(), 8);
+        assert_eq!(iter.next(), Some(&13));
+        assert_eq!(iter.next_back(), Some(&121));
+        assert_eq!(iter.len(), 6);
+        assert_eq!(iter.by_ref().count(), 6);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_indexed_iter() {
+        let array = array_3d();
+        let view = array.view().moveaxis(Axis(2), Axis(0)).unwrap();
+
+        let mut count = 0;
+        for (index, &value) in view.indexed_iter() {
+            let [k, i, j] = [index[0], index[1], index[2]];
+            assert_eq!(value, i * 100 + j * 10 + k);
+            count += 1;
+        }
+        assert_eq!(count, 24);
+
+        let (index, value) = view.indexed_iter().next_back().unwrap();
+        assert_eq!(index.as_slice(), &[3, 1, 2]);
+        assert_eq!(*value, 123);
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut array = Array2::from_shape_fn(Dim2::new([3, 4]), |_| 0);
+        for (i, value) in (&mut array).into_iter().enumerate() {
+            *value = i;
+        }
+        assert_eq!(array[[2, 1]], 9);
+
+        // Only the elements of the view are visited
+        {
+            let mut view: ArrayViewMut2<'_, usize> =
+                array.slice_mut(s![..;2, 1..;2]);
+            view.iter_mut().rev().for_each(|value| *value += 100);
+        }
+        assert_eq!(array[[0, 1]], 101);
+        assert_eq!(array[[2, 3]], 111);
+        assert_eq!(array[[1, 1]], 5);
+        assert_eq!(array.iter().filter(|&&value| value >= 100).count(), 4);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let array = array_3d();
+        let values: Vec<usize> = array.to_owned().into_iter().collect();
+        assert_eq!(values, expected(&array.view()));
+
+        // Owned arrays may have their axes permuted without copying
+        let transposed = array.transpose();
+        let mut iter = transposed.into_iter();
+        assert_eq!(iter.len(), 24);
+        assert_eq!(iter.next(), Some(0));
+        assert_eq!(iter.next(), Some(100));
+        assert_eq!(iter.next_back(), Some(123));
+        assert_eq!(iter.next_back(), Some(23));
+        assert_eq!(iter.len(), 20);
+
+        let empty = Array2::<u8>::zeros(Dim2::new([0, 3]));
+        assert_eq!(empty.iter().len(), 0);
+        assert_eq!(empty.into_iter().next_back(), None);
+    }
+}
@@ -0,0 +1,553 @@
+//! Joining arrays together, and splitting them into views.
+
+use crate::{
+    array::{base::ArrayBase, type_remap::ArrayView},
+    backend::host::{
+        host_backend::HostBackend,
+        host_storage::{HostData, HostStorage},
+    },
+    dimension::{
+        axes::{Axes, Axis},
+        dim::Dimension,
+        index::{next_index, unravel_index},
+    },
+    error::ShapeError,
+    types::{IDim, UDim},
+};
+
+/// Join arrays along an existing axis. Every array must have the same shape,
+/// except along `axis`.
+///
+/// The elements are copied into a new array, in which the arrays appear one
+/// after another along `axis`.
+///
+/// # Errors
+/// Returns [`ShapeError::NoArrays`] if `arrays` is empty,
+/// [`ShapeError::AxisOutOfBounds`] if `axis` does not exist, or
+/// [`ShapeError::JoinMismatch`] if the shapes of the arrays differ along any
+/// other axis.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::axes::Axis;
+/// use tensr::dimension::dim::{Dim2, Dimension};
+///
+/// let a = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+/// let b = Array2::from_vec(Dim2::new([2, 1]), vec![5, 6]).unwrap();
+///
+/// let joined = tensr::concatenate(Axis(1), &[a.view(), b.view()]).unwrap();
+/// assert_eq!(joined.shape().as_slice(), &[2, 3]);
+/// assert_eq!(joined[[1, 2]], 6);
+/// ```
+pub fn concatenate<T, D>(
+    axis: Axis,
+    arrays: &[ArrayView<'_, T, D>],
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, D>, ShapeError>
+where
+    T: Copy,
+    D: Dimension,
+{
+    let Axis(axis) = axis;
+    let Some(first) = arrays.first() else {
+        return Err(ShapeError::NoArrays);
+    };
+    let expected = first.axes.shape.as_slice();
+    let ndim = expected.len();
+    if axis >= ndim {
+        return Err(ShapeError::AxisOutOfBounds { axis, ndim });
+    }
+
+    let mut shape = first.axes.shape.clone();
+    let mut total = 0;
+    for array in arrays {
+        let found = array.axes.shape.as_slice();
+        let matches = found.len() == ndim
+            && found
+                .iter()
+                .zip(expected)
+                .enumerate()
+                .all(|(a, (len, expected))| a == axis || len == expected);
+        if !matches {
+            return Err(ShapeError::JoinMismatch {
+                axis,
+                expected: expected.to_vec(),
+                found: found.to_vec(),
+            });
+        }
+        total += found[axis];
+    }
+
+    // Safety: Only the length of the joined axis changes
+    unsafe {
+        shape.as_slice_mut()[axis] = total;
+    }
+
+    // Each array contributes a block of its trailing axes for every index
+    // into the leading axes, so the blocks are interleaved in that order
+    let outer = &shape.as_slice()[..axis];
+    let mut data = Vec::with_capacity(shape.len());
+    let mut index = vec![0; ndim];
+    for block in 0..outer.iter().product() {
+        for array in arrays {
+            let inner = &array.axes.shape.as_slice()[axis..];
+            if inner.contains(&0) {
+                continue;
+            }
+
+            unravel_index(block, outer, &mut index[..axis]);
+            index[axis..].fill(0);
+            loop {
+                data.push(array.storage[array.axes.offset_of(&index)]);
+                if !next_index(&mut index[axis..], inner) {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(ArrayBase::new(
+        Axes::new_with_default_stride(shape),
+        HostStorage::from_vec(data),
+    ))
+}
+
+/// Join arrays of the same shape along a new axis.
+///
+/// The new axis is inserted before `axis`, or appended if `axis` is equal to
+/// the number of axes. The result has one more axis than the arrays, so
+/// stacking [`Dim8`] arrays produces a [`DimDyn`] array.
+///
+/// [`Dim8`]: crate::dimension::dim::Dim8
+/// [`DimDyn`]: crate::dimension::dyn_dim::DimDyn
+///
+/// # Errors
+/// Returns [`ShapeError::NoArrays`] if `arrays` is empty,
+/// [`ShapeError::AxisOutOfBounds`] if `axis` is greater than the number of
+/// axes, or [`ShapeError::JoinMismatch`] if the arrays have different shapes.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array1;
+/// use tensr::dimension::axes::Axis;
+/// use tensr::dimension::dim::{Dim1, Dimension};
+///
+/// let a = Array1::from_vec(Dim1::new([3]), vec![1, 2, 3]).unwrap();
+/// let b = Array1::from_vec(Dim1::new([3]), vec![4, 5, 6]).unwrap();
+///
+/// let rows = tensr::stack(Axis(0), &[a.view(), b.view()]).unwrap();
+/// assert_eq!(rows.shape().as_slice(), &[2, 3]);
+/// assert_eq!(rows[[1, 0]], 4);
+///
+/// let columns = tensr::stack(Axis(1), &[a.view(), b.view()]).unwrap();
+/// assert_eq!(columns.shape().as_slice(), &[3, 2]);
+/// assert_eq!(columns[[0, 1]], 4);
+/// ```
+pub fn stack<T, D>(
+    axis: Axis,
+    arrays: &[ArrayView<'_, T, D>],
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, D::Larger>, ShapeError>
+where
+    T: Copy,
+    D: Dimension,
+{
+    let Some(first) = arrays.first() else {
+        return Err(ShapeError::NoArrays);
+    };
+    let expected = first.axes.shape.as_slice();
+    if let Some(array) =
+        arrays.iter().find(|array| array.axes.shape.as_slice() != expected)
+    {
+        return Err(ShapeError::JoinMismatch {
+            axis: axis.0,
+            expected: expected.to_vec(),
+            found: array.axes.shape.as_slice().to_vec(),
+        });
+    }
+
+    let expanded = arrays
+        .iter()
+        .map(|array| array.view().insert_axis(axis))
+        .collect::<Result<Vec<_>, _>>()?;
+    concatenate(axis, &expanded)
+}
+
+/// Join arrays along their first axis, so matrices are stacked vertically.
+/// This is [`concatenate`] along axis zero; use [`stack`] to join vectors as
+/// the rows of a matrix.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`concatenate`].
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::{Dim2, Dimension};
+///
+/// let a = Array2::from_vec(Dim2::new([1, 2]), vec![1, 2]).unwrap();
+/// let b = Array2::from_vec(Dim2::new([2, 2]), vec![3, 4, 5, 6]).unwrap();
+///
+/// let joined = tensr::vstack(&[a.view(), b.view()]).unwrap();
+/// assert_eq!(joined.shape().as_slice(), &[3, 2]);
+/// assert_eq!(joined[[2, 0]], 5);
+/// ```
+pub fn vstack<T, D>(
+    arrays: &[ArrayView<'_, T, D>],
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, D>, ShapeError>
+where
+    T: Copy,
+    D: Dimension,
+{
+    concatenate(Axis(0), arrays)
+}
+
+/// Join arrays horizontally, along their second axis, or along their only
+/// axis if they are vectors.
+///
+/// # Errors
+/// Returns an error under the same conditions as [`concatenate`].
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array1;
+/// use tensr::dimension::dim::{Dim1, Dimension};
+///
+/// let a = Array1::from_vec(Dim1::new([2]), vec![1, 2]).unwrap();
+/// let b = Array1::from_vec(Dim1::new([1]), vec![3]).unwrap();
+///
+/// let joined = tensr::hstack(&[a.view(), b.view()]).unwrap();
+/// assert_eq!(joined.shape().as_slice(), &[3]);
+/// assert_eq!(joined[[2]], 3);
+/// ```
+pub fn hstack<T, D>(
+    arrays: &[ArrayView<'_, T, D>],
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, D>, ShapeError>
+where
+    T: Copy,
+    D: Dimension,
+{
+    let is_vector =
+        arrays.first().is_some_and(|array| array.axes.shape.ndim() == 1);
+    concatenate(Axis(usize::from(!is_vector)), arrays)
+}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    /// Return a view of the elements with indices in `start..start + len`
+    /// along `axis`, which must be within the axis.
+    fn view_range(
+        &self,
+        axis: usize,
+        start: usize,
+        len: usize,
+    ) -> ArrayView<'_, StorageType::Scalar, NDims> {
+        let mut range = self.axes.clone();
+
+        // Safety: Only the length of `axis` changes, and it is reduced
+        unsafe {
+            range.shape.as_slice_mut()[axis] = len;
+        }
+        if len > 0 {
+            range.stride.offset = range.stride.offset_along(axis, start);
+        }
+
+        // Safety: The new axes address a subset of the elements of the array
+        unsafe { self.view_with_axes(range) }
+    }
+
+    /// Return the length of `axis`, or an error if it does not exist
+    fn axis_len(&self, axis: usize) -> Result<usize, ShapeError> {
+        let shape = self.axes.shape.as_slice();
+        shape
+            .get(axis)
+            .copied()
+            .ok_or(ShapeError::AxisOutOfBounds { axis, ndim: shape.len() })
+    }
+
+    /// Split the array into two views along `axis`, where the first view
+    /// contains the elements with indices before `index` along that axis,
+    /// and the second contains the rest. No elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` does not exist, or
+    /// [`ShapeError::IndexOutOfBounds`] if `index` is greater than the length
+    /// of the axis.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([2, 5]), |i| i[0] * 5 + i[1]);
+    /// let (left, right) = array.split_at(Axis(1), 2).unwrap();
+    /// assert_eq!(left.shape().as_slice(), &[2, 2]);
+    /// assert_eq!(right.shape().as_slice(), &[2, 3]);
+    /// assert_eq!(right[[1, 0]], 7);
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn split_at(
+        &self,
+        axis: Axis,
+        index: usize,
+    ) -> Result<
+        (
+            ArrayView<'_, StorageType::Scalar, NDims>,
+            ArrayView<'_, StorageType::Scalar, NDims>,
+        ),
+        ShapeError,
+    > {
+        let Axis(axis) = axis;
+        let len = self.axis_len(axis)?;
+        if index > len {
+            return Err(ShapeError::IndexOutOfBounds {
+                axis,
+                index: IDim::try_from(index).unwrap_or(IDim::MAX),
+                len,
+            });
+        }
+
+        Ok((
+            self.view_range(axis, 0, index),
+            self.view_range(axis, index, len - index),
+        ))
+    }
+
+    /// Split the array into `sections` views of equal length along `axis`.
+    /// No elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` does not exist, or
+    /// [`ShapeError::InvalidSplit`] if `sections` is zero or does not divide
+    /// the length of the axis. See [`ArrayBase::array_split`] to allow
+    /// sections of unequal length.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::Dim1;
+    ///
+    /// let array = Array1::from_shape_fn(Dim1::new([6]), |i| i[0]);
+    /// let parts = array.split(Axis(0), 3).unwrap();
+    /// assert_eq!(parts.len(), 3);
+    /// assert_eq!(parts[2][[0]], 4);
+    ///
+    /// assert!(array.split(Axis(0), 4).is_err());
+    /// ```
+    pub fn split(
+        &self,
+        axis: Axis,
+        sections: usize,
+    ) -> Result<Vec<ArrayView<'_, StorageType::Scalar, NDims>>, ShapeError>
+    {
+        let len = self.axis_len(axis.0)?;
+        if sections == 0 || !len.is_multiple_of(sections) {
+            return Err(ShapeError::InvalidSplit {
+                axis: axis.0,
+                len,
+                sections,
+            });
+        }
+
+        self.array_split(axis, sections)
+    }
+
+    /// Split the array into `sections` views along `axis`, where the lengths
+    /// of the views differ by at most one. If the length of the axis is not
+    /// divisible by `sections`, the first views are one element longer. No
+    /// elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` does not exist, or
+    /// [`ShapeError::InvalidSplit`] if `sections` is zero.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::{Dim1, Dimension};
+    ///
+    /// let array = Array1::from_shape_fn(Dim1::new([7]), |i| i[0]);
+    /// let parts = array.array_split(Axis(0), 3).unwrap();
+    /// let lengths: Vec<usize> =
+    ///     parts.iter().map(|part| part.shape().as_slice()[0]).collect();
+    /// assert_eq!(lengths, [3, 2, 2]);
+    /// assert_eq!(parts[1][[0]], 3);
+    /// ```
+    pub fn array_split(
+        &self,
+        axis: Axis,
+        sections: usize,
+    ) -> Result<Vec<ArrayView<'_, StorageType::Scalar, NDims>>, ShapeError>
+    {
+        let Axis(axis) = axis;
+        let len = self.axis_len(axis)?;
+        if sections == 0 {
+            return Err(ShapeError::InvalidSplit { axis, len, sections });
+        }
+
+        let (base, extra) = (len / sections, len % sections);
+        let mut start = 0;
+        Ok((0..sections)
+            .map(|section| {
+                let section_len = base + UDim::from(section < extra);
+                let view = self.view_range(axis, start, section_len);
+                start += section_len;
+                view
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array::{
+            test_fixtures::array_3d,
+            type_remap::{Array2, Array3, Array8, ArrayDyn},
+        },
+        dimension::{
+            dim::{Dim2, Dim3, Dim8},
+            dyn_dim::DimDyn,
+        },
+        s,
+    };
+
+    #[test]
+    fn test_concatenate() {
+        let array = array_3d();
+
+        // Joining the pieces of a split along each axis recovers the array,
+        // including when the pieces are strided views
+        for axis in 0..3 {
+            let len = array.shape().as_slice()[axis];
+            let parts: [_; 2] =
+                array.split_at(Axis(axis), len - 1).unwrap().into();
+            let joined = concatenate(Axis(axis), &parts).unwrap();
+            assert_eq!(joined.shape().as_slice(), array.shape().as_slice());
+            assert_eq!(joined.sum(), array.sum());
+            assert_eq!(joined[[1, 2, 3]], 123);
+            assert_eq!(joined[[1, 0, 2]], 102);
+        }
+
+        let reversed: ArrayView<'_, usize, Dim3> =
+            array.slice(s![.., ..;-1, ..]);
+        let joined =
+            concatenate(Axis(2), &[array.view(), reversed, array.t().t()])
+                .unwrap();
+        assert_eq!(joined.shape().as_slice(), &[2, 3, 12]);
+        assert_eq!(joined[[1, 0, 5]], 121);
+        assert_eq!(joined[[1, 0, 9]], 101);
+
+        // Empty arrays contribute nothing
+        let empty = Array3::<usize>::zeros(Dim3::new([2, 0, 4]));
+        let joined =
+            concatenate(Axis(1), &[empty.view(), array.view()]).unwrap();
+        assert_eq!(joined.shape().as_slice(), array.shape().as_slice());
+        assert_eq!(joined[[0, 2, 1]], 21);
+    }
+
+    #[test]
+    fn test_stack() {
+        let a = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+        let b =
+            Array2::from_shape_fn(Dim2::new([2, 3]), |i| 10 + i[0] * 3 + i[1]);
+
+        let stacked: Array3<usize> =
+            stack(Axis(1), &[a.view(), b.view()]).unwrap();
+        assert_eq!(stacked.shape().as_slice(), &[2, 2, 3]);
+        assert_eq!(stacked[[1, 0, 2]], 5);
+        assert_eq!(stacked[[1, 1, 2]], 15);
+
+        let last = stack(Axis(2), &[a.view(), b.view()]).unwrap();
+        assert_eq!(last.shape().as_slice(), &[2, 3, 2]);
+        assert_eq!(last[[0, 2, 1]], 12);
+
+        let big = Array8::<u8>::ones(Dim8::new([1, 2, 1, 2, 1, 2, 1, 2]));
+        let dynamic: ArrayDyn<u8> =
+            stack(Axis(8), &[big.view(), big.view(), big.view()]).unwrap();
+        assert_eq!(dynamic.shape().as_slice(), &[1, 2, 1, 2, 1, 2, 1, 2, 3]);
+
+        let dyn_array =
+            ArrayDyn::from_shape_fn(DimDyn::new_from(vec![2usize]), |i| i[0]);
+        let stacked =
+            stack(Axis(0), &[dyn_array.view(), dyn_array.view()]).unwrap();
+        assert_eq!(stacked.shape().as_slice(), &[2, 2]);
+    }
+
+    #[test]
+    fn test_join_errors() {
+        let a = Array2::<u8>::zeros(Dim2::new([2, 3]));
+        let b = Array2::<u8>::zeros(Dim2::new([3, 3]));
+
+        assert_eq!(
+            concatenate::<u8, Dim2>(Axis(0), &[]).err(),
+            Some(ShapeError::NoArrays)
+        );
+        assert_eq!(
+            concatenate(Axis(1), &[a.view(), b.view()]).err(),
+            Some(ShapeError::JoinMismatch {
+                axis: 1,
+                expected: vec![2, 3],
+                found: vec![3, 3],
+            })
+        );
+        assert_eq!(
+            concatenate(Axis(2), &[a.view(), b.view()]).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 2, ndim: 2 })
+        );
+        assert_eq!(
+            vstack(&[a.view(), b.view()]).unwrap().shape().as_slice(),
+            &[5, 3]
+        );
+        assert!(hstack(&[a.view(), b.view()]).is_err());
+        assert!(stack(Axis(0), &[a.view(), b.view()]).is_err());
+        assert_eq!(
+            stack(Axis(3), &[a.view(), a.view()]).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 3, ndim: 3 })
+        );
+    }
+
+    #[test]
+    fn test_split() {
+        let array = array_3d();
+
+        let (left, right) = array.split_at(Axis(2), 4).unwrap();
+        assert_eq!(left.shape().as_slice(), array.shape().as_slice());
+        assert_eq!(right.shape().as_slice(), &[2, 3, 0]);
+        assert_eq!(
+            array.split_at(Axis(2), 5).err(),
+            Some(ShapeError::IndexOutOfBounds { axis: 2, index: 5, len: 4 })
+        );
+        assert_eq!(
+            array.split_at(Axis(3), 0).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 3, ndim: 3 })
+        );
+
+        // Splitting a reversed view keeps the order of the view
+        let reversed: ArrayView<'_, usize, Dim3> =
+            array.slice(s![.., .., ..;-1]);
+        let parts = reversed.split(Axis(2), 2).unwrap();
+        assert_eq!(parts[0][[1, 1, 0]], 113);
+        assert_eq!(parts[1][[1, 1, 1]], 110);
+
+        let parts = array.array_split(Axis(1), 2).unwrap();
+        assert_eq!(parts[0].shape().as_slice(), &[2, 2, 4]);
+        assert_eq!(parts[1].shape().as_slice(), &[2, 1, 4]);
+        assert_eq!(parts[1][[1, 0, 3]], 123);
+
+        let parts = array.array_split(Axis(0), 3).unwrap();
+        assert_eq!(parts[2].shape().as_slice(), &[0, 3, 4]);
+
+        assert_eq!(
+            array.split(Axis(1), 2).err(),
+            Some(ShapeError::InvalidSplit { axis: 1, len: 3, sections: 2 })
+        );
+        assert!(array.array_split(Axis(1), 0).is_err());
+    }
+}
@@ -0,0 +1,436 @@
+//! Matrix multiplication of arrays and views.
+
+use crate::{
+    array::base::ArrayBase,
+    backend::{
+        host::{
+            host_backend::HostBackend,
+            host_gemm::{host_batched_gemm, GemmScalar},
+            host_storage::HostStorage,
+        },
+        traits::Storage,
+    },
+    dimension::{
+        axes::Axes,
+        dim::Dimension,
+        index::next_index,
+        matmul::{matmul_shape, MatMulShape},
+        stride::advance,
+    },
+    error::ShapeError,
+    types::UDim,
+};
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: Storage + Sync,
+    StorageType::Scalar: GemmScalar,
+    NDims: Dimension,
+{
+    /// The strides between rows and between columns of each matrix when the
+    /// array is an operand of a matrix multiplication. A vector is a single
+    /// row on the left, and a single column on the right, so the stride along
+    /// the missing axis is zero.
+    fn matrix_strides(&self, is_lhs: bool) -> (usize, usize) {
+        match *self.axes.stride.strides.as_slice() {
+            [stride] if is_lhs => (0, stride),
+            [stride] => (stride, 0),
+            [.., row, column] => (row, column),
+            [] => unreachable!("matrix operands have at least one axis"),
+        }
+    }
+
+    /// The storage offset of the first element of the matrix at each
+    /// (row-major) index of the broadcast batch shape `batch`. The batch axes
+    /// of the array are aligned with the trailing axes of `batch`, and axes
+    /// of length one are repeated.
+    fn batch_offsets(&self, batch: &[UDim]) -> Vec<usize> {
+        let shape = self.axes.shape.as_slice();
+        let strides = self.axes.stride.strides.as_slice();
+        let own = shape.len().saturating_sub(2);
+        let skip = batch.len() - own;
+
+        let count: usize = batch.iter().product();
+        let mut index: Vec<UDim> = vec![0; batch.len()];
+        let mut offsets = Vec::with_capacity(count);
+        for _ in 0..count {
+            let offset = (0..own)
+                .filter(|&axis| shape[axis] != 1)
+                .fold(self.axes.stride.offset, |acc, axis| {
+                    advance(acc, index[skip + axis], strides[axis])
+                });
+            offsets.push(offset);
+            next_index(&mut index, batch);
+        }
+        offsets
+    }
+
+    /// Compute the matrix product of `self` and `rhs`, which may be matrices,
+    /// vectors or stacks of matrices.
+    ///
+    /// As in `NumPy`'s `matmul`, a vector on the left is treated as a row
+    /// vector and a vector on the right as a column vector, and that axis is
+    /// removed from the result, so the product of two vectors has zero
+    /// dimensions. Arrays with more than two axes are stacks of matrices
+    /// indexed by their leading axes, which are broadcast together, so a
+    /// `(B, M, K)` stack can be multiplied by a single `(K, N)` matrix. The
+    /// products in a stack are computed in parallel.
+    ///
+    /// The product is computed by a cache-blocked, parallel GEMM kernel which
+    /// reads the operands through their strides, so transposed, sliced and
+    /// reversed views are multiplied without being copied.
+    ///
+    /// Unlike element-wise operations, the product is computed eagerly, since
+    /// each element depends on a whole row and column and cannot be computed
+    /// on its own inside a fused loop. The result is a new owned array, which
+    /// can then be an operand of a lazy expression: in
+    /// `alpha * a.matmul(&b) + beta * &c`, the product is computed first and
+    /// only the scaling and addition are fused.
+    ///
+    /// # Panics
+    /// Panics if the number of columns of `self` does not match the number of
+    /// rows of `rhs`, or if their batch axes cannot be broadcast together.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::{Array1, Array2};
+    /// use tensr::dimension::dim::{Dim1, Dim2};
+    ///
+    /// let a = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+    /// let x = Array1::from_vec(Dim1::new([2]), vec![5, 6]).unwrap();
+    ///
+    /// let y = a.matmul(&x);
+    /// assert_eq!(y[0], 17);
+    /// assert_eq!(y[1], 39);
+    ///
+    /// let squared = a.matmul(&a);
+    /// assert_eq!(squared[[1, 0]], 15);
+    /// ```
+    #[track_caller]
+    pub fn matmul<RhsStorage, RhsDims>(
+        &self,
+        rhs: &ArrayBase<HostBackend, RhsStorage, RhsDims>,
+    ) -> ArrayBase<
+        HostBackend,
+        HostStorage<StorageType::Scalar>,
+        <NDims as MatMulShape<RhsDims>>::Output,
+    >
+    where
+        RhsStorage: Storage<Scalar = StorageType::Scalar> + Sync,
+        RhsDims: Dimension,
+        NDims: MatMulShape<RhsDims>,
+    {
+        self.try_matmul(rhs).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// Compute the matrix product of `self` and `rhs`, which may be matrices,
+    /// vectors or stacks of matrices. See [`ArrayBase::matmul`].
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::MatMulMismatch`] if the number of columns of
+    /// `self` does not match the number of rows of `rhs`, or
+    /// [`ShapeError::BroadcastMismatch`] if their batch axes cannot be
+    /// broadcast together.
+    #[allow(clippy::type_complexity)]
+    pub fn try_matmul<RhsStorage, RhsDims>(
+        &self,
+        rhs: &ArrayBase<HostBackend, RhsStorage, RhsDims>,
+    ) -> Result<
+        ArrayBase<
+            HostBackend,
+            HostStorage<StorageType::Scalar>,
+            <NDims as MatMulShape<RhsDims>>::Output,
+        >,
+        ShapeError,
+    >
+    where
+        RhsStorage: Storage<Scalar = StorageType::Scalar> + Sync,
+        RhsDims: Dimension,
+        NDims: MatMulShape<RhsDims>,
+    {
+        let (shape, sizes) = matmul_shape(
+            self.axes.shape.as_slice(),
+            rhs.axes.shape.as_slice(),
+        )?;
+
+        let a_offsets = self.batch_offsets(&sizes.batch);
+        let b_offsets = rhs.batch_offsets(&sizes.batch);
+        let (a_row, a_column) = self.matrix_strides(true);
+        let (b_row, b_column) = rhs.matrix_strides(false);
+        let (a_storage, b_storage) = (&self.storage, &rhs.storage);
+
+        let storage = host_batched_gemm(
+            a_offsets.len(),
+            sizes.m,
+            sizes.k,
+            sizes.n,
+            |batch: usize, i: usize, p: usize| {
+                a_storage
+                    [advance(advance(a_offsets[batch], i, a_row), p, a_column)]
+            },
+            |batch: usize, p: usize, j: usize| {
+                b_storage
+                    [advance(advance(b_offsets[batch], p, b_row), j, b_column)]
+            },
+        );
+
+        Ok(ArrayBase::new(Axes::new_with_default_stride(shape), storage))
+    }
+
+    /// Compute the dot product of `self` and `rhs`. This is the same as
+    /// [`ArrayBase::matmul`], so for two vectors it is their inner product,
+    /// as a zero-dimensional array.
+    ///
+    /// # Panics
+    /// Panics if the number of columns of `self` does not match the number of
+    /// rows of `rhs`.
+    #[track_caller]
+    pub fn dot<RhsStorage, RhsDims>(
+        &self,
+        rhs: &ArrayBase<HostBackend, RhsStorage, RhsDims>,
+    ) -> ArrayBase<
+        HostBackend,
+        HostStorage<StorageType::Scalar>,
+        <NDims as MatMulShape<RhsDims>>::Output,
+    >
+    where
+        RhsStorage: Storage<Scalar = StorageType::Scalar> + Sync,
+        RhsDims: Dimension,
+        NDims: MatMulShape<RhsDims>,
+    {
+        self.matmul(rhs)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use crate::{
+        array::type_remap::{
+            Array1, Array2, Array3, Array4, ArrayDyn, ArrayView2, ArrayView3,
+        },
+        dimension::{
+            dim::{Dim1, Dim2, Dim3, Dim4, Dimension},
+            dyn_dim::DimDyn,
+        },
+        error::ShapeError,
+        s,
+    };
+
+    #[allow(clippy::many_single_char_names)]
+    fn naive(a: &Array2<i64>, b: &Array2<i64>) -> Array2<i64> {
+        let (m, k) = (a.shape().as_slice()[0], a.shape().as_slice()[1]);
+        let n = b.shape().as_slice()[1];
+        Array2::from_shape_fn(Dim2::new([m, n]), |i| {
+            (0..k).map(|p| a[[i[0], p]] * b[[p, i[1]]]).sum()
+        })
+    }
+
+    fn assert_same(lhs: &Array2<i64>, rhs: &Array2<i64>) {
+        assert_eq!(lhs.shape().as_slice(), rhs.shape().as_slice());
+        let [m, n] = [lhs.shape().as_slice()[0], lhs.shape().as_slice()[1]];
+        for i in 0..m {
+            for j in 0..n {
+                assert_eq!(lhs[[i, j]], rhs[[i, j]], "element [{i}, {j}]");
+            }
+        }
+    }
+
+    #[test]
+    fn test_matrix_matrix() {
+        let a = Array2::from_shape_fn(Dim2::new([67, 300]), |i| {
+            i64::try_from((i[0] * 7 + i[1]) % 11).unwrap() - 5
+        });
+        let b = Array2::from_shape_fn(Dim2::new([300, 45]), |i| {
+            i64::try_from((i[0] + i[1] * 3) % 13).unwrap() - 6
+        });
+
+        assert_same(&a.matmul(&b), &naive(&a, &b));
+    }
+
+    #[test]
+    fn test_vectors() {
+        let a = Array2::from_vec(Dim2::new([2, 3]), vec![1, 2, 3, 4, 5, 6])
+            .unwrap();
+        let x = Array1::from_vec(Dim1::new([3]), vec![1, 0, -1]).unwrap();
+        let y = Array1::from_vec(Dim1::new([2]), vec![2, 1]).unwrap();
+
+        let ax = a.matmul(&x);
+        assert_eq!(ax.shape().as_slice(), &[2]);
+        assert_eq!(ax[0], -2);
+        assert_eq!(ax[1], -2);
+
+        let ya = y.matmul(&a);
+        assert_eq!(ya.shape().as_slice(), &[3]);
+        assert_eq!(ya[2], 12);
+
+        let inner = x.dot(&x);
+        assert_eq!(inner.shape().as_slice(), &[] as &[usize]);
+        assert_eq!(inner[[]], 2);
+    }
+
+    #[test]
+    fn test_strided_operands() {
+        let data: Vec<i64> = (0..60).map(|i| i % 9 - 4).collect();
+        let a = Array2::from_vec(Dim2::new([6, 10]), data.clone()).unwrap();
+
+        // The transpose of `a`, without copying
+        let t = ArrayView2::from_slice_with_strides(
+            &data,
+            Dim2::new([10, 6]),
+            &[1, 10],
+            0,
+        )
+        .unwrap();
+        let gram = a.matmul(&t);
+        assert_same(&gram, &naive(&a, &t.to_owned()));
+        assert_eq!(gram[[2, 4]], gram[[4, 2]]);
+
+        // Reversed and stepped views
+        let view = a.slice(s![..;-1, 1..;3]);
+        let product = view.matmul(&t.slice(s![1..;3, ..]));
+        assert_same(
+            &product,
+            &naive(&view.to_owned(), &t.slice(s![1..;3, ..]).to_owned()),
+        );
+    }
+
+    #[test]
+    fn test_product_as_operand() {
+        let a =
+            Array2::from_vec(Dim2::new([2, 2]), vec![1.0_f64, 2.0, 3.0, 4.0])
+                .unwrap();
+        let c = Array2::from_vec(Dim2::new([2, 2]), vec![1.0_f64; 4]).unwrap();
+
+        let result = (2.0_f64 * a.matmul(&a) + 0.5_f64 * &c).eval();
+        assert_eq!(result[[0, 0]], 14.5);
+        assert_eq!(result[[1, 1]], 44.5);
+    }
+
+    /// The matrix at `batch` in a stack of matrices
+    fn matrix_at(stack: &Array3<i64>, batch: usize) -> Array2<i64> {
+        let shape = stack.shape().as_slice();
+        Array2::from_shape_fn(Dim2::new([shape[1], shape[2]]), |i| {
+            stack[[batch, i[0], i[1]]]
+        })
+    }
+
+    #[test]
+    fn test_batched() {
+        let a = Array3::from_shape_fn(Dim3::new([4, 5, 6]), |i| {
+            i64::try_from(i[0] * 31 + i[1] * 7 + i[2]).unwrap() % 9 - 4
+        });
+        let b = Array3::from_shape_fn(Dim3::new([4, 6, 3]), |i| {
+            i64::try_from(i[0] + i[1] * 5 + i[2] * 3).unwrap() % 7 - 3
+        });
+
+        let product = a.matmul(&b);
+        assert_eq!(product.shape().as_slice(), &[4, 5, 3]);
+        for batch in 0..4 {
+            let expected = naive(&matrix_at(&a, batch), &matrix_at(&b, batch));
+            assert_same(&matrix_at(&product, batch), &expected);
+        }
+
+        // A single matrix is broadcast against every matrix in the stack
+        let weights = matrix_at(&b, 2);
+        let product = a.matmul(&weights);
+        assert_eq!(product.shape().as_slice(), &[4, 5, 3]);
+        for batch in 0..4 {
+            let expected = naive(&matrix_at(&a, batch), &weights);
+            assert_same(&matrix_at(&product, batch), &expected);
+        }
+
+        // The matrix may also be on the left, and the stack a strided view
+        let stack: ArrayView3<'_, i64> = b.slice(s![.., ..3, ..]);
+        let product = weights.matmul(&stack);
+        assert_eq!(product.shape().as_slice(), &[4, 6, 3]);
+        let expected = naive(&weights, &matrix_at(&stack.to_owned(), 1));
+        assert_same(&matrix_at(&product, 1), &expected);
+    }
+
+    #[test]
+    fn test_broadcast_batch() {
+        let a = Array4::from_shape_fn(Dim4::new([2, 1, 3, 2]), |i| {
+            i64::try_from(i[0] * 10 + i[2] * 2 + i[3]).unwrap()
+        });
+        let b = Array3::from_shape_fn(Dim3::new([3, 2, 2]), |i| {
+            i64::try_from(i[0] + i[1] * 2 + i[2]).unwrap() - 1
+        });
+
+        let product = a.matmul(&b);
+        assert_eq!(product.shape().as_slice(), &[2, 3, 3, 2]);
+        for outer in 0..2 {
+            for inner in 0..3 {
+                for i in 0..3 {
+                    for j in 0..2 {
+                        let expected: i64 = (0..2)
+                            .map(|p| a[[outer, 0, i, p]] * b[[inner, p, j]])
+                            .sum();
+                        assert_eq!(product[[outer, inner, i, j]], expected);
+                    }
+                }
+            }
+        }
+
+        // Vectors and dynamic dimensions
+        let x = Array1::from_vec(Dim1::new([2]), vec![1, -1]).unwrap();
+        let bx = b.matmul(&x);
+        assert_eq!(bx.shape().as_slice(), &[3, 2]);
+        assert_eq!(bx[[2, 1]], b[[2, 1, 0]] - b[[2, 1, 1]]);
+
+        let dynamic = ArrayDyn::from_shape_fn(
+            DimDyn::new_from(vec![2usize, 2, 2]),
+            |i| i64::try_from(i[0] * 4 + i[1] * 2 + i[2]).unwrap(),
+        );
+        let squared = dynamic.matmul(&dynamic);
+        assert_eq!(squared.shape().as_slice(), &[2, 2, 2]);
+        // [[4, 5], [6, 7]] squared
+        assert_eq!(squared[[1, 0, 0].as_slice()], 46);
+        assert_eq!(squared[[1, 1, 1].as_slice()], 79);
+    }
+
+    #[test]
+    fn test_batch_mismatch() {
+        let a = Array3::<f32>::zeros(Dim3::new([2, 3, 4]));
+        let b = Array3::<f32>::zeros(Dim3::new([3, 4, 5]));
+        assert_eq!(
+            a.try_matmul(&b).err(),
+            Some(ShapeError::BroadcastMismatch {
+                lhs: vec![2, 3, 4],
+                rhs: vec![3, 4, 5]
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty() {
+        let a = Array2::<f32>::zeros(Dim2::new([3, 0]));
+        let b = Array2::<f32>::zeros(Dim2::new([0, 4]));
+        let product = a.matmul(&b);
+        assert_eq!(product.shape().as_slice(), &[3, 4]);
+        assert_eq!(product[[2, 3]], 0.0);
+    }
+
+    #[test]
+    fn test_mismatch() {
+        let a = Array2::<f32>::zeros(Dim2::new([3, 4]));
+        let b = Array2::<f32>::zeros(Dim2::new([3, 4]));
+        assert_eq!(
+            a.try_matmul(&b).err(),
+            Some(ShapeError::MatMulMismatch {
+                lhs: vec![3, 4],
+                rhs: vec![3, 4]
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "shapes [3] and [4] are not aligned for matrix multiplication"
+    )]
+    fn test_mismatch_panics() {
+        let a = Array1::<f32>::zeros(Dim1::new([3]));
+        let b = Array1::<f32>::zeros(Dim1::new([4]));
+        let _ = a.dot(&b);
+    }
+}
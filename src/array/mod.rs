@@ -1,5 +1,29 @@
+//! Arrays, views and the lazily evaluated expressions built from them.
+
+pub mod assign_ops;
 pub mod base;
+pub mod binary_functions;
 pub mod binary_ops;
+pub mod constructors;
+pub mod einsum;
+pub mod function_1;
 pub mod function_2;
+pub mod function_3;
+pub mod index;
+pub mod iter;
+pub mod join;
+pub mod matmul;
+pub mod reduce;
+pub mod reorder;
+pub mod scalar;
+pub mod scan;
+pub mod shape;
+pub mod slice;
+pub mod ternary_functions;
+#[cfg(test)]
+mod test_fixtures;
 pub mod traits;
+pub mod transpose;
 pub mod type_remap;
+pub mod unary_ops;
+pub mod view;
@@ -0,0 +1,981 @@
+//! Reductions, such as sums and maxima, over a whole array or along an
+//! axis.
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::{
+    array::base::ArrayBase,
+    backend::{
+        host::{
+            host_backend::HostBackend,
+            host_reductions::{self, HostReduction},
+            host_storage::HostStorage,
+        },
+        traits::Storage,
+    },
+    dimension::{
+        axes::Axis,
+        dim::Dimension,
+        index::{next_index, unravel_index},
+        stride::advance,
+    },
+    error::ShapeError,
+    types::UDim,
+};
+
+/// The number of elements folded sequentially by each parallel task in a
+/// whole-array reduction.
+///
+/// This is fixed, rather than depending on the number of threads, so elements
+/// are always combined in the same order and floating-point results are
+/// reproducible.
+const REDUCTION_BLOCK: usize = 4096;
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: Storage + Sync,
+    StorageType::Scalar: Send,
+    NDims: Dimension + Sync,
+{
+    /// Fold the elements with row-major indices in `start..end` using `R`.
+    fn fold_range<R>(&self, start: usize, end: usize) -> R::Acc
+    where
+        R: HostReduction<StorageType::Scalar>,
+    {
+        let mut acc = R::init();
+
+        if self.axes.is_contiguous() {
+            let offset = self.axes.stride.offset;
+            for i in start..end {
+                acc = R::fold(acc, i, self.storage[offset + i]);
+            }
+        } else {
+            let shape = self.axes.shape.as_slice();
+            let mut index = vec![0; shape.len()];
+            unravel_index(start, shape, &mut index);
+
+            for i in start..end {
+                acc =
+                    R::fold(acc, i, self.storage[self.axes.offset_of(&index)]);
+                next_index(&mut index, shape);
+            }
+        }
+
+        acc
+    }
+
+    /// Find the storage offset of the first element of a lane along `axis`,
+    /// where `lane` is the row-major index of the lane among all lanes (the
+    /// index of the element with `axis` removed). `index` is scratch space
+    /// with one entry per axis.
+    pub(crate) fn lane_offset(
+        &self,
+        lane: usize,
+        axis: usize,
+        index: &mut [UDim],
+    ) -> usize {
+        let shape = self.axes.shape.as_slice();
+        let mut flat = lane;
+        for a in (0..shape.len()).rev() {
+            if a == axis {
+                index[a] = 0;
+            } else {
+                index[a] = flat % shape[a];
+                flat /= shape[a];
+            }
+        }
+        self.axes.offset_of(index)
+    }
+
+    /// Reduce every element of the array using `R`, in row-major order.
+    ///
+    /// The elements are split into blocks of [`REDUCTION_BLOCK`] elements,
+    /// which are folded in parallel and then combined in order.
+    fn reduce_all<R>(&self) -> Option<R::Output>
+    where
+        R: HostReduction<StorageType::Scalar>,
+    {
+        let len = self.axes.shape.len();
+        let blocks: Vec<_> = (0..len.div_ceil(REDUCTION_BLOCK))
+            .into_par_iter()
+            .map(|block| {
+                let start = block * REDUCTION_BLOCK;
+                self.fold_range::<R>(start, len.min(start + REDUCTION_BLOCK))
+            })
+            .collect();
+
+        R::finish(blocks.into_iter().fold(R::init(), R::combine), len)
+    }
+
+    /// Reduce each lane of the array along `axis` using `R`, where `name` is
+    /// the name of the reduction.
+    ///
+    /// Returns the shape of the result, which is the shape of the array with
+    /// `axis` removed, and the result for each lane in row-major order. Lanes
+    /// are reduced in parallel.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds, or if `R` has no value for an empty
+    /// lane and `axis` has length zero.
+    #[track_caller]
+    fn reduce_lanes<R>(
+        &self,
+        axis: Axis,
+        name: &str,
+    ) -> (Vec<UDim>, Vec<R::Output>)
+    where
+        R: HostReduction<StorageType::Scalar>,
+    {
+        let shape = self.axes.shape.as_slice();
+        let ndim = shape.len();
+        let Axis(axis) = axis;
+        assert!(axis < ndim, "{}", ShapeError::AxisOutOfBounds { axis, ndim });
+
+        let mut out_shape = shape.to_vec();
+        let lane_len = out_shape.remove(axis);
+        let out_len: usize = out_shape.iter().product();
+
+        let empty_lane = || -> ! {
+            panic!(
+                "cannot compute `{name}` along axis {axis}, which has length \
+                 zero, since `{name}` has no value for no elements"
+            )
+        };
+
+        // If there is only one lane, its elements are also the elements of
+        // the array in row-major order, so the lane can be split into blocks
+        if out_len == 1 {
+            let result = self.reduce_all::<R>().unwrap_or_else(|| empty_lane());
+            return (out_shape, vec![result]);
+        }
+
+        let stride = self.axes.stride.strides.as_slice()[axis];
+        let data = (0..out_len)
+            .into_par_iter()
+            .map_init(
+                || vec![0; ndim],
+                |index, lane| {
+                    let base = self.lane_offset(lane, axis, index);
+
+                    let acc = (0..lane_len).fold(R::init(), |acc, k| {
+                        R::fold(acc, k, self.storage[advance(base, k, stride)])
+                    });
+
+                    R::finish(acc, lane_len)
+                },
+            )
+            .collect::<Vec<_>>();
+
+        let data = data
+            .into_iter()
+            .map(|value| value.unwrap_or_else(|| empty_lane()))
+            .collect();
+
+        (out_shape, data)
+    }
+}
+
+/// Build the result of a per-axis reduction from the shape and data returned
+/// by `reduce_lanes`. If `keep` is given, the reduced axis is kept in the
+/// result with length one.
+fn lanes_to_array<T, D>(
+    mut shape: Vec<UDim>,
+    data: Vec<T>,
+    keep: Option<Axis>,
+) -> ArrayBase<HostBackend, HostStorage<T>, D>
+where
+    T: Copy,
+    D: Dimension,
+{
+    if let Some(Axis(axis)) = keep {
+        shape.insert(axis, 1);
+    }
+
+    let shape = D::from_slice(&shape)
+        .expect("reduced shape has an invalid number of axes");
+    ArrayBase::from_vec(shape, data)
+        .expect("reduced shape does not match the result")
+}
+
+/// Generate the whole-array and per-axis methods for a reduction.
+///
+/// `total` reductions always have a value, while `partial` reductions (such
+/// as `max`) have no value for an empty array, so the whole-array method
+/// returns an [`Option`].
+macro_rules! reduction_methods {
+    ($name: ident, $fn_name: ident, $kind: ident, $doc: literal) => {
+        paste::paste! {
+            reduction_methods!(@whole $name, $fn_name, $kind, $doc);
+
+            #[doc = $doc]
+            ///
+            /// The reduction is computed for each lane along `axis`, and the
+            /// axis is removed from the result.
+            ///
+            /// # Panics
+            #[doc = reduction_methods!(@axis_panics $kind)]
+            #[track_caller]
+            pub fn [< $fn_name _axis >](
+                &self,
+                axis: Axis,
+            ) -> ArrayBase<
+                HostBackend,
+                HostStorage<
+                    <host_reductions::[< Host $name Reduction >] as
+                        HostReduction<StorageType::Scalar>>::Output,
+                >,
+                NDims::Smaller,
+            >
+            where
+                host_reductions::[< Host $name Reduction >]:
+                    HostReduction<StorageType::Scalar>,
+            {
+                let (shape, data) = self.reduce_lanes::<
+                    host_reductions::[< Host $name Reduction >],
+                >(axis, stringify!($fn_name));
+
+                lanes_to_array(shape, data, None)
+            }
+
+            #[doc = $doc]
+            ///
+            /// The reduction is computed for each lane along `axis`, which
+            /// is kept in the result with length one, so the result can be
+            /// broadcast against the original array.
+            ///
+            /// # Panics
+            #[doc = reduction_methods!(@axis_panics $kind)]
+            #[track_caller]
+            pub fn [< $fn_name _axis_keepdims >](
+                &self,
+                axis: Axis,
+            ) -> ArrayBase<
+                HostBackend,
+                HostStorage<
+                    <host_reductions::[< Host $name Reduction >] as
+                        HostReduction<StorageType::Scalar>>::Output,
+                >,
+                NDims,
+            >
+            where
+                host_reductions::[< Host $name Reduction >]:
+                    HostReduction<StorageType::Scalar>,
+            {
+                let (shape, data) = self.reduce_lanes::<
+                    host_reductions::[< Host $name Reduction >],
+                >(axis, stringify!($fn_name));
+
+                lanes_to_array(shape, data, Some(axis))
+            }
+        }
+    };
+
+    (@axis_panics total) => {
+        "Panics if `axis` is out of bounds for the array."
+    };
+
+    (@axis_panics partial) => {
+        "Panics if `axis` is out of bounds for the array, or if `axis` has \
+         length zero (and the other axes do not), since the reduction has no \
+         value for an empty lane. The whole-array method returns `None` in \
+         this case instead."
+    };
+
+    (@whole $name: ident, $fn_name: ident, total, $doc: literal) => {
+        paste::paste! {
+            #[doc = $doc]
+            ///
+            /// The reduction is computed over every element of the array.
+            pub fn $fn_name(
+                &self,
+            ) -> <host_reductions::[< Host $name Reduction >] as
+                HostReduction<StorageType::Scalar>>::Output
+            where
+                host_reductions::[< Host $name Reduction >]:
+                    HostReduction<StorageType::Scalar>,
+            {
+                self.reduce_all::<host_reductions::[< Host $name Reduction >]>()
+                    .expect(concat!("`", stringify!($fn_name), "` always has a value"))
+            }
+        }
+    };
+
+    (@whole $name: ident, $fn_name: ident, partial, $doc: literal) => {
+        paste::paste! {
+            #[doc = $doc]
+            ///
+            /// The reduction is computed over every element of the array, and
+            /// is `None` if the array is empty.
+            pub fn $fn_name(
+                &self,
+            ) -> Option<
+                <host_reductions::[< Host $name Reduction >] as
+                    HostReduction<StorageType::Scalar>>::Output,
+            >
+            where
+                host_reductions::[< Host $name Reduction >]:
+                    HostReduction<StorageType::Scalar>,
+            {
+                self.reduce_all::<host_reductions::[< Host $name Reduction >]>()
+            }
+        }
+    };
+}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: Storage + Sync,
+    StorageType::Scalar: Send,
+    NDims: Dimension + Sync,
+{
+    reduction_methods!(
+        Sum,
+        sum,
+        total,
+        "Compute the sum of the elements. Integer sums wrap on overflow, as in \
+         `NumPy`."
+    );
+    reduction_methods!(
+        Prod,
+        prod,
+        total,
+        "Compute the product of the elements. Integer products wrap on \
+         overflow, as in `NumPy`."
+    );
+    reduction_methods!(
+        Mean,
+        mean,
+        partial,
+        "Compute the arithmetic mean of the elements. Integer elements are \
+         converted to `f64`, so the mean is neither truncated nor affected by \
+         overflow."
+    );
+    reduction_methods!(
+        Min,
+        min,
+        partial,
+        "Find the smallest element. NaN is propagated."
+    );
+    reduction_methods!(
+        Max,
+        max,
+        partial,
+        "Find the largest element. NaN is propagated."
+    );
+    reduction_methods!(
+        ArgMin,
+        argmin,
+        partial,
+        "Find the (row-major) index of the smallest element. If there are \
+         several, the first is returned, and the index of the first NaN takes \
+         priority over every other value."
+    );
+    reduction_methods!(
+        ArgMax,
+        argmax,
+        partial,
+        "Find the (row-major) index of the largest element. If there are \
+         several, the first is returned, and the index of the first NaN takes \
+         priority over every other value."
+    );
+    reduction_methods!(
+        NanSum,
+        nansum,
+        total,
+        "Compute the sum of the elements, ignoring NaN."
+    );
+    reduction_methods!(
+        NanMean,
+        nanmean,
+        total,
+        "Compute the arithmetic mean of the elements, ignoring NaN. The mean \
+         is NaN if there are no other elements."
+    );
+    reduction_methods!(
+        NanMin,
+        nanmin,
+        total,
+        "Find the smallest element, ignoring NaN. The result is NaN if there \
+         are no other elements."
+    );
+    reduction_methods!(
+        NanMax,
+        nanmax,
+        total,
+        "Find the largest element, ignoring NaN. The result is NaN if there \
+         are no other elements."
+    );
+    reduction_methods!(
+        LogSumExp,
+        logsumexp,
+        total,
+        "Compute `ln(sum(exp(x)))` without overflow, by scaling by the largest \
+         element. The result for no elements is negative infinity."
+    );
+    reduction_methods!(
+        Any,
+        any,
+        total,
+        "Returns `true` if any element of a boolean array is `true`."
+    );
+    reduction_methods!(
+        All,
+        all,
+        total,
+        "Returns `true` if every element of a boolean array is `true`."
+    );
+}
+
+/// Compute a variance from the moments of a set of elements, where `ddof` is
+/// subtracted from the number of elements in the divisor.
+#[track_caller]
+fn moments_to_variance<T: Float>(
+    (count, _, m2): (usize, T, T),
+    ddof: usize,
+) -> T {
+    assert!(
+        ddof <= count,
+        "`ddof` ({ddof}) is greater than the number of elements ({count})"
+    );
+
+    T::from(count - ddof).map_or_else(T::nan, |divisor| m2 / divisor)
+}
+
+/// Generate the whole-array and per-axis methods for a statistic computed
+/// from the variance of the elements.
+macro_rules! moment_methods {
+    ($fn_name: ident, |$var: ident| $map: expr, $doc: literal) => {
+        paste::paste! {
+            #[doc = $doc]
+            ///
+            /// The divisor is `n - ddof`, where `n` is the number of elements,
+            /// so `ddof = 0` gives the population statistic and `ddof = 1`
+            /// the sample statistic. The result is NaN if `n == ddof`.
+            ///
+            /// # Panics
+            /// Panics if `ddof` is greater than the number of elements.
+            #[track_caller]
+            pub fn $fn_name(&self, ddof: usize) -> StorageType::Scalar {
+                let moments = self
+                    .reduce_all::<host_reductions::HostMomentsReduction>()
+                    .expect("moments always have a value");
+                let $var = moments_to_variance(moments, ddof);
+                $map
+            }
+
+            #[doc = $doc]
+            ///
+            /// The statistic is computed for each lane along `axis`, with
+            /// divisor `n - ddof` where `n` is the length of the axis, and
+            /// the axis is removed from the result.
+            ///
+            /// # Panics
+            /// Panics if `axis` is out of bounds for the array, or if `ddof`
+            /// is greater than the length of the axis.
+            #[track_caller]
+            pub fn [< $fn_name _axis >](
+                &self,
+                axis: Axis,
+                ddof: usize,
+            ) -> ArrayBase<
+                HostBackend,
+                HostStorage<StorageType::Scalar>,
+                NDims::Smaller,
+            > {
+                let (shape, data) = self.[< $fn_name _lanes >](axis, ddof);
+                lanes_to_array(shape, data, None)
+            }
+
+            #[doc = $doc]
+            ///
+            /// The statistic is computed for each lane along `axis`, with
+            /// divisor `n - ddof` where `n` is the length of the axis. The
+            /// axis is kept in the result with length one, so the result can
+            /// be broadcast against the original array.
+            ///
+            /// # Panics
+            /// Panics if `axis` is out of bounds for the array, or if `ddof`
+            /// is greater than the length of the axis.
+            #[track_caller]
+            pub fn [< $fn_name _axis_keepdims >](
+                &self,
+                axis: Axis,
+                ddof: usize,
+            ) -> ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims>
+            {
+                let (shape, data) = self.[< $fn_name _lanes >](axis, ddof);
+                lanes_to_array(shape, data, Some(axis))
+            }
+
+            #[track_caller]
+            fn [< $fn_name _lanes >](
+                &self,
+                axis: Axis,
+                ddof: usize,
+            ) -> (Vec<UDim>, Vec<StorageType::Scalar>) {
+                let (shape, moments) = self
+                    .reduce_lanes::<host_reductions::HostMomentsReduction>(
+                        axis,
+                        stringify!($fn_name),
+                    );
+
+                let data = moments
+                    .into_iter()
+                    .map(|moments| {
+                        let $var = moments_to_variance(moments, ddof);
+                        $map
+                    })
+                    .collect();
+                (shape, data)
+            }
+        }
+    };
+}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: Storage + Sync,
+    StorageType::Scalar: Float + Send + Sync,
+    NDims: Dimension + Sync,
+{
+    moment_methods!(
+        var,
+        |variance| variance,
+        "Compute the variance of the elements."
+    );
+    moment_methods!(
+        std,
+        |variance| variance.sqrt(),
+        "Compute the standard deviation of the elements."
+    );
+
+    /// Compute the softmax of the array along `axis`, which is
+    /// `exp(x - logsumexp(x))` for each lane `x`. Every lane of the result
+    /// is non-negative and sums to one, and large elements do not overflow.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds for the array.
+    #[track_caller]
+    pub fn softmax(
+        &self,
+        axis: Axis,
+    ) -> ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims> {
+        let (_, lse) = self
+            .reduce_lanes::<host_reductions::HostLogSumExpReduction>(
+                axis, "softmax",
+            );
+
+        let shape = self.axes.shape.as_slice();
+        let len = self.axes.shape.len();
+        let Axis(axis) = axis;
+
+        // Each block of elements is computed in parallel, and the blocks are
+        // concatenated in row-major order
+        let blocks: Vec<Vec<_>> = (0..len.div_ceil(REDUCTION_BLOCK))
+            .into_par_iter()
+            .map(|block| {
+                let start = block * REDUCTION_BLOCK;
+                let end = len.min(start + REDUCTION_BLOCK);
+
+                let mut index = vec![0; shape.len()];
+                unravel_index(start, shape, &mut index);
+
+                (start..end)
+                    .map(|_| {
+                        // The lane is the row-major index of the element with
+                        // `axis` removed
+                        let lane = (0..shape.len())
+                            .filter(|&a| a != axis)
+                            .fold(0, |lane, a| lane * shape[a] + index[a]);
+                        let value = self.storage[self.axes.offset_of(&index)];
+                        next_index(&mut index, shape);
+                        (value - lse[lane]).exp()
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let data = blocks.into_iter().flatten().collect();
+        ArrayBase::from_vec(self.axes.shape.clone(), data)
+            .expect("softmax shape does not match the result")
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use crate::{
+        array::{
+            test_fixtures::matrix,
+            type_remap::{Array1, Array2, Array3, ArrayDyn, ArrayView2},
+        },
+        dimension::{
+            axes::Axis,
+            dim::{Dim1, Dim2, Dim3, Dimension},
+            dyn_dim::DimDyn,
+        },
+        s,
+    };
+
+    #[test]
+    fn test_whole_array() {
+        let a = matrix();
+
+        assert_eq!(a.sum(), 66);
+        assert_eq!(a.prod(), 0);
+        assert_eq!(a.min(), Some(0));
+        assert_eq!(a.max(), Some(11));
+        assert_eq!(a.argmin(), Some(0));
+        assert_eq!(a.argmax(), Some(11));
+        assert_eq!(a.mean(), Some(5.5));
+
+        let b = Array1::from_vec(Dim1::new([4]), vec![2.0_f64, 0.5, 4.0, 1.0])
+            .unwrap();
+        assert_eq!(b.prod(), 4.0);
+        assert_eq!(b.mean(), Some(1.875));
+        assert_eq!(b.argmin(), Some(1));
+    }
+
+    #[test]
+    fn test_integer_mean() {
+        // The sum of these does not fit in the element type
+        let ones = Array1::<u8>::ones(Dim1::new([300]));
+        assert_eq!(ones.mean(), Some(1.0));
+
+        // Nor does the number of elements
+        let signed = Array1::from_shape_fn(Dim1::new([200]), |i| {
+            if i[0] % 2 == 0 {
+                -100_i8
+            } else {
+                101
+            }
+        });
+        assert_eq!(signed.mean(), Some(0.5));
+
+        let rows = matrix().mean_axis(Axis(1));
+        assert_eq!(rows[0], 1.5);
+        assert_eq!(rows[2], 9.5);
+
+        let large =
+            Array1::from_vec(Dim1::new([2]), vec![u64::MAX, u64::MAX]).unwrap();
+        assert_eq!(large.mean(), Some(18_446_744_073_709_551_615.0));
+    }
+
+    #[test]
+    fn test_integer_overflow() {
+        let unsigned =
+            Array1::<u8>::from_vec(Dim1::new([3]), vec![200, 100, 1]).unwrap();
+        assert_eq!(unsigned.sum(), 45);
+        assert_eq!(unsigned.prod(), 32);
+
+        let signed =
+            Array1::<i8>::from_vec(Dim1::new([3]), vec![100, 100, -1]).unwrap();
+        assert_eq!(signed.sum(), -57);
+        assert_eq!(signed.prod(), -16);
+
+        // Blocks summed in parallel wrap in the same way
+        let ones = Array1::<u8>::ones(Dim1::new([100_000]));
+        assert_eq!(ones.sum(), 160);
+        assert_eq!(ones.sum_axis(Axis(0))[[]], 160);
+    }
+
+    #[test]
+    fn test_sum_axis() {
+        let a = matrix();
+
+        let columns = a.sum_axis(Axis(0));
+        assert_eq!(columns.shape().as_slice(), &[4]);
+        assert_eq!(columns[0], 12);
+        assert_eq!(columns[3], 21);
+
+        let rows = a.sum_axis(Axis(1));
+        assert_eq!(rows.shape().as_slice(), &[3]);
+        assert_eq!(rows[0], 6);
+        assert_eq!(rows[2], 38);
+
+        let kept = a.sum_axis_keepdims(Axis(1));
+        assert_eq!(kept.shape().as_slice(), &[3, 1]);
+        assert_eq!(kept[[1, 0]], 22);
+
+        // The kept axis allows the result to be broadcast against the input
+        let shifted = (&a - &a.min_axis_keepdims(Axis(0))).eval();
+        assert_eq!(shifted[[0, 0]], 0);
+        assert_eq!(shifted[[2, 3]], 8);
+    }
+
+    #[test]
+    fn test_min_max_axis() {
+        let a = Array2::from_vec(
+            Dim2::new([2, 3]),
+            vec![3.0_f32, -1.0, 7.0, 2.0, f32::NAN, 9.0],
+        )
+        .unwrap();
+
+        let min = a.min_axis(Axis(1));
+        assert_eq!(min[0], -1.0);
+        assert!(min[1].is_nan());
+
+        let max = a.max_axis(Axis(0));
+        assert_eq!(max[0], 3.0);
+        assert!(max[1].is_nan());
+        assert_eq!(max[2], 9.0);
+
+        let argmax = a.argmax_axis(Axis(1));
+        assert_eq!(argmax[0], 2);
+        assert_eq!(argmax[1], 1);
+
+        let argmin = a.argmin_axis_keepdims(Axis(0));
+        assert_eq!(argmin.shape().as_slice(), &[1, 3]);
+        assert_eq!(argmin[[0, 0]], 1);
+        assert_eq!(argmin[[0, 2]], 0);
+
+        // Ties are resolved in favour of the first element
+        let ties = Array1::from_vec(Dim1::new([4]), vec![1, 5, 5, 1]).unwrap();
+        assert_eq!(ties.argmax(), Some(1));
+        assert_eq!(ties.argmin(), Some(0));
+    }
+
+    #[test]
+    fn test_any_all() {
+        let a = matrix();
+        let mask = a.gt(5_i64).eval();
+
+        assert!(mask.any());
+        assert!(!mask.all());
+
+        let rows = mask.all_axis(Axis(1));
+        assert!(!rows[0]);
+        assert!(!rows[1]);
+        assert!(rows[2]);
+
+        let columns = mask.any_axis(Axis(0));
+        assert!((0..4).all(|i| columns[i]));
+    }
+
+    #[test]
+    fn test_output_dimensions() {
+        let a = Array3::from_shape_fn(Dim3::new([2, 3, 4]), |i| {
+            i[0] * 100 + i[1] * 10 + i[2]
+        });
+
+        let reduced = a.max_axis(Axis(1));
+        assert_eq!(reduced.shape().as_slice(), &[2, 4]);
+        assert_eq!(reduced[[1, 3]], 123);
+
+        let vector = Array1::from_vec(Dim1::new([3]), vec![1, 2, 3]).unwrap();
+        let scalar = vector.sum_axis(Axis(0));
+        assert_eq!(scalar.shape().as_slice(), &[] as &[usize]);
+        assert_eq!(scalar[[]], 6);
+
+        let dynamic = ArrayDyn::from_shape_fn(
+            DimDyn::new_from(vec![2usize, 2, 2]),
+            |i| i[0] + i[1] + i[2],
+        );
+        let reduced = dynamic.sum_axis(Axis(2));
+        assert_eq!(reduced.shape().as_slice(), &[2, 2]);
+        assert_eq!(reduced[[1, 1].as_slice()], 5);
+
+        let kept = dynamic.prod_axis_keepdims(Axis(0));
+        assert_eq!(kept.shape().as_slice(), &[1, 2, 2]);
+    }
+
+    #[test]
+    fn test_strided_views() {
+        let a = matrix();
+        let view: ArrayView2<'_, i64> = a.slice(s![..;-1, 1..;2]);
+
+        // [[9, 11], [5, 7], [1, 3]]
+        assert_eq!(view.sum(), 36);
+        assert_eq!(view.argmax(), Some(1));
+
+        let rows = view.sum_axis(Axis(1));
+        assert_eq!(rows[0], 20);
+        assert_eq!(rows[2], 4);
+
+        let columns = view.min_axis(Axis(0));
+        assert_eq!(columns[0], 1);
+        assert_eq!(columns[1], 3);
+    }
+
+    #[test]
+    fn test_large_reduction_is_deterministic() {
+        let len = 100_003;
+        let a = Array1::from_shape_fn(Dim1::new([len]), |i| {
+            1.0 / f64::from(u32::try_from(i[0] % 97 + 1).unwrap())
+        });
+
+        let first = a.sum();
+        for _ in 0..4 {
+            assert_eq!(a.sum(), first);
+            assert_eq!(a.sum_axis(Axis(0))[[]], first);
+        }
+
+        let expected: f64 = (0..len)
+            .map(|i| 1.0 / f64::from(u32::try_from(i % 97 + 1).unwrap()))
+            .sum();
+        assert!((first - expected).abs() < 1e-9);
+
+        let large = Array2::from_shape_fn(Dim2::new([300, 500]), |i| i[1]);
+        assert_eq!(large.argmax(), Some(499));
+        assert_eq!(large.max_axis(Axis(1))[299], 499);
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = Array2::<f32>::zeros(Dim2::new([0, 3]));
+        assert_eq!(empty.sum(), 0.0);
+        assert_eq!(empty.prod(), 1.0);
+        assert_eq!(empty.max(), None);
+        assert_eq!(empty.mean(), None);
+        assert_eq!(empty.argmin(), None);
+
+        let sums = empty.sum_axis(Axis(0));
+        assert_eq!(sums.shape().as_slice(), &[3]);
+        assert_eq!(sums[2], 0.0);
+
+        let maxima = empty.max_axis(Axis(1));
+        assert_eq!(maxima.shape().as_slice(), &[0]);
+    }
+
+    #[test]
+    fn test_compensated_sum() {
+        // Adding each one to 1e8 directly would round it away in `f32`
+        let mut data = vec![1.0_f32; 1001];
+        data[0] = 1e8;
+        let a = Array1::from_vec(Dim1::new([1001]), data).unwrap();
+        assert_eq!(a.sum(), 100_001_000.0);
+        assert_eq!(a.mean(), Some(100_001_000.0 / 1001.0));
+
+        let large = Array1::from_shape_fn(Dim1::new([1_000_000]), |_| 0.1_f32);
+        assert!((large.sum() - 100_000.0).abs() < 0.01);
+
+        let infinite =
+            Array1::from_vec(Dim1::new([3]), vec![1.0_f32, f32::INFINITY, 2.0])
+                .unwrap();
+        assert_eq!(infinite.sum(), f32::INFINITY);
+    }
+
+    #[test]
+    fn test_var_std() {
+        let a = Array2::from_vec(
+            Dim2::new([2, 4]),
+            vec![2.0_f64, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0],
+        )
+        .unwrap();
+
+        assert_eq!(a.var(0), 4.0);
+        assert_eq!(a.std(0), 2.0);
+        assert_eq!(a.var(1), 32.0 / 7.0);
+
+        let rows = a.var_axis(Axis(1), 0);
+        assert_eq!(rows.shape().as_slice(), &[2]);
+        assert_eq!(rows[0], 0.75);
+        assert!((rows[1] - 2.75).abs() < 1e-12);
+
+        let columns = a.std_axis_keepdims(Axis(0), 1);
+        assert_eq!(columns.shape().as_slice(), &[1, 4]);
+        assert!((columns[[0, 0]] - 4.5_f64.sqrt()).abs() < 1e-12);
+        assert!((columns[[0, 3]] - 12.5_f64.sqrt()).abs() < 1e-12);
+
+        let single = Array1::from_vec(Dim1::new([1]), vec![3.0_f32]).unwrap();
+        assert_eq!(single.var(0), 0.0);
+        assert!(single.var(1).is_nan());
+
+        // A large offset does not destroy the precision of the variance
+        let offset = Array1::from_shape_fn(Dim1::new([10_000]), |i| {
+            1e9 + f64::from(u32::try_from(i[0] % 2).unwrap())
+        });
+        assert!((offset.var(0) - 0.25).abs() < 1e-9);
+        let first = offset.var(1);
+        assert!((0..4).all(|_| offset.var(1) == first));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "`ddof` (3) is greater than the number of elements (2)"
+    )]
+    fn test_ddof_too_large() {
+        let a = Array2::<f32>::zeros(Dim2::new([2, 5]));
+        let _ = a.var_axis(Axis(0), 3);
+    }
+
+    #[test]
+    fn test_logsumexp_softmax() {
+        let a = Array2::from_vec(
+            Dim2::new([2, 3]),
+            vec![1000.0_f64, 1000.0, 1000.0, 0.0, 1.0, 2.0],
+        )
+        .unwrap();
+
+        let lse = a.logsumexp_axis(Axis(1));
+        assert!((lse[0] - (1000.0 + 3.0_f64.ln())).abs() < 1e-9);
+        let expected = (1.0_f64 + 1.0_f64.exp() + 2.0_f64.exp()).ln();
+        assert!((lse[1] - expected).abs() < 1e-12);
+
+        let empty = Array1::<f32>::zeros(Dim1::new([0]));
+        assert_eq!(empty.logsumexp(), f32::NEG_INFINITY);
+        let infinite =
+            Array1::from_vec(Dim1::new([2]), vec![f32::INFINITY, 1.0]).unwrap();
+        assert_eq!(infinite.logsumexp(), f32::INFINITY);
+
+        let softmax = a.softmax(Axis(1));
+        assert_eq!(softmax.shape().as_slice(), &[2, 3]);
+        assert!((softmax[[0, 1]] - 1.0 / 3.0).abs() < 1e-12);
+        let row: f64 = (0..3).map(|j| softmax[[1, j]]).sum();
+        assert!((row - 1.0).abs() < 1e-12);
+        assert!(softmax[[1, 2]] > softmax[[1, 1]]);
+
+        // Along a strided, reversed axis
+        let view = a.slice(s![.., ..;-1]);
+        let reversed = view.softmax(Axis(1));
+        assert_eq!(reversed[[1, 0]], softmax[[1, 2]]);
+
+        let columns = a.softmax(Axis(0));
+        assert!((columns[[0, 0]] + columns[[1, 0]] - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_nan_reductions() {
+        let a = Array2::from_vec(
+            Dim2::new([2, 3]),
+            vec![1.0_f32, f32::NAN, 3.0, f32::NAN, f32::NAN, f32::NAN],
+        )
+        .unwrap();
+
+        assert_eq!(a.nansum(), 4.0);
+        assert_eq!(a.nanmean(), 2.0);
+        assert_eq!(a.nanmax(), 3.0);
+        assert_eq!(a.nanmin(), 1.0);
+        assert!(a.sum().is_nan());
+
+        let rows = a.nanmean_axis(Axis(1));
+        assert_eq!(rows[0], 2.0);
+        assert!(rows[1].is_nan());
+
+        let sums = a.nansum_axis(Axis(1));
+        assert_eq!(sums[1], 0.0);
+
+        let maxima = a.nanmax_axis_keepdims(Axis(0));
+        assert_eq!(maxima.shape().as_slice(), &[1, 3]);
+        assert_eq!(maxima[[0, 0]], 1.0);
+        assert!(maxima[[0, 1]].is_nan());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "cannot compute `mean` along axis 0, which has length zero"
+    )]
+    fn test_empty_axis() {
+        let empty = Array2::<f32>::zeros(Dim2::new([0, 3]));
+        let _ = empty.mean_axis(Axis(0));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "axis 2 is out of bounds for an array with 2 dimensions"
+    )]
+    fn test_axis_out_of_bounds() {
+        let _ = matrix().sum_axis(Axis(2));
+    }
+}
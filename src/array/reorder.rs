@@ -0,0 +1,510 @@
+//! Operations which reorder or repeat the elements of an array, such as
+//! `flip`, `roll` and `pad`.
+
+use crate::{
+    array::{base::ArrayBase, type_remap::ArrayView},
+    backend::host::{
+        host_backend::HostBackend,
+        host_storage::{HostData, HostStorage},
+    },
+    dimension::{axes::Axis, dim::Dimension, stride::advance},
+    error::ShapeError,
+    types::UDim,
+};
+
+/// How the values added by [`ArrayBase::pad`] are chosen. Using `NumPy`'s
+/// names, with an axis containing `a b c d`:
+///
+/// | Mode          | Padded by two on each side |
+/// |---------------|----------------------------|
+/// | `Constant(0)` | `0 0 a b c d 0 0`          |
+/// | `Edge`        | `a a a b c d d d`          |
+/// | `Reflect`     | `c b a b c d c b`          |
+/// | `Wrap`        | `c d a b c d a b`          |
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadMode<T> {
+    /// Pad with a single value
+    Constant(T),
+
+    /// Repeat the first and last elements along each axis
+    Edge,
+
+    /// Mirror the elements about the first and last elements, which are not
+    /// repeated
+    Reflect,
+
+    /// Continue periodically, as if the axis wraps around
+    Wrap,
+}
+
+/// The number of positions by which an axis of length `len` is rolled when
+/// rolling by `shift`, which may be negative, as a value in `0..len`
+const fn normalise_shift(shift: isize, len: usize) -> usize {
+    let magnitude = shift.unsigned_abs() % len;
+    if shift >= 0 || magnitude == 0 {
+        magnitude
+    } else {
+        len - magnitude
+    }
+}
+
+/// The index along an axis of length `len` of the element copied to index
+/// `i` of the same axis padded by `before` elements at the start, or `None`
+/// if the padding is a constant. `len` must not be zero.
+fn pad_source<T>(
+    mode: &PadMode<T>,
+    i: usize,
+    before: usize,
+    len: usize,
+) -> Option<UDim> {
+    if (before..before + len).contains(&i) {
+        return Some(i - before);
+    }
+
+    match mode {
+        PadMode::Constant(_) => None,
+        PadMode::Edge => Some(if i < before { 0 } else { len - 1 }),
+        PadMode::Wrap => Some((i + len - before % len) % len),
+        PadMode::Reflect if len == 1 => Some(0),
+        PadMode::Reflect => {
+            let period = 2 * (len - 1);
+            let position = (i + period - before % period) % period;
+            Some(if position < len { position } else { period - position })
+        }
+    }
+}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    /// Copy elements into a new array with the given `shape`, where
+    /// `sources[axis][i]` is the index along `axis` of the element copied to
+    /// index `i`. Any element with no source along some axis is set to
+    /// `fill`.
+    fn gather(
+        &self,
+        shape: NDims,
+        sources: &[Vec<Option<UDim>>],
+        fill: Option<StorageType::Scalar>,
+    ) -> ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims> {
+        let strides = self.axes.stride.strides.as_slice();
+
+        ArrayBase::from_shape_fn(shape, |index| {
+            let mut offset = self.axes.stride.offset;
+            for ((table, &i), &stride) in sources.iter().zip(index).zip(strides)
+            {
+                let Some(source) = table[i] else {
+                    return fill
+                        .expect("only padding has elements without a source");
+                };
+                offset = advance(offset, source, stride);
+            }
+            self.storage[offset]
+        })
+    }
+
+    /// Return the length of `axis`, or an error if it does not exist
+    fn checked_axis_len(&self, axis: Axis) -> Result<usize, ShapeError> {
+        let shape = self.axes.shape.as_slice();
+        shape.get(axis.0).copied().ok_or(ShapeError::AxisOutOfBounds {
+            axis: axis.0,
+            ndim: shape.len(),
+        })
+    }
+
+    /// Return an error unless one value is given for each axis
+    fn check_per_axis(&self, count: usize) -> Result<(), ShapeError> {
+        let ndim = self.axes.shape.ndim() as usize;
+        if count == ndim {
+            Ok(())
+        } else {
+            Err(ShapeError::DimensionMismatch { expected: ndim, found: count })
+        }
+    }
+
+    /// The source of each element along each axis when the array is copied
+    /// unchanged
+    fn identity_sources(&self) -> Vec<Vec<Option<UDim>>> {
+        self.axes
+            .shape
+            .as_slice()
+            .iter()
+            .map(|&len| (0..len).map(Some).collect())
+            .collect()
+    }
+
+    /// Return a view of the array with the order of the elements along `axis`
+    /// reversed. No elements are copied, since the view has a negative stride
+    /// along that axis.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` does not exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let image = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+    /// let mirrored = image.flip(Axis(1)).unwrap();
+    /// assert_eq!(mirrored[[0, 0]], 2);
+    /// assert_eq!(mirrored[[1, 2]], 3);
+    /// ```
+    pub fn flip(
+        &self,
+        axis: Axis,
+    ) -> Result<ArrayView<'_, StorageType::Scalar, NDims>, ShapeError> {
+        let len = self.checked_axis_len(axis)?;
+        let mut flipped = self.axes.clone();
+
+        if len > 0 {
+            // Safety: The stride is negated, and the offset moved to the last
+            // element, so the same elements are addressed in reverse order
+            unsafe {
+                flipped.stride.offset =
+                    flipped.stride.offset_along(axis.0, len - 1);
+                let stride = &mut flipped.stride.strides.as_slice_mut()[axis.0];
+                *stride = stride.wrapping_neg();
+            }
+        }
+
+        // Safety: The axes address the same elements as the array
+        Ok(unsafe { self.view_with_axes(flipped) })
+    }
+
+    /// Copy the array with its elements shifted by `shift` positions along
+    /// `axis`, where elements shifted past the end of the axis wrap around
+    /// to the start. A negative shift moves elements towards the start.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` does not exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::Dim1;
+    ///
+    /// let array = Array1::from_vec(Dim1::new([4]), vec![1, 2, 3, 4]).unwrap();
+    ///
+    /// let rolled = array.roll(1, Axis(0)).unwrap();
+    /// assert_eq!([rolled[0], rolled[1], rolled[2], rolled[3]], [4, 1, 2, 3]);
+    ///
+    /// let rolled = array.roll(-5, Axis(0)).unwrap();
+    /// assert_eq!([rolled[0], rolled[1], rolled[2], rolled[3]], [2, 3, 4, 1]);
+    /// ```
+    pub fn roll(
+        &self,
+        shift: isize,
+        axis: Axis,
+    ) -> Result<
+        ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims>,
+        ShapeError,
+    > {
+        let len = self.checked_axis_len(axis)?;
+        let mut sources = self.identity_sources();
+        if len > 0 {
+            let shift = normalise_shift(shift, len);
+            sources[axis.0] =
+                (0..len).map(|i| Some((i + len - shift) % len)).collect();
+        }
+
+        Ok(self.gather(self.axes.shape.clone(), &sources, None))
+    }
+
+    /// Copy the array repeatedly along each axis, so that it appears
+    /// `reps[axis]` times along `axis`.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::DimensionMismatch`] if `reps` does not have one
+    /// value for each axis.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_vec(Dim2::new([1, 2]), vec![1, 2]).unwrap();
+    /// let tiled = array.tile(&[2, 3]).unwrap();
+    /// assert_eq!(tiled.shape().as_slice(), &[2, 6]);
+    /// assert_eq!(tiled[[1, 4]], 1);
+    /// assert_eq!(tiled[[1, 5]], 2);
+    /// ```
+    pub fn tile(
+        &self,
+        reps: &[usize],
+    ) -> Result<
+        ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims>,
+        ShapeError,
+    > {
+        self.check_per_axis(reps.len())?;
+
+        let mut shape = self.axes.shape.clone();
+        let sources: Vec<_> = self
+            .axes
+            .shape
+            .as_slice()
+            .iter()
+            .zip(reps)
+            .map(|(&len, &rep)| {
+                (0..len * rep).map(|i| Some(i % len)).collect::<Vec<_>>()
+            })
+            .collect();
+
+        // Safety: Only the lengths of the axes change
+        unsafe {
+            for (len, source) in shape.as_slice_mut().iter_mut().zip(&sources) {
+                *len = source.len();
+            }
+        }
+
+        Ok(self.gather(shape, &sources, None))
+    }
+
+    /// Copy the array with each element repeated `n` times along `axis`, so
+    /// that `a b` becomes `a a b b` for `n = 2`.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` does not exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+    /// let repeated = array.repeat(3, Axis(0)).unwrap();
+    /// assert_eq!(repeated.shape().as_slice(), &[6, 2]);
+    /// assert_eq!(repeated[[2, 1]], 2);
+    /// assert_eq!(repeated[[3, 0]], 3);
+    /// ```
+    pub fn repeat(
+        &self,
+        n: usize,
+        axis: Axis,
+    ) -> Result<
+        ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims>,
+        ShapeError,
+    > {
+        let len = self.checked_axis_len(axis)?;
+
+        let mut shape = self.axes.shape.clone();
+        let mut sources = self.identity_sources();
+        sources[axis.0] = (0..len * n).map(|i| Some(i / n)).collect();
+
+        // Safety: Only the length of `axis` changes
+        unsafe {
+            shape.as_slice_mut()[axis.0] = len * n;
+        }
+
+        Ok(self.gather(shape, &sources, None))
+    }
+
+    /// Copy the array with `widths[axis].0` elements added before the start
+    /// of each axis and `widths[axis].1` added after its end, with values
+    /// chosen by `mode` (see [`PadMode`]).
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::DimensionMismatch`] if `widths` does not have one
+    /// value for each axis, or [`ShapeError::PadEmptyAxis`] if an axis of
+    /// length zero is padded with any mode other than
+    /// [`PadMode::Constant`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::reorder::PadMode;
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+    ///
+    /// let padded = array.pad(&[(1, 1), (0, 2)], PadMode::Constant(0)).unwrap();
+    /// assert_eq!(padded.shape().as_slice(), &[4, 4]);
+    /// assert_eq!(padded[[0, 0]], 0);
+    /// assert_eq!(padded[[1, 1]], 2);
+    ///
+    /// let padded = array.pad(&[(0, 0), (2, 0)], PadMode::Wrap).unwrap();
+    /// assert_eq!(padded[[1, 0]], 3);
+    /// assert_eq!(padded[[1, 1]], 4);
+    /// ```
+    pub fn pad(
+        &self,
+        widths: &[(usize, usize)],
+        mode: PadMode<StorageType::Scalar>,
+    ) -> Result<
+        ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims>,
+        ShapeError,
+    > {
+        self.check_per_axis(widths.len())?;
+
+        let mut shape = self.axes.shape.clone();
+        let mut sources = Vec::with_capacity(widths.len());
+        for (axis, (&len, &(before, after))) in
+            self.axes.shape.as_slice().iter().zip(widths).enumerate()
+        {
+            let padded = before + len + after;
+            let source = if len == 0 {
+                if padded > 0 && !matches!(mode, PadMode::Constant(_)) {
+                    return Err(ShapeError::PadEmptyAxis { axis });
+                }
+                vec![None; padded]
+            } else {
+                (0..padded).map(|i| pad_source(&mode, i, before, len)).collect()
+            };
+            sources.push(source);
+        }
+
+        // Safety: Only the lengths of the axes change
+        unsafe {
+            for (len, source) in shape.as_slice_mut().iter_mut().zip(&sources) {
+                *len = source.len();
+            }
+        }
+
+        let fill = match mode {
+            PadMode::Constant(value) => Some(value),
+            _ => None,
+        };
+        Ok(self.gather(shape, &sources, fill))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array::type_remap::{Array1, Array2, Array3},
+        dimension::dim::{Dim1, Dim2, Dim3},
+        s,
+    };
+
+    fn to_vec(array: &Array1<i32>) -> Vec<i32> {
+        (0..array.shape().as_slice()[0]).map(|i| array[i]).collect()
+    }
+
+    fn vector(values: &[i32]) -> Array1<i32> {
+        Array1::from_vec(Dim1::new([values.len()]), values.to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_flip() {
+        let array = Array3::from_shape_fn(Dim3::new([2, 3, 4]), |i| {
+            i[0] * 100 + i[1] * 10 + i[2]
+        });
+
+        let flipped = array.flip(Axis(1)).unwrap();
+        assert_eq!(flipped[[1, 0, 3]], 123);
+        assert_eq!(flipped[[1, 2, 3]], 103);
+        assert_eq!(flipped.sum(), array.sum());
+
+        // Flipping twice restores the original layout
+        let twice = flipped.flip(Axis(1)).unwrap();
+        assert!(twice.is_contiguous());
+        assert_eq!(twice.offset(), 0);
+
+        // Flipping a view which was already reversed
+        let reversed: ArrayView<'_, usize, Dim3> =
+            array.slice(s![.., .., ..;-2]);
+        let flipped = reversed.flip(Axis(2)).unwrap();
+        assert_eq!(flipped[[0, 0, 0]], 1);
+        assert_eq!(flipped[[0, 0, 1]], 3);
+
+        let empty = Array2::<u8>::zeros(Dim2::new([0, 3]));
+        assert_eq!(empty.flip(Axis(0)).unwrap().shape().as_slice(), &[0, 3]);
+        assert_eq!(
+            array.flip(Axis(3)).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 3, ndim: 3 })
+        );
+    }
+
+    #[test]
+    fn test_roll_tile_repeat() {
+        let array = vector(&[1, 2, 3, 4, 5]);
+        assert_eq!(to_vec(&array.roll(2, Axis(0)).unwrap()), [4, 5, 1, 2, 3]);
+        assert_eq!(to_vec(&array.roll(-7, Axis(0)).unwrap()), [3, 4, 5, 1, 2]);
+        assert_eq!(to_vec(&array.roll(10, Axis(0)).unwrap()), [1, 2, 3, 4, 5]);
+
+        let flipped = array.flip(Axis(0)).unwrap();
+        assert_eq!(to_vec(&flipped.roll(1, Axis(0)).unwrap()), [1, 5, 4, 3, 2]);
+        assert_eq!(
+            to_vec(&flipped.tile(&[2]).unwrap()),
+            [5, 4, 3, 2, 1, 5, 4, 3, 2, 1]
+        );
+        assert_eq!(
+            to_vec(&flipped.repeat(2, Axis(0)).unwrap()),
+            [5, 5, 4, 4, 3, 3, 2, 2, 1, 1]
+        );
+        assert!(to_vec(&array.repeat(0, Axis(0)).unwrap()).is_empty());
+
+        let matrix =
+            Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+        let rolled = matrix.roll(1, Axis(1)).unwrap();
+        assert_eq!(rolled[[1, 0]], 5);
+        assert_eq!(rolled[[1, 1]], 3);
+
+        let tiled = matrix.t().tile(&[2, 1]).unwrap();
+        assert_eq!(tiled.shape().as_slice(), &[6, 2]);
+        assert_eq!(tiled[[4, 1]], 4);
+
+        assert_eq!(
+            matrix.tile(&[2]).err(),
+            Some(ShapeError::DimensionMismatch { expected: 2, found: 1 })
+        );
+        assert!(matrix.roll(1, Axis(2)).is_err());
+        assert!(matrix.repeat(1, Axis(2)).is_err());
+    }
+
+    #[test]
+    fn test_pad_modes() {
+        let array = vector(&[1, 2, 3, 4]);
+        let pad = |mode| to_vec(&array.pad(&[(3, 5)], mode).unwrap());
+
+        assert_eq!(
+            pad(PadMode::Constant(0)),
+            [0, 0, 0, 1, 2, 3, 4, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(pad(PadMode::Edge), [1, 1, 1, 1, 2, 3, 4, 4, 4, 4, 4, 4]);
+        assert_eq!(pad(PadMode::Reflect), [4, 3, 2, 1, 2, 3, 4, 3, 2, 1, 2, 3]);
+        assert_eq!(pad(PadMode::Wrap), [2, 3, 4, 1, 2, 3, 4, 1, 2, 3, 4, 1]);
+
+        let single = vector(&[7]);
+        let padded = single.pad(&[(2, 1)], PadMode::Reflect).unwrap();
+        assert_eq!(to_vec(&padded), [7, 7, 7, 7]);
+    }
+
+    #[test]
+    fn test_pad_matrix() {
+        let matrix =
+            Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+        let padded = matrix.pad(&[(1, 1), (1, 1)], PadMode::Edge).unwrap();
+        assert_eq!(padded.shape().as_slice(), &[4, 5]);
+        assert_eq!(padded[[0, 0]], 0);
+        assert_eq!(padded[[3, 4]], 5);
+        assert_eq!(padded[[2, 2]], 4);
+
+        // Padding a strided view
+        let flipped = matrix.flip(Axis(0)).unwrap();
+        let padded = flipped.pad(&[(0, 1), (2, 0)], PadMode::Wrap).unwrap();
+        assert_eq!(padded.shape().as_slice(), &[3, 5]);
+        assert_eq!(padded[[0, 0]], 4);
+        assert_eq!(padded[[0, 2]], 3);
+        assert_eq!(padded[[2, 4]], 5);
+
+        let empty = Array2::<usize>::zeros(Dim2::new([0, 3]));
+        let padded =
+            empty.pad(&[(1, 0), (0, 0)], PadMode::Constant(9)).unwrap();
+        assert_eq!(padded[[0, 2]], 9);
+        assert_eq!(
+            empty.pad(&[(1, 0), (0, 0)], PadMode::Edge).err(),
+            Some(ShapeError::PadEmptyAxis { axis: 0 })
+        );
+        assert_eq!(
+            empty.pad(&[(0, 0)], PadMode::Edge).err(),
+            Some(ShapeError::DimensionMismatch { expected: 2, found: 1 })
+        );
+    }
+}
@@ -0,0 +1,171 @@
+//! Scalar operands in lazily evaluated expressions.
+
+use std::marker::PhantomData;
+
+use crate::{
+    array::{
+        base::ArrayBase, function_1::TensrFn1, function_2::TensrFn2,
+        function_3::TensrFn3, traits::GetWriteableBuffer,
+    },
+    backend::traits::{
+        self, ContainerLength, ContainerScalarType, ContainerShape,
+        ScalarAccessor,
+    },
+    dimension::dim::{Dim0, Dimension},
+    types::UDim,
+};
+
+/// A constant scalar operand in a lazily-evaluated expression. This is created
+/// when an array or function object is combined with a scalar value, such as
+/// in `&a * 2.0`.
+///
+/// A scalar has a zero-dimensional shape, so it is broadcast to the shape of
+/// the other operand without ever being copied.
+pub struct TensrScalar<Backend, T> {
+    pub(crate) value: T,
+    backend: PhantomData<Backend>,
+}
+
+impl<Backend, T> TensrScalar<Backend, T> {
+    /// Create a new scalar operand with the given value
+    pub const fn new(value: T) -> Self {
+        Self { value, backend: PhantomData }
+    }
+
+    /// The value of the scalar
+    pub const fn value(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<Backend, T> ContainerLength for TensrScalar<Backend, T> {
+    fn len(&self) -> usize {
+        1
+    }
+}
+
+impl<Backend, T> ContainerScalarType for TensrScalar<Backend, T>
+where
+    T: Copy,
+{
+    type Scalar = T;
+}
+
+impl<Backend, T> ContainerShape for TensrScalar<Backend, T> {
+    type NDims = Dim0;
+
+    fn get_shape(&self) -> Self::NDims {
+        Dim0::new([])
+    }
+}
+
+impl<Backend, T> GetWriteableBuffer for TensrScalar<Backend, T>
+where
+    Backend: traits::Backend,
+    T: Copy,
+{
+    type Buffer = <Backend::OwnedStorage<T> as GetWriteableBuffer>::Buffer;
+
+    unsafe fn get_buffer_and_set_no_free(
+        &mut self,
+        _: usize,
+    ) -> Option<Self::Buffer> {
+        // A scalar has no buffer which could be reused
+        None
+    }
+}
+
+impl<Backend, T> ScalarAccessor for TensrScalar<Backend, T>
+where
+    T: Copy,
+{
+    #[inline(always)]
+    fn get_scalar(&self, _: usize) -> Self::Scalar {
+        self.value
+    }
+
+    #[inline(always)]
+    fn get_scalar_nd(&self, _: &[UDim]) -> Self::Scalar {
+        self.value
+    }
+
+    #[inline(always)]
+    fn is_contiguous(&self) -> bool {
+        true
+    }
+
+    #[inline(always)]
+    fn supports_flat_index(&self, _: usize) -> bool {
+        // The value is the same at every index
+        true
+    }
+}
+
+/// A value which can be used as an operand of a named lazy function.
+///
+/// For example, this is the exponent in `a.pow(2.0)`. Arrays, views and
+/// function objects are used directly, while scalars are wrapped in a
+/// [`TensrScalar`].
+pub trait IntoOperand<Backend> {
+    /// The type of the operand in the expression
+    type Operand;
+
+    /// Convert the value into an operand
+    fn into_operand(self) -> Self::Operand;
+}
+
+macro_rules! scalar_into_operand {
+    ($t: ty) => {
+        impl<Backend> IntoOperand<Backend> for $t {
+            type Operand = TensrScalar<Backend, $t>;
+
+            #[inline(always)]
+            fn into_operand(self) -> Self::Operand {
+                TensrScalar::new(self)
+            }
+        }
+    };
+}
+
+crate::repeat_tensr_types!(scalar_into_operand);
+scalar_into_operand!(bool);
+
+/// Implement [`IntoOperand`] for a type which is already a valid operand.
+macro_rules! identity_into_operand {
+    ([$($generic: tt)*], $operand: ty) => {
+        impl<$($generic)*> IntoOperand<Backend> for $operand
+        where
+            Backend: traits::Backend,
+        {
+            type Operand = Self;
+
+            #[inline(always)]
+            fn into_operand(self) -> Self::Operand {
+                self
+            }
+        }
+    };
+}
+
+identity_into_operand!([Backend, T], TensrScalar<Backend, T>);
+identity_into_operand!(
+    [Backend, S: traits::Storage, N: Dimension],
+    ArrayBase<Backend, S, N>
+);
+identity_into_operand!(
+    ['a, Backend, S: traits::Storage, N: Dimension],
+    &'a ArrayBase<Backend, S, N>
+);
+identity_into_operand!(
+    ['a, Backend, S: traits::Storage, N: Dimension],
+    &'a mut ArrayBase<Backend, S, N>
+);
+identity_into_operand!(['a, Backend, Op, Arg], TensrFn1<'a, Backend, Op, Arg>);
+identity_into_operand!(
+    ['a, Backend, Op, Lhs, Rhs],
+    TensrFn2<'a, Backend, Op, Lhs, Rhs>
+);
+identity_into_operand!(
+    ['a, Backend, Op, Arg0, Arg1, Arg2],
+    TensrFn3<'a, Backend, Op, Arg0, Arg1, Arg2>
+);
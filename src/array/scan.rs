@@ -0,0 +1,378 @@
+//! Cumulative operations along an axis, such as `cumsum`.
+
+use rayon::prelude::*;
+
+use crate::{
+    array::base::ArrayBase,
+    backend::{
+        host::{
+            host_backend::HostBackend,
+            host_kernels::{self, HostBinaryOp},
+            host_storage::HostStorage,
+        },
+        op_traits,
+        traits::Storage,
+    },
+    dimension::{axes::Axis, dim::Dimension, stride::advance},
+    error::ShapeError,
+};
+
+/// The number of elements scanned sequentially by each parallel task in the
+/// prefix scan of a single lane.
+///
+/// As with reductions, this is fixed so that floating-point results do not
+/// depend on the number of threads.
+const SCAN_BLOCK: usize = 4096;
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: Storage + Sync,
+    StorageType::Scalar: Send,
+    NDims: Dimension + Sync,
+{
+    /// Scan every lane along `axis`, writing the results into a row-major
+    /// array with the same shape. `scan_lane` is called with the storage
+    /// offset of the first element of a lane, the stride along the lane and
+    /// its length, and returns the scanned lane. Lanes are scanned in
+    /// parallel.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds.
+    #[track_caller]
+    fn scan_lanes<U, F>(
+        &self,
+        axis: Axis,
+        scan_lane: F,
+    ) -> ArrayBase<HostBackend, HostStorage<U>, NDims>
+    where
+        U: Copy + Send,
+        F: Fn(usize, usize, usize) -> Vec<U> + Sync,
+    {
+        let shape = self.axes.shape.as_slice();
+        let ndim = shape.len();
+        let Axis(axis) = axis;
+        assert!(axis < ndim, "{}", ShapeError::AxisOutOfBounds { axis, ndim });
+
+        let lane_len = shape[axis];
+        let len = self.axes.shape.len();
+        let lanes = len.checked_div(lane_len).unwrap_or(0);
+        let stride = self.axes.stride.strides.as_slice()[axis];
+
+        let scanned: Vec<Vec<U>> = (0..lanes)
+            .into_par_iter()
+            .map_init(
+                || vec![0; ndim],
+                |index, lane| {
+                    scan_lane(
+                        self.lane_offset(lane, axis, index),
+                        stride,
+                        lane_len,
+                    )
+                },
+            )
+            .collect();
+
+        // Lane `lane` starts at the row-major position of its first element,
+        // and its elements are `inner` apart, where `inner` is the number of
+        // elements in each step along `axis`
+        let inner: usize = shape[axis + 1..].iter().product();
+        let data = if lanes <= 1 {
+            scanned.into_iter().flatten().collect()
+        } else {
+            let mut data = Vec::with_capacity(len);
+            for position in 0..len {
+                let step = (position / inner) % lane_len;
+                let lane =
+                    (position / (inner * lane_len)) * inner + position % inner;
+                data.push(scanned[lane][step]);
+            }
+            data
+        };
+
+        ArrayBase::from_vec(self.axes.shape.clone(), data)
+            .expect("scanned shape does not match the result")
+    }
+
+    /// Compute the inclusive scan of a lane with the associative operation
+    /// `op`, splitting it into blocks of [`SCAN_BLOCK`] elements.
+    ///
+    /// Each block is scanned in parallel, the running total before each
+    /// block is computed sequentially from the last element of every earlier
+    /// block, and then the running totals are applied to each block in
+    /// parallel.
+    fn prefix_scan<F>(
+        &self,
+        base: usize,
+        stride: usize,
+        len: usize,
+        op: F,
+    ) -> Vec<StorageType::Scalar>
+    where
+        StorageType::Scalar: Sync,
+        F: Fn(StorageType::Scalar, StorageType::Scalar) -> StorageType::Scalar
+            + Sync,
+    {
+        let element = |k: usize| self.storage[advance(base, k, stride)];
+
+        let mut blocks: Vec<Vec<_>> = (0..len.div_ceil(SCAN_BLOCK))
+            .into_par_iter()
+            .map(|block| {
+                let start = block * SCAN_BLOCK;
+                let end = len.min(start + SCAN_BLOCK);
+
+                let mut acc = element(start);
+                let mut out = Vec::with_capacity(end - start);
+                out.push(acc);
+                for k in start + 1..end {
+                    acc = op(acc, element(k));
+                    out.push(acc);
+                }
+                out
+            })
+            .collect();
+
+        let mut carries = Vec::with_capacity(blocks.len());
+        let mut carry = None;
+        for block in &blocks {
+            carries.push(carry);
+            let last = block[block.len() - 1];
+            carry = Some(carry.map_or(last, |carry| op(carry, last)));
+        }
+
+        blocks.par_iter_mut().zip(carries).for_each(|(block, carry)| {
+            if let Some(carry) = carry {
+                for value in block.iter_mut() {
+                    *value = op(carry, *value);
+                }
+            }
+        });
+
+        blocks.into_iter().flatten().collect()
+    }
+
+    /// Compute an inclusive scan along `axis`. Each element of the result is
+    /// `f(previous, x)`, where `x` is the corresponding element of the array
+    /// and `previous` is the previous element of the result along `axis`, or
+    /// `init` for the first element of each lane.
+    ///
+    /// Lanes are scanned in parallel, but each lane is scanned sequentially
+    /// since `f` need not be associative.
+    ///
+    /// # Panics
+    /// Panics if `axis` is out of bounds for the array.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::dimension::{axes::Axis, dim::Dim1};
+    ///
+    /// let a = Array1::from_vec(Dim1::new([4]), vec![1, 2, 3, 4]).unwrap();
+    /// let running = a.scan_axis(Axis(0), 10, |acc, x| acc - x);
+    /// assert_eq!(running[3], 0);
+    /// ```
+    #[track_caller]
+    pub fn scan_axis<U, F>(
+        &self,
+        axis: Axis,
+        init: U,
+        f: F,
+    ) -> ArrayBase<HostBackend, HostStorage<U>, NDims>
+    where
+        U: Copy + Send + Sync,
+        F: Fn(U, StorageType::Scalar) -> U + Sync,
+    {
+        self.scan_lanes(axis, |base, stride, len| {
+            let mut acc = init;
+            (0..len)
+                .map(|k| {
+                    acc = f(acc, self.storage[advance(base, k, stride)]);
+                    acc
+                })
+                .collect()
+        })
+    }
+}
+
+/// Generate a cumulative method which scans each lane with a binary kernel.
+macro_rules! cumulative_method {
+    ($fn_name: ident, $kernel: ty, $doc: literal) => {
+        #[doc = $doc]
+        ///
+        /// The result has the same shape as the array, and each element is
+        /// computed from the elements up to and including it along `axis`.
+        /// Lanes are scanned in parallel, and a single long lane is split
+        /// into blocks which are scanned in parallel and then combined.
+        ///
+        /// # Panics
+        /// Panics if `axis` is out of bounds for the array.
+        #[track_caller]
+        pub fn $fn_name(
+            &self,
+            axis: Axis,
+        ) -> ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims>
+        where
+            $kernel: HostBinaryOp<StorageType::Scalar>
+                + op_traits::BinaryOp<
+                    Output<StorageType::Scalar> = StorageType::Scalar,
+                >,
+        {
+            self.scan_lanes(axis, |base, stride, len| {
+                self.prefix_scan(base, stride, len, <$kernel>::apply_scalar)
+            })
+        }
+    };
+}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: Storage + Sync,
+    StorageType::Scalar: Send + Sync,
+    NDims: Dimension + Sync,
+{
+    cumulative_method!(
+        cumsum,
+        host_kernels::HostAddKernel,
+        "Compute the cumulative sum along `axis`."
+    );
+    cumulative_method!(
+        cumprod,
+        host_kernels::HostMulKernel,
+        "Compute the cumulative product along `axis`."
+    );
+    cumulative_method!(
+        cummin,
+        host_kernels::HostMinimumKernel,
+        "Compute the running minimum along `axis`. NaN is propagated."
+    );
+    cumulative_method!(
+        cummax,
+        host_kernels::HostMaximumKernel,
+        "Compute the running maximum along `axis`. NaN is propagated."
+    );
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use crate::{
+        array::{
+            test_fixtures::matrix,
+            type_remap::{Array1, Array2, Array3, ArrayView2},
+        },
+        dimension::{
+            axes::Axis,
+            dim::{Dim1, Dim2, Dim3, Dimension},
+        },
+        s,
+    };
+
+    #[test]
+    fn test_cumsum() {
+        let a = matrix();
+
+        let rows = a.cumsum(Axis(1));
+        assert_eq!(rows.shape().as_slice(), &[3, 4]);
+        assert_eq!(rows[[0, 3]], 6);
+        assert_eq!(rows[[2, 1]], 17);
+
+        let columns = a.cumsum(Axis(0));
+        assert_eq!(columns[[0, 2]], 2);
+        assert_eq!(columns[[1, 2]], 8);
+        assert_eq!(columns[[2, 3]], 21);
+    }
+
+    #[test]
+    fn test_cumulative_ops() {
+        let a = Array1::from_vec(
+            Dim1::new([6]),
+            vec![3.0_f32, 1.0, 4.0, f32::NAN, 5.0, 2.0],
+        )
+        .unwrap();
+
+        let prod = a.cumprod(Axis(0));
+        assert_eq!(prod[2], 12.0);
+        assert!(prod[3].is_nan());
+
+        let max = a.cummax(Axis(0));
+        assert_eq!(max[1], 3.0);
+        assert_eq!(max[2], 4.0);
+        assert!(max[5].is_nan());
+
+        let min = a.cummin(Axis(0));
+        assert_eq!(min[1], 1.0);
+        assert_eq!(min[2], 1.0);
+        assert!(min[4].is_nan());
+    }
+
+    #[test]
+    fn test_scan_axis() {
+        let a = Array3::from_shape_fn(Dim3::new([2, 3, 2]), |i| {
+            i[0] * 100 + i[1] * 10 + i[2]
+        });
+
+        // Concatenate the last digits along the middle axis
+        let digits = a.scan_axis(Axis(1), 0_u64, |acc, x| {
+            acc * 10 + u64::try_from(x % 10).unwrap()
+        });
+        assert_eq!(digits.shape().as_slice(), &[2, 3, 2]);
+        assert_eq!(digits[[0, 2, 0]], 0);
+        assert_eq!(digits[[1, 2, 1]], 111);
+
+        let count = a.scan_axis(Axis(2), 0_usize, |acc, _| acc + 1);
+        assert_eq!(count[[1, 1, 1]], 2);
+    }
+
+    #[test]
+    fn test_strided_view() {
+        let a = matrix();
+        let view: ArrayView2<'_, i64> = a.slice(s![..;-1, 1..;2]);
+
+        // [[9, 11], [5, 7], [1, 3]]
+        let columns = view.cumsum(Axis(0));
+        assert_eq!(columns[[1, 0]], 14);
+        assert_eq!(columns[[2, 1]], 21);
+
+        let rows = view.cummin(Axis(1));
+        assert_eq!(rows[[0, 1]], 9);
+    }
+
+    #[test]
+    fn test_parallel_prefix() {
+        let len = 100_003;
+        let a = Array1::from_shape_fn(Dim1::new([len]), |i| i[0] % 7);
+        let sums = a.cumsum(Axis(0));
+
+        let mut expected = 0;
+        for i in 0..len {
+            expected += i % 7;
+            assert_eq!(sums[i], expected);
+        }
+
+        let floats = Array1::from_shape_fn(Dim1::new([len]), |i| {
+            1.0 / f64::from(u32::try_from(i[0] % 13 + 1).unwrap())
+        });
+        let first = floats.cumsum(Axis(0));
+        for _ in 0..4 {
+            assert_eq!(floats.cumsum(Axis(0))[len - 1], first[len - 1]);
+        }
+
+        let max = a.cummax(Axis(0));
+        assert_eq!(max[5], 5);
+        assert_eq!(max[len - 1], 6);
+    }
+
+    #[test]
+    fn test_empty() {
+        let empty = Array2::<f32>::zeros(Dim2::new([0, 3]));
+        assert_eq!(empty.cumsum(Axis(0)).shape().as_slice(), &[0, 3]);
+        assert_eq!(empty.cumprod(Axis(1)).shape().as_slice(), &[0, 3]);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "axis 2 is out of bounds for an array with 2 dimensions"
+    )]
+    fn test_axis_out_of_bounds() {
+        let _ = matrix().cumsum(Axis(2));
+    }
+}
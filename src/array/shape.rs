@@ -0,0 +1,582 @@
+//! Changing the shape of an array without changing its elements.
+
+use crate::{
+    array::{
+        base::ArrayBase,
+        type_remap::{Array1, ArrayCow},
+    },
+    backend::{
+        host::{
+            host_backend::HostBackend, host_storage::HostData,
+            host_view_storage::HostCowStorage,
+        },
+        traits,
+    },
+    dimension::{
+        axes::{Axes, Axis},
+        dim::{Dim1, Dimension},
+        dyn_dim::DimDyn,
+        stride::Stride,
+    },
+    error::ShapeError,
+    types::{IDim, UDim},
+};
+
+/// Resolve a requested shape for an array with `len` elements, where at most
+/// one length may be `-1`, in which case it is inferred from the others
+fn infer_shape(len: usize, shape: &[IDim]) -> Result<Vec<UDim>, ShapeError> {
+    let invalid = |reason: String| ShapeError::InvalidShape {
+        shape: shape.to_vec(),
+        reason,
+    };
+
+    let mut inferred = None;
+    let mut resolved = Vec::with_capacity(shape.len());
+    for (axis, &value) in shape.iter().enumerate() {
+        match UDim::try_from(value) {
+            Ok(value) => resolved.push(value),
+            Err(_) if value == -1 && inferred.is_none() => {
+                inferred = Some(axis);
+                resolved.push(1);
+            }
+            Err(_) if value == -1 => {
+                return Err(invalid("only one length can be inferred".into()));
+            }
+            Err(_) => {
+                return Err(invalid(format!(
+                    "the length {value} of axis {axis} is negative"
+                )));
+            }
+        }
+    }
+
+    if let Some(axis) = inferred {
+        let known: usize = resolved.iter().product();
+        if known == 0 || !len.is_multiple_of(known) {
+            return Err(invalid(format!(
+                "the length of axis {axis} cannot be inferred for an array \
+                 with {len} elements"
+            )));
+        }
+        resolved[axis] = len / known;
+    }
+
+    Ok(resolved)
+}
+
+/// Build axes with the given shape, strides and offset, or return an error if
+/// the number of axes is not valid for `D`
+fn axes_from_parts<D: Dimension>(
+    shape: &[UDim],
+    strides: &[UDim],
+    offset: usize,
+) -> Result<Axes<D>, ShapeError> {
+    let mismatch = || ShapeError::DimensionMismatch {
+        expected: D::NDIM.unwrap_or(shape.len()),
+        found: shape.len(),
+    };
+
+    Ok(Axes::new(
+        D::from_slice(shape).ok_or_else(mismatch)?,
+        Stride::new(offset, D::from_slice(strides).ok_or_else(mismatch)?),
+    ))
+}
+
+impl<Backend, StorageType, NDims> ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    /// Replace the axes of the array, keeping its storage
+    fn with_axes<D: Dimension>(
+        self,
+        axes: Axes<D>,
+    ) -> ArrayBase<Backend, StorageType, D> {
+        ArrayBase {
+            axes,
+            storage: self.storage,
+            phantom_backend: std::marker::PhantomData,
+        }
+    }
+
+    /// Give the array a new shape, which may have a different dimension type,
+    /// without copying any elements. The elements are taken in row-major
+    /// order.
+    ///
+    /// This is always possible for contiguous arrays, and for views whose
+    /// strides allow it. Use [`ArrayBase::reshape`] to copy the elements when
+    /// necessary.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::ReshapeMismatch`] if `shape` has a different
+    /// number of elements, or [`ShapeError::IncompatibleLayout`] if the
+    /// elements cannot be given the new shape without copying them.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::{Array1, ArrayDyn};
+    /// use tensr::dimension::dim::{Dim1, Dim2, Dimension};
+    /// use tensr::dimension::dyn_dim::DimDyn;
+    ///
+    /// let array = Array1::from_shape_fn(Dim1::new([6]), |i| i[0]);
+    /// let matrix = array.into_shape(Dim2::new([2, 3])).unwrap();
+    /// assert_eq!(matrix[[1, 0]], 3);
+    ///
+    /// let dynamic: ArrayDyn<usize> =
+    ///     matrix.into_shape(DimDyn::new_from(vec![3usize, 2])).unwrap();
+    /// assert_eq!(dynamic.shape().as_slice(), &[3, 2]);
+    /// ```
+    pub fn into_shape<D: Dimension>(
+        self,
+        shape: D,
+    ) -> Result<ArrayBase<Backend, StorageType, D>, ShapeError> {
+        if shape.len() != self.axes.shape.len() {
+            return Err(ShapeError::ReshapeMismatch {
+                from: self.axes.shape.as_slice().to_vec(),
+                to: shape.as_slice().to_vec(),
+            });
+        }
+
+        let to = shape.as_slice().to_vec();
+        match self.axes.reshaped(shape) {
+            Some(axes) => Ok(self.with_axes(axes)),
+            None => Err(ShapeError::IncompatibleLayout {
+                shape: self.axes.shape.as_slice().to_vec(),
+                strides: (0..self.axes.shape.ndim() as usize)
+                    .map(|axis| self.axes.stride.stride(axis))
+                    .collect(),
+                to,
+            }),
+        }
+    }
+
+    /// Remove every axis of length one. Since the number of axes which remain
+    /// is only known at runtime, the result has a [`DimDyn`] shape. No
+    /// elements are copied.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array3;
+    /// use tensr::dimension::dim::{Dim3, Dimension};
+    ///
+    /// let array = Array3::from_shape_fn(Dim3::new([1, 3, 1]), |i| i[1]);
+    /// let squeezed = array.squeeze();
+    /// assert_eq!(squeezed.shape().as_slice(), &[3]);
+    /// assert_eq!(squeezed[[2]], 2);
+    /// ```
+    pub fn squeeze(self) -> ArrayBase<Backend, StorageType, DimDyn> {
+        let (shape, strides): (Vec<UDim>, Vec<UDim>) = self
+            .axes
+            .shape
+            .as_slice()
+            .iter()
+            .zip(self.axes.stride.strides.as_slice())
+            .filter(|&(&len, _)| len != 1)
+            .unzip();
+
+        let axes = Axes::new(
+            DimDyn::new_from(shape),
+            Stride::new(self.axes.stride.offset, DimDyn::new_from(strides)),
+        );
+        self.with_axes(axes)
+    }
+
+    /// Insert a new axis of length one before `axis`, which may be equal to
+    /// the number of axes to append it. No elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` is greater than the
+    /// number of axes.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::{Dim1, Dimension};
+    ///
+    /// let array = Array1::from_shape_fn(Dim1::new([3]), |i| i[0]);
+    /// let column = array.insert_axis(Axis(1)).unwrap();
+    /// assert_eq!(column.shape().as_slice(), &[3, 1]);
+    /// assert_eq!(column[[2, 0]], 2);
+    /// ```
+    pub fn insert_axis(
+        self,
+        axis: Axis,
+    ) -> Result<ArrayBase<Backend, StorageType, NDims::Larger>, ShapeError>
+    {
+        let Axis(axis) = axis;
+        let mut shape = self.axes.shape.as_slice().to_vec();
+        let mut strides = self.axes.stride.strides.as_slice().to_vec();
+        if axis > shape.len() {
+            return Err(ShapeError::AxisOutOfBounds {
+                axis,
+                ndim: shape.len() + 1,
+            });
+        }
+
+        // The stride is never used, but is chosen as it would be for a
+        // contiguous array
+        let stride = if axis < shape.len() {
+            strides[axis].wrapping_mul(shape[axis])
+        } else {
+            1
+        };
+        shape.insert(axis, 1);
+        strides.insert(axis, stride);
+
+        let new_axes =
+            axes_from_parts(&shape, &strides, self.axes.stride.offset)?;
+        Ok(self.with_axes(new_axes))
+    }
+
+    /// Remove `axis`, which must have length one. No elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if `axis` does not exist, or
+    /// [`ShapeError::NotSingleton`] if it does not have length one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([1, 4]), |i| i[1]);
+    /// let row = array.remove_axis(Axis(0)).unwrap();
+    /// assert_eq!(row.shape().as_slice(), &[4]);
+    /// ```
+    pub fn remove_axis(
+        self,
+        axis: Axis,
+    ) -> Result<ArrayBase<Backend, StorageType, NDims::Smaller>, ShapeError>
+    {
+        let Axis(axis) = axis;
+        let mut shape = self.axes.shape.as_slice().to_vec();
+        let mut strides = self.axes.stride.strides.as_slice().to_vec();
+        if axis >= shape.len() {
+            return Err(ShapeError::AxisOutOfBounds {
+                axis,
+                ndim: shape.len(),
+            });
+        }
+        if shape[axis] != 1 {
+            return Err(ShapeError::NotSingleton { axis, len: shape[axis] });
+        }
+        shape.remove(axis);
+        strides.remove(axis);
+
+        let new_axes =
+            axes_from_parts(&shape, &strides, self.axes.stride.offset)?;
+        Ok(self.with_axes(new_axes))
+    }
+}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    /// View the elements with a new shape, which must have the same number
+    /// of elements, or copy them if the strides of the array do not allow it
+    fn reshape_cow<D: Dimension>(
+        &self,
+        shape: D,
+    ) -> ArrayCow<'_, StorageType::Scalar, D> {
+        if let Some(axes) = self.axes.reshaped(shape.clone()) {
+            // Safety: The new axes address the same elements as the old ones
+            let view = unsafe { self.view_with_axes(axes) };
+            return ArrayBase {
+                axes: view.axes,
+                storage: HostCowStorage::Borrowed(view.storage),
+                phantom_backend: std::marker::PhantomData,
+            };
+        }
+
+        let owned = self.to_owned();
+        ArrayBase {
+            axes: Axes::new_with_default_stride(shape),
+            storage: HostCowStorage::Owned(owned.storage),
+            phantom_backend: std::marker::PhantomData,
+        }
+    }
+
+    /// Give the elements of the array a new shape, in row-major order. At
+    /// most one length may be `-1`, in which case it is inferred from the
+    /// number of elements.
+    ///
+    /// The result is a view of the array if its strides allow it, as they
+    /// always do for contiguous arrays, and a copy otherwise.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::InvalidShape`] if a length is negative (other
+    /// than a single `-1`), or cannot be inferred,
+    /// [`ShapeError::DimensionMismatch`] if the number of lengths is not
+    /// valid for `D`, or [`ShapeError::ReshapeMismatch`] if the new shape has
+    /// a different number of elements.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::s;
+    /// use tensr::array::type_remap::{Array2, ArrayView2};
+    /// use tensr::dimension::dim::{Dim2, Dim3, Dimension};
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([4, 6]), |i| i[0] * 6 + i[1]);
+    ///
+    /// let reshaped = array.reshape::<Dim3>(&[2, -1, 3]).unwrap();
+    /// assert_eq!(reshaped.shape().as_slice(), &[2, 4, 3]);
+    /// assert_eq!(reshaped[[1, 0, 2]], 14);
+    /// assert!(reshaped.is_view());
+    ///
+    /// // The first three columns cannot be viewed as a vector, so they are
+    /// // copied
+    /// let columns: ArrayView2<'_, usize> = array.slice(s![.., ..3]);
+    /// let flat = columns.reshape::<Dim2>(&[1, 12]).unwrap();
+    /// assert_eq!(flat[[0, 3]], 6);
+    /// assert!(!flat.is_view());
+    ///
+    /// assert!(array.reshape::<Dim2>(&[5, -1]).is_err());
+    /// ```
+    pub fn reshape<D: Dimension>(
+        &self,
+        shape: &[IDim],
+    ) -> Result<ArrayCow<'_, StorageType::Scalar, D>, ShapeError> {
+        let len = self.axes.shape.len();
+        let resolved = infer_shape(len, shape)?;
+        let Some(new_shape) = D::from_slice(&resolved) else {
+            return Err(ShapeError::DimensionMismatch {
+                expected: D::NDIM.unwrap_or(resolved.len()),
+                found: resolved.len(),
+            });
+        };
+
+        if new_shape.len() != len {
+            return Err(ShapeError::ReshapeMismatch {
+                from: self.axes.shape.as_slice().to_vec(),
+                to: resolved,
+            });
+        }
+
+        Ok(self.reshape_cow(new_shape))
+    }
+
+    /// Return the elements of the array as a vector, in row-major order. The
+    /// result is a view if the strides of the array allow it, and a copy
+    /// otherwise. See [`ArrayBase::flatten`] to always copy the elements.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+    /// let flat = array.ravel();
+    /// assert_eq!(flat.shape().as_slice(), &[6]);
+    /// assert_eq!(flat[[4]], 4);
+    /// assert!(flat.is_view());
+    /// ```
+    pub fn ravel(&self) -> ArrayCow<'_, StorageType::Scalar, Dim1> {
+        self.reshape_cow(Dim1::new([self.axes.shape.len()]))
+    }
+
+    /// Copy the elements of the array into a new vector, in row-major order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+    /// let flat = array.flatten();
+    /// assert_eq!(flat[[5]], 5);
+    /// ```
+    pub fn flatten(&self) -> Array1<StorageType::Scalar> {
+        let owned = self.to_owned();
+        ArrayBase::new(
+            Axes::new_with_default_stride(Dim1::new([owned.axes.shape.len()])),
+            owned.storage,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array::{
+            test_fixtures::array_3d,
+            type_remap::{Array2, Array3, ArrayDyn, ArrayView2, ArrayView3},
+        },
+        dimension::dim::{Dim0, Dim2, Dim3, Dim4},
+        s,
+    };
+
+    #[test]
+    fn test_reshape_contiguous() {
+        let array = array_3d();
+        let reshaped = array.reshape::<Dim2>(&[6, 4]).unwrap();
+        assert!(reshaped.is_view());
+        assert_eq!(reshaped.shape().as_slice(), &[6, 4]);
+        assert_eq!(reshaped[[4, 1]], 111);
+
+        let inferred = array.reshape::<Dim4>(&[-1, 2, 1, 2]).unwrap();
+        assert_eq!(inferred.shape().as_slice(), &[6, 2, 1, 2]);
+        assert_eq!(inferred[[5, 1, 0, 1]], 123);
+
+        let dynamic = array.reshape::<DimDyn>(&[24]).unwrap();
+        assert_eq!(dynamic[[23]], 123);
+
+        let empty = Array2::<f32>::zeros(Dim2::new([0, 3]));
+        let reshaped = empty.reshape::<Dim3>(&[3, 0, -1]);
+        assert!(reshaped.is_err());
+        assert_eq!(
+            empty.reshape::<Dim2>(&[3, 0]).unwrap().shape().as_slice(),
+            &[3, 0]
+        );
+    }
+
+    #[test]
+    fn test_reshape_strided() {
+        let array = array_3d();
+
+        // Slicing the first axis keeps the last two axes nested, so they can
+        // be merged without copying
+        let view: ArrayView3<'_, usize> = array.slice(s![..;-1, .., ..]);
+        let merged = view.reshape::<Dim2>(&[2, 12]).unwrap();
+        assert!(merged.is_view());
+        assert_eq!(merged[[0, 5]], 111);
+        assert_eq!(merged[[1, 11]], 23);
+
+        // Taking the first two rows of each matrix leaves a gap between
+        // matrices, so the first two axes cannot be merged
+        let view: ArrayView3<'_, usize> = array.slice(s![.., ..2, ..]);
+        let copied = view.reshape::<Dim2>(&[4, -1]).unwrap();
+        assert!(!copied.is_view());
+        assert!(copied.is_contiguous());
+        assert_eq!(copied[[1, 0]], 10);
+        assert_eq!(copied[[3, 3]], 113);
+
+        // But the last axis can still be split
+        let split = view.reshape::<Dim4>(&[2, 2, 2, 2]).unwrap();
+        assert!(split.is_view());
+        assert_eq!(split[[1, 1, 1, 0]], 112);
+    }
+
+    #[test]
+    fn test_reshape_errors() {
+        let array = array_3d();
+        assert_eq!(
+            array.reshape::<Dim2>(&[5, 5]).err(),
+            Some(ShapeError::ReshapeMismatch {
+                from: vec![2, 3, 4],
+                to: vec![5, 5]
+            })
+        );
+        assert_eq!(
+            array.reshape::<Dim2>(&[-1, -1]).err(),
+            Some(ShapeError::InvalidShape {
+                shape: vec![-1, -1],
+                reason: "only one length can be inferred".into(),
+            })
+        );
+        assert!(matches!(
+            array.reshape::<Dim2>(&[-2, 12]),
+            Err(ShapeError::InvalidShape { .. })
+        ));
+        assert!(matches!(
+            array.reshape::<Dim2>(&[7, -1]),
+            Err(ShapeError::InvalidShape { .. })
+        ));
+        assert_eq!(
+            array.reshape::<Dim3>(&[24]).err(),
+            Some(ShapeError::DimensionMismatch { expected: 3, found: 1 })
+        );
+
+        let message = array.reshape::<Dim2>(&[5, 5]).err().unwrap().to_string();
+        assert_eq!(
+            message,
+            "cannot reshape an array of shape [2, 3, 4] with 24 elements into \
+             shape [5, 5] with 25 elements"
+        );
+    }
+
+    #[test]
+    fn test_into_shape() {
+        let array = array_3d();
+        let ptr = array.storage.ptr.0.as_ptr();
+
+        let dynamic: ArrayDyn<usize> =
+            array.into_shape(DimDyn::new_from(vec![4usize, 6])).unwrap();
+        assert_eq!(dynamic.storage.ptr.0.as_ptr(), ptr);
+        assert_eq!(dynamic[[3, 5]], 123);
+
+        let back = dynamic.into_shape(Dim3::new([2, 3, 4])).unwrap();
+        assert_eq!(back[[1, 2, 3]], 123);
+
+        let transposed = ArrayView2::from_slice_with_strides(
+            &[0, 1, 2, 3, 4, 5],
+            Dim2::new([3, 2]),
+            &[1, 3],
+            0,
+        )
+        .unwrap();
+        assert_eq!(
+            transposed.into_shape(Dim1::new([6])).err(),
+            Some(ShapeError::IncompatibleLayout {
+                shape: vec![3, 2],
+                strides: vec![1, 3],
+                to: vec![6],
+            })
+        );
+    }
+
+    #[test]
+    fn test_flatten_and_ravel() {
+        let array = array_3d();
+        let view: ArrayView3<'_, usize> = array.slice(s![.., 1.., ..;-1]);
+
+        let flat = view.flatten();
+        assert_eq!(flat.shape().as_slice(), &[16]);
+        assert_eq!(flat[[0]], 13);
+        assert_eq!(flat[[15]], 120);
+
+        let ravelled = view.ravel();
+        assert!(!ravelled.is_view());
+        assert_eq!(ravelled[[15]], 120);
+        assert!(array.ravel().is_view());
+    }
+
+    #[test]
+    fn test_squeeze_and_axes() {
+        let array = Array3::from_shape_fn(Dim3::new([1, 4, 1]), |i| i[1]);
+        let squeezed = array.view().squeeze();
+        assert_eq!(squeezed.shape().as_slice(), &[4]);
+        assert_eq!(squeezed[[3]], 3);
+
+        let row = array.view().remove_axis(Axis(2)).unwrap();
+        assert_eq!(row.shape().as_slice(), &[1, 4]);
+        assert_eq!(
+            array.view().remove_axis(Axis(1)).err(),
+            Some(ShapeError::NotSingleton { axis: 1, len: 4 })
+        );
+        assert_eq!(
+            array.view().remove_axis(Axis(3)).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 3, ndim: 3 })
+        );
+
+        let expanded = row.insert_axis(Axis(1)).unwrap();
+        assert_eq!(expanded.shape().as_slice(), &[1, 1, 4]);
+        assert!(expanded.is_contiguous());
+        assert_eq!(expanded[[0, 0, 2]], 2);
+        assert_eq!(
+            expanded.insert_axis(Axis(4)).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 4, ndim: 4 })
+        );
+
+        let scalar = Array1::from_shape_fn(Dim1::new([1]), |_| 7)
+            .remove_axis(Axis(0))
+            .unwrap();
+        let _: &Dim0 = scalar.shape();
+        assert_eq!(scalar[[]], 7);
+    }
+}
@@ -0,0 +1,253 @@
+//! Slicing arrays into views.
+
+use crate::{
+    array::{
+        base::ArrayBase,
+        type_remap::{ArrayView, ArrayViewMut},
+    },
+    backend::{
+        host::{host_backend::HostBackend, host_storage::HostData},
+        traits::StorageMut,
+    },
+    dimension::{dim::Dimension, slice::SliceArg},
+};
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    /// Return an immutable view of a slice of the array. The slice is usually
+    /// created with the [`s!`](crate::s) macro. No data is copied.
+    ///
+    /// # Panics
+    /// Panics if the slice does not match the number of dimensions of the
+    /// array, or if any index or range is out of bounds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::s;
+    /// use tensr::array::type_remap::{Array2, ArrayView1};
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let array = Array2::from_shape_fn(Dim2::new([3, 4]), |i| i[0] * 4 + i[1]);
+    ///
+    /// // The second column, in reverse
+    /// let column: ArrayView1<'_, usize> = array.slice(s![..;-1, 1]);
+    /// assert_eq!(column.shape().as_slice(), &[3]);
+    /// assert_eq!(column.get_scalar_nd(&[0]), 9);
+    /// ```
+    #[track_caller]
+    pub fn slice<I>(
+        &self,
+        info: I,
+    ) -> ArrayView<'_, StorageType::Scalar, I::OutDim>
+    where
+        I: SliceArg<NDims>,
+    {
+        let axes = self
+            .axes
+            .slice(info.slice_elems())
+            .unwrap_or_else(|err| panic!("invalid slice: {err}"));
+
+        // Safety: Axes::slice only produces axes within the original ones
+        unsafe { self.view_with_axes(axes) }
+    }
+
+    /// Return a mutable view of a slice of the array. Writing to the view
+    /// modifies the elements of the array.
+    ///
+    /// # Panics
+    /// Panics if the slice does not match the number of dimensions of the
+    /// array, or if any index or range is out of bounds.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::s;
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let mut array = Array2::from_shape_fn(Dim2::new([3, 4]), |_| 0);
+    /// array.slice_mut(s![1.., ..;2]).fill(1);
+    ///
+    /// assert_eq!(array.get_scalar_nd(&[0, 0]), 0);
+    /// assert_eq!(array.get_scalar_nd(&[1, 2]), 1);
+    /// assert_eq!(array.get_scalar_nd(&[2, 3]), 0);
+    /// ```
+    #[track_caller]
+    pub fn slice_mut<I>(
+        &mut self,
+        info: I,
+    ) -> ArrayViewMut<'_, StorageType::Scalar, I::OutDim>
+    where
+        StorageType: StorageMut,
+        I: SliceArg<NDims>,
+    {
+        let axes = self
+            .axes
+            .slice(info.slice_elems())
+            .unwrap_or_else(|err| panic!("invalid slice: {err}"));
+
+        // Safety: Axes::slice only produces axes within the original ones,
+        // and never maps two indices to the same element except through new
+        // axes of length one
+        unsafe { self.view_mut_with_axes(axes) }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        array::{
+            test_fixtures::labelled_3d,
+            type_remap::{
+                Array1, Array2, Array3, ArrayDyn, ArrayView0, ArrayView1,
+                ArrayView2, ArrayView3,
+            },
+        },
+        backend::traits::{ContainerLength, ScalarAccessor},
+        dimension::{
+            dim::{Dim1, Dim2, Dim3, Dimension},
+            dyn_dim::DimDyn,
+            slice::{NewAxis, SliceElem},
+        },
+        s,
+    };
+
+    fn array_3d() -> Array3<usize> {
+        labelled_3d(Dim3::new([4, 5, 6]))
+    }
+
+    #[test]
+    fn test_slice_ranges() {
+        let array = array_3d();
+        let view: ArrayView3<'_, usize> = array.slice(s![1..3, 2.., ..4]);
+
+        assert_eq!(view.shape().as_slice(), &[2, 3, 4]);
+        assert!(!view.is_contiguous());
+        assert_eq!(view.get_scalar_nd(&[0, 0, 0]), 120);
+        assert_eq!(view.get_scalar_nd(&[1, 2, 3]), 243);
+
+        let full: ArrayView3<'_, usize> = array.slice(s![.., .., ..]);
+        assert!(full.is_contiguous());
+        assert_eq!(full.len(), array.len());
+    }
+
+    #[test]
+    fn test_slice_steps() {
+        let array = array_3d();
+        let view: ArrayView3<'_, usize> = array.slice(s![..;2, 1..=3, ..;-1]);
+
+        assert_eq!(view.shape().as_slice(), &[2, 3, 6]);
+        assert_eq!(view.get_scalar_nd(&[0, 0, 0]), 15);
+        assert_eq!(view.get_scalar_nd(&[1, 2, 5]), 230);
+
+        let owned = view.to_owned();
+        assert!(owned.is_contiguous());
+        assert_eq!(owned.get_scalar(0), 15);
+        assert_eq!(owned.get_scalar(owned.len() - 1), 230);
+    }
+
+    #[test]
+    fn test_slice_negative_indices() {
+        let array = Array1::from_shape_fn(Dim1::new([10]), |i| i[0]);
+        let view: ArrayView1<'_, usize> = array.slice(s![-4..-1]);
+
+        assert_eq!(view.shape().as_slice(), &[3]);
+        assert_eq!(view.get_scalar_nd(&[0]), 6);
+        assert_eq!(view.get_scalar_nd(&[2]), 8);
+
+        let tail: ArrayView1<'_, usize> = array.slice(s![-3..;-2]);
+        assert_eq!(tail.shape().as_slice(), &[2]);
+        assert_eq!(tail.get_scalar_nd(&[0]), 9);
+        assert_eq!(tail.get_scalar_nd(&[1]), 7);
+    }
+
+    #[test]
+    fn test_slice_index_and_new_axis() {
+        let array = array_3d();
+
+        let row: ArrayView1<'_, usize> = array.slice(s![2, 3, ..]);
+        assert_eq!(row.shape().as_slice(), &[6]);
+        assert!(row.is_contiguous());
+        assert_eq!(row.get_scalar_nd(&[4]), 234);
+
+        let expanded: ArrayView3<'_, usize> =
+            array.slice(s![NewAxis, -1, .., NewAxis, 0]);
+        assert_eq!(expanded.shape().as_slice(), &[1, 5, 1]);
+        assert_eq!(expanded.get_scalar_nd(&[0, 3, 0]), 330);
+
+        let scalar: ArrayView0<'_, usize> = array.slice(s![1, 2, 3]);
+        assert_eq!(scalar.len(), 1);
+        assert_eq!(scalar.get_scalar_nd(&[]), 123);
+    }
+
+    #[test]
+    fn test_slice_of_slice() {
+        let array = array_3d();
+        let view: ArrayView2<'_, usize> = array.slice(s![1.., ..;2, 3]);
+        let inner: ArrayView2<'_, usize> = view.slice(s![..;-1, 1..]);
+
+        assert_eq!(inner.shape().as_slice(), &[3, 2]);
+        assert_eq!(inner.get_scalar_nd(&[0, 0]), 323);
+        assert_eq!(inner.get_scalar_nd(&[2, 1]), 143);
+    }
+
+    #[test]
+    fn test_slice_mut() {
+        let mut array = Array2::from_shape_fn(Dim2::new([4, 4]), |_| 0);
+        array.slice_mut(s![1..3, 1..3]).fill(1);
+        array.slice_mut(s![..;3, -1]).fill(2);
+
+        let expected = [0, 0, 0, 2, 0, 1, 1, 0, 0, 1, 1, 0, 0, 0, 0, 2];
+        for (i, v) in expected.iter().enumerate() {
+            assert_eq!(array.get_scalar(i), *v);
+        }
+    }
+
+    #[test]
+    fn test_slice_dyn() {
+        let array = Array3::from_shape_fn(Dim3::new([2, 3, 4]), |i| {
+            i[0] * 100 + i[1] * 10 + i[2]
+        });
+        let row: ArrayView2<'_, usize> = array.slice(s![.., 1, ..]);
+        let view: ArrayView2<'_, usize> = row.slice(s![..;-1, ..]);
+        assert_eq!(view.get_scalar_nd(&[0, 2]), 112);
+
+        let dyn_array = ArrayDyn::from_shape_fn(
+            DimDyn::new_from(vec![2usize, 3, 4]),
+            |i| i[0] * 100 + i[1] * 10 + i[2],
+        );
+
+        let view = dyn_array.slice(s![1, .., 2..;2]);
+        assert_eq!(view.shape().as_slice(), &[3, 1]);
+        assert_eq!(view.get_scalar_nd(&[2, 0]), 122);
+
+        let elems = vec![
+            SliceElem::from(..),
+            SliceElem::NewAxis,
+            0.into(),
+            (-1).into(),
+        ];
+        let view = dyn_array.slice(&elems);
+        assert_eq!(view.shape().as_slice(), &[2, 1]);
+        assert_eq!(view.get_scalar_nd(&[1, 0]), 103);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid slice")]
+    fn test_slice_out_of_bounds() {
+        let array = array_3d();
+        let _ = array.slice(s![.., 5, ..]);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid slice")]
+    fn test_slice_dyn_wrong_ndim() {
+        let dyn_array =
+            ArrayDyn::from_shape_fn(DimDyn::new_from(vec![2usize, 3]), |_| 0);
+        let _ = dyn_array.slice(s![.., .., ..]);
+    }
+}
@@ -0,0 +1,259 @@
+//! Element-wise operations with three operands, such as `select` and
+//! `clip`.
+
+use crate::{
+    array::{
+        base::ArrayBase, function_1::TensrFn1, function_2::TensrFn2,
+        function_3::TensrFn3, scalar::IntoOperand,
+    },
+    backend::traits::{self, ContainerShape},
+    dimension::dim::Dimension,
+};
+
+/// Invoke `$macro!(Name, name, first, second, doc)` for every element-wise
+/// operation with three operands, where `first` and `second` name the
+/// operands other than the receiver.
+macro_rules! repeat_ternary_functions {
+    ($macro: tt) => {
+        $macro!(
+            Select,
+            select,
+            on_true,
+            on_false,
+            "Use this (boolean) mask to lazily select elements from `on_true` \
+             where the mask is `true`, and from `on_false` otherwise. This is \
+             equivalent to NumPy's `where(mask, on_true, on_false)`."
+        );
+        $macro!(
+            Clip,
+            clip,
+            lower,
+            upper,
+            "Lazily clamp each element to the range `[lower, upper]`. NaN is \
+             propagated."
+        );
+    };
+}
+
+/// Generate a method which lazily applies an operation with three operands,
+/// borrowing the array as the first operand.
+macro_rules! array_ternary_function {
+    ($op_name: ident, $fn_name: ident, $first: ident, $second: ident, $doc: literal) => {
+        paste::paste! {
+            #[doc = $doc]
+            ///
+            /// The other operands may be arrays, views, expressions or
+            /// scalars, and are broadcast together with the array.
+            ///
+            /// # Panics
+            /// Panics if the shapes cannot be broadcast together.
+            #[track_caller]
+            pub fn $fn_name<First, Second>(
+                &self,
+                $first: First,
+                $second: Second,
+            ) -> TensrFn3<
+                '_,
+                Backend,
+                Backend::[< $op_name Kernel >],
+                &Self,
+                First::Operand,
+                Second::Operand,
+            >
+            where
+                First: IntoOperand<Backend>,
+                First::Operand: ContainerShape,
+                Second: IntoOperand<Backend>,
+                Second::Operand: ContainerShape,
+            {
+                TensrFn3::new(
+                    self,
+                    $first.into_operand(),
+                    $second.into_operand(),
+                )
+            }
+        }
+    };
+}
+
+/// Generate a method which lazily applies an operation with three operands,
+/// consuming a function object as the first operand.
+macro_rules! function_ternary_function {
+    ($op_name: ident, $fn_name: ident, $first: ident, $second: ident, $doc: literal) => {
+        paste::paste! {
+            #[doc = $doc]
+            ///
+            /// The other operands may be arrays, views, expressions or
+            /// scalars, and are broadcast together with the result of this
+            /// expression.
+            ///
+            /// # Panics
+            /// Panics if the shapes cannot be broadcast together.
+            #[track_caller]
+            pub fn $fn_name<First, Second>(
+                self,
+                $first: First,
+                $second: Second,
+            ) -> TensrFn3<
+                'a,
+                Backend,
+                Backend::[< $op_name Kernel >],
+                Self,
+                First::Operand,
+                Second::Operand,
+            >
+            where
+                Self: ContainerShape,
+                First: IntoOperand<Backend>,
+                First::Operand: ContainerShape,
+                Second: IntoOperand<Backend>,
+                Second::Operand: ContainerShape,
+            {
+                TensrFn3::new(
+                    self,
+                    $first.into_operand(),
+                    $second.into_operand(),
+                )
+            }
+        }
+    };
+}
+
+impl<Backend, StorageType, NDims> ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    repeat_ternary_functions!(array_ternary_function);
+}
+
+impl<'a, Backend, Op, Arg> TensrFn1<'a, Backend, Op, Arg>
+where
+    Backend: traits::Backend,
+{
+    repeat_ternary_functions!(function_ternary_function);
+}
+
+impl<'a, Backend, Op, Lhs, Rhs> TensrFn2<'a, Backend, Op, Lhs, Rhs>
+where
+    Backend: traits::Backend,
+{
+    repeat_ternary_functions!(function_ternary_function);
+}
+
+impl<'a, Backend, Op, Arg0, Arg1, Arg2>
+    TensrFn3<'a, Backend, Op, Arg0, Arg1, Arg2>
+where
+    Backend: traits::Backend,
+{
+    repeat_ternary_functions!(function_ternary_function);
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use crate::{
+        array::type_remap::{Array1, Array2},
+        dimension::dim::{Dim1, Dim2, Dimension},
+    };
+
+    #[test]
+    fn test_select() {
+        let a =
+            Array1::from_vec(Dim1::new([4]), vec![1.0_f64, -2.0, 3.0, -4.0])
+                .unwrap();
+        let b = Array1::from_vec(Dim1::new([4]), vec![10.0, 20.0, 30.0, 40.0])
+            .unwrap();
+
+        let result = a.gt(0.0).select(&a, &b).eval();
+        assert_eq!(result[0], 1.0);
+        assert_eq!(result[1], 20.0);
+        assert_eq!(result[3], 40.0);
+
+        // Scalars and expressions can be selected from too
+        let result = a.lt(0.0).select(&a * 2.0, 0.0).eval();
+        assert_eq!(result[0], 0.0);
+        assert_eq!(result[3], -8.0);
+
+        // And an owned mask can be used directly
+        let mask =
+            Array1::from_vec(Dim1::new([4]), vec![true, false, false, true])
+                .unwrap();
+        let result = mask.select(1_i32, -1_i32).eval();
+        assert_eq!(result[0], 1);
+        assert_eq!(result[1], -1);
+    }
+
+    #[test]
+    fn test_select_broadcast() {
+        let column =
+            Array2::from_vec(Dim2::new([3, 1]), vec![true, false, true])
+                .unwrap();
+        let row = Array1::from_vec(Dim1::new([2]), vec![1_u32, 2]).unwrap();
+
+        let result = column.select(&row, 0_u32).eval();
+        assert_eq!(result.shape().as_slice(), &[3, 2]);
+        assert_eq!(result[[0, 1]], 2);
+        assert_eq!(result[[1, 0]], 0);
+        assert_eq!(result[[2, 0]], 1);
+    }
+
+    #[test]
+    fn test_clip_relu() {
+        let a = Array1::from_vec(
+            Dim1::new([5]),
+            vec![-2.0_f32, -0.5, 0.25, 3.0, f32::NAN],
+        )
+        .unwrap();
+
+        let clipped = a.clip(-1.0_f32, 1.0_f32).eval();
+        assert_eq!(clipped[0], -1.0);
+        assert_eq!(clipped[2], 0.25);
+        assert_eq!(clipped[3], 1.0);
+        assert!(clipped[4].is_nan());
+
+        let relu = a.relu().eval();
+        assert_eq!(relu[0], 0.0);
+        assert_eq!(relu[3], 3.0);
+        assert!(relu[4].is_nan());
+
+        // Bounds may be arrays, and the result can be used in further
+        // expressions
+        let upper =
+            Array1::from_vec(Dim1::new([5]), vec![0.0, 0.0, 0.0, 2.0, 0.0])
+                .unwrap();
+        let result = (a.clip(-0.5_f32, &upper) * 2.0_f32).eval();
+        assert_eq!(result[0], -1.0);
+        assert_eq!(result[2], 0.0);
+        assert_eq!(result[3], 4.0);
+    }
+
+    #[test]
+    fn test_fused_buffer_reuse() {
+        let a = Array1::from_vec(Dim1::new([4]), vec![1, -2, 3, -4]).unwrap();
+        let b = Array1::from_slice(Dim1::new([4]), &[5, 6, 7, 8]).unwrap();
+        let ptr = b.storage.ptr.0;
+
+        // The owned `on_true` operand is reused for the output, while the
+        // mask (which has a different scalar type) is not
+        let result = a.gt(0_i32).select(b, &a).eval();
+        assert_eq!(result.storage.ptr.0, ptr);
+        assert_eq!(result[0], 5);
+        assert_eq!(result[1], -2);
+
+        let ptr = result.storage.ptr.0;
+        let clipped = (result + 1_i32).clip(0_i32, 6_i32).relu().eval();
+        assert_eq!(clipped.storage.ptr.0, ptr);
+        assert_eq!(clipped[0], 6);
+        assert_eq!(clipped[1], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be broadcast")]
+    fn test_select_shape_mismatch() {
+        let mask = Array1::from_vec(Dim1::new([3]), vec![true; 3]).unwrap();
+        let a = Array1::<f32>::zeros(Dim1::new([2]));
+        let _ = mask.select(&a, 0.0_f32);
+    }
+}
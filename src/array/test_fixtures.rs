@@ -0,0 +1,30 @@
+//! Arrays shared by the unit tests of several array modules.
+
+use crate::{
+    array::type_remap::{Array2, Array3},
+    dimension::dim::{Dim2, Dim3},
+};
+
+/// An array whose element at `[i, j, k]` is `i * 100 + j * 10 + k`, so each
+/// element spells out its own index while every axis is shorter than ten.
+pub fn labelled_3d(shape: Dim3) -> Array3<usize> {
+    Array3::from_shape_fn(shape, |i| i[0] * 100 + i[1] * 10 + i[2])
+}
+
+/// A `2 x 3 x 4` array built by [`labelled_3d`]
+pub fn array_3d() -> Array3<usize> {
+    labelled_3d(Dim3::new([2, 3, 4]))
+}
+
+/// The matrix
+///
+/// ```text
+/// [[0, 1,  2,  3],
+///  [4, 5,  6,  7],
+///  [8, 9, 10, 11]]
+/// ```
+pub fn matrix() -> Array2<i64> {
+    Array2::from_shape_fn(Dim2::new([3, 4]), |i| {
+        i64::try_from(i[0] * 4 + i[1]).unwrap()
+    })
+}
@@ -1,4 +1,9 @@
+//! Traits shared by arrays and the expressions built from them.
+
+/// A container whose buffer may be reused to store the result of a lazily
+/// evaluated expression
 pub trait GetWriteableBuffer {
+    /// A pointer to the buffer
     type Buffer;
 
     /// If possible, return the underlying buffer and tell this container to
@@ -0,0 +1,298 @@
+//! Permuting the axes of an array, as a view of the same elements.
+
+use crate::{
+    array::{base::ArrayBase, type_remap::ArrayView},
+    backend::{
+        host::{host_backend::HostBackend, host_storage::HostData},
+        traits,
+    },
+    dimension::{axes::Axis, dim::Dimension},
+    error::ShapeError,
+};
+
+/// Return an error if `axis` does not exist in an array with `ndim` axes
+const fn check_axis(axis: usize, ndim: usize) -> Result<(), ShapeError> {
+    if axis < ndim {
+        Ok(())
+    } else {
+        Err(ShapeError::AxisOutOfBounds { axis, ndim })
+    }
+}
+
+impl<Backend, StorageType, NDims> ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    /// Reverse the order of the axes, so the element at `[i, j, k]` moves to
+    /// `[k, j, i]`. For a matrix, this is its transpose.
+    ///
+    /// Only the shape and strides are changed, so no elements are copied.
+    /// Call this on a view (or use [`ArrayBase::t`]) to keep the original
+    /// array.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array3;
+    /// use tensr::dimension::dim::{Dim3, Dimension};
+    ///
+    /// let array = Array3::from_shape_fn(Dim3::new([2, 3, 4]), |i| i[0]);
+    /// let transposed = array.transpose();
+    /// assert_eq!(transposed.shape().as_slice(), &[4, 3, 2]);
+    /// assert_eq!(transposed[[3, 2, 1]], 1);
+    /// ```
+    #[must_use]
+    pub fn transpose(mut self) -> Self {
+        let order: Vec<usize> =
+            (0..self.axes.shape.ndim() as usize).rev().collect();
+        self.axes = self.axes.permuted(&order);
+        self
+    }
+
+    /// Reorder the axes, so that axis `i` of the result is axis `axes[i]` of
+    /// the array. No elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::InvalidPermutation`] if `axes` does not contain
+    /// every axis of the array exactly once.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array3;
+    /// use tensr::dimension::dim::{Dim3, Dimension};
+    ///
+    /// let array = Array3::from_shape_fn(Dim3::new([2, 3, 4]), |i| {
+    ///     i[0] * 100 + i[1] * 10 + i[2]
+    /// });
+    ///
+    /// let permuted = array.permuted_axes([1, 2, 0]).unwrap();
+    /// assert_eq!(permuted.shape().as_slice(), &[3, 4, 2]);
+    /// assert_eq!(permuted[[2, 3, 1]], 123);
+    /// ```
+    pub fn permuted_axes(
+        mut self,
+        axes: impl AsRef<[usize]>,
+    ) -> Result<Self, ShapeError> {
+        let order = axes.as_ref();
+        let ndim = self.axes.shape.ndim() as usize;
+
+        let mut seen = vec![false; ndim];
+        let is_permutation = order.len() == ndim
+            && order.iter().all(|&axis| {
+                axis < ndim && !std::mem::replace(&mut seen[axis], true)
+            });
+        if !is_permutation {
+            return Err(ShapeError::InvalidPermutation {
+                axes: order.to_vec(),
+                ndim,
+            });
+        }
+
+        self.axes = self.axes.permuted(order);
+        Ok(self)
+    }
+
+    /// Swap two axes of the array in place. No elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if either axis does not exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::{Dim2, Dimension};
+    ///
+    /// let mut array = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[1]);
+    /// array.swap_axes(Axis(0), Axis(1)).unwrap();
+    /// assert_eq!(array.shape().as_slice(), &[3, 2]);
+    /// assert_eq!(array[[2, 0]], 2);
+    /// ```
+    pub fn swap_axes(&mut self, a: Axis, b: Axis) -> Result<(), ShapeError> {
+        let ndim = self.axes.shape.ndim() as usize;
+        check_axis(a.0, ndim)?;
+        check_axis(b.0, ndim)?;
+
+        // Safety: The number of axes is unchanged, and each length is moved
+        // together with its stride
+        unsafe {
+            self.axes.shape.as_slice_mut().swap(a.0, b.0);
+            self.axes.stride.strides.as_slice_mut().swap(a.0, b.0);
+        }
+        Ok(())
+    }
+
+    /// Move the axis `source` to position `destination`, keeping the order
+    /// of the other axes. No elements are copied.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::AxisOutOfBounds`] if either axis does not exist.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array3;
+    /// use tensr::dimension::axes::Axis;
+    /// use tensr::dimension::dim::{Dim3, Dimension};
+    ///
+    /// let array = Array3::<f32>::zeros(Dim3::new([2, 3, 4]));
+    /// let moved = array.moveaxis(Axis(0), Axis(2)).unwrap();
+    /// assert_eq!(moved.shape().as_slice(), &[3, 4, 2]);
+    /// ```
+    pub fn moveaxis(
+        mut self,
+        source: Axis,
+        destination: Axis,
+    ) -> Result<Self, ShapeError> {
+        let ndim = self.axes.shape.ndim() as usize;
+        check_axis(source.0, ndim)?;
+        check_axis(destination.0, ndim)?;
+
+        let mut order: Vec<usize> =
+            (0..ndim).filter(|&axis| axis != source.0).collect();
+        order.insert(destination.0, source.0);
+
+        self.axes = self.axes.permuted(&order);
+        Ok(self)
+    }
+}
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    /// Return a view of the array with its axes reversed, which is the
+    /// transpose of a matrix. See [`ArrayBase::transpose`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let a = Array2::from_vec(Dim2::new([2, 3]), vec![1, 2, 3, 4, 5, 6])
+    ///     .unwrap();
+    ///
+    /// // The Gram matrix, without copying the transpose
+    /// let gram = a.t().matmul(&a);
+    /// assert_eq!(gram[[0, 2]], 1 * 3 + 4 * 6);
+    /// ```
+    pub fn t(&self) -> ArrayView<'_, StorageType::Scalar, NDims> {
+        self.view().transpose()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array::{
+            test_fixtures::array_3d,
+            type_remap::{Array2, ArrayDyn},
+        },
+        dimension::{dim::Dim2, dyn_dim::DimDyn},
+        s,
+    };
+
+    #[test]
+    fn test_permutations() {
+        let array = array_3d();
+
+        let t = array.t();
+        assert_eq!(t.shape().as_slice(), &[4, 3, 2]);
+        assert!(!t.is_contiguous());
+        assert_eq!(t[[3, 1, 1]], 113);
+        assert!(t.transpose().is_contiguous());
+
+        let mut swapped = array.view();
+        swapped.swap_axes(Axis(0), Axis(2)).unwrap();
+        assert_eq!(swapped.shape().as_slice(), &[4, 3, 2]);
+        assert_eq!(swapped[[2, 0, 1]], 102);
+
+        let moved = array.view().moveaxis(Axis(2), Axis(0)).unwrap();
+        assert_eq!(moved.shape().as_slice(), &[4, 2, 3]);
+        assert_eq!(moved[[3, 1, 2]], 123);
+
+        let identity = array.view().moveaxis(Axis(1), Axis(1)).unwrap();
+        assert!(identity.is_contiguous());
+
+        let dynamic: ArrayDyn<usize> =
+            array.into_shape(DimDyn::new_from(vec![2usize, 12])).unwrap();
+        let permuted = dynamic.permuted_axes(vec![1, 0]).unwrap();
+        assert_eq!(permuted.shape().as_slice(), &[12, 2]);
+        assert_eq!(permuted[[11, 1]], 123);
+    }
+
+    #[test]
+    fn test_permutation_errors() {
+        let array = array_3d();
+        assert_eq!(
+            array.view().permuted_axes([0, 1]).err(),
+            Some(ShapeError::InvalidPermutation { axes: vec![0, 1], ndim: 3 })
+        );
+        assert_eq!(
+            array.view().permuted_axes([0, 2, 2]).err(),
+            Some(ShapeError::InvalidPermutation {
+                axes: vec![0, 2, 2],
+                ndim: 3,
+            })
+        );
+        assert!(array.view().permuted_axes([0, 1, 3]).is_err());
+
+        let mut view = array.view();
+        assert_eq!(
+            view.swap_axes(Axis(0), Axis(3)).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 3, ndim: 3 })
+        );
+        assert_eq!(
+            array.view().moveaxis(Axis(4), Axis(0)).err(),
+            Some(ShapeError::AxisOutOfBounds { axis: 4, ndim: 3 })
+        );
+    }
+
+    #[test]
+    fn test_transposed_operands() {
+        let a = Array2::from_shape_fn(Dim2::new([3, 4]), |i| i[0] * 4 + i[1]);
+        let b = Array2::from_shape_fn(Dim2::new([4, 3]), |i| i[0] * 10 + i[1]);
+
+        // Lazy arithmetic mixing transposed and contiguous operands
+        let sum = (&a.t() + &b).eval();
+        for i in 0..4 {
+            for j in 0..3 {
+                assert_eq!(sum[[i, j]], a[[j, i]] + b[[i, j]]);
+            }
+        }
+
+        // Reductions along each axis of a transposed, reversed view
+        let reversed: ArrayView<'_, usize, Dim2> = a.slice(s![..;-1, ..]);
+        let view = reversed.transpose();
+        let rows = view.sum_axis(Axis(1));
+        let columns = view.sum_axis(Axis(0));
+        for i in 0..4 {
+            assert_eq!(rows[[i]], (0..3).map(|j| a[[j, i]]).sum::<usize>());
+        }
+        for j in 0..3 {
+            assert_eq!(
+                columns[[j]],
+                (0..4).map(|i| a[[2 - j, i]]).sum::<usize>()
+            );
+        }
+        assert_eq!(view.sum(), a.sum());
+
+        // Matrix products with transposed operands on either side
+        let product = a.t().matmul(&b.t());
+        for i in 0..4 {
+            for j in 0..4 {
+                let expected: usize =
+                    (0..3).map(|k| a[[k, i]] * b[[j, k]]).sum();
+                assert_eq!(product[[i, j]], expected);
+            }
+        }
+
+        // Reshaping a transposed view copies it in the new row-major order
+        let t = a.t();
+        let flat = t.ravel();
+        assert!(!flat.is_view());
+        assert_eq!(flat[[1]], a[[1, 0]]);
+    }
+}
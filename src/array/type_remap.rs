@@ -1,14 +1,87 @@
+//! Aliases of [`ArrayBase`] for each kind of array and view on the host.
+
 use super::base::ArrayBase;
 use crate::{
-    backend::host::{host_backend::HostBackend, host_storage::HostStorage},
-    dimension::dim,
+    backend::host::{
+        host_backend::HostBackend,
+        host_storage::HostStorage,
+        host_view_storage::{
+            HostCowStorage, HostViewMutStorage, HostViewStorage,
+        },
+    },
+    dimension::{dim, dyn_dim::DimDyn},
 };
 
+/// An owned, zero-dimensional array
+pub type Array0<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim0>;
+/// An owned, one-dimensional array
 pub type Array1<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim1>;
+/// An owned, two-dimensional array
 pub type Array2<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim2>;
+/// An owned, three-dimensional array
 pub type Array3<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim3>;
+/// An owned, four-dimensional array
 pub type Array4<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim4>;
+/// An owned, five-dimensional array
 pub type Array5<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim5>;
+/// An owned, six-dimensional array
 pub type Array6<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim6>;
+/// An owned, seven-dimensional array
 pub type Array7<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim7>;
+/// An owned, eight-dimensional array
 pub type Array8<T> = ArrayBase<HostBackend, HostStorage<T>, dim::Dim8>;
+/// An owned, dynamic-dimensional array
+pub type ArrayDyn<T> = ArrayBase<HostBackend, HostStorage<T>, DimDyn>;
+
+/// A view which borrows the elements of an array, with dimension type `D`
+pub type ArrayView<'a, T, D> =
+    ArrayBase<HostBackend, HostViewStorage<'a, T>, D>;
+/// A borrowed, zero-dimensional view
+pub type ArrayView0<'a, T> = ArrayView<'a, T, dim::Dim0>;
+/// A borrowed, one-dimensional view
+pub type ArrayView1<'a, T> = ArrayView<'a, T, dim::Dim1>;
+/// A borrowed, two-dimensional view
+pub type ArrayView2<'a, T> = ArrayView<'a, T, dim::Dim2>;
+/// A borrowed, three-dimensional view
+pub type ArrayView3<'a, T> = ArrayView<'a, T, dim::Dim3>;
+/// A borrowed, four-dimensional view
+pub type ArrayView4<'a, T> = ArrayView<'a, T, dim::Dim4>;
+/// A borrowed, five-dimensional view
+pub type ArrayView5<'a, T> = ArrayView<'a, T, dim::Dim5>;
+/// A borrowed, six-dimensional view
+pub type ArrayView6<'a, T> = ArrayView<'a, T, dim::Dim6>;
+/// A borrowed, seven-dimensional view
+pub type ArrayView7<'a, T> = ArrayView<'a, T, dim::Dim7>;
+/// A borrowed, eight-dimensional view
+pub type ArrayView8<'a, T> = ArrayView<'a, T, dim::Dim8>;
+/// A borrowed, dynamic-dimensional view
+pub type ArrayViewDyn<'a, T> = ArrayView<'a, T, DimDyn>;
+
+/// A view which mutably borrows the elements of an array, with dimension
+/// type `D`
+pub type ArrayViewMut<'a, T, D> =
+    ArrayBase<HostBackend, HostViewMutStorage<'a, T>, D>;
+/// A mutably borrowed, zero-dimensional view
+pub type ArrayViewMut0<'a, T> = ArrayViewMut<'a, T, dim::Dim0>;
+/// A mutably borrowed, one-dimensional view
+pub type ArrayViewMut1<'a, T> = ArrayViewMut<'a, T, dim::Dim1>;
+/// A mutably borrowed, two-dimensional view
+pub type ArrayViewMut2<'a, T> = ArrayViewMut<'a, T, dim::Dim2>;
+/// A mutably borrowed, three-dimensional view
+pub type ArrayViewMut3<'a, T> = ArrayViewMut<'a, T, dim::Dim3>;
+/// A mutably borrowed, four-dimensional view
+pub type ArrayViewMut4<'a, T> = ArrayViewMut<'a, T, dim::Dim4>;
+/// A mutably borrowed, five-dimensional view
+pub type ArrayViewMut5<'a, T> = ArrayViewMut<'a, T, dim::Dim5>;
+/// A mutably borrowed, six-dimensional view
+pub type ArrayViewMut6<'a, T> = ArrayViewMut<'a, T, dim::Dim6>;
+/// A mutably borrowed, seven-dimensional view
+pub type ArrayViewMut7<'a, T> = ArrayViewMut<'a, T, dim::Dim7>;
+/// A mutably borrowed, eight-dimensional view
+pub type ArrayViewMut8<'a, T> = ArrayViewMut<'a, T, dim::Dim8>;
+/// A mutably borrowed, dynamic-dimensional view
+pub type ArrayViewMutDyn<'a, T> = ArrayViewMut<'a, T, DimDyn>;
+
+/// An array which either owns its elements or borrows them from another
+/// array, with dimension type `D`
+pub type ArrayCow<'a, T, D> = ArrayBase<HostBackend, HostCowStorage<'a, T>, D>;
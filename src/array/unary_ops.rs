@@ -0,0 +1,315 @@
+//! Lazily evaluated unary operators and element-wise math functions.
+
+use crate::{
+    array::{
+        base::ArrayBase, function_1::TensrFn1, function_2::TensrFn2,
+        function_3::TensrFn3, traits::GetWriteableBuffer,
+    },
+    backend::{op_traits, traits},
+    dimension::dim::Dimension,
+};
+
+/// Implement a unary operator, such as [`std::ops::Neg`], for an operand type,
+/// producing a lazily evaluated [`TensrFn1`].
+macro_rules! unary_op {
+    (
+        $op_name: ident, $op_fn: ident,
+        $out_lifetime: lifetime, [$($lifetime: lifetime)?],
+        [$($generic: ident: $bound: path),*],
+        $operand: ty
+    ) => {
+        paste::paste! {
+            impl<$($lifetime,)? Backend, $($generic),*> std::ops::$op_name
+                for $operand
+            where
+                Backend: traits::Backend,
+                $($generic: $bound,)*
+            {
+                type Output = TensrFn1<
+                    $out_lifetime,
+                    Backend,
+                    Backend::[< $op_name Kernel >],
+                    $operand,
+                >;
+
+                #[inline(always)]
+                fn $op_fn(self) -> Self::Output {
+                    Self::Output::new(self)
+                }
+            }
+        }
+    };
+}
+
+/// Implement a unary operator for every kind of operand.
+macro_rules! unary_operators {
+    ($op_name: ident, $op_fn: ident) => {
+        unary_op!(
+            $op_name, $op_fn,
+            'static, [],
+            [StorageType: traits::Storage, NDims: Dimension],
+            ArrayBase<Backend, StorageType, NDims>
+        );
+        unary_op!(
+            $op_name, $op_fn,
+            'a, ['a],
+            [StorageType: traits::Storage, NDims: Dimension],
+            &'a ArrayBase<Backend, StorageType, NDims>
+        );
+        unary_op!(
+            $op_name, $op_fn,
+            'a, ['a],
+            [StorageType: traits::Storage, NDims: Dimension],
+            &'a mut ArrayBase<Backend, StorageType, NDims>
+        );
+        unary_op!(
+            $op_name, $op_fn,
+            'a, ['a],
+            [Op: op_traits::UnaryOp, Arg: GetWriteableBuffer],
+            TensrFn1<'a, Backend, Op, Arg>
+        );
+        unary_op!(
+            $op_name, $op_fn,
+            'a, ['a],
+            [
+                Op: op_traits::BinaryOp,
+                Lhs: GetWriteableBuffer,
+                Rhs: GetWriteableBuffer
+            ],
+            TensrFn2<'a, Backend, Op, Lhs, Rhs>
+        );
+        unary_op!(
+            $op_name, $op_fn,
+            'a, ['a],
+            [Op: op_traits::TernaryOp, Arg0: Sized, Arg1: Sized, Arg2: Sized],
+            TensrFn3<'a, Backend, Op, Arg0, Arg1, Arg2>
+        );
+    };
+}
+
+unary_operators!(Neg, neg);
+unary_operators!(Not, not);
+
+/// Generate a method which lazily applies an element-wise unary operation to
+/// an array, borrowing it.
+macro_rules! array_unary_method {
+    (Neg, $_fn_name: ident) => {};
+    (Not, $_fn_name: ident) => {};
+    ($op_name: ident, $fn_name: ident) => {
+        paste::paste! {
+            #[doc = concat!(
+                "Lazily apply `", stringify!($fn_name), "` to each element of ",
+                "the array."
+            )]
+            #[inline(always)]
+            pub const fn $fn_name(
+                &self,
+            ) -> TensrFn1<'_, Backend, Backend::[< $op_name Kernel >], &Self> {
+                TensrFn1::new(self)
+            }
+        }
+    };
+}
+
+/// Generate a method which lazily applies an element-wise unary operation to
+/// the result of a function object, consuming it.
+macro_rules! function_unary_method {
+    (Neg, $_fn_name: ident) => {};
+    (Not, $_fn_name: ident) => {};
+    ($op_name: ident, $fn_name: ident) => {
+        paste::paste! {
+            #[doc = concat!(
+                "Lazily apply `", stringify!($fn_name), "` to each element of ",
+                "the result of this expression."
+            )]
+            #[inline(always)]
+            pub const fn $fn_name(
+                self,
+            ) -> TensrFn1<'a, Backend, Backend::[< $op_name Kernel >], Self> {
+                TensrFn1::new(self)
+            }
+        }
+    };
+}
+
+impl<Backend, StorageType, NDims> ArrayBase<Backend, StorageType, NDims>
+where
+    Backend: traits::Backend,
+    StorageType: traits::Storage,
+    NDims: Dimension,
+{
+    crate::repeat_unary_ops!(array_unary_method);
+}
+
+impl<'a, Backend, Op, Arg> TensrFn1<'a, Backend, Op, Arg>
+where
+    Backend: traits::Backend,
+{
+    crate::repeat_unary_ops!(function_unary_method);
+}
+
+impl<'a, Backend, Op, Lhs, Rhs> TensrFn2<'a, Backend, Op, Lhs, Rhs>
+where
+    Backend: traits::Backend,
+{
+    crate::repeat_unary_ops!(function_unary_method);
+}
+
+impl<'a, Backend, Op, Arg0, Arg1, Arg2>
+    TensrFn3<'a, Backend, Op, Arg0, Arg1, Arg2>
+where
+    Backend: traits::Backend,
+{
+    crate::repeat_unary_ops!(function_unary_method);
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use crate::{
+        array::type_remap::{Array1, Array2, ArrayView2},
+        dimension::dim::{Dim1, Dim2, Dimension},
+        s,
+    };
+
+    fn values() -> Array1<f64> {
+        Array1::from_vec(Dim1::new([5]), vec![-2.5, -0.5, 0.25, 0.5, 3.0])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_neg() {
+        let a = Array1::from_vec(Dim1::new([3]), vec![1, -2, 3]).unwrap();
+
+        let b = (-&a).eval();
+        assert_eq!(b[1], 2);
+
+        // Negating an expression, then negating the result again
+        let c = -(-(&a + &b));
+        assert_eq!(c.eval()[2], 0);
+    }
+
+    #[test]
+    fn test_not() {
+        let a = Array1::from_vec(Dim1::new([3]), vec![0_u8, 1, 255]).unwrap();
+
+        // `!` is bitwise for integers and logical for booleans
+        let b = (!&a).eval();
+        assert_eq!(b[0], 255);
+        assert_eq!(b[2], 0);
+
+        let mask = Array1::from_vec(Dim1::new([2]), vec![true, false]).unwrap();
+        let inverted = (!mask).eval();
+        assert!(!inverted[0]);
+        assert!(inverted[1]);
+    }
+
+    #[test]
+    fn test_math_functions() {
+        let a = values();
+
+        macro_rules! check {
+            ($method: ident, $expected: expr) => {
+                let result = a.$method().eval();
+                for i in 0..5 {
+                    let expected: f64 = $expected(a[i]);
+                    assert!(
+                        result[i] == expected
+                            || (result[i].is_nan() && expected.is_nan()),
+                        "{}({}) = {}, expected {}",
+                        stringify!($method),
+                        a[i],
+                        result[i],
+                        expected
+                    );
+                }
+            };
+        }
+
+        check!(abs, f64::abs);
+        check!(sqrt, f64::sqrt);
+        check!(exp, f64::exp);
+        check!(ln, f64::ln);
+        check!(log2, f64::log2);
+        check!(log10, f64::log10);
+        check!(sin, f64::sin);
+        check!(cos, f64::cos);
+        check!(tan, f64::tan);
+        check!(asin, f64::asin);
+        check!(acos, f64::acos);
+        check!(atan, f64::atan);
+        check!(tanh, f64::tanh);
+        check!(sigmoid, |x: f64| 1.0 / (1.0 + (-x).exp()));
+        check!(floor, f64::floor);
+        check!(ceil, f64::ceil);
+        check!(round, f64::round);
+        check!(signum, f64::signum);
+        check!(recip, f64::recip);
+    }
+
+    #[test]
+    fn test_integer_functions() {
+        let a = Array1::from_vec(Dim1::new([3]), vec![-3_i32, 0, 7]).unwrap();
+
+        let abs = a.abs().eval();
+        assert_eq!(abs[0], 3);
+
+        let signum = a.signum().eval();
+        assert_eq!(signum[0], -1);
+        assert_eq!(signum[1], 0);
+        assert_eq!(signum[2], 1);
+    }
+
+    #[test]
+    fn test_compose() {
+        let a = values();
+        let b = Array1::from_vec(Dim1::new([5]), vec![1.0; 5]).unwrap();
+
+        // Unary nodes can be used on either side of a binary operation, and
+        // with scalars
+        let result = (a.abs() * 2.0 - &b).sqrt().eval();
+        assert!(result[2].is_nan());
+        assert_eq!(result[4], 5.0_f64.sqrt());
+
+        let result = (&b + a.floor().abs()).eval();
+        assert_eq!(result[0], 4.0);
+
+        let result = (a.exp().ln() + a.sin()).eval();
+        assert_eq!(result[3], 0.5_f64.exp().ln() + 0.5_f64.sin());
+    }
+
+    #[test]
+    fn test_buffer_reuse() {
+        let a =
+            Array1::from_slice(Dim1::new([3]), &[1.0_f32, 4.0, 16.0]).unwrap();
+        let ptr = a.storage.ptr.0;
+
+        let b = (a * 1.0).sqrt().eval();
+        assert_eq!(b.storage.ptr.0, ptr);
+        assert_eq!(b[2], 4.0);
+
+        let c = (-b).eval();
+        assert_eq!(c.storage.ptr.0, ptr);
+        assert_eq!(c[1], -2.0);
+    }
+
+    #[test]
+    fn test_strided() {
+        let a = Array2::from_shape_fn(Dim2::new([3, 4]), |i| {
+            f64::from(u32::try_from(i[0] * 4 + i[1]).unwrap())
+        });
+        let view: ArrayView2<'_, f64> = a.slice(s![..;-1, 1..;2]);
+
+        let result = view.sqrt().eval();
+        assert_eq!(result.shape().as_slice(), &[3, 2]);
+        assert_eq!(result[[0, 0]], 3.0);
+        assert_eq!(result[[2, 1]], 3.0_f64.sqrt());
+
+        let mut out = Array2::from_shape_fn(Dim2::new([3, 2]), |_| 1.0);
+        out += view.recip();
+        assert_eq!(out[[0, 1]], 1.0 + 1.0 / 11.0);
+
+        (-&view).eval_into(&mut out);
+        assert_eq!(out[[1, 0]], -5.0);
+    }
+}
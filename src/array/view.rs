@@ -0,0 +1,478 @@
+//! Borrowed views of arrays, and arrays which may be either owned or
+//! borrowed.
+
+use std::ptr::NonNull;
+
+use crate::{
+    array::{
+        base::ArrayBase,
+        type_remap::{ArrayCow, ArrayView, ArrayViewMut},
+    },
+    backend::{
+        host::{
+            host_backend::HostBackend,
+            host_storage::{HostData, HostNonNull, HostStorage},
+            host_view_storage::{
+                HostCowStorage, HostViewMutStorage, HostViewStorage,
+            },
+        },
+        traits::{ScalarAccessor, StorageMut},
+    },
+    dimension::{
+        axes::Axes, dim::Dimension, index::next_index, stride::Stride,
+    },
+    error::ShapeError,
+    types::{IDim, UDim},
+};
+
+impl<StorageType, NDims> ArrayBase<HostBackend, StorageType, NDims>
+where
+    StorageType: HostData,
+    NDims: Dimension,
+{
+    /// Return an immutable view of the array. The view shares the data of the
+    /// array, so no elements are copied.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array2;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// let array = Array2::from_vec(Dim2::new([2, 2]), vec![1, 2, 3, 4]).unwrap();
+    /// let view = array.view();
+    /// assert_eq!(view.get_scalar(3), 4);
+    /// ```
+    pub fn view(&self) -> ArrayView<'_, StorageType::Scalar, NDims> {
+        // Safety: The axes are already valid for this storage
+        unsafe { self.view_with_axes(self.axes.clone()) }
+    }
+
+    /// Return a mutable view of the array. Writing to the view modifies the
+    /// elements of the array.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::Array1;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim1;
+    ///
+    /// let mut array = Array1::from_vec(Dim1::new([3]), vec![1, 2, 3]).unwrap();
+    /// array.view_mut().fill(5);
+    /// assert_eq!(array.get_scalar(0), 5);
+    /// ```
+    pub fn view_mut(&mut self) -> ArrayViewMut<'_, StorageType::Scalar, NDims>
+    where
+        StorageType: StorageMut,
+    {
+        // Safety: The axes are already valid for this storage
+        unsafe { self.view_mut_with_axes(self.axes.clone()) }
+    }
+
+    /// Create an immutable view of this array's storage with arbitrary axes.
+    /// This is the building block for operations such as slicing and
+    /// transposing, which only change the shape, strides and offset.
+    ///
+    /// # Safety
+    /// Every element addressed by `axes` must lie within the storage.
+    pub(crate) unsafe fn view_with_axes<D>(
+        &self,
+        axes: Axes<D>,
+    ) -> ArrayView<'_, StorageType::Scalar, D>
+    where
+        D: Dimension,
+    {
+        ArrayBase {
+            axes,
+            storage: HostViewStorage::new(
+                self.storage.as_non_null(),
+                self.storage.len(),
+            ),
+            phantom_backend: std::marker::PhantomData,
+        }
+    }
+
+    /// Create a mutable view of this array's storage with arbitrary axes.
+    ///
+    /// # Safety
+    /// Every element addressed by `axes` must lie within the storage, and no
+    /// two indices may address the same element.
+    pub(crate) unsafe fn view_mut_with_axes<D>(
+        &mut self,
+        axes: Axes<D>,
+    ) -> ArrayViewMut<'_, StorageType::Scalar, D>
+    where
+        StorageType: StorageMut,
+        D: Dimension,
+    {
+        ArrayBase {
+            axes,
+            storage: HostViewMutStorage::new(
+                self.storage.as_non_null(),
+                self.storage.len(),
+            ),
+            phantom_backend: std::marker::PhantomData,
+        }
+    }
+
+    /// Copy the elements of this array (or view) into a new, contiguous array
+    /// which owns its data.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::{Array1, ArrayView1};
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim1;
+    ///
+    /// let data = [1, 2, 3, 4, 5, 6];
+    /// let view = ArrayView1::from_slice_with_strides(&data, Dim1::new([3]), &[2], 1).unwrap();
+    ///
+    /// let owned: Array1<i32> = view.to_owned();
+    /// assert!(owned.is_contiguous());
+    /// assert_eq!(owned.get_scalar(2), 6);
+    /// ```
+    pub fn to_owned(
+        &self,
+    ) -> ArrayBase<HostBackend, HostStorage<StorageType::Scalar>, NDims> {
+        let shape = self.axes.shape.clone();
+        let len = shape.len();
+
+        let data = if self.is_contiguous() {
+            let offset = self.axes.stride.offset;
+            (offset..offset + len).map(|i| self.storage[i]).collect()
+        } else {
+            let mut data = Vec::with_capacity(len);
+
+            if len > 0 {
+                let mut index = vec![0; shape.ndim() as usize];
+                loop {
+                    data.push(self.get_scalar_nd(&index));
+
+                    if !next_index(&mut index, shape.as_slice()) {
+                        break;
+                    }
+                }
+            }
+
+            data
+        };
+
+        ArrayBase::new(
+            Axes::new_with_default_stride(shape),
+            HostStorage::from_vec(data),
+        )
+    }
+}
+
+impl<'a, T, NDims> ArrayView<'a, T, NDims>
+where
+    T: Copy,
+    NDims: Dimension,
+{
+    /// Create a view of contiguous, row-major data in a slice.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::LengthMismatch`] if the length of `data` is not
+    /// equal to the number of elements in `shape`.
+    pub fn from_slice(data: &'a [T], shape: NDims) -> Result<Self, ShapeError> {
+        if data.len() != shape.len() {
+            return Err(ShapeError::LengthMismatch {
+                expected: shape.len(),
+                found: data.len(),
+            });
+        }
+
+        Ok(Self {
+            axes: Axes::new_with_default_stride(shape),
+            storage: unsafe {
+                HostViewStorage::new(slice_ptr(data), data.len())
+            },
+            phantom_backend: std::marker::PhantomData,
+        })
+    }
+
+    /// Create a view of data in a slice, where the element at index
+    /// `[i, j, ...]` is located at `offset + i * strides[0] + j * strides[1] +
+    /// ...`. Strides are measured in elements and may be negative.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::DimensionMismatch`] if the number of strides does
+    /// not match the number of dimensions in `shape`, or
+    /// [`ShapeError::OutOfBounds`] if any element of the view would lie
+    /// outside of `data`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::array::type_remap::ArrayView2;
+    /// use tensr::backend::traits::ScalarAccessor;
+    /// use tensr::dimension::dim::Dim2;
+    ///
+    /// // View the data as the transpose of a 2x3 matrix
+    /// let data = [1, 2, 3, 4, 5, 6];
+    /// let view = ArrayView2::from_slice_with_strides(&data, Dim2::new([3, 2]), &[1, 3], 0).unwrap();
+    ///
+    /// assert_eq!(view.get_scalar_nd(&[0, 1]), 4);
+    /// assert_eq!(view.get_scalar_nd(&[2, 0]), 3);
+    /// ```
+    pub fn from_slice_with_strides(
+        data: &'a [T],
+        shape: NDims,
+        strides: &[IDim],
+        offset: usize,
+    ) -> Result<Self, ShapeError> {
+        if strides.len() != shape.ndim() as usize {
+            return Err(ShapeError::DimensionMismatch {
+                expected: shape.ndim() as usize,
+                found: strides.len(),
+            });
+        }
+
+        let mut stride = shape.clone();
+        for (s, &v) in unsafe { stride.as_slice_mut() }.iter_mut().zip(strides)
+        {
+            #[allow(clippy::cast_sign_loss)]
+            {
+                *s = v as UDim;
+            }
+        }
+
+        let axes = Axes::new(shape, Stride::new(offset, stride));
+
+        #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+        if let Some((min, max)) = axes.storage_bounds() {
+            if min < 0 {
+                return Err(ShapeError::OutOfBounds {
+                    index: min,
+                    len: data.len(),
+                });
+            }
+
+            if max as usize >= data.len() {
+                return Err(ShapeError::OutOfBounds {
+                    index: max,
+                    len: data.len(),
+                });
+            }
+        }
+
+        Ok(Self {
+            axes,
+            storage: unsafe {
+                HostViewStorage::new(slice_ptr(data), data.len())
+            },
+            phantom_backend: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<'a, T, NDims> ArrayViewMut<'a, T, NDims>
+where
+    T: Copy,
+    NDims: Dimension,
+{
+    /// Create a mutable view of contiguous, row-major data in a slice.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::LengthMismatch`] if the length of `data` is not
+    /// equal to the number of elements in `shape`.
+    pub fn from_slice_mut(
+        data: &'a mut [T],
+        shape: NDims,
+    ) -> Result<Self, ShapeError> {
+        if data.len() != shape.len() {
+            return Err(ShapeError::LengthMismatch {
+                expected: shape.len(),
+                found: data.len(),
+            });
+        }
+
+        let length = data.len();
+        Ok(Self {
+            axes: Axes::new_with_default_stride(shape),
+            storage: unsafe {
+                HostViewMutStorage::new(slice_ptr(data), length)
+            },
+            phantom_backend: std::marker::PhantomData,
+        })
+    }
+}
+
+impl<T, NDims> ArrayCow<'_, T, NDims>
+where
+    T: Copy,
+    NDims: Dimension,
+{
+    /// Returns `true` if the array borrows the elements of another array,
+    /// rather than owning a copy of them.
+    pub const fn is_view(&self) -> bool {
+        self.storage.is_borrowed()
+    }
+
+    /// Convert the array into one which owns its data, copying the elements
+    /// only if they are borrowed.
+    pub fn into_owned(self) -> ArrayBase<HostBackend, HostStorage<T>, NDims> {
+        match self.storage {
+            HostCowStorage::Owned(storage) => ArrayBase {
+                axes: self.axes,
+                storage,
+                phantom_backend: std::marker::PhantomData,
+            },
+            HostCowStorage::Borrowed(_) => self.to_owned(),
+        }
+    }
+}
+
+/// Return a [`HostNonNull`] pointing to the start of a slice
+fn slice_ptr<T>(data: &[T]) -> HostNonNull<T> {
+    HostNonNull(NonNull::from(data).cast())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        array::{
+            function_2::Function2,
+            type_remap::{Array1, Array2, ArrayView1, ArrayView2},
+        },
+        backend::traits::ContainerLength,
+        dimension::dim::{Dim1, Dim2},
+    };
+
+    #[test]
+    fn test_view_shares_data() {
+        let mut array =
+            Array2::from_shape_fn(Dim2::new([3, 4]), |i| i[0] * 4 + i[1]);
+        let ptr = array.storage.ptr.0.as_ptr();
+
+        let view = array.view();
+        assert_eq!(view.storage.ptr.0.as_ptr(), ptr);
+        assert_eq!(view.get_scalar_nd(&[2, 3]), 11);
+
+        array.view_mut().fill(7);
+        assert_eq!(array.get_scalar(5), 7);
+    }
+
+    #[test]
+    fn test_strided_view() {
+        let data: Vec<i32> = (0..12).collect();
+
+        // Every other column of a 3x4 matrix
+        let view = ArrayView2::from_slice_with_strides(
+            &data,
+            Dim2::new([3, 2]),
+            &[4, 2],
+            1,
+        )
+        .unwrap();
+
+        assert!(!view.is_contiguous());
+        assert_eq!(view.len(), 6);
+
+        let owned = view.to_owned();
+        assert!(owned.is_contiguous());
+        for (i, v) in [1, 3, 5, 7, 9, 11].iter().enumerate() {
+            assert_eq!(owned.get_scalar(i), *v);
+        }
+    }
+
+    #[test]
+    fn test_negative_stride_view() {
+        let data = [1, 2, 3, 4, 5];
+        let view = ArrayView1::from_slice_with_strides(
+            &data,
+            Dim1::new([5]),
+            &[-1],
+            4,
+        )
+        .unwrap();
+
+        for i in 0..5 {
+            assert_eq!(view.get_scalar_nd(&[i]), 5 - i);
+        }
+    }
+
+    #[test]
+    fn test_strided_view_out_of_bounds() {
+        let data = [0; 6];
+
+        let result = ArrayView2::from_slice_with_strides(
+            &data,
+            Dim2::new([2, 3]),
+            &[3, 1],
+            1,
+        );
+        assert_eq!(
+            result.err(),
+            Some(ShapeError::OutOfBounds { index: 6, len: 6 })
+        );
+
+        let result = ArrayView1::from_slice_with_strides(
+            &data,
+            Dim1::new([3]),
+            &[-1],
+            1,
+        );
+        assert_eq!(
+            result.err(),
+            Some(ShapeError::OutOfBounds { index: -1, len: 6 })
+        );
+
+        let result = ArrayView1::from_slice_with_strides(
+            &data,
+            Dim1::new([3]),
+            &[1, 1],
+            0,
+        );
+        assert_eq!(
+            result.err(),
+            Some(ShapeError::DimensionMismatch { expected: 1, found: 2 })
+        );
+    }
+
+    #[test]
+    fn test_mutable_view_fill_only_touches_view() {
+        let mut data = [0; 6];
+
+        {
+            let mut view =
+                ArrayViewMut::from_slice_mut(&mut data, Dim2::new([2, 3]))
+                    .unwrap();
+
+            // Restrict the view to the middle column
+            view.axes =
+                Axes::new(Dim2::new([2, 1]), Stride::new(1, Dim2::new([3, 1])));
+            view.fill(9);
+        }
+
+        assert_eq!(data, [0, 9, 0, 0, 9, 0]);
+    }
+
+    #[test]
+    fn test_strided_view_in_expression() {
+        let data: Vec<i64> = (0..6).collect();
+        let transposed = ArrayView2::from_slice_with_strides(
+            &data,
+            Dim2::new([3, 2]),
+            &[1, 3],
+            0,
+        )
+        .unwrap();
+
+        let ones = Array2::<i64>::ones(Dim2::new([3, 2]));
+        let mut out = Array2::<i64>::zeros(Dim2::new([3, 2]));
+
+        (&transposed + &ones).apply(&mut out);
+
+        for i in 0..3 {
+            for j in 0..2 {
+                assert_eq!(out.get_scalar_nd(&[i, j]), data[j * 3 + i] + 1);
+            }
+        }
+
+        let sum = Array1::from_vec(Dim1::new([3]), vec![1i64, 2, 3]).unwrap();
+        let mut out = Array1::<i64>::zeros(Dim1::new([3]));
+        (sum.view() * sum.view()).apply(&mut out);
+        assert_eq!(out.get_scalar(2), 9);
+    }
+}
@@ -1,10 +1,147 @@
+//! Macros which invoke another macro once for each operator, function or
+//! scalar type.
+
+/// Invoke `$macro!(Name, name, operator)` for every binary operator, where
+/// `Name` is the [`std::ops`] trait, `name` is its method and `operator` is
+/// the operator token.
 #[macro_export]
 macro_rules! repeat_binary_ops {
+    ($macro: tt) => {
+        $crate::repeat_arithmetic_ops!($macro);
+        $crate::repeat_bitwise_ops!($macro);
+    };
+}
+
+/// Invoke `$macro!(Name, name, operator)` for every arithmetic operator. These
+/// are implemented for all [`TensrType`]s.
+///
+/// [`TensrType`]: crate::backend::types::TensrType
+#[macro_export]
+macro_rules! repeat_arithmetic_ops {
     ($macro: tt) => {
         $macro!(Add, add, +);
         $macro!(Sub, sub, -);
         $macro!(Mul, mul, *);
         $macro!(Div, div, /);
+        $macro!(Rem, rem, %);
+    };
+}
+
+/// Invoke `$macro!(Name, name, operator)` for every bitwise and shift
+/// operator. These are only implemented for integer types.
+#[macro_export]
+macro_rules! repeat_bitwise_ops {
+    ($macro: tt) => {
+        $macro!(BitAnd, bitand, &);
+        $macro!(BitOr, bitor, |);
+        $macro!(BitXor, bitxor, ^);
+        $macro!(Shl, shl, <<);
+        $macro!(Shr, shr, >>);
+    };
+}
+
+/// Invoke `$macro!(Name, name)` for every named, element-wise binary function.
+///
+/// Examples include `pow` and `maximum`. `Name` is used to name the kernel
+/// (e.g. `PowKernel`), and `name` is the name of the corresponding method.
+#[macro_export]
+macro_rules! repeat_binary_functions {
+    ($macro: tt) => {
+        $macro!(Pow, pow);
+        $macro!(Powi, powi);
+        $macro!(Minimum, minimum);
+        $macro!(Maximum, maximum);
+        $macro!(Atan2, atan2);
+        $macro!(Hypot, hypot);
+        $macro!(Copysign, copysign);
+        $macro!(LogicalAnd, logical_and);
+        $macro!(LogicalOr, logical_or);
+        $macro!(LogicalXor, logical_xor);
+    };
+}
+
+/// Invoke `$macro!(Name, name)` for every element-wise comparison. Comparisons
+/// produce an array of `bool`s, regardless of the scalar type of the operands.
+#[macro_export]
+macro_rules! repeat_comparison_ops {
+    ($macro: tt) => {
+        $macro!(Eq, eq);
+        $macro!(Ne, ne);
+        $macro!(Lt, lt);
+        $macro!(Le, le);
+        $macro!(Gt, gt);
+        $macro!(Ge, ge);
+    };
+}
+
+/// Invoke `$macro!(Name, name)` for every element-wise operation with three
+/// operands. `Name` is used to name the kernel (e.g. `SelectKernel`), and
+/// `name` is the name of the corresponding method.
+#[macro_export]
+macro_rules! repeat_ternary_ops {
+    ($macro: tt) => {
+        $macro!(Select, select);
+        $macro!(Clip, clip);
+    };
+}
+
+/// Invoke `$macro!(Name, name)` for every element-wise unary operation. `Name`
+/// is used to name the kernel (e.g. `SqrtKernel`), and `name` is the name of
+/// the corresponding method.
+#[macro_export]
+macro_rules! repeat_unary_ops {
+    ($macro: tt) => {
+        $macro!(Neg, neg);
+        $macro!(Abs, abs);
+        $macro!(Sqrt, sqrt);
+        $macro!(Exp, exp);
+        $macro!(Ln, ln);
+        $macro!(Log2, log2);
+        $macro!(Log10, log10);
+        $macro!(Sin, sin);
+        $macro!(Cos, cos);
+        $macro!(Tan, tan);
+        $macro!(Asin, asin);
+        $macro!(Acos, acos);
+        $macro!(Atan, atan);
+        $macro!(Tanh, tanh);
+        $macro!(Sigmoid, sigmoid);
+        $macro!(Floor, floor);
+        $macro!(Ceil, ceil);
+        $macro!(Round, round);
+        $macro!(Signum, signum);
+        $macro!(Recip, recip);
+        $macro!(Not, not);
+        $macro!(LogicalNot, logical_not);
+        $macro!(Relu, relu);
+    };
+}
+
+/// Invoke `$macro!(T, args...)` for every built-in [`TensrType`], passing any
+/// additional arguments through unchanged.
+///
+/// [`TensrType`]: crate::backend::types::TensrType
+#[macro_export]
+macro_rules! repeat_tensr_types {
+    ($macro: tt $(, $args: tt)*) => {
+        $crate::repeat_integer_types!($macro $(, $args)*);
+        $macro!(f32 $(, $args)*);
+        $macro!(f64 $(, $args)*);
+    };
+}
+
+/// Invoke `$macro!(T, args...)` for every built-in integer [`TensrType`].
+///
+/// [`TensrType`]: crate::backend::types::TensrType
+#[macro_export]
+macro_rules! repeat_integer_types {
+    ($macro: tt $(, $args: tt)*) => {
+        $macro!(i16 $(, $args)*);
+        $macro!(i32 $(, $args)*);
+        $macro!(i64 $(, $args)*);
+        $macro!(u16 $(, $args)*);
+        $macro!(u32 $(, $args)*);
+        $macro!(u64 $(, $args)*);
     };
 }
 
@@ -1,3 +1,5 @@
+//! The [`HostBackend`] type and the kernels it uses.
+
 use crate::backend::{
     host::{host_kernels, host_storage::HostStorage},
     traits::Backend,
@@ -11,12 +13,12 @@ macro_rules! kernel_repeater {
     };
 }
 
-macro_rules! repeat_kernel_repeater {
-    ($([$name: ident, $_1: tt, $_2: tt]),*) => {
-        $(
-            kernel_repeater!($name, $_1, $_2);
-        )*
-    }
+macro_rules! named_kernel_repeater {
+    ($name: ident, $_1: tt) => {
+        paste::paste! {
+            type [< $name Kernel >] = host_kernels::[< Host $name Kernel >];
+        }
+    };
 }
 
 /// The host backend for Tensr, which allows you to perform calculations on the
@@ -33,6 +35,8 @@ impl Backend for HostBackend {
     where
         T: Copy;
     crate::repeat_binary_ops!(kernel_repeater);
-    // repeat_kernel_repeater!(crate:array_binary_ops!());
-    // repeat_kernel_repeater!([Add, add, +]);
+    crate::repeat_binary_functions!(named_kernel_repeater);
+    crate::repeat_comparison_ops!(named_kernel_repeater);
+    crate::repeat_unary_ops!(named_kernel_repeater);
+    crate::repeat_ternary_ops!(named_kernel_repeater);
 }
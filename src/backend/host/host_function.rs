@@ -1,13 +1,20 @@
+//! Evaluation of lazy expressions on the host.
+
 use crate::{
-    array::function_2::{Function2, TensrFn2},
+    array::{
+        function_1::{Function1, TensrFn1},
+        function_2::{Function2, TensrFn2},
+        function_3::{Function3, TensrFn3},
+    },
     backend::{
         host::{host_backend::HostBackend, host_kernels},
-        traits::{ScalarAccessor, ScalarWriter},
+        traits::{ContainerShape, ScalarAccessor, ScalarWriter},
     },
+    dimension::{dim::Dimension, index::next_index},
+    types::UDim,
 };
 
-impl<'a, Op, Lhs, Rhs> ScalarAccessor
-    for TensrFn2<'a, HostBackend, Op, Lhs, Rhs>
+impl<Op, Lhs, Rhs> ScalarAccessor for TensrFn2<'_, HostBackend, Op, Lhs, Rhs>
 where
     Op: host_kernels::HostBinaryOp<Lhs::Scalar>,
     Lhs: ScalarAccessor,
@@ -17,25 +24,461 @@ where
     fn get_scalar(&self, index: usize) -> Self::Scalar {
         Op::apply_scalar(self.lhs.get_scalar(index), self.rhs.get_scalar(index))
     }
+
+    #[inline(always)]
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar {
+        Op::apply_scalar(
+            self.lhs.get_scalar_nd(index),
+            self.rhs.get_scalar_nd(index),
+        )
+    }
+
+    #[inline(always)]
+    fn is_contiguous(&self) -> bool {
+        self.supports_flat_index(self.shape.len())
+    }
+
+    #[inline(always)]
+    fn supports_flat_index(&self, len: usize) -> bool {
+        // Flat indexing is only valid if neither operand is broadcast
+        self.lhs.supports_flat_index(len) && self.rhs.supports_flat_index(len)
+    }
 }
 
-impl<'a, Op, Lhs, Rhs, Out> Function2<Out>
-    for TensrFn2<'a, HostBackend, Op, Lhs, Rhs>
+impl<Op, Lhs, Rhs, Out> Function2<Out>
+    for TensrFn2<'_, HostBackend, Op, Lhs, Rhs>
 where
     Op: host_kernels::HostBinaryOp<Lhs::Scalar>,
     Lhs: ScalarAccessor,
     Rhs: ScalarAccessor<Scalar = Lhs::Scalar>,
-    Out: ScalarAccessor<Scalar = Lhs::Scalar> + ScalarWriter,
+    Out: ScalarAccessor<Scalar = Op::Output<Lhs::Scalar>>
+        + ScalarWriter
+        + ContainerShape,
+{
+    #[track_caller]
+    fn apply(&self, out: &mut Out) {
+        evaluate_into(self, self.shape.as_slice(), out);
+    }
+}
+
+impl<Op, Arg> ScalarAccessor for TensrFn1<'_, HostBackend, Op, Arg>
+where
+    Op: host_kernels::HostUnaryOp<Arg::Scalar>,
+    Arg: ScalarAccessor,
+{
+    #[inline(always)]
+    fn get_scalar(&self, index: usize) -> Self::Scalar {
+        Op::apply_scalar(self.arg.get_scalar(index))
+    }
+
+    #[inline(always)]
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar {
+        Op::apply_scalar(self.arg.get_scalar_nd(index))
+    }
+
+    #[inline(always)]
+    fn is_contiguous(&self) -> bool {
+        self.arg.is_contiguous()
+    }
+
+    #[inline(always)]
+    fn supports_flat_index(&self, len: usize) -> bool {
+        self.arg.supports_flat_index(len)
+    }
+}
+
+impl<Op, Arg, Out> Function1<Out> for TensrFn1<'_, HostBackend, Op, Arg>
+where
+    Op: host_kernels::HostUnaryOp<Arg::Scalar>,
+    Arg: ScalarAccessor + ContainerShape,
+    Out: ScalarAccessor<Scalar = Arg::Scalar> + ScalarWriter + ContainerShape,
 {
+    #[track_caller]
     fn apply(&self, out: &mut Out) {
-        for i in 0..self.lhs.len() {
-            out.write_scalar(
-                Op::apply_scalar(
-                    self.lhs.get_scalar(i),
-                    self.rhs.get_scalar(i),
-                ),
-                i,
+        let shape = self.arg.get_shape();
+        evaluate_into(self, shape.as_slice(), out);
+    }
+}
+
+impl<Op, Arg0, Arg1, Arg2> ScalarAccessor
+    for TensrFn3<'_, HostBackend, Op, Arg0, Arg1, Arg2>
+where
+    Op: host_kernels::HostFunctionOp<(
+        Arg0::Scalar,
+        Arg1::Scalar,
+        Arg2::Scalar,
+    )>,
+    Arg0: ScalarAccessor,
+    Arg1: ScalarAccessor,
+    Arg2: ScalarAccessor,
+{
+    #[inline(always)]
+    fn get_scalar(&self, index: usize) -> Self::Scalar {
+        let (arg0, arg1, arg2) = &self.args;
+        Op::apply_scalar((
+            arg0.get_scalar(index),
+            arg1.get_scalar(index),
+            arg2.get_scalar(index),
+        ))
+    }
+
+    #[inline(always)]
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar {
+        let (arg0, arg1, arg2) = &self.args;
+        Op::apply_scalar((
+            arg0.get_scalar_nd(index),
+            arg1.get_scalar_nd(index),
+            arg2.get_scalar_nd(index),
+        ))
+    }
+
+    #[inline(always)]
+    fn is_contiguous(&self) -> bool {
+        self.supports_flat_index(self.shape.len())
+    }
+
+    #[inline(always)]
+    fn supports_flat_index(&self, len: usize) -> bool {
+        let (arg0, arg1, arg2) = &self.args;
+        arg0.supports_flat_index(len)
+            && arg1.supports_flat_index(len)
+            && arg2.supports_flat_index(len)
+    }
+}
+
+impl<Op, Arg0, Arg1, Arg2, Out> Function3<Out>
+    for TensrFn3<'_, HostBackend, Op, Arg0, Arg1, Arg2>
+where
+    Op: host_kernels::HostFunctionOp<(
+        Arg0::Scalar,
+        Arg1::Scalar,
+        Arg2::Scalar,
+    )>,
+    Arg0: ScalarAccessor,
+    Arg1: ScalarAccessor,
+    Arg2: ScalarAccessor,
+    Out: ScalarAccessor<Scalar = Op::Output> + ScalarWriter + ContainerShape,
+{
+    #[track_caller]
+    fn apply(&self, out: &mut Out) {
+        evaluate_into(self, self.shape.as_slice(), out);
+    }
+}
+
+/// Evaluate every element of `expr`, which has the given `shape`, and write
+/// the results into `out`.
+///
+/// # Panics
+/// Panics if the shape of `out` is not equal to `shape`.
+#[track_caller]
+fn evaluate_into<Expr, Out>(expr: &Expr, shape: &[UDim], out: &mut Out)
+where
+    Expr: ScalarAccessor,
+    Out: ScalarAccessor<Scalar = Expr::Scalar> + ScalarWriter + ContainerShape,
+{
+    let out_shape = out.get_shape();
+    assert_eq!(
+        out_shape.as_slice(),
+        shape,
+        "output shape does not match the shape of the expression"
+    );
+
+    let len = out_shape.len();
+    if expr.supports_flat_index(len) && out.is_contiguous() {
+        for i in 0..len {
+            out.write_scalar(expr.get_scalar(i), i);
+        }
+    } else {
+        // At least one of the operands is strided or broadcast, so we must
+        // step through every multi-dimensional index in turn
+        if out_shape.is_empty() {
+            return;
+        }
+
+        let mut index = vec![0; shape.len()];
+        loop {
+            out.write_scalar_nd(expr.get_scalar_nd(&index), &index);
+
+            if !next_index(&mut index, shape) {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use super::*;
+    use crate::{
+        array::type_remap::{
+            Array1, Array2, Array3, ArrayDyn, ArrayView2, ArrayViewMut2,
+        },
+        backend::traits::ContainerLength,
+        dimension::{
+            dim::{Dim1, Dim2, Dim3},
+            dyn_dim::DimDyn,
+        },
+        error::ShapeError,
+        s,
+    };
+
+    #[test]
+    fn test_broadcast_add() {
+        let column =
+            Array2::from_vec(Dim2::new([3, 1]), vec![0, 10, 20]).unwrap();
+        let row =
+            Array2::from_vec(Dim2::new([1, 4]), vec![1, 2, 3, 4]).unwrap();
+
+        let expr = &column + &row;
+        let shape: Dim2 = expr.get_shape();
+        assert_eq!(shape.as_slice(), &[3, 4]);
+        assert_eq!(expr.len(), 12);
+        assert!(!expr.is_contiguous());
+
+        let mut out = Array2::zeros(Dim2::new([3, 4]));
+        expr.apply(&mut out);
+
+        for i in 0..3 {
+            for j in 0..4 {
+                assert_eq!(out[[i, j]], i * 10 + j + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_broadcast_different_ndim() {
+        let matrix =
+            Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+        let vector =
+            Array1::from_vec(Dim1::new([3]), vec![100, 200, 300]).unwrap();
+
+        let expr = &vector * &matrix;
+        let shape: Dim2 = expr.get_shape();
+        assert_eq!(shape.as_slice(), &[2, 3]);
+
+        let mut out = Array2::zeros(Dim2::new([2, 3]));
+        expr.apply(&mut out);
+        assert_eq!(out[[0, 2]], 600);
+        assert_eq!(out[[1, 0]], 300);
+
+        // A nested expression, where the inner result is broadcast again
+        let cube = Array3::from_shape_fn(Dim3::new([2, 1, 3]), |i| i[0]);
+        let expr = (&matrix + &vector) + &cube;
+        let shape: Dim3 = expr.get_shape();
+        assert_eq!(shape.as_slice(), &[2, 2, 3]);
+
+        let mut out = Array3::zeros(Dim3::new([2, 2, 3]));
+        expr.apply(&mut out);
+        assert_eq!(out[[1, 1, 2]], 5 + 300 + 1);
+        assert_eq!(out[[0, 1, 0]], 3 + 100);
+    }
+
+    #[test]
+    fn test_broadcast_dyn() {
+        let matrix = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[1]);
+        let dyn_array = ArrayDyn::from_shape_fn(
+            DimDyn::new_from(vec![4usize, 1, 1]),
+            |i| i[0] * 10,
+        );
+
+        let expr = &matrix + &dyn_array;
+        let shape: DimDyn = expr.get_shape();
+        assert_eq!(shape.as_slice(), &[4, 2, 3]);
+
+        let mut out = ArrayDyn::zeros(DimDyn::new_from(vec![4usize, 2, 3]));
+        expr.apply(&mut out);
+        assert_eq!(out[[3, 1, 2]], 32);
+    }
+
+    #[test]
+    fn test_broadcast_strided_view() {
+        let data =
+            Array2::from_shape_fn(Dim2::new([4, 4]), |i| i[0] * 4 + i[1]);
+
+        // The last column, reversed, as a column vector
+        let column: ArrayView2<'_, usize> = data.slice(s![..;-1, 3..]);
+        let row = Array1::from_vec(Dim1::new([2]), vec![0, 1000]).unwrap();
+
+        let mut out = Array2::zeros(Dim2::new([4, 2]));
+        (&column + &row).apply(&mut out);
+
+        assert_eq!(out[[0, 0]], 15);
+        assert_eq!(out[[3, 1]], 1003);
+    }
+
+    #[test]
+    fn test_broadcast_mismatch() {
+        let a = Array2::<f32>::zeros(Dim2::new([3, 2]));
+        let b = Array1::<f32>::zeros(Dim1::new([3]));
+
+        let result =
+            TensrFn2::<HostBackend, host_kernels::HostAddKernel, _, _>::try_new(
+                &a, &b,
             );
+
+        assert_eq!(
+            result.err().map(|e| e.to_string()),
+            Some(
+                ShapeError::BroadcastMismatch { lhs: vec![3, 2], rhs: vec![3] }
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot be broadcast together")]
+    fn test_broadcast_mismatch_operator() {
+        let a = Array2::<f32>::zeros(Dim2::new([3, 2]));
+        let b = Array2::<f32>::zeros(Dim2::new([2, 3]));
+        let _ = &a + &b;
+    }
+
+    #[test]
+    #[should_panic(expected = "output shape does not match")]
+    fn test_apply_wrong_output_shape() {
+        let a = Array2::<f32>::zeros(Dim2::new([3, 1]));
+        let b = Array1::<f32>::zeros(Dim1::new([2]));
+        let mut out = Array2::<f32>::zeros(Dim2::new([3, 1]));
+        (&a + &b).apply(&mut out);
+    }
+
+    #[test]
+    fn test_scalar_operands() {
+        let a = Array1::from_slice(Dim1::new([4]), &[1.0_f64, 2.0, 3.0, 4.0])
+            .unwrap();
+        let b =
+            Array1::from_vec(Dim1::new([4]), vec![3.0, 3.0, 3.0, 3.0]).unwrap();
+
+        // The whole expression is evaluated in a single, flat pass
+        let expr = (&a * 2.0 + &b) / 3.0;
+        assert!(expr.is_contiguous());
+
+        let mut out = Array1::zeros(Dim1::new([4]));
+        expr.apply(&mut out);
+        let expected = [5.0 / 3.0, 7.0 / 3.0, 3.0, 11.0 / 3.0];
+        for (i, v) in expected.iter().enumerate() {
+            assert_eq!(out[i], *v);
         }
+
+        (1.0 - &a).apply(&mut out);
+        assert_eq!(out[3], -3.0);
+
+        (8.0 / (&a * &a)).apply(&mut out);
+        assert_eq!(out[1], 2.0);
+    }
+
+    #[test]
+    fn test_scalar_operand_types() {
+        let ints = Array2::from_shape_fn(Dim2::new([2, 2]), |i| {
+            u32::try_from(i[0] * 2 + i[1]).unwrap()
+        });
+
+        let expr = 10 - &ints * 2;
+        let shape: Dim2 = expr.get_shape();
+        assert_eq!(shape.as_slice(), &[2, 2]);
+
+        let mut out = Array2::zeros(Dim2::new([2, 2]));
+        expr.apply(&mut out);
+        assert_eq!(out[[1, 1]], 4);
+
+        let singles = Array1::<f32>::ones(Dim1::new([3]));
+        let mut out = Array1::<f32>::zeros(Dim1::new([3]));
+        (singles * 0.5_f32 + 0.25).apply(&mut out);
+        assert_eq!(out[2], 0.75);
+    }
+
+    #[test]
+    fn test_scalar_operand_strided() {
+        let data = Array2::from_shape_fn(Dim2::new([3, 4]), |i| {
+            u64::try_from(i[0] * 4 + i[1]).unwrap()
+        });
+        let column: ArrayView2<'_, u64> = data.slice(s![.., 1..2]);
+
+        let expr = &column * 100 + 1;
+        assert!(!expr.is_contiguous());
+
+        let mut out = Array2::zeros(Dim2::new([3, 1]));
+        expr.apply(&mut out);
+        assert_eq!(out[[2, 0]], 901);
+    }
+
+    #[test]
+    fn test_eval() {
+        let a = Array2::from_shape_fn(Dim2::new([2, 3]), |i| i[0] * 3 + i[1]);
+        let b = Array1::from_vec(Dim1::new([3]), vec![10, 20, 30]).unwrap();
+
+        let c = (&a + &b).eval();
+        assert_eq!(c.shape().as_slice(), &[2, 3]);
+        assert_eq!(c[[1, 2]], 35);
+
+        let d: Array2<usize> = (&c * &a).into();
+        assert_eq!(d[[0, 1]], 21);
+    }
+
+    #[test]
+    fn test_eval_reuses_owned_buffer() {
+        let a = Array1::from_slice(Dim1::new([4]), &[1.0_f64, 2.0, 3.0, 4.0])
+            .unwrap();
+        let b =
+            Array1::from_vec(Dim1::new([4]), vec![1.0, 1.0, 1.0, 1.0]).unwrap();
+        let ptr = a.storage.ptr.0;
+
+        // The owned operand is nested inside the expression
+        let c = ((a * 2.0) + &b).eval();
+        assert_eq!(c.storage.ptr.0, ptr);
+        assert_eq!(c[3], 9.0);
+
+        // The right hand side can be reused too
+        let d = (&b - c).eval();
+        assert_eq!(d.storage.ptr.0, ptr);
+        assert_eq!(d[0], -2.0);
+    }
+
+    #[test]
+    fn test_eval_does_not_reuse_incompatible_buffers() {
+        // The operand is broadcast, so it is smaller than the result
+        let column = Array2::from_vec(Dim2::new([2, 1]), vec![1, 2]).unwrap();
+        let row = Array2::from_vec(Dim2::new([1, 3]), vec![0, 10, 20]).unwrap();
+        let ptr = column.storage.ptr.0;
+
+        let out = (column + &row).eval();
+        assert_ne!(out.storage.ptr.0, ptr);
+        assert_eq!(out[[1, 2]], 22);
+
+        // Borrowed operands must never be reused
+        let a = Array1::from_vec(Dim1::new([2]), vec![1, 2]).unwrap();
+        let mut b = Array1::from_vec(Dim1::new([2]), vec![3, 4]).unwrap();
+        let out = (&a + &mut b).eval();
+        assert_ne!(out.storage.ptr.0, a.storage.ptr.0);
+        assert_ne!(out.storage.ptr.0, b.storage.ptr.0);
+        assert_eq!(b[1], 4);
+        assert_eq!(out[1], 6);
+    }
+
+    #[test]
+    fn test_eval_into() {
+        let a = Array1::from_vec(Dim1::new([3]), vec![1, 2, 3]).unwrap();
+        let mut out = Array1::zeros(Dim1::new([3]));
+        (&a * &a).eval_into(&mut out);
+        assert_eq!(out[2], 9);
+
+        // Write into a strided, mutable view of a larger array
+        let mut data = Array2::zeros(Dim2::new([3, 2]));
+        let mut column: ArrayViewMut2<'_, i32> = data.slice_mut(s![..;-1, 1..]);
+        let b = Array2::from_vec(Dim2::new([3, 1]), vec![10, 20, 30]).unwrap();
+        (&b + 1_i32).eval_into(&mut column);
+
+        assert_eq!(data[[0, 1]], 31);
+        assert_eq!(data[[2, 1]], 11);
+        assert_eq!(data[[1, 0]], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "output shape does not match")]
+    fn test_eval_into_wrong_shape() {
+        let a = Array1::<f32>::ones(Dim1::new([3]));
+        let mut out = Array1::<f32>::zeros(Dim1::new([4]));
+        (&a + &a).eval_into(&mut out);
     }
 }
@@ -0,0 +1,338 @@
+//! A cache-blocked, parallel general matrix multiplication (GEMM) kernel for
+//! the host.
+//!
+//! The structure follows the GotoBLAS/BLIS design. The right-hand operand is
+//! split into blocks of `KC` rows and `NC` columns, and the left-hand operand
+//! into blocks of `MC` rows and `KC` columns. Each block is packed into a
+//! contiguous, [`MEM_ALIGN`]-aligned buffer of narrow panels, in the order
+//! they are read by the micro-kernel, which computes an `MR` by `NR` tile of
+//! the result in registers.
+//!
+//! Since operands are only read while packing, they may have any strides,
+//! including transposed and negative strides, without being copied first.
+//!
+//! Blocks of rows of the result are computed in parallel. Every element of the
+//! result is accumulated in the same order however the work is split between
+//! threads, so results are reproducible.
+//!
+//! [`MEM_ALIGN`]: crate::backend::host::host_storage::MEM_ALIGN
+
+use std::ops::Mul;
+
+use num_traits::Zero;
+use rayon::prelude::*;
+
+use crate::backend::host::host_storage::HostStorage;
+
+/// The number of rows of the result computed by each call of the micro-kernel
+const MR: usize = 4;
+
+/// The number of columns of the result computed by each call of the
+/// micro-kernel
+const NR: usize = 8;
+
+/// The number of rows in each packed block of the left-hand operand, and in
+/// each block of the result computed by a parallel task
+const MC: usize = 64;
+
+/// The number of columns of the left-hand operand (and rows of the right-hand
+/// operand) in each packed block
+const KC: usize = 256;
+
+/// The number of columns in each packed block of the right-hand operand
+const NC: usize = 1024;
+
+/// A scalar type which can be multiplied by [`host_gemm`]
+pub trait GemmScalar: Copy + Send + Sync + Zero + Mul<Output = Self> {}
+
+impl<T> GemmScalar for T where T: Copy + Send + Sync + Zero + Mul<Output = T> {}
+
+/// Allocate an aligned buffer of `len` zeros
+fn aligned_zeros<T: GemmScalar>(len: usize) -> HostStorage<T> {
+    // Safety: Every element is written before the storage is returned
+    unsafe {
+        let storage = HostStorage::<T>::new_uninit(len);
+        let data = storage.ptr.0.as_ptr();
+        for i in 0..len {
+            data.add(i).write(T::zero());
+        }
+        storage
+    }
+}
+
+/// Pack the rows `rows` and columns `cols` of the left-hand operand into
+/// panels of [`MR`] rows. Within each panel, the `MR` elements of each column
+/// are consecutive. Rows past the end of the block are padded with zeros.
+fn pack_lhs<T, A>(
+    packed: &mut [T],
+    a: &A,
+    rows: std::ops::Range<usize>,
+    cols: std::ops::Range<usize>,
+) where
+    T: GemmScalar,
+    A: Fn(usize, usize) -> T,
+{
+    let kc = cols.len();
+    for (panel, chunk) in packed.chunks_exact_mut(kc * MR).enumerate() {
+        let first_row = rows.start + panel * MR;
+        for (p, column) in chunk.chunks_exact_mut(MR).enumerate() {
+            for (i, value) in column.iter_mut().enumerate() {
+                let row = first_row + i;
+                *value = if row < rows.end {
+                    a(row, cols.start + p)
+                } else {
+                    T::zero()
+                };
+            }
+        }
+    }
+}
+
+/// Pack the rows `rows` and columns `cols` of the right-hand operand into
+/// panels of [`NR`] columns. Within each panel, the `NR` elements of each row
+/// are consecutive. Columns past the end of the block are padded with zeros.
+fn pack_rhs<T, B>(
+    packed: &mut [T],
+    b: &B,
+    rows: std::ops::Range<usize>,
+    cols: std::ops::Range<usize>,
+) where
+    T: GemmScalar,
+    B: Fn(usize, usize) -> T + Sync,
+{
+    let kc = rows.len();
+    packed.par_chunks_exact_mut(kc * NR).enumerate().for_each(
+        |(panel, chunk)| {
+            let first_col = cols.start + panel * NR;
+            for (p, row) in chunk.chunks_exact_mut(NR).enumerate() {
+                for (j, value) in row.iter_mut().enumerate() {
+                    let col = first_col + j;
+                    *value = if col < cols.end {
+                        b(rows.start + p, col)
+                    } else {
+                        T::zero()
+                    };
+                }
+            }
+        },
+    );
+}
+
+/// Multiply a packed panel of [`MR`] rows of the left-hand operand by a packed
+/// panel of [`NR`] columns of the right-hand operand
+#[inline(always)]
+fn micro_kernel<T: GemmScalar>(a: &[T], b: &[T]) -> [[T; NR]; MR] {
+    let mut acc = [[T::zero(); NR]; MR];
+    for (a, b) in a.chunks_exact(MR).zip(b.chunks_exact(NR)) {
+        for (row, &a) in acc.iter_mut().zip(a) {
+            for (acc, &b) in row.iter_mut().zip(b) {
+                *acc = *acc + a * b;
+            }
+        }
+    }
+    acc
+}
+
+/// Compute the product of an `m` by `k` matrix and a `k` by `n` matrix,
+/// returning it in new, aligned storage in row-major order.
+///
+/// The operands are given as functions returning the element at a row and
+/// column, so they may be stored with any layout. Each element is read a
+/// small, bounded number of times, when the block containing it is packed.
+#[allow(clippy::many_single_char_names)]
+pub fn host_gemm<T, A, B>(
+    m: usize,
+    k: usize,
+    n: usize,
+    a: A,
+    b: B,
+) -> HostStorage<T>
+where
+    T: GemmScalar,
+    A: Fn(usize, usize) -> T + Sync,
+    B: Fn(usize, usize) -> T + Sync,
+{
+    host_batched_gemm(1, m, k, n, |_, i, p| a(i, p), |_, p, j| b(p, j))
+}
+
+/// Compute `batch` independent products of `m` by `k` matrices and `k` by
+/// `n` matrices, returning them one after another in new, aligned storage,
+/// each in row-major order.
+///
+/// The operands are given as functions returning the element at a batch
+/// index, row and column, as in [`host_gemm`]. The products are computed in
+/// parallel, and each product is also parallelised as in [`host_gemm`], so
+/// both large stacks of small matrices and small stacks of large matrices
+/// use every thread.
+#[allow(clippy::many_single_char_names)]
+pub fn host_batched_gemm<T, A, B>(
+    batch: usize,
+    m: usize,
+    k: usize,
+    n: usize,
+    a: A,
+    b: B,
+) -> HostStorage<T>
+where
+    T: GemmScalar,
+    A: Fn(usize, usize, usize) -> T + Sync,
+    B: Fn(usize, usize, usize) -> T + Sync,
+{
+    let len = batch * m * n;
+    let mut storage = aligned_zeros(len);
+    if len == 0 {
+        return storage;
+    }
+
+    storage[0..len].par_chunks_mut(m * n).enumerate().for_each(
+        |(index, out)| {
+            gemm_into(
+                m,
+                k,
+                n,
+                &|i, p| a(index, i, p),
+                &|p, j| b(index, p, j),
+                out,
+            );
+        },
+    );
+    storage
+}
+
+/// Compute the product of an `m` by `k` matrix and a `k` by `n` matrix,
+/// adding it to `out`, which is in row-major order
+#[allow(clippy::many_single_char_names)]
+fn gemm_into<T, A, B>(m: usize, k: usize, n: usize, a: &A, b: &B, out: &mut [T])
+where
+    T: GemmScalar,
+    A: Fn(usize, usize) -> T + Sync,
+    B: Fn(usize, usize) -> T + Sync,
+{
+    if m == 0 || n == 0 || k == 0 {
+        return;
+    }
+
+    // Matrix-vector products have no reuse to exploit, so packing into panels
+    // would only waste work
+    if n == 1 {
+        out.par_iter_mut().enumerate().for_each(|(i, value)| {
+            *value = (0..k).fold(T::zero(), |acc, p| acc + a(i, p) * b(p, 0));
+        });
+        return;
+    }
+    if m == 1 {
+        out.par_iter_mut().enumerate().for_each(|(j, value)| {
+            *value = (0..k).fold(T::zero(), |acc, p| acc + a(0, p) * b(p, j));
+        });
+        return;
+    }
+
+    let mut packed_b =
+        aligned_zeros(KC.min(k) * NC.min(n.next_multiple_of(NR)));
+
+    for jc in (0..n).step_by(NC) {
+        let nc = NC.min(n - jc);
+        for pc in (0..k).step_by(KC) {
+            let kc = KC.min(k - pc);
+
+            let packed_b = &mut packed_b[0..kc * nc.next_multiple_of(NR)];
+            pack_rhs(packed_b, b, pc..pc + kc, jc..jc + nc);
+            let packed_b = &*packed_b;
+
+            out.par_chunks_mut(MC * n).enumerate().for_each_init(
+                || aligned_zeros(MC * KC),
+                |packed_a, (block, rows)| {
+                    let ic = block * MC;
+                    let mc = rows.len() / n;
+
+                    let packed_a =
+                        &mut packed_a[0..mc.next_multiple_of(MR) * kc];
+                    pack_lhs(packed_a, a, ic..ic + mc, pc..pc + kc);
+
+                    for jr in (0..nc).step_by(NR) {
+                        let b_panel = &packed_b[jr * kc..(jr + NR) * kc];
+                        for ir in (0..mc).step_by(MR) {
+                            let a_panel = &packed_a[ir * kc..(ir + MR) * kc];
+                            let tile = micro_kernel(a_panel, b_panel);
+
+                            for (i, tile_row) in
+                                tile.iter().enumerate().take(mc - ir)
+                            {
+                                let start = (ir + i) * n + jc + jr;
+                                let row =
+                                    &mut rows[start..start + NR.min(nc - jr)];
+                                for (value, &product) in
+                                    row.iter_mut().zip(tile_row)
+                                {
+                                    *value = *value + product;
+                                }
+                            }
+                        }
+                    }
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[allow(clippy::many_single_char_names)]
+    fn naive(m: usize, k: usize, n: usize, a: &[i64], b: &[i64]) -> Vec<i64> {
+        let mut out = vec![0; m * n];
+        for i in 0..m {
+            for j in 0..n {
+                out[i * n + j] =
+                    (0..k).map(|p| a[i * k + p] * b[p * n + j]).sum();
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn test_host_gemm() {
+        // Sizes which are not multiples of any block size, and which span
+        // several blocks in every direction
+        for &(m, k, n) in
+            &[(1, 1, 1), (3, 5, 2), (70, 300, 9), (130, 257, 1030), (5, 0, 4)]
+        {
+            let a: Vec<i64> =
+                (0..m * k).map(|i| i64::try_from(i % 7).unwrap() - 3).collect();
+            let b: Vec<i64> =
+                (0..k * n).map(|i| i64::try_from(i % 5).unwrap() - 2).collect();
+
+            let out =
+                host_gemm(m, k, n, |i, p| a[i * k + p], |p, j| b[p * n + j]);
+            assert_eq!(out[0..m * n], naive(m, k, n, &a, &b), "{m}x{k}x{n}");
+        }
+    }
+
+    #[test]
+    #[allow(clippy::many_single_char_names)]
+    fn test_host_batched_gemm() {
+        let (batch, m, k, n) = (5, 9, 12, 10);
+        let a: Vec<i64> = (0..batch * m * k)
+            .map(|i| i64::try_from(i % 7).unwrap() - 3)
+            .collect();
+        let b: Vec<i64> =
+            (0..k * n).map(|i| i64::try_from(i % 5).unwrap() - 2).collect();
+
+        // The right-hand operand is shared by every product
+        let out = host_batched_gemm(
+            batch,
+            m,
+            k,
+            n,
+            |index, i, p| a[(index * m + i) * k + p],
+            |_, p, j| b[p * n + j],
+        );
+
+        for index in 0..batch {
+            let lhs = &a[index * m * k..(index + 1) * m * k];
+            let start = index * m * n;
+            assert_eq!(out[start..start + m * n], naive(m, k, n, lhs, &b));
+        }
+    }
+}
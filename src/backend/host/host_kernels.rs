@@ -1,7 +1,52 @@
-use crate::backend::op_traits;
+//! Element-wise kernels which compute each operation on the host.
 
-pub trait HostBinaryOp<T>: op_traits::BinaryOp {
-    fn apply_scalar(lhs: T, rhs: T) -> T;
+use std::cmp::Ordering;
+
+use num_traits::{Float, Signed, Zero};
+
+use crate::{
+    array::traits::GetWriteableBuffer,
+    backend::{host::host_storage::HostNonNull, op_traits},
+};
+
+/// A host kernel for an operation with two operands of type `T`
+pub trait HostBinaryOp<T: Copy>: op_traits::BinaryOp {
+    /// Apply the operation to a single pair of elements
+    fn apply_scalar(lhs: T, rhs: T) -> Self::Output<T>;
+}
+
+/// A host kernel for an operation with one operand of type `T`
+pub trait HostUnaryOp<T>: op_traits::UnaryOp {
+    /// Apply the operation to a single element
+    fn apply_scalar(value: T) -> T;
+}
+
+/// A host kernel for an operation with any number of operands. `Scalars` is a
+/// tuple containing the scalar type of each operand.
+pub trait HostFunctionOp<Scalars>: op_traits::FunctionOp<Scalars> {
+    /// Apply the operation to a single element of each operand
+    fn apply_scalar(args: Scalars) -> Self::Output;
+}
+
+/// Implement [`op_traits::BinaryOp`] for a kernel whose result has the same
+/// scalar type as its operands, allowing an operand's buffer to be reused.
+macro_rules! preserving_binary_op {
+    ($kernel: ty) => {
+        impl op_traits::BinaryOp for $kernel {
+            type Output<T: Copy> = T;
+        }
+
+        impl<Buffer> op_traits::ReuseBuffer<Buffer> for $kernel {
+            type Output = Buffer;
+
+            #[inline(always)]
+            unsafe fn reuse_buffer(
+                take: impl FnOnce() -> Option<Buffer>,
+            ) -> Option<Buffer> {
+                take()
+            }
+        }
+    };
 }
 
 /// Generate a host kernel for a trivial binary operation, such as addition,
@@ -9,9 +54,14 @@ pub trait HostBinaryOp<T>: op_traits::BinaryOp {
 macro_rules! host_binary_kernel {
     ($operation_name: ident, $name: ident, $operation: tt) => {
         paste::paste! {
+            #[doc = concat!(
+                "The host kernel for the `",
+                stringify!($operation_name),
+                "` operation"
+            )]
             pub struct [< Host $operation_name Kernel >];
 
-            impl op_traits::BinaryOp for [< Host $operation_name Kernel >] {}
+            preserving_binary_op!([< Host $operation_name Kernel >]);
 
             impl<T> HostBinaryOp<T> for [< Host $operation_name Kernel >]
             where
@@ -27,3 +77,317 @@ macro_rules! host_binary_kernel {
 }
 
 crate::repeat_binary_ops!(host_binary_kernel);
+
+/// Generate a host kernel for a named binary function, such as `pow`. The
+/// kernel is implemented for every type satisfying `$bound`, and `$lhs` and
+/// `$rhs` are bound to the inputs when evaluating `$body`.
+macro_rules! host_function_kernel {
+    (
+        $operation_name: ident, $bound: path,
+        |$lhs: ident, $rhs: ident| $body: expr
+    ) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host kernel for the `",
+                stringify!($operation_name),
+                "` operation"
+            )]
+            pub struct [< Host $operation_name Kernel >];
+
+            preserving_binary_op!([< Host $operation_name Kernel >]);
+
+            impl<T> HostBinaryOp<T> for [< Host $operation_name Kernel >]
+            where
+                T: Copy + $bound,
+            {
+                #[inline(always)]
+                fn apply_scalar($lhs: T, $rhs: T) -> T {
+                    $body
+                }
+            }
+        }
+    };
+}
+
+host_function_kernel!(Pow, Float, |lhs, rhs| lhs.powf(rhs));
+host_function_kernel!(Atan2, Float, |lhs, rhs| lhs.atan2(rhs));
+host_function_kernel!(Hypot, Float, |lhs, rhs| lhs.hypot(rhs));
+host_function_kernel!(Copysign, Float, |lhs, rhs| lhs.copysign(rhs));
+
+// The exponent is stored with the same type as the base. `powi` only builds
+// integer exponents, but they may not fit in an `i32` once converted (such as
+// `i32::MAX` as an `f32`), and the kernel can be applied to any operands, so
+// anything other than an integer in range is raised with `powf` instead
+host_function_kernel!(Powi, Float, |lhs, rhs| {
+    match rhs.to_i32() {
+        Some(n) if T::from(n) == Some(rhs) => lhs.powi(n),
+        _ => lhs.powf(rhs),
+    }
+});
+
+// NaN is propagated from either operand, matching NumPy's behaviour
+host_function_kernel!(Minimum, PartialOrd, |lhs, rhs| {
+    match lhs.partial_cmp(&rhs) {
+        Some(Ordering::Greater) => rhs,
+        Some(_) => lhs,
+        None if lhs.partial_cmp(&lhs).is_none() => lhs,
+        None => rhs,
+    }
+});
+host_function_kernel!(Maximum, PartialOrd, |lhs, rhs| {
+    match lhs.partial_cmp(&rhs) {
+        Some(Ordering::Less) => rhs,
+        Some(_) => lhs,
+        None if lhs.partial_cmp(&lhs).is_none() => lhs,
+        None => rhs,
+    }
+});
+
+/// Generate a host kernel for a logical operation, which is only implemented
+/// for `bool`.
+macro_rules! host_logical_kernel {
+    ($operation_name: ident, |$lhs: ident, $rhs: ident| $body: expr) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host kernel for the `",
+                stringify!($operation_name),
+                "` operation"
+            )]
+            pub struct [< Host $operation_name Kernel >];
+
+            preserving_binary_op!([< Host $operation_name Kernel >]);
+
+            impl HostBinaryOp<bool> for [< Host $operation_name Kernel >] {
+                #[inline(always)]
+                fn apply_scalar($lhs: bool, $rhs: bool) -> bool {
+                    $body
+                }
+            }
+        }
+    };
+}
+
+host_logical_kernel!(LogicalAnd, |lhs, rhs| lhs && rhs);
+host_logical_kernel!(LogicalOr, |lhs, rhs| lhs || rhs);
+host_logical_kernel!(LogicalXor, |lhs, rhs| lhs != rhs);
+
+/// Generate a host kernel for an element-wise comparison. The result is
+/// always a `bool`, so the buffers of the operands are never reused.
+macro_rules! host_comparison_kernel {
+    ($operation_name: ident, $bound: path, |$lhs: ident, $rhs: ident| $body: expr) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host kernel for the `",
+                stringify!($operation_name),
+                "` operation"
+            )]
+            pub struct [< Host $operation_name Kernel >];
+
+            impl op_traits::BinaryOp for [< Host $operation_name Kernel >] {
+                type Output<T: Copy> = bool;
+            }
+
+            impl<T> op_traits::ReuseBuffer<HostNonNull<T>>
+                for [< Host $operation_name Kernel >]
+            {
+                type Output = HostNonNull<bool>;
+
+                #[inline(always)]
+                unsafe fn reuse_buffer(
+                    _take: impl FnOnce() -> Option<HostNonNull<T>>,
+                ) -> Option<HostNonNull<bool>> {
+                    None
+                }
+            }
+
+            impl<T> HostBinaryOp<T> for [< Host $operation_name Kernel >]
+            where
+                T: Copy + $bound,
+            {
+                #[inline(always)]
+                fn apply_scalar($lhs: T, $rhs: T) -> bool {
+                    $body
+                }
+            }
+        }
+    };
+}
+
+host_comparison_kernel!(Eq, PartialEq, |lhs, rhs| lhs == rhs);
+host_comparison_kernel!(Ne, PartialEq, |lhs, rhs| lhs != rhs);
+host_comparison_kernel!(Lt, PartialOrd, |lhs, rhs| lhs < rhs);
+host_comparison_kernel!(Le, PartialOrd, |lhs, rhs| lhs <= rhs);
+host_comparison_kernel!(Gt, PartialOrd, |lhs, rhs| lhs > rhs);
+host_comparison_kernel!(Ge, PartialOrd, |lhs, rhs| lhs >= rhs);
+
+/// Generate a host kernel for an element-wise unary operation. The kernel is
+/// implemented for every type satisfying `$bound`, and `$value` is bound to the
+/// input when evaluating `$body`.
+macro_rules! host_unary_kernel {
+    ($operation_name: ident, $bound: path, |$value: ident| $body: expr) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host kernel for the `",
+                stringify!($operation_name),
+                "` operation"
+            )]
+            pub struct [< Host $operation_name Kernel >];
+
+            impl op_traits::UnaryOp for [< Host $operation_name Kernel >] {}
+
+            impl<T> HostUnaryOp<T> for [< Host $operation_name Kernel >]
+            where
+                T: Copy + $bound,
+            {
+                #[inline(always)]
+                fn apply_scalar($value: T) -> T {
+                    $body
+                }
+            }
+        }
+    };
+}
+
+host_unary_kernel!(Neg, std::ops::Neg<Output = T>, |value| -value);
+host_unary_kernel!(Not, std::ops::Not<Output = T>, |value| !value);
+host_unary_kernel!(Abs, Signed, |value| value.abs());
+host_unary_kernel!(Signum, Signed, |value| value.signum());
+
+host_unary_kernel!(Sqrt, Float, |value| value.sqrt());
+host_unary_kernel!(Exp, Float, |value| value.exp());
+host_unary_kernel!(Ln, Float, |value| value.ln());
+host_unary_kernel!(Log2, Float, |value| value.log2());
+host_unary_kernel!(Log10, Float, |value| value.log10());
+host_unary_kernel!(Sin, Float, |value| value.sin());
+host_unary_kernel!(Cos, Float, |value| value.cos());
+host_unary_kernel!(Tan, Float, |value| value.tan());
+host_unary_kernel!(Asin, Float, |value| value.asin());
+host_unary_kernel!(Acos, Float, |value| value.acos());
+host_unary_kernel!(Atan, Float, |value| value.atan());
+host_unary_kernel!(Tanh, Float, |value| value.tanh());
+host_unary_kernel!(Sigmoid, Float, |value| T::one()
+    / (T::one() + (-value).exp()));
+host_unary_kernel!(Floor, Float, |value| value.floor());
+host_unary_kernel!(Ceil, Float, |value| value.ceil());
+host_unary_kernel!(Round, Float, |value| value.round());
+host_unary_kernel!(Recip, Float, |value| value.recip());
+
+/// Logical negation, which is only implemented for `bool`. Unlike [`Not`],
+/// this is not available for integers, where `!` is a bitwise operation.
+///
+/// [`Not`]: HostNotKernel
+pub struct HostLogicalNotKernel;
+
+impl op_traits::UnaryOp for HostLogicalNotKernel {}
+
+impl HostUnaryOp<bool> for HostLogicalNotKernel {
+    #[inline(always)]
+    fn apply_scalar(value: bool) -> bool {
+        !value
+    }
+}
+
+/// Rectified linear unit, replacing negative values with zero. NaN is
+/// propagated.
+pub struct HostReluKernel;
+
+impl op_traits::UnaryOp for HostReluKernel {}
+
+impl<T> HostUnaryOp<T> for HostReluKernel
+where
+    T: Copy + Zero + PartialOrd,
+{
+    #[inline(always)]
+    fn apply_scalar(value: T) -> T {
+        if value < T::zero() {
+            T::zero()
+        } else {
+            value
+        }
+    }
+}
+
+/// Select elements from the second operand where the first (a `bool` mask) is
+/// `true`, and from the third operand otherwise.
+pub struct HostSelectKernel;
+
+impl op_traits::TernaryOp for HostSelectKernel {}
+
+impl<T: Copy> op_traits::FunctionOp<(bool, T, T)> for HostSelectKernel {
+    type Output = T;
+}
+
+impl<T: Copy> HostFunctionOp<(bool, T, T)> for HostSelectKernel {
+    #[inline(always)]
+    fn apply_scalar((mask, on_true, on_false): (bool, T, T)) -> T {
+        if mask {
+            on_true
+        } else {
+            on_false
+        }
+    }
+}
+
+impl<Mask, OnTrue, OnFalse>
+    op_traits::ReuseOperandBuffer<(Mask, OnTrue, OnFalse)> for HostSelectKernel
+where
+    OnTrue: GetWriteableBuffer,
+    OnFalse: GetWriteableBuffer<Buffer = OnTrue::Buffer>,
+{
+    type Buffer = OnTrue::Buffer;
+
+    unsafe fn reuse_buffer(
+        (_, on_true, on_false): &mut (Mask, OnTrue, OnFalse),
+        len: usize,
+    ) -> Option<Self::Buffer> {
+        // The mask has a different scalar type, so it is never reused
+        on_true
+            .get_buffer_and_set_no_free(len)
+            .or_else(|| on_false.get_buffer_and_set_no_free(len))
+    }
+}
+
+/// Clamp the first operand to the range given by the second (lower bound) and
+/// third (upper bound) operands. NaN is propagated.
+pub struct HostClipKernel;
+
+impl op_traits::TernaryOp for HostClipKernel {}
+
+impl<T: Copy> op_traits::FunctionOp<(T, T, T)> for HostClipKernel {
+    type Output = T;
+}
+
+impl<T> HostFunctionOp<(T, T, T)> for HostClipKernel
+where
+    T: Copy + PartialOrd,
+{
+    #[inline(always)]
+    fn apply_scalar((value, lower, upper): (T, T, T)) -> T {
+        if value < lower {
+            lower
+        } else if value > upper {
+            upper
+        } else {
+            value
+        }
+    }
+}
+
+impl<Arg, Lower, Upper> op_traits::ReuseOperandBuffer<(Arg, Lower, Upper)>
+    for HostClipKernel
+where
+    Arg: GetWriteableBuffer,
+    Lower: GetWriteableBuffer<Buffer = Arg::Buffer>,
+    Upper: GetWriteableBuffer<Buffer = Arg::Buffer>,
+{
+    type Buffer = Arg::Buffer;
+
+    unsafe fn reuse_buffer(
+        (arg, lower, upper): &mut (Arg, Lower, Upper),
+        len: usize,
+    ) -> Option<Self::Buffer> {
+        arg.get_buffer_and_set_no_free(len)
+            .or_else(|| lower.get_buffer_and_set_no_free(len))
+            .or_else(|| upper.get_buffer_and_set_no_free(len))
+    }
+}
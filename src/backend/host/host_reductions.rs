@@ -0,0 +1,743 @@
+//! Kernels for reducing many elements to a single value on the host, such as
+//! summing an array or finding its maximum.
+
+use std::cmp::Ordering;
+
+use num_traits::{Float, NumCast, One};
+
+use crate::backend::{
+    host::host_kernels::{HostBinaryOp, HostMaximumKernel, HostMinimumKernel},
+    op_traits,
+};
+
+/// A reduction over elements of type `T`.
+///
+/// Elements are folded into an accumulator in order, along with their index.
+/// Large reductions are split into consecutive blocks which are folded
+/// independently (possibly in parallel) and then combined from left to right,
+/// so `combine` always receives the accumulator of the earlier elements as
+/// `lhs`.
+pub trait HostReduction<T> {
+    /// The intermediate state of the reduction
+    type Acc: Copy + Send;
+
+    /// The result of the reduction
+    type Output: Copy + Send;
+
+    /// The state of the reduction before any elements have been seen
+    fn init() -> Self::Acc;
+
+    /// Add the element `value`, found at `index`, to the reduction
+    fn fold(acc: Self::Acc, index: usize, value: T) -> Self::Acc;
+
+    /// Combine the states of two consecutive blocks of elements
+    fn combine(lhs: Self::Acc, rhs: Self::Acc) -> Self::Acc;
+
+    /// Compute the result of a reduction over `count` elements. Returns
+    /// `None` if the reduction has no value, such as the maximum of an empty
+    /// array.
+    fn finish(acc: Self::Acc, count: usize) -> Option<Self::Output>;
+}
+
+/// Returns `true` if `value` is not equal to itself, meaning it is NaN
+#[inline(always)]
+fn is_nan<T: Copy + PartialOrd>(value: T) -> bool {
+    value.partial_cmp(&value).is_none()
+}
+
+/// Define a reduction whose accumulator is a single value of type `T`, and
+/// which always has a result (the identity for an empty reduction).
+macro_rules! host_fold_reduction {
+    (
+        $name: ident, [$($bound: tt)*], $init: expr,
+        |$lhs: ident, $rhs: ident| $combine: expr
+    ) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host reduction for `",
+                stringify!($name),
+                "`"
+            )]
+            pub struct [< Host $name Reduction >];
+
+            impl<T> HostReduction<T> for [< Host $name Reduction >]
+            where
+                T: Copy + Send + $($bound)*,
+            {
+                type Acc = T;
+                type Output = T;
+
+                #[inline(always)]
+                fn init() -> T {
+                    $init
+                }
+
+                #[inline(always)]
+                fn fold(acc: T, _index: usize, value: T) -> T {
+                    Self::combine(acc, value)
+                }
+
+                #[inline(always)]
+                fn combine($lhs: T, $rhs: T) -> T {
+                    $combine
+                }
+
+                #[inline(always)]
+                fn finish(acc: T, _count: usize) -> Option<T> {
+                    Some(acc)
+                }
+            }
+        }
+    };
+}
+
+host_fold_reduction!(Prod, [Multipliable], T::one(), |lhs, rhs| {
+    T::prod_mul(lhs, rhs)
+});
+
+/// A type whose product can be computed by [`HostProdReduction`].
+///
+/// Integer products wrap on overflow, as in `NumPy`, rather than panicking in
+/// debug builds.
+pub trait Multipliable: Copy + Send + One {
+    /// Multiply two partial products
+    fn prod_mul(lhs: Self, rhs: Self) -> Self;
+}
+
+macro_rules! integer_multipliable {
+    ($t: ty) => {
+        impl Multipliable for $t {
+            #[inline(always)]
+            fn prod_mul(lhs: $t, rhs: $t) -> $t {
+                lhs.wrapping_mul(rhs)
+            }
+        }
+    };
+}
+
+crate::repeat_integer_types!(integer_multipliable);
+integer_multipliable!(i8);
+integer_multipliable!(isize);
+integer_multipliable!(u8);
+integer_multipliable!(usize);
+
+macro_rules! float_multipliable {
+    ($t: ty) => {
+        impl Multipliable for $t {
+            #[inline(always)]
+            fn prod_mul(lhs: $t, rhs: $t) -> $t {
+                lhs * rhs
+            }
+        }
+    };
+}
+
+float_multipliable!(f32);
+float_multipliable!(f64);
+
+/// A type which can be summed by [`HostSumReduction`].
+///
+/// Integer sums wrap on overflow, as in `NumPy`. Floating-point types use
+/// compensated (Kahan-Babuska-Neumaier) summation, so the rounding error of a
+/// sum does not grow with the number of elements.
+pub trait Summable: Copy + Send {
+    /// The running state of a sum
+    type SumAcc: Copy + Send;
+
+    /// The state of an empty sum
+    fn sum_init() -> Self::SumAcc;
+
+    /// Add `value` to the sum
+    fn sum_add(acc: Self::SumAcc, value: Self) -> Self::SumAcc;
+
+    /// Combine the sums of two blocks of elements
+    fn sum_combine(lhs: Self::SumAcc, rhs: Self::SumAcc) -> Self::SumAcc;
+
+    /// The value of the sum
+    fn sum_total(acc: Self::SumAcc) -> Self;
+}
+
+macro_rules! integer_summable {
+    ($t: ty) => {
+        impl Summable for $t {
+            type SumAcc = $t;
+
+            #[inline(always)]
+            fn sum_init() -> $t {
+                0
+            }
+
+            #[inline(always)]
+            fn sum_add(acc: $t, value: $t) -> $t {
+                acc.wrapping_add(value)
+            }
+
+            #[inline(always)]
+            fn sum_combine(lhs: $t, rhs: $t) -> $t {
+                lhs.wrapping_add(rhs)
+            }
+
+            #[inline(always)]
+            fn sum_total(acc: $t) -> $t {
+                acc
+            }
+        }
+    };
+}
+
+// Reductions do not require a `TensrType`, so they are also available for the
+// integer types which cannot be used as scalar operands
+crate::repeat_integer_types!(integer_summable);
+integer_summable!(i8);
+integer_summable!(isize);
+integer_summable!(u8);
+integer_summable!(usize);
+
+macro_rules! float_summable {
+    ($t: ty) => {
+        impl Summable for $t {
+            /// The running sum and the low-order bits lost when computing it
+            type SumAcc = ($t, $t);
+
+            #[inline(always)]
+            fn sum_init() -> ($t, $t) {
+                (0.0, 0.0)
+            }
+
+            #[inline(always)]
+            fn sum_add((sum, compensation): ($t, $t), value: $t) -> ($t, $t) {
+                let total = sum + value;
+                let lost = if sum.abs() >= value.abs() {
+                    (sum - total) + value
+                } else {
+                    (value - total) + sum
+                };
+                (total, compensation + lost)
+            }
+
+            #[inline(always)]
+            fn sum_combine(lhs: ($t, $t), rhs: ($t, $t)) -> ($t, $t) {
+                let (sum, compensation) = Self::sum_add(lhs, rhs.0);
+                (sum, compensation + rhs.1)
+            }
+
+            #[inline(always)]
+            fn sum_total((sum, compensation): ($t, $t)) -> $t {
+                // Once the sum overflows (or becomes NaN) the compensation is
+                // meaningless, and may itself be NaN
+                if sum.is_finite() {
+                    sum + compensation
+                } else {
+                    sum
+                }
+            }
+        }
+    };
+}
+
+float_summable!(f32);
+float_summable!(f64);
+
+/// The sum of the elements. The sum of an empty reduction is zero.
+pub struct HostSumReduction;
+
+impl<T: Summable> HostReduction<T> for HostSumReduction {
+    type Acc = T::SumAcc;
+    type Output = T;
+
+    #[inline(always)]
+    fn init() -> T::SumAcc {
+        T::sum_init()
+    }
+
+    #[inline(always)]
+    fn fold(acc: T::SumAcc, _index: usize, value: T) -> T::SumAcc {
+        T::sum_add(acc, value)
+    }
+
+    #[inline(always)]
+    fn combine(lhs: T::SumAcc, rhs: T::SumAcc) -> T::SumAcc {
+        T::sum_combine(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn finish(acc: T::SumAcc, _count: usize) -> Option<T> {
+        Some(T::sum_total(acc))
+    }
+}
+
+/// A type whose mean can be computed by [`HostMeanReduction`].
+///
+/// Floating-point elements are averaged in their own type. Integers are
+/// converted to `f64` before they are summed, as in `NumPy`, so the sum cannot
+/// overflow and the mean is not truncated.
+pub trait Averageable: Copy + Send {
+    /// The type of the mean
+    type Mean: Float + Summable;
+
+    /// Convert an element to the type of the mean
+    fn to_mean(self) -> Self::Mean;
+}
+
+macro_rules! float_averageable {
+    ($t: ty) => {
+        impl Averageable for $t {
+            type Mean = $t;
+
+            #[inline(always)]
+            fn to_mean(self) -> $t {
+                self
+            }
+        }
+    };
+}
+
+float_averageable!(f32);
+float_averageable!(f64);
+
+macro_rules! integer_averageable {
+    ($t: ty) => {
+        impl Averageable for $t {
+            type Mean = f64;
+
+            #[inline(always)]
+            #[allow(clippy::cast_precision_loss, clippy::cast_lossless)]
+            fn to_mean(self) -> f64 {
+                self as f64
+            }
+        }
+    };
+}
+
+crate::repeat_integer_types!(integer_averageable);
+integer_averageable!(i8);
+integer_averageable!(isize);
+integer_averageable!(u8);
+integer_averageable!(usize);
+
+/// The arithmetic mean, computed in the floating-point type given by
+/// [`Averageable`]. The result of an empty reduction is `None`.
+pub struct HostMeanReduction;
+
+impl<T: Averageable> HostReduction<T> for HostMeanReduction {
+    type Acc = <T::Mean as Summable>::SumAcc;
+    type Output = T::Mean;
+
+    #[inline(always)]
+    fn init() -> Self::Acc {
+        T::Mean::sum_init()
+    }
+
+    #[inline(always)]
+    fn fold(acc: Self::Acc, _index: usize, value: T) -> Self::Acc {
+        T::Mean::sum_add(acc, value.to_mean())
+    }
+
+    #[inline(always)]
+    fn combine(lhs: Self::Acc, rhs: Self::Acc) -> Self::Acc {
+        T::Mean::sum_combine(lhs, rhs)
+    }
+
+    fn finish(acc: Self::Acc, count: usize) -> Option<T::Mean> {
+        if count == 0 {
+            return None;
+        }
+
+        Some(T::Mean::sum_total(acc) / <T::Mean as NumCast>::from(count)?)
+    }
+}
+
+/// The sum of the elements which are not NaN
+pub struct HostNanSumReduction;
+
+impl<T: Float + Summable> HostReduction<T> for HostNanSumReduction {
+    type Acc = T::SumAcc;
+    type Output = T;
+
+    #[inline(always)]
+    fn init() -> T::SumAcc {
+        T::sum_init()
+    }
+
+    #[inline(always)]
+    fn fold(acc: T::SumAcc, _index: usize, value: T) -> T::SumAcc {
+        if value.is_nan() {
+            acc
+        } else {
+            T::sum_add(acc, value)
+        }
+    }
+
+    #[inline(always)]
+    fn combine(lhs: T::SumAcc, rhs: T::SumAcc) -> T::SumAcc {
+        T::sum_combine(lhs, rhs)
+    }
+
+    #[inline(always)]
+    fn finish(acc: T::SumAcc, _count: usize) -> Option<T> {
+        Some(T::sum_total(acc))
+    }
+}
+
+/// The arithmetic mean of the elements which are not NaN. The result is NaN
+/// if there are no such elements.
+pub struct HostNanMeanReduction;
+
+impl<T: Float + Summable> HostReduction<T> for HostNanMeanReduction {
+    /// The sum and number of the elements which are not NaN
+    type Acc = (T::SumAcc, usize);
+    type Output = T;
+
+    #[inline(always)]
+    fn init() -> Self::Acc {
+        (T::sum_init(), 0)
+    }
+
+    #[inline(always)]
+    fn fold(acc: Self::Acc, _index: usize, value: T) -> Self::Acc {
+        if value.is_nan() {
+            acc
+        } else {
+            (T::sum_add(acc.0, value), acc.1 + 1)
+        }
+    }
+
+    #[inline(always)]
+    fn combine(lhs: Self::Acc, rhs: Self::Acc) -> Self::Acc {
+        (T::sum_combine(lhs.0, rhs.0), lhs.1 + rhs.1)
+    }
+
+    fn finish((sum, count): Self::Acc, _count: usize) -> Option<T> {
+        if count == 0 {
+            return Some(T::nan());
+        }
+
+        Some(T::sum_total(sum) / T::from(count)?)
+    }
+}
+
+/// Define a reduction which finds the smallest or largest element which is
+/// not NaN. The result is NaN if there are no such elements.
+macro_rules! host_nan_extremum_reduction {
+    ($name: ident, $prefer: tt) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host reduction for `",
+                stringify!($name),
+                "`"
+            )]
+            pub struct [< Host $name Reduction >];
+
+            impl<T: Float + Send> HostReduction<T> for [< Host $name Reduction >] {
+                type Acc = Option<T>;
+                type Output = T;
+
+                #[inline(always)]
+                fn init() -> Option<T> {
+                    None
+                }
+
+                #[inline(always)]
+                fn fold(acc: Option<T>, _index: usize, value: T) -> Option<T> {
+                    if value.is_nan() {
+                        acc
+                    } else {
+                        Self::combine(acc, Some(value))
+                    }
+                }
+
+                #[inline(always)]
+                fn combine(lhs: Option<T>, rhs: Option<T>) -> Option<T> {
+                    match (lhs, rhs) {
+                        (Some(lhs), Some(rhs)) => {
+                            Some(if rhs $prefer lhs { rhs } else { lhs })
+                        }
+                        (lhs, None) => lhs,
+                        (None, rhs) => rhs,
+                    }
+                }
+
+                #[inline(always)]
+                fn finish(acc: Option<T>, _count: usize) -> Option<T> {
+                    Some(acc.unwrap_or_else(T::nan))
+                }
+            }
+        }
+    };
+}
+
+host_nan_extremum_reduction!(NanMin, <);
+host_nan_extremum_reduction!(NanMax, >);
+
+/// The moments from which the variance of the elements is computed.
+///
+/// These are the number of elements, their mean and the sum of their squared
+/// deviations from the mean. They are updated using Welford's algorithm, and
+/// blocks are combined using the formula of Chan et al., which are both
+/// numerically stable.
+pub struct HostMomentsReduction;
+
+impl<T: Float + Send> HostReduction<T> for HostMomentsReduction {
+    /// The count, mean and sum of squared deviations
+    type Acc = (usize, T, T);
+    type Output = (usize, T, T);
+
+    #[inline(always)]
+    fn init() -> Self::Acc {
+        (0, T::zero(), T::zero())
+    }
+
+    #[inline(always)]
+    fn fold(
+        (count, mean, m2): Self::Acc,
+        _index: usize,
+        value: T,
+    ) -> Self::Acc {
+        let count = count + 1;
+        let delta = value - mean;
+        let mean = mean + delta / T::from(count).unwrap_or_else(T::nan);
+        (count, mean, m2 + delta * (value - mean))
+    }
+
+    #[inline(always)]
+    fn combine(lhs: Self::Acc, rhs: Self::Acc) -> Self::Acc {
+        if lhs.0 == 0 {
+            return rhs;
+        }
+        if rhs.0 == 0 {
+            return lhs;
+        }
+
+        let count = lhs.0 + rhs.0;
+        let (Some(n_lhs), Some(n_rhs), Some(n)) =
+            (T::from(lhs.0), T::from(rhs.0), T::from(count))
+        else {
+            return (count, T::nan(), T::nan());
+        };
+
+        let delta = rhs.1 - lhs.1;
+        let mean = lhs.1 + delta * (n_rhs / n);
+        let m2 = lhs.2 + rhs.2 + delta * delta * (n_lhs * n_rhs / n);
+        (count, mean, m2)
+    }
+
+    #[inline(always)]
+    fn finish(acc: Self::Acc, _count: usize) -> Option<Self::Output> {
+        Some(acc)
+    }
+}
+
+/// The logarithm of the sum of the exponentials of the elements.
+///
+/// This is computed without overflow by scaling every exponential by that of
+/// the running maximum. The result of an empty reduction is negative infinity.
+pub struct HostLogSumExpReduction;
+
+impl<T: Float + Send> HostReduction<T> for HostLogSumExpReduction {
+    /// The largest element so far, and the sum of `exp(x - max)`
+    type Acc = (T, T);
+    type Output = T;
+
+    #[inline(always)]
+    fn init() -> (T, T) {
+        (T::neg_infinity(), T::zero())
+    }
+
+    #[inline(always)]
+    fn fold(acc: (T, T), _index: usize, value: T) -> (T, T) {
+        Self::combine(acc, (value, T::one()))
+    }
+
+    #[inline(always)]
+    fn combine(lhs: (T, T), rhs: (T, T)) -> (T, T) {
+        if lhs.0.is_nan() || rhs.0.is_nan() {
+            return (T::nan(), T::nan());
+        }
+
+        let max = lhs.0.max(rhs.0);
+        if max.is_infinite() {
+            // Either every element so far is negative infinity, or one is
+            // positive infinity, and `x - max` would be NaN
+            return (max, lhs.1 + rhs.1);
+        }
+
+        (max, lhs.1 * (lhs.0 - max).exp() + rhs.1 * (rhs.0 - max).exp())
+    }
+
+    #[inline(always)]
+    fn finish((max, sum): (T, T), _count: usize) -> Option<T> {
+        Some(max + sum.ln())
+    }
+}
+
+/// Define a reduction which applies a binary kernel, such as `maximum`, to
+/// every element. The result of an empty reduction is `None`.
+macro_rules! host_kernel_reduction {
+    ($name: ident, $kernel: ty) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host reduction for `",
+                stringify!($name),
+                "`"
+            )]
+            pub struct [< Host $name Reduction >];
+
+            impl<T> HostReduction<T> for [< Host $name Reduction >]
+            where
+                T: Copy + Send,
+                $kernel: HostBinaryOp<T>,
+                $kernel: op_traits::BinaryOp<Output<T> = T>,
+            {
+                type Acc = Option<T>;
+                type Output = T;
+
+                #[inline(always)]
+                fn init() -> Option<T> {
+                    None
+                }
+
+                #[inline(always)]
+                fn fold(acc: Option<T>, _index: usize, value: T) -> Option<T> {
+                    Some(acc.map_or(value, |acc| {
+                        <$kernel>::apply_scalar(acc, value)
+                    }))
+                }
+
+                #[inline(always)]
+                fn combine(lhs: Option<T>, rhs: Option<T>) -> Option<T> {
+                    match (lhs, rhs) {
+                        (Some(lhs), Some(rhs)) => {
+                            Some(<$kernel>::apply_scalar(lhs, rhs))
+                        }
+                        (lhs, None) => lhs,
+                        (None, rhs) => rhs,
+                    }
+                }
+
+                #[inline(always)]
+                fn finish(acc: Option<T>, _count: usize) -> Option<T> {
+                    acc
+                }
+            }
+        }
+    };
+}
+
+host_kernel_reduction!(Min, HostMinimumKernel);
+host_kernel_reduction!(Max, HostMaximumKernel);
+
+/// Choose between the best element found so far and a later `candidate`. The
+/// candidate is only chosen if it compares as `prefer` to the current best, so
+/// ties are resolved in favour of the first element. NaN always wins, since
+/// the first NaN is both the minimum and maximum of a sequence containing it.
+#[inline(always)]
+fn arg_select<T: Copy + PartialOrd>(
+    best: (usize, T),
+    candidate: (usize, T),
+    prefer: Ordering,
+) -> (usize, T) {
+    if is_nan(best.1) {
+        best
+    } else if is_nan(candidate.1)
+        || candidate.1.partial_cmp(&best.1) == Some(prefer)
+    {
+        candidate
+    } else {
+        best
+    }
+}
+
+/// Define a reduction which finds the index of the smallest or largest
+/// element. The result of an empty reduction is `None`.
+macro_rules! host_arg_reduction {
+    ($name: ident, $prefer: expr) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host reduction for `",
+                stringify!($name),
+                "`"
+            )]
+            pub struct [< Host $name Reduction >];
+
+            impl<T> HostReduction<T> for [< Host $name Reduction >]
+            where
+                T: Copy + Send + PartialOrd,
+            {
+                type Acc = Option<(usize, T)>;
+                type Output = usize;
+
+                #[inline(always)]
+                fn init() -> Self::Acc {
+                    None
+                }
+
+                #[inline(always)]
+                fn fold(acc: Self::Acc, index: usize, value: T) -> Self::Acc {
+                    Self::combine(acc, Some((index, value)))
+                }
+
+                #[inline(always)]
+                fn combine(lhs: Self::Acc, rhs: Self::Acc) -> Self::Acc {
+                    match (lhs, rhs) {
+                        (Some(lhs), Some(rhs)) => {
+                            Some(arg_select(lhs, rhs, $prefer))
+                        }
+                        (lhs, None) => lhs,
+                        (None, rhs) => rhs,
+                    }
+                }
+
+                #[inline(always)]
+                fn finish(acc: Self::Acc, _count: usize) -> Option<usize> {
+                    let (index, _) = acc?;
+                    Some(index)
+                }
+            }
+        }
+    };
+}
+
+host_arg_reduction!(ArgMin, Ordering::Less);
+host_arg_reduction!(ArgMax, Ordering::Greater);
+
+/// Define a logical reduction over `bool` elements.
+macro_rules! host_logical_reduction {
+    ($name: ident, $init: literal, |$lhs: ident, $rhs: ident| $combine: expr) => {
+        paste::paste! {
+            #[doc = concat!(
+                "The host reduction for `",
+                stringify!($name),
+                "`"
+            )]
+            pub struct [< Host $name Reduction >];
+
+            impl HostReduction<bool> for [< Host $name Reduction >] {
+                type Acc = bool;
+                type Output = bool;
+
+                #[inline(always)]
+                fn init() -> bool {
+                    $init
+                }
+
+                #[inline(always)]
+                fn fold(acc: bool, _index: usize, value: bool) -> bool {
+                    Self::combine(acc, value)
+                }
+
+                #[inline(always)]
+                fn combine($lhs: bool, $rhs: bool) -> bool {
+                    $combine
+                }
+
+                #[inline(always)]
+                fn finish(acc: bool, _count: usize) -> Option<bool> {
+                    Some(acc)
+                }
+            }
+        }
+    };
+}
+
+host_logical_reduction!(Any, false, |lhs, rhs| lhs || rhs);
+host_logical_reduction!(All, true, |lhs, rhs| lhs && rhs);
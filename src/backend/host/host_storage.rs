@@ -1,3 +1,5 @@
+//! Owned storage for arrays in host memory.
+
 use std::ptr::NonNull;
 
 use rayon::prelude::*;
@@ -6,9 +8,10 @@ use crate::{
     array::traits::GetWriteableBuffer,
     backend::traits::{
         ContainerLength, ContainerScalarType, ContainerStorageType,
-        OwnedStorage, ScalarAccessor, ScalarWriter, Storage,
+        OwnedStorage, ScalarAccessor, ScalarWriter, Storage, StorageMut,
     },
     dimension::dim::Dimension,
+    types::UDim,
 };
 
 /// The number of bytes to align heap-allocated memory to.
@@ -32,6 +35,19 @@ impl<T> Clone for HostNonNull<T> {
     }
 }
 
+/// Where the memory owned by a [`HostStorage`] object came from, which
+/// determines how it must be freed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostAllocation {
+    /// Allocated by [`HostStorage::new_uninit`] with an alignment of
+    /// [`MEM_ALIGN`] bytes
+    Aligned,
+
+    /// Taken from a [`Vec<T>`] by [`HostStorage::from_vec`], so it was
+    /// allocated with the alignment of `T`
+    Vec,
+}
+
 /// An [`OwnedStorage`] object for data in host memory
 ///
 /// # Example
@@ -52,9 +68,29 @@ impl<T> Clone for HostNonNull<T> {
 /// assert_eq!(host_storage[6..=9], [7, 8, 9, 10]);
 /// ```
 pub struct HostStorage<T> {
+    /// A pointer to the first element
     pub ptr: HostNonNull<T>,
+
+    /// The number of elements
     pub length: usize,
+
+    /// Whether the elements are freed when the storage is dropped. This is
+    /// cleared once the buffer has been handed to another container.
     pub free_on_drop: bool,
+
+    /// How the buffer was allocated, which determines how it is freed
+    pub allocation: HostAllocation,
+}
+
+/// A storage type whose elements live in host memory.
+///
+/// This is implemented by [`HostStorage`] and by the host view storage types,
+/// and allows views to be created from any of them.
+pub trait HostData: Storage {
+    /// Return a pointer to the first element of the underlying data. Note
+    /// that this is the start of the storage, not the first element of an
+    /// array which uses it (which may have a non-zero offset).
+    fn as_non_null(&self) -> HostNonNull<Self::Scalar>;
 }
 
 impl<T> Storage for HostStorage<T>
@@ -63,11 +99,26 @@ where
 {
     type OwnedStorageType = Self;
 
+    unsafe fn set_no_free(&mut self) {}
+}
+
+impl<T> StorageMut for HostStorage<T>
+where
+    T: Copy,
+{
     fn fill(&mut self, value: Self::Scalar) {
         (0..self.length).for_each(|i| self[i] = value);
     }
+}
 
-    unsafe fn set_no_free(&mut self) {}
+impl<T> HostData for HostStorage<T>
+where
+    T: Copy,
+{
+    #[inline(always)]
+    fn as_non_null(&self) -> HostNonNull<Self::Scalar> {
+        self.ptr
+    }
 }
 
 impl<T> ContainerLength for HostStorage<T> {
@@ -114,6 +165,16 @@ where
     unsafe fn get_raw(&self) -> Self::Raw {
         self.ptr
     }
+
+    unsafe fn from_raw(raw: Self::Raw, len: usize) -> Self {
+        // Only aligned allocations are handed out as writeable buffers
+        Self {
+            ptr: raw,
+            length: len,
+            free_on_drop: true,
+            allocation: HostAllocation::Aligned,
+        }
+    }
 }
 
 impl<T> HostStorage<T> {
@@ -141,24 +202,15 @@ impl<T> HostStorage<T> {
         T: Default,
     {
         unsafe {
-            let data = std::alloc::alloc(
-                std::alloc::Layout::from_size_align_unchecked(
-                    length * core::mem::size_of::<T>(),
-                    MEM_ALIGN,
-                ),
-            )
-            .cast::<T>();
+            let storage = Self::new_uninit(length);
+            let data = storage.ptr.0.as_ptr();
 
             // Initialise all elements to their default value
             for i in 0..length {
-                *data.add(i) = T::default();
+                data.add(i).write(T::default());
             }
 
-            Self {
-                ptr: HostNonNull(NonNull::new(data).unwrap()),
-                length,
-                free_on_drop: true,
-            }
+            storage
         }
     }
 
@@ -184,26 +236,158 @@ impl<T> HostStorage<T> {
     /// Panics if the memory allocation fail
     #[must_use]
     pub unsafe fn new_uninit(length: usize) -> Self {
-        let data =
-            std::alloc::alloc(std::alloc::Layout::from_size_align_unchecked(
-                length * core::mem::size_of::<T>(),
-                MEM_ALIGN,
-            ))
-            .cast::<T>();
+        // Allocating zero bytes is undefined behaviour, so empty storage
+        // uses a dangling pointer instead. Nothing is freed for these, since
+        // the length is zero
+        if length == 0 || core::mem::size_of::<T>() == 0 {
+            return Self {
+                ptr: HostNonNull(NonNull::dangling()),
+                length,
+                free_on_drop: true,
+                allocation: HostAllocation::Aligned,
+            };
+        }
+
+        let layout = Self::aligned_layout(length);
+        let data = std::alloc::alloc(layout).cast::<T>();
+        if data.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
 
         Self {
-            ptr: HostNonNull(NonNull::new(data).unwrap()),
+            ptr: HostNonNull(NonNull::new_unchecked(data)),
             length,
-            free_on_drop: false,
+            free_on_drop: true,
+            allocation: HostAllocation::Aligned,
         }
     }
 
-    pub fn take_as_vec(&mut self) -> Vec<T> {
+    /// The layout of an aligned allocation of `length` elements, used both to
+    /// allocate and to free it
+    const unsafe fn aligned_layout(length: usize) -> std::alloc::Layout {
+        std::alloc::Layout::from_size_align_unchecked(
+            length * core::mem::size_of::<T>(),
+            MEM_ALIGN,
+        )
+    }
+
+    /// Create a new [`HostStorage`] object from a [`Vec<T>`].
+    ///
+    /// If the vector's allocation is already aligned to [`MEM_ALIGN`] bytes
+    /// and contains no spare capacity, it is reused directly. Otherwise, the
+    /// data is copied into a new, aligned allocation.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::backend::host::host_storage::HostStorage;
+    ///
+    /// let host_storage = HostStorage::from_vec(vec![1, 2, 3, 4]);
+    /// assert_eq!(host_storage.length, 4);
+    /// assert_eq!(host_storage[0..4], [1, 2, 3, 4]);
+    /// ```
+    #[must_use]
+    pub fn from_vec(data: Vec<T>) -> Self
+    where
+        T: Copy,
+    {
+        let aligned = (data.as_ptr() as usize).is_multiple_of(MEM_ALIGN);
+
+        if aligned && data.len() == data.capacity() && !data.is_empty() {
+            // The allocation is freed by rebuilding a vector with identical
+            // length and capacity, so ownership can be transferred without
+            // copying
+            let mut data = std::mem::ManuallyDrop::new(data);
+            let length = data.len();
+
+            Self {
+                // Safety: A vector's pointer is never null
+                ptr: HostNonNull(unsafe {
+                    NonNull::new_unchecked(data.as_mut_ptr())
+                }),
+                length,
+                free_on_drop: true,
+                allocation: HostAllocation::Vec,
+            }
+        } else {
+            Self::from_slice(&data)
+        }
+    }
+
+    /// Create a new [`HostStorage`] object containing a copy of the elements
+    /// in `data`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::backend::host::host_storage::HostStorage;
+    ///
+    /// let host_storage = HostStorage::from_slice(&[1.0f32, 2.0, 3.0]);
+    /// assert_eq!(host_storage.length, 3);
+    /// assert_eq!(host_storage[1], 2.0);
+    /// ```
+    #[must_use]
+    pub fn from_slice(data: &[T]) -> Self
+    where
+        T: Copy,
+    {
         unsafe {
-            // Set length to zero so we do not free data
-            let length = self.length;
-            self.length = 0;
-            Vec::from_raw_parts(self.ptr.0.as_ptr(), length, length)
+            let storage = Self::new_uninit(data.len());
+            std::ptr::copy_nonoverlapping(
+                data.as_ptr(),
+                storage.ptr.0.as_ptr(),
+                data.len(),
+            );
+            storage
+        }
+    }
+
+    /// Move the elements into a [`Vec<T>`], leaving the storage empty.
+    ///
+    /// Storage created from a vector gives its allocation back without
+    /// copying. Aligned allocations cannot be owned by a vector, so their
+    /// elements are moved into a new vector and the allocation is freed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use tensr::backend::host::host_storage::HostStorage;
+    ///
+    /// let mut host_storage = HostStorage::from_slice(&[1, 2, 3]);
+    /// assert_eq!(host_storage.take_as_vec(), vec![1, 2, 3]);
+    /// assert_eq!(host_storage.length, 0);
+    /// ```
+    #[allow(clippy::same_length_and_capacity)]
+    pub fn take_as_vec(&mut self) -> Vec<T> {
+        // Set length to zero so the data is not freed again
+        let length = std::mem::take(&mut self.length);
+        let ptr = self.ptr.0.as_ptr();
+
+        match self.allocation {
+            // The allocation is always exactly `length` elements, so the
+            // length and capacity of the resulting vector are the same
+            HostAllocation::Vec => unsafe {
+                Vec::from_raw_parts(ptr, length, length)
+            },
+            HostAllocation::Aligned => {
+                let mut data = Vec::with_capacity(length);
+                unsafe {
+                    std::ptr::copy_nonoverlapping(
+                        ptr,
+                        data.as_mut_ptr(),
+                        length,
+                    );
+                    data.set_len(length);
+                    Self::free_aligned(ptr, length);
+                }
+                data
+            }
+        }
+    }
+
+    /// Free an aligned allocation of `length` elements without dropping them
+    unsafe fn free_aligned(ptr: *mut T, length: usize) {
+        // Empty and zero-sized storage uses a dangling pointer, which was
+        // never allocated
+        if length > 0 && core::mem::size_of::<T>() > 0 {
+            std::alloc::dealloc(ptr.cast::<u8>(), Self::aligned_layout(length));
         }
     }
 }
@@ -262,6 +446,15 @@ where
     fn get_scalar(&self, index: usize) -> Self::Scalar {
         self[index]
     }
+
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar {
+        // Storage is one-dimensional
+        self[index[0]]
+    }
+
+    fn is_contiguous(&self) -> bool {
+        true
+    }
 }
 
 impl<T> ScalarWriter for HostStorage<T>
@@ -271,15 +464,31 @@ where
     fn write_scalar(&mut self, value: Self::Scalar, index: usize) {
         self[index] = value;
     }
+
+    fn write_scalar_nd(&mut self, value: Self::Scalar, index: &[UDim]) {
+        self[index[0]] = value;
+    }
 }
 
 impl<T> Drop for HostStorage<T> {
     fn drop(&mut self) {
         // If the length is zero, there is nothing to free
-        if self.free_on_drop && self.length > 0 {
+        if !self.free_on_drop || self.length == 0 {
+            return;
+        }
+
+        match self.allocation {
             // We can convert the data into a vec and drop that instead, so
             // the logic is handled by the STL
-            drop(self.take_as_vec());
+            HostAllocation::Vec => drop(self.take_as_vec()),
+            HostAllocation::Aligned => unsafe {
+                let length = std::mem::take(&mut self.length);
+                let ptr = self.ptr.0.as_ptr();
+                std::ptr::drop_in_place(std::ptr::slice_from_raw_parts_mut(
+                    ptr, length,
+                ));
+                Self::free_aligned(ptr, length);
+            },
         }
     }
 }
@@ -383,9 +592,6 @@ impl<T> std::ops::Index<std::ops::RangeInclusive<usize>> for HostStorage<T> {
     type Output = [T];
 
     fn index(&self, index: std::ops::RangeInclusive<usize>) -> &Self::Output {
-        let start = *index.start();
-        let end = *index.end();
-
         #[cold]
         #[inline(never)]
         #[track_caller]
@@ -393,6 +599,9 @@ impl<T> std::ops::Index<std::ops::RangeInclusive<usize>> for HostStorage<T> {
             panic!("index (is {index}) must be <= len (is {len})");
         }
 
+        let start = *index.start();
+        let end = *index.end();
+
         if start >= self.length {
             assert_failed(start, self.length)
         }
@@ -417,7 +626,9 @@ impl<T> GetWriteableBuffer for HostStorage<T> {
         &mut self,
         len: usize,
     ) -> Option<Self::Buffer> {
-        if self.length >= len {
+        // The new owner frees the buffer as an aligned allocation of `len`
+        // elements, so memory taken from a vector cannot be handed out
+        if self.length == len && self.allocation == HostAllocation::Aligned {
             self.free_on_drop = false;
             Some(self.ptr)
         } else {
@@ -427,6 +638,7 @@ impl<T> GetWriteableBuffer for HostStorage<T> {
 }
 
 #[cfg(test)]
+#[allow(clippy::float_cmp, clippy::items_after_statements)]
 mod test {
     use std::hint::black_box;
 
@@ -493,7 +705,7 @@ mod test {
             #[test]
             fn $name() {
                 let n = 1000;
-                let mut v = Vec::new();
+                let v;
 
                 // Drop s to check the memory is not freed
                 {
@@ -576,6 +788,43 @@ mod test {
         };
     }
 
+    #[test]
+    fn test_allocation_origin() {
+        // Storage which adopts a vector's allocation, whatever its alignment
+        let mut data = std::mem::ManuallyDrop::new(vec![1u16, 2, 3]);
+        let mut adopted = HostStorage {
+            ptr: HostNonNull(NonNull::new(data.as_mut_ptr()).unwrap()),
+            length: data.len(),
+            free_on_drop: true,
+            allocation: HostAllocation::Vec,
+        };
+
+        // It cannot be reused by storage which frees aligned allocations
+        assert!(unsafe { adopted.get_buffer_and_set_no_free(3) }.is_none());
+        assert_eq!(adopted.take_as_vec(), vec![1, 2, 3]);
+        drop(adopted);
+
+        let mut aligned = HostStorage::from_slice(&[1u16, 2, 3]);
+        assert_eq!(aligned.allocation, HostAllocation::Aligned);
+        assert!(unsafe { aligned.get_buffer_and_set_no_free(2) }.is_none());
+
+        let buffer = unsafe { aligned.get_buffer_and_set_no_free(3) }.unwrap();
+        drop(aligned);
+        let mut reused = unsafe { HostStorage::from_raw(buffer, 3) };
+        assert_eq!(reused.take_as_vec(), vec![1, 2, 3]);
+
+        // Vectors are only adopted if they are already aligned
+        for length in 1..64 {
+            let data = vec![0u8; length];
+            let aligned = (data.as_ptr() as usize).is_multiple_of(MEM_ALIGN);
+            let storage = HostStorage::from_vec(data);
+            assert_eq!(
+                storage.allocation == HostAllocation::Vec,
+                aligned && length > 0
+            );
+        }
+    }
+
     test_all_fundamental!(test_alloc);
     test_all_fundamental!(test_alloc_uninit);
     test_all_fundamental!(test_take_as_vec);
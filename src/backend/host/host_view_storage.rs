@@ -0,0 +1,260 @@
+//! Borrowed storage for views of host arrays.
+
+use std::marker::PhantomData;
+
+use crate::{
+    array::traits::GetWriteableBuffer,
+    backend::{
+        host::host_storage::{HostData, HostNonNull, HostStorage},
+        traits::{
+            ContainerLength, ContainerScalarType, ContainerStorageType,
+            Storage, StorageMut,
+        },
+    },
+};
+
+/// A non-owning, immutable [`Storage`] object which borrows data in host
+/// memory. This is the storage type used by array views.
+///
+/// The view refers to the entire underlying buffer. The elements which belong
+/// to a particular view are selected by the offset and strides of the array
+/// which uses it.
+pub struct HostViewStorage<'a, T> {
+    pub(crate) ptr: HostNonNull<T>,
+    pub(crate) length: usize,
+    lifetime: PhantomData<&'a T>,
+}
+
+/// A non-owning, mutable [`Storage`] object which borrows data in host memory.
+/// This is the storage type used by mutable array views.
+///
+/// See [`HostViewStorage`] for more information.
+pub struct HostViewMutStorage<'a, T> {
+    pub(crate) ptr: HostNonNull<T>,
+    pub(crate) length: usize,
+    lifetime: PhantomData<&'a mut T>,
+}
+
+impl<T> HostViewStorage<'_, T> {
+    /// Create a new [`HostViewStorage`] referring to `length` elements
+    /// starting at `ptr`.
+    ///
+    /// # Safety
+    /// The pointer must be valid for reads of `length` elements for the
+    /// lifetime of the view, and the data must not be mutated during that
+    /// time.
+    pub(crate) const unsafe fn new(ptr: HostNonNull<T>, length: usize) -> Self {
+        Self { ptr, length, lifetime: PhantomData }
+    }
+}
+
+impl<T> HostViewMutStorage<'_, T> {
+    /// Create a new [`HostViewMutStorage`] referring to `length` elements
+    /// starting at `ptr`.
+    ///
+    /// # Safety
+    /// The pointer must be valid for reads and writes of `length` elements for
+    /// the lifetime of the view, and the data must not be accessed through any
+    /// other object during that time.
+    pub(crate) const unsafe fn new(ptr: HostNonNull<T>, length: usize) -> Self {
+        Self { ptr, length, lifetime: PhantomData }
+    }
+}
+
+macro_rules! host_view_storage_impl {
+    ($name: ident) => {
+        impl<T> ContainerLength for $name<'_, T> {
+            fn len(&self) -> usize {
+                self.length
+            }
+        }
+
+        impl<T> ContainerScalarType for $name<'_, T>
+        where
+            T: Copy,
+        {
+            type Scalar = T;
+        }
+
+        impl<T> ContainerStorageType for $name<'_, T>
+        where
+            T: Copy,
+        {
+            type Storage = Self;
+        }
+
+        impl<T> Storage for $name<'_, T>
+        where
+            T: Copy,
+        {
+            type OwnedStorageType = HostStorage<T>;
+
+            unsafe fn set_no_free(&mut self) {}
+        }
+
+        impl<T> HostData for $name<'_, T>
+        where
+            T: Copy,
+        {
+            #[inline(always)]
+            fn as_non_null(&self) -> HostNonNull<Self::Scalar> {
+                self.ptr
+            }
+        }
+
+        impl<T> GetWriteableBuffer for $name<'_, T> {
+            type Buffer = HostNonNull<T>;
+
+            unsafe fn get_buffer_and_set_no_free(
+                &mut self,
+                _: usize,
+            ) -> Option<Self::Buffer> {
+                // The data is borrowed, so it can never be taken by a result
+                None
+            }
+        }
+
+        impl<T> std::ops::Index<usize> for $name<'_, T> {
+            type Output = T;
+
+            fn index(&self, index: usize) -> &Self::Output {
+                #[cold]
+                #[inline(never)]
+                #[track_caller]
+                fn assert_failed(index: usize, len: usize) -> ! {
+                    panic!("index (is {index}) must be <= len (is {len})");
+                }
+
+                #[cfg(debug_assertions)]
+                if index >= self.length {
+                    assert_failed(index, self.length)
+                }
+
+                unsafe { self.ptr.0.as_ptr().add(index).as_ref().unwrap() }
+            }
+        }
+    };
+}
+
+host_view_storage_impl!(HostViewStorage);
+host_view_storage_impl!(HostViewMutStorage);
+
+impl<T> std::ops::IndexMut<usize> for HostViewMutStorage<'_, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        #[cold]
+        #[inline(never)]
+        #[track_caller]
+        fn assert_failed(index: usize, len: usize) -> ! {
+            panic!("index (is {index}) must be <= len (is {len})");
+        }
+
+        #[cfg(debug_assertions)]
+        if index >= self.length {
+            assert_failed(index, self.length)
+        }
+
+        unsafe { self.ptr.0.as_ptr().add(index).as_mut().unwrap() }
+    }
+}
+
+impl<T> StorageMut for HostViewMutStorage<'_, T>
+where
+    T: Copy,
+{
+    fn fill(&mut self, value: Self::Scalar) {
+        (0..self.length).for_each(|i| self[i] = value);
+    }
+}
+
+/// A [`Storage`] object which either borrows data in host memory, like
+/// [`HostViewStorage`], or owns a copy of it, like [`HostStorage`].
+///
+/// This is the storage type of arrays returned by operations such as
+/// reshaping, which only copy the elements of an array when its strides
+/// require it.
+pub enum HostCowStorage<'a, T> {
+    /// The data is borrowed from another array
+    Borrowed(HostViewStorage<'a, T>),
+
+    /// The data is owned
+    Owned(HostStorage<T>),
+}
+
+impl<T> HostCowStorage<'_, T> {
+    /// Returns `true` if the data is borrowed from another array
+    #[must_use]
+    pub const fn is_borrowed(&self) -> bool {
+        matches!(self, Self::Borrowed(_))
+    }
+}
+
+impl<T> ContainerLength for HostCowStorage<'_, T> {
+    fn len(&self) -> usize {
+        match self {
+            Self::Borrowed(storage) => storage.len(),
+            Self::Owned(storage) => storage.len(),
+        }
+    }
+}
+
+impl<T> ContainerScalarType for HostCowStorage<'_, T>
+where
+    T: Copy,
+{
+    type Scalar = T;
+}
+
+impl<T> ContainerStorageType for HostCowStorage<'_, T>
+where
+    T: Copy,
+{
+    type Storage = Self;
+}
+
+impl<T> Storage for HostCowStorage<'_, T>
+where
+    T: Copy,
+{
+    type OwnedStorageType = HostStorage<T>;
+
+    unsafe fn set_no_free(&mut self) {}
+}
+
+impl<T> HostData for HostCowStorage<'_, T>
+where
+    T: Copy,
+{
+    #[inline(always)]
+    fn as_non_null(&self) -> HostNonNull<Self::Scalar> {
+        match self {
+            Self::Borrowed(storage) => storage.as_non_null(),
+            Self::Owned(storage) => storage.as_non_null(),
+        }
+    }
+}
+
+impl<T> GetWriteableBuffer for HostCowStorage<'_, T> {
+    type Buffer = HostNonNull<T>;
+
+    unsafe fn get_buffer_and_set_no_free(
+        &mut self,
+        len: usize,
+    ) -> Option<Self::Buffer> {
+        match self {
+            Self::Borrowed(_) => None,
+            Self::Owned(storage) => storage.get_buffer_and_set_no_free(len),
+        }
+    }
+}
+
+impl<T> std::ops::Index<usize> for HostCowStorage<'_, T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, index: usize) -> &Self::Output {
+        match self {
+            Self::Borrowed(storage) => &storage[index],
+            Self::Owned(storage) => &storage[index],
+        }
+    }
+}
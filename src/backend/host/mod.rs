@@ -1,4 +1,9 @@
+//! A backend which stores arrays in host memory and computes on the CPU.
+
 pub mod host_backend;
 pub mod host_function;
+pub mod host_gemm;
 pub mod host_kernels;
+pub mod host_reductions;
 pub mod host_storage;
+pub mod host_view_storage;
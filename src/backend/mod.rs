@@ -1,3 +1,5 @@
+//! The storage and computation behind arrays.
+
 pub mod helper_macros;
 pub mod host;
 pub mod op_traits;
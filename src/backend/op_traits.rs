@@ -1,5 +1,76 @@
-pub trait BinaryOp {}
+//! Marker traits describing the operations which backends implement.
 
+/// An operation with two operands
+pub trait BinaryOp {
+    /// The scalar type of the result when the operation is applied to two
+    /// operands of type `T`. This is `T` for arithmetic operations, but may be
+    /// different, such as `bool` for comparisons.
+    type Output<T: Copy>: Copy;
+}
+
+/// An operation with one operand
+pub trait UnaryOp {}
+
+/// An operation with three operands
+pub trait TernaryOp {}
+
+/// An operation with any number of operands, such as `select`. `Scalars` is a
+/// tuple containing the scalar type of each operand.
+pub trait FunctionOp<Scalars> {
+    /// The scalar type of the result
+    type Output: Copy;
+}
+
+/// Controls which operand buffers of a [`FunctionOp`] may be reused to store
+/// its result. `Operands` is a tuple containing every operand.
+pub trait ReuseOperandBuffer<Operands> {
+    /// The type of buffer which stores the result of the operation
+    type Buffer;
+
+    /// Take the buffer of one of the `operands`, if any of them can be reused
+    /// to store a result with `len` elements.
+    ///
+    /// # Safety
+    /// The same requirements as
+    /// [`GetWriteableBuffer::get_buffer_and_set_no_free`] apply. In addition,
+    /// the buffer may only be taken from an operand whose elements are each
+    /// read before the corresponding element of the result is written.
+    ///
+    /// [`GetWriteableBuffer::get_buffer_and_set_no_free`]:
+    ///     crate::array::traits::GetWriteableBuffer::get_buffer_and_set_no_free
+    unsafe fn reuse_buffer(
+        operands: &mut Operands,
+        len: usize,
+    ) -> Option<Self::Buffer>;
+}
+
+/// Controls whether a buffer of type `Buffer`, taken from an operand of a
+/// binary operation, may be reused to store the result of the operation.
+///
+/// This is only possible if the result has the same scalar type as the
+/// operands, so operations which change the scalar type (such as comparisons)
+/// never reuse a buffer.
+pub trait ReuseBuffer<Buffer> {
+    /// The type of buffer which stores the result of the operation
+    type Output;
+
+    /// Call `take` to take ownership of an operand's buffer, if the buffer can
+    /// be reused for the result. If not, `take` must not be called, so the
+    /// operand still frees its data.
+    ///
+    /// # Safety
+    /// The same requirements as
+    /// [`GetWriteableBuffer::get_buffer_and_set_no_free`] apply.
+    ///
+    /// [`GetWriteableBuffer::get_buffer_and_set_no_free`]:
+    ///     crate::array::traits::GetWriteableBuffer::get_buffer_and_set_no_free
+    unsafe fn reuse_buffer(
+        take: impl FnOnce() -> Option<Buffer>,
+    ) -> Option<Self::Output>;
+}
+
+/// A binary operation on two scalars of type `T`
 pub trait ScalarKernel<T> {
+    /// Apply the operation to a single pair of elements
     fn apply_scalar(lhs: T, rhs: T) -> T;
 }
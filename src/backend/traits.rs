@@ -1,16 +1,47 @@
 //! Traits and types for defining and implementing backends for arrays. This
 //! includes Backend structs and storage types.
 
-use crate::{backend::op_traits, dimension::dim::Dimension};
+use crate::{
+    array::traits::GetWriteableBuffer, backend::op_traits,
+    dimension::dim::Dimension, types::UDim,
+};
 
 macro_rules! kernel_type_repeater {
     ($name: ident, $_1: tt, $_2: tt) => {
         paste::paste! {
+            #[doc = concat!("The kernel for `", stringify!($name), "`")]
             type [< $name Kernel >]: op_traits::BinaryOp;
         }
     };
 }
 
+macro_rules! function_kernel_type_repeater {
+    ($name: ident, $_1: tt) => {
+        paste::paste! {
+            #[doc = concat!("The kernel for `", stringify!($name), "`")]
+            type [< $name Kernel >]: op_traits::BinaryOp;
+        }
+    };
+}
+
+macro_rules! ternary_kernel_type_repeater {
+    ($name: ident, $_1: tt) => {
+        paste::paste! {
+            #[doc = concat!("The kernel for `", stringify!($name), "`")]
+            type [< $name Kernel >]: op_traits::TernaryOp;
+        }
+    };
+}
+
+macro_rules! unary_kernel_type_repeater {
+    ($name: ident, $_1: tt) => {
+        paste::paste! {
+            #[doc = concat!("The kernel for `", stringify!($name), "`")]
+            type [< $name Kernel >]: op_traits::UnaryOp;
+        }
+    };
+}
+
 /// The [`Backend`] trait is used to mark structs as a valid backend for
 /// calculations.
 ///
@@ -19,11 +50,15 @@ macro_rules! kernel_type_repeater {
 pub trait Backend {
     /// A type representing an object which can allocate, manage and store
     /// memory for a given [`Backend`]. Elements are of type `T`.
-    type OwnedStorage<T>: OwnedStorage
+    type OwnedStorage<T>: OwnedStorage<Scalar = T> + GetWriteableBuffer
     where
         T: Copy;
 
     crate::repeat_binary_ops!(kernel_type_repeater);
+    crate::repeat_binary_functions!(function_kernel_type_repeater);
+    crate::repeat_comparison_ops!(function_kernel_type_repeater);
+    crate::repeat_unary_ops!(unary_kernel_type_repeater);
+    crate::repeat_ternary_ops!(ternary_kernel_type_repeater);
 }
 
 /// This trait marks an object as being a container with a length, and provides
@@ -74,22 +109,20 @@ pub trait MutableContainerStorageAccessor:
 /// Marks a struct as depending on a given backend. Generally, only structs with
 /// the same backend can be used together.
 pub trait ContainerBackendType: ContainerStorageType {
+    /// The backend of the container
     type Backend: Backend;
 }
 
 /// A trait marking an object as a storage medium. It may or may not own the
-/// data that it contains.
+/// data that it contains, and the data may or may not be mutable.
 pub trait Storage:
     ContainerLength
     + ContainerScalarType
     + std::ops::Index<usize, Output = Self::Scalar>
-    + std::ops::IndexMut<usize>
 {
     /// The equivalent storage type, but which owns the data it stores
     type OwnedStorageType: OwnedStorage;
 
-    fn fill(&mut self, value: Self::Scalar);
-
     /// Mark the data to not be freed when the main object is dropped. This is
     /// necessary for preventing invalid memory accesses when reusing the same
     /// storage object.
@@ -99,10 +132,18 @@ pub trait Storage:
     unsafe fn set_no_free(&mut self);
 }
 
+/// A trait marking a storage medium as allowing its data to be modified. This
+/// is implemented by owned storage and mutable views, but not by immutable
+/// views.
+pub trait StorageMut: Storage + std::ops::IndexMut<usize> {
+    /// Set every element in the storage to `value`
+    fn fill(&mut self, value: Self::Scalar);
+}
+
 /// A trait marking an object as owning the data it contains. If this is the
 /// case, the data must be stored contiguously and must be paired with a
 /// backend.
-pub trait OwnedStorage: Storage {
+pub trait OwnedStorage: StorageMut {
     /// The raw type of the data stored by this object. For example, this may be
     /// a pointer to the underlying data.
     type Raw;
@@ -139,17 +180,70 @@ pub trait OwnedStorage: Storage {
     /// The caller must ensure that the pointer is valid for the lifetime of the
     /// object and that immutable data is not written to.
     unsafe fn get_raw(&self) -> Self::Raw;
+
+    /// Create a new storage object which takes ownership of `raw`, such as a
+    /// buffer returned by [`GetWriteableBuffer::get_buffer_and_set_no_free`].
+    /// The data is freed when the new object is dropped.
+    ///
+    /// # Safety
+    /// `raw` must refer to an allocation made by this storage type containing
+    /// exactly `len` elements, and no other object may free it.
+    unsafe fn from_raw(raw: Self::Raw, len: usize) -> Self;
+}
+
+/// Provides the shape of a container or lazily-evaluated expression.
+pub trait ContainerShape {
+    /// The dimension type used to represent the shape
+    type NDims: Dimension;
+
+    /// Return the shape of the container
+    fn get_shape(&self) -> Self::NDims;
 }
 
 /// Allows direct access to scalar values in a container.
 pub trait ScalarAccessor: ContainerLength + ContainerScalarType {
-    /// Return the `index`'th element of a data container or wrapper
+    /// Return the `index`'th element of a data container or wrapper.
+    ///
+    /// For strided containers, such as array views, the flat index is only
+    /// meaningful if [`ScalarAccessor::is_contiguous`] returns `true`. Use
+    /// [`ScalarAccessor::get_scalar_nd`] otherwise.
     fn get_scalar(&self, index: usize) -> Self::Scalar;
+
+    /// Return the element at the multi-dimensional `index`, taking strides
+    /// into account.
+    ///
+    /// The index may have more dimensions than the container, in which case
+    /// it is aligned with the trailing axes and the leading values are
+    /// ignored. Indices along axes of length one are also ignored. This means
+    /// an operand can be read using the index of a larger, broadcast result.
+    fn get_scalar_nd(&self, index: &[UDim]) -> Self::Scalar;
+
+    /// Returns `true` if the elements of this object are laid out
+    /// contiguously in row-major order, meaning they can be accessed with a
+    /// flat index through [`ScalarAccessor::get_scalar`].
+    fn is_contiguous(&self) -> bool;
+
+    /// Returns `true` if this object can be read with flat indices through
+    /// [`ScalarAccessor::get_scalar`] when it is used as an operand of a
+    /// result with `len` elements.
+    ///
+    /// By default, this requires the object to be contiguous and to have
+    /// exactly `len` elements, meaning it is not broadcast. Objects such as
+    /// constant scalars, which have the same value at every index, can
+    /// override this to allow faster evaluation.
+    fn supports_flat_index(&self, len: usize) -> bool {
+        self.is_contiguous() && self.len() == len
+    }
 }
 
 /// Allows writing scalar values to a container.
 pub trait ScalarWriter: ContainerLength + ContainerScalarType {
     /// Write a value to the `index`'th element of a data container or
-    /// wrapper
+    /// wrapper. As with [`ScalarAccessor::get_scalar`], this is only
+    /// meaningful for contiguous containers.
     fn write_scalar(&mut self, value: Self::Scalar, index: usize);
+
+    /// Write a value to the element at the multi-dimensional `index`, taking
+    /// strides into account.
+    fn write_scalar_nd(&mut self, value: Self::Scalar, index: &[UDim]);
 }
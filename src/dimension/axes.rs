@@ -1,16 +1,42 @@
-use crate::{dimension::dim::Dimension, types::DimLen};
+//! The shape, strides and offset which locate each element of an array in
+//! its storage.
 
+use crate::{
+    dimension::{
+        dim::Dimension,
+        stride::{advance, Stride},
+    },
+    types::{DimLen, IDim, UDim},
+};
+
+/// Identifies a single axis of an array, such as `Axis(1)` for the columns of
+/// a matrix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Axis(pub usize);
+
+/// The shape and strides of an array, which together locate each element in
+/// its storage
+#[derive(Debug, Clone)]
 pub struct Axes<Dim: Dimension> {
     pub(crate) shape: Dim,
-    pub(crate) stride: Dim,
+    pub(crate) stride: Stride<Dim>,
 }
 
 impl<Dim: Dimension> Axes<Dim> {
+    /// Create a new [`Axes`] object from a shape and a [`Stride`].
+    pub const fn new(shape: Dim, stride: Stride<Dim>) -> Self {
+        Self { shape, stride }
+    }
+
+    /// Create a new [`Axes`] object with the row-major strides of a
+    /// contiguous array with the given shape
     pub fn new_with_default_stride(shape: Dim) -> Self
     where
         Dim::IndexScalar: std::ops::MulAssign,
     {
-        let mut stride = Dim::zero();
+        // Cloning the shape (rather than using `Dim::zero`) ensures the stride
+        // has the correct number of axes for dynamically-dimensioned arrays
+        let mut stride = shape.clone();
 
         let l = shape.ndim();
         let mut s = Dim::IndexScalar::from(1u16);
@@ -20,12 +46,205 @@ impl<Dim: Dimension> Axes<Dim> {
             // Safety: We are constructing a new Dim, so we know that the
             // stride is valid for a contiguous array
             unsafe {
-                stride.get_mut()[j as usize] = s.clone();
+                stride.get_mut()[j as usize] = s;
             }
 
-            s *= shape[j as DimLen].clone();
+            s *= shape[j as DimLen];
         }
 
-        Self { shape, stride }
+        Self { shape, stride: Stride::new(0, stride) }
+    }
+
+    /// Returns `true` if the elements described by these axes are laid out
+    /// contiguously in row-major order, starting at the offset. Axes with a
+    /// length of one are ignored, since their stride is never used.
+    pub fn is_contiguous(&self) -> bool {
+        let shape = self.shape.as_slice();
+        let strides = self.stride.strides.as_slice();
+
+        let mut expected = 1;
+        for (&len, &stride) in shape.iter().zip(strides).rev() {
+            if len == 0 {
+                return true;
+            }
+
+            if len != 1 && stride != expected {
+                return false;
+            }
+
+            expected *= len;
+        }
+
+        true
+    }
+
+    /// Return the location, within the underlying storage, of the element at
+    /// the multi-dimensional `index`.
+    #[inline(always)]
+    pub fn offset_of(&self, index: &[UDim]) -> usize {
+        self.stride.offset_of(index)
+    }
+
+    /// Return the location, within the underlying storage, of the element at
+    /// the multi-dimensional `index` when these axes are broadcast to a larger
+    /// shape. The index is aligned with the trailing axes, so any extra leading
+    /// indices are ignored, as are indices along axes of length one. This is
+    /// equivalent to treating the strides of those axes as zero.
+    ///
+    /// For an index with the same number of dimensions as these axes, this
+    /// gives the same result as [`Axes::offset_of`].
+    #[inline(always)]
+    pub fn broadcast_offset_of(&self, index: &[UDim]) -> usize {
+        let shape = self.shape.as_slice();
+        let index = &index[index.len() - shape.len()..];
+
+        index.iter().zip(shape).zip(self.stride.strides.as_slice()).fold(
+            self.stride.offset,
+            |acc, ((&i, &len), &s)| {
+                if len == 1 {
+                    acc
+                } else {
+                    advance(acc, i, s)
+                }
+            },
+        )
+    }
+
+    /// Return the location, within the underlying storage, of the element at
+    /// the multi-dimensional `index`, or `None` if the number of indices does
+    /// not match the number of dimensions or any index is out of bounds.
+    #[inline(always)]
+    pub fn checked_offset_of(&self, index: &[UDim]) -> Option<usize> {
+        let shape = self.shape.as_slice();
+
+        if index.len() == shape.len()
+            && index.iter().zip(shape).all(|(&i, &len)| i < len)
+        {
+            Some(self.stride.offset_of(index))
+        } else {
+            None
+        }
+    }
+
+    /// Return the smallest and largest storage locations that can be
+    /// addressed by these axes, or `None` if there are no elements. The
+    /// smallest location may be negative if the strides are invalid.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn storage_bounds(&self) -> Option<(IDim, IDim)> {
+        if self.shape.is_empty() {
+            return None;
+        }
+
+        let mut min = self.stride.offset as IDim;
+        let mut max = min;
+
+        for (&len, &stride) in
+            self.shape.as_slice().iter().zip(self.stride.strides.as_slice())
+        {
+            let extent = (len as IDim - 1) * stride as IDim;
+            if extent < 0 {
+                min += extent;
+            } else {
+                max += extent;
+            }
+        }
+
+        Some((min, max))
+    }
+
+    /// Return axes with the same elements in a different order, where axis
+    /// `i` of the result is axis `order[i]` of these axes. `order` must be a
+    /// permutation of the axes.
+    #[must_use]
+    pub fn permuted(&self, order: &[usize]) -> Self {
+        debug_assert_eq!(order.len(), self.shape.ndim() as usize);
+
+        let mut axes = self.clone();
+        let shape = self.shape.as_slice();
+        let strides = self.stride.strides.as_slice();
+
+        // Safety: The number of axes is unchanged, and each length is moved
+        // together with its stride
+        let (new_shape, new_strides) = unsafe {
+            (axes.shape.as_slice_mut(), axes.stride.strides.as_slice_mut())
+        };
+        for (i, &axis) in order.iter().enumerate() {
+            new_shape[i] = shape[axis];
+            new_strides[i] = strides[axis];
+        }
+
+        axes
+    }
+
+    /// Return axes which address the same elements in the same row-major
+    /// order with the new `shape`, or `None` if that is impossible with the
+    /// current strides. `shape` must have the same number of elements as
+    /// these axes.
+    ///
+    /// Each run of axes whose lengths multiply to the same value in the old
+    /// and new shapes must have strides which nest within each other (as in
+    /// a contiguous array), in which case the run can be split differently.
+    pub fn reshaped<D: Dimension>(&self, shape: D) -> Option<Axes<D>> {
+        debug_assert_eq!(self.shape.len(), shape.len());
+
+        if self.is_contiguous() {
+            let mut axes = Axes::new_with_default_stride(shape);
+            axes.stride.offset = self.stride.offset;
+            return Some(axes);
+        }
+
+        // Axes of length one can be ignored, since their strides are unused
+        let old: Vec<(UDim, UDim)> = self
+            .shape
+            .as_slice()
+            .iter()
+            .copied()
+            .zip(self.stride.strides.as_slice().iter().copied())
+            .filter(|&(len, _)| len != 1)
+            .collect();
+
+        let mut strides = shape.clone();
+        let new = shape.as_slice();
+        {
+            // Safety: Only the strides are modified, not the shape
+            let out = unsafe { strides.as_slice_mut() };
+            out.fill(0);
+
+            let (mut old_start, mut new_start) = (0, 0);
+            while old_start < old.len() && new_start < new.len() {
+                // Find the shortest runs of axes with the same length
+                let (mut old_end, mut new_end) = (old_start + 1, new_start + 1);
+                let mut old_len = old[old_start].0;
+                let mut new_len = new[new_start];
+                while old_len != new_len {
+                    if old_len < new_len {
+                        old_len *= old[old_end].0;
+                        old_end += 1;
+                    } else {
+                        new_len *= new[new_end];
+                        new_end += 1;
+                    }
+                }
+
+                // The strides are signed, so they are multiplied with
+                // wrapping arithmetic
+                let nested = old[old_start..old_end]
+                    .windows(2)
+                    .all(|pair| pair[0].1 == pair[1].1.wrapping_mul(pair[1].0));
+                if !nested {
+                    return None;
+                }
+
+                out[new_end - 1] = old[old_end - 1].1;
+                for k in (new_start + 1..new_end).rev() {
+                    out[k - 1] = out[k].wrapping_mul(new[k]);
+                }
+
+                old_start = old_end;
+                new_start = new_end;
+            }
+        }
+
+        Some(Axes::new(shape, Stride::new(self.stride.offset, strides)))
     }
 }
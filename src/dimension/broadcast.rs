@@ -0,0 +1,223 @@
+//! NumPy-style broadcasting of shapes.
+//!
+//! Two shapes are broadcast by aligning their trailing axes. Along each axis,
+//! the lengths must either be equal, or one of them must be one, in which case
+//! the single element is repeated along that axis. Missing leading axes are
+//! treated as having length one. For example, shapes `[3, 1]` and `[4]`
+//! broadcast to `[3, 4]`.
+
+use crate::{
+    dimension::{
+        dim::{
+            Dim0, Dim1, Dim2, Dim3, Dim4, Dim5, Dim6, Dim7, Dim8, Dimension,
+        },
+        dyn_dim::DimDyn,
+    },
+    error::ShapeError,
+    types::UDim,
+};
+
+/// Computes the dimension type of the result of broadcasting two dimension
+/// types together.
+///
+/// For fixed dimensions, this is the larger of the two. If either is
+/// [`DimDyn`], the result is [`DimDyn`].
+pub trait BroadcastShape<Other: Dimension>: Dimension {
+    /// The dimension type of the broadcast shape
+    type Output: Dimension;
+}
+
+/// Implement [`BroadcastShape`] between `Dim$n` and each smaller `Dim$m`, in
+/// both directions, as well as between `Dim$n` and itself.
+macro_rules! broadcast_shape_impl {
+    ($n: literal: $($m: literal),*) => {
+        paste::paste! {
+            impl BroadcastShape<[< Dim $n >]> for [< Dim $n >] {
+                type Output = [< Dim $n >];
+            }
+
+            impl BroadcastShape<DimDyn> for [< Dim $n >] {
+                type Output = DimDyn;
+            }
+
+            $(
+                impl BroadcastShape<[< Dim $m >]> for [< Dim $n >] {
+                    type Output = [< Dim $n >];
+                }
+
+                impl BroadcastShape<[< Dim $n >]> for [< Dim $m >] {
+                    type Output = [< Dim $n >];
+                }
+            )*
+        }
+    };
+}
+
+broadcast_shape_impl!(0:);
+broadcast_shape_impl!(1: 0);
+broadcast_shape_impl!(2: 0, 1);
+broadcast_shape_impl!(3: 0, 1, 2);
+broadcast_shape_impl!(4: 0, 1, 2, 3);
+broadcast_shape_impl!(5: 0, 1, 2, 3, 4);
+broadcast_shape_impl!(6: 0, 1, 2, 3, 4, 5);
+broadcast_shape_impl!(7: 0, 1, 2, 3, 4, 5, 6);
+broadcast_shape_impl!(8: 0, 1, 2, 3, 4, 5, 6, 7);
+
+impl<Other: Dimension> BroadcastShape<Other> for DimDyn {
+    type Output = Self;
+}
+
+/// Compute the shape resulting from broadcasting `lhs` and `rhs` together.
+///
+/// # Errors
+/// Returns [`ShapeError::BroadcastMismatch`] if the shapes are not compatible,
+/// or [`ShapeError::DimensionMismatch`] if the result cannot be represented
+/// by `Out`.
+///
+/// # Example
+/// ```rust
+/// use tensr::dimension::broadcast::broadcast_shapes;
+/// use tensr::dimension::dim::{Dim2, Dimension};
+///
+/// let shape: Dim2 = broadcast_shapes(&[3, 1], &[4]).unwrap();
+/// assert_eq!(shape.as_slice(), &[3, 4]);
+///
+/// assert!(broadcast_shapes::<Dim2>(&[3, 2], &[4]).is_err());
+/// ```
+pub fn broadcast_shapes<Out: Dimension>(
+    lhs: &[UDim],
+    rhs: &[UDim],
+) -> Result<Out, ShapeError> {
+    let ndim = lhs.len().max(rhs.len());
+    let mut shape = vec![1; ndim];
+
+    // Align the trailing axes of each shape
+    let lhs_skip = ndim - lhs.len();
+    let rhs_skip = ndim - rhs.len();
+
+    for (axis, len) in shape.iter_mut().enumerate() {
+        let l = if axis < lhs_skip { 1 } else { lhs[axis - lhs_skip] };
+        let r = if axis < rhs_skip { 1 } else { rhs[axis - rhs_skip] };
+
+        *len = match (l, r) {
+            (l, r) if l == r => l,
+            (1, r) => r,
+            (l, 1) => l,
+            _ => {
+                return Err(ShapeError::BroadcastMismatch {
+                    lhs: lhs.to_vec(),
+                    rhs: rhs.to_vec(),
+                })
+            }
+        };
+    }
+
+    Out::from_slice(&shape).ok_or_else(|| ShapeError::DimensionMismatch {
+        expected: Out::NDIM.unwrap_or_default(),
+        found: ndim,
+    })
+}
+
+/// Check that `from` can be broadcast to exactly the shape `to`.
+///
+/// This is the case if every trailing axis of `from` has either
+/// the same length as the corresponding axis of `to`, or a length of one.
+///
+/// # Errors
+/// Returns [`ShapeError::BroadcastTo`] if `from` cannot be broadcast to `to`.
+///
+/// # Example
+/// ```rust
+/// use tensr::dimension::broadcast::check_broadcast_to;
+///
+/// assert!(check_broadcast_to(&[1, 4], &[3, 4]).is_ok());
+/// assert!(check_broadcast_to(&[3, 4], &[1, 4]).is_err());
+/// ```
+pub fn check_broadcast_to(
+    from: &[UDim],
+    to: &[UDim],
+) -> Result<(), ShapeError> {
+    let compatible = from.len() <= to.len()
+        && from
+            .iter()
+            .rev()
+            .zip(to.iter().rev())
+            .all(|(&f, &t)| f == t || f == 1);
+
+    if compatible {
+        Ok(())
+    } else {
+        Err(ShapeError::BroadcastTo { from: from.to_vec(), to: to.to_vec() })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_broadcast_shapes() {
+        let shape: Dim2 = broadcast_shapes(&[3, 1], &[1, 4]).unwrap();
+        assert_eq!(shape.as_slice(), &[3, 4]);
+
+        let shape: Dim3 = broadcast_shapes(&[5, 1, 4], &[3, 1]).unwrap();
+        assert_eq!(shape.as_slice(), &[5, 3, 4]);
+
+        let shape: Dim2 = broadcast_shapes(&[], &[2, 2]).unwrap();
+        assert_eq!(shape.as_slice(), &[2, 2]);
+
+        let shape: Dim1 = broadcast_shapes(&[0], &[1]).unwrap();
+        assert_eq!(shape.as_slice(), &[0]);
+
+        let shape: DimDyn = broadcast_shapes(&[2, 1, 3], &[4, 1]).unwrap();
+        assert_eq!(shape.as_slice(), &[2, 4, 3]);
+    }
+
+    #[test]
+    fn test_broadcast_shapes_errors() {
+        assert_eq!(
+            broadcast_shapes::<Dim2>(&[3, 2], &[3]).err(),
+            Some(ShapeError::BroadcastMismatch {
+                lhs: vec![3, 2],
+                rhs: vec![3]
+            })
+        );
+        assert_eq!(
+            broadcast_shapes::<Dim2>(&[0], &[2]).err(),
+            Some(ShapeError::BroadcastMismatch { lhs: vec![0], rhs: vec![2] })
+        );
+        assert_eq!(
+            broadcast_shapes::<Dim1>(&[2, 2], &[2]).err(),
+            Some(ShapeError::DimensionMismatch { expected: 1, found: 2 })
+        );
+    }
+
+    #[test]
+    fn test_check_broadcast_to() {
+        assert!(check_broadcast_to(&[], &[2, 3]).is_ok());
+        assert!(check_broadcast_to(&[3], &[2, 3]).is_ok());
+        assert!(check_broadcast_to(&[2, 1], &[2, 3]).is_ok());
+        assert!(check_broadcast_to(&[1, 1, 3], &[1, 3]).is_err());
+        assert_eq!(
+            check_broadcast_to(&[2, 3], &[2, 1]),
+            Err(ShapeError::BroadcastTo { from: vec![2, 3], to: vec![2, 1] })
+        );
+    }
+
+    #[test]
+    fn test_broadcast_shape_types() {
+        fn output<A, B>() -> Option<usize>
+        where
+            A: BroadcastShape<B>,
+            B: Dimension,
+        {
+            <A as BroadcastShape<B>>::Output::NDIM
+        }
+
+        assert_eq!(output::<Dim0, Dim3>(), Some(3));
+        assert_eq!(output::<Dim4, Dim2>(), Some(4));
+        assert_eq!(output::<Dim8, Dim8>(), Some(8));
+        assert_eq!(output::<Dim2, DimDyn>(), None);
+        assert_eq!(output::<DimDyn, Dim5>(), None);
+    }
+}
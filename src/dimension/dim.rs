@@ -1,5 +1,12 @@
-use crate::types::{DimLen, UDim};
+//! Fixed and dynamic numbers of dimensions.
 
+use crate::{
+    dimension::dyn_dim::DimDyn,
+    types::{DimLen, UDim},
+};
+
+/// Invoke `$macro!(1, 2, ..., 8)` with every fixed number of dimensions
+/// greater than zero.
 #[macro_export]
 macro_rules! repeat_for_dims {
     ($macro: tt) => {
@@ -12,23 +19,57 @@ macro_rules! repeat_for_dims {
 pub trait Dimension:
     std::fmt::Debug + Clone + std::ops::Index<DimLen, Output = Self::IndexScalar>
 {
+    /// The type of each axis length
     type IndexScalar: From<u16>
         + std::ops::Mul
         + std::ops::MulAssign
         + Copy
         + std::fmt::Debug;
+
+    /// The type storing the length of every axis
     type Index: std::ops::Index<usize, Output = Self::IndexScalar>
         + std::ops::IndexMut<usize>
         + Clone;
 
+    /// The number of axes for fixed-dimension types, or `None` if the number
+    /// of axes is only known at runtime.
+    const NDIM: Option<usize>;
+
+    /// The dimension type with one more axis than this one. Beyond eight
+    /// dimensions, this is [`DimDyn`].
+    type Larger: Dimension;
+
+    /// The dimension type with one fewer axis than this one. [`Dim0`] is its
+    /// own smaller dimension.
+    type Smaller: Dimension;
+
+    /// A dimension object whose axes all have length zero
     fn zero() -> Self;
+
+    /// Create a new dimension object from a slice of axis lengths. Returns
+    /// `None` if the number of values is not valid for this dimension type.
+    fn from_slice(values: &[UDim]) -> Option<Self>;
+
+    /// The number of axes
     fn ndim(&self) -> DimLen;
+
+    /// The number of axes, as a `usize`
     fn len(&self) -> usize;
 
+    /// Returns `true` if there are no axes
     fn is_empty(&self) -> bool {
         self.len() == 0
     }
 
+    /// Return the extent of each axis as a slice.
+    fn as_slice(&self) -> &[UDim];
+
+    /// Return the extent of each axis as a mutable slice.
+    ///
+    /// # Safety
+    /// See [`Dimension::get_mut`].
+    unsafe fn as_slice_mut(&mut self) -> &mut [UDim];
+
     /// Return a mutable reference to the underlying storage.
     ///
     /// # Safety
@@ -66,16 +107,25 @@ impl<Index> Dim<Index> {
 }
 
 macro_rules! dim_def {
-    ($($n: literal),*) => {
+    ($(($n: literal, $smaller: ty, $larger: ty)),*) => {
        $(
         paste::paste! {
+            #[doc = concat!("A shape with ", stringify!($n), " axes")]
             pub type [< Dim $n >] = Dim<[UDim; $n]>;
             impl Dimension for [< Dim $n >] {
                 type IndexScalar = UDim;
                 type Index = [UDim; $n];
+                type Larger = $larger;
+                type Smaller = $smaller;
+
+                const NDIM: Option<usize> = Some($n);
 
                 fn zero() -> Self {
-                    Self::new([UDim::from(0u16); $n])
+                    Self::new([0; $n])
+                }
+
+                fn from_slice(values: &[UDim]) -> Option<Self> {
+                    <[UDim; $n]>::try_from(values).ok().map(Self::new)
                 }
 
                 fn ndim(&self) -> DimLen {
@@ -86,6 +136,14 @@ macro_rules! dim_def {
                     (0..$n).into_iter().fold(1, |acc, i| acc * self.index[i])
                 }
 
+                fn as_slice(&self) -> &[UDim] {
+                    &self.index
+                }
+
+                unsafe fn as_slice_mut(&mut self) -> &mut [UDim] {
+                    &mut self.index
+                }
+
                 unsafe fn get_mut(&mut self) -> &mut Self::Index {
                     &mut self.index
                 }
@@ -115,7 +173,17 @@ macro_rules! dim_def {
     };
 }
 
-repeat_for_dims!(dim_def);
+dim_def!(
+    (0, Dim0, Dim1),
+    (1, Dim0, Dim2),
+    (2, Dim1, Dim3),
+    (3, Dim2, Dim4),
+    (4, Dim3, Dim5),
+    (5, Dim4, Dim6),
+    (6, Dim5, Dim7),
+    (7, Dim6, Dim8),
+    (8, Dim7, DimDyn)
+);
 
 #[cfg(test)]
 mod test {
@@ -1,4 +1,10 @@
-use std::ops::Deref;
+//! Storage for the shape and strides of an array whose number of dimensions
+//! is only known at runtime.
+
+use std::{
+    fmt::Write,
+    ops::{Deref, DerefMut},
+};
 
 use crate::{
     dimension::dim::{Dim, Dimension},
@@ -15,6 +21,8 @@ use crate::{
 /// cases will likely yield a performance improvement in general.
 const MAX_STACK_DIMS: usize = 4;
 
+/// The lengths or strides of the axes of a [`DimDyn`], stored on the stack
+/// when there are few enough axes
 #[derive(Debug)]
 pub enum DynIndex {
     /// Stack-allocated array storing `.0` dimensions
@@ -34,11 +42,13 @@ impl Clone for DynIndex {
 }
 
 impl DynIndex {
+    /// An index with no axes
     #[must_use]
     pub const fn zero() -> Self {
         Self::Stack(0, [0; MAX_STACK_DIMS])
     }
 
+    /// The number of axes
     #[must_use]
     pub const fn len(&self) -> DimLen {
         #[allow(clippy::cast_possible_truncation)]
@@ -48,6 +58,7 @@ impl DynIndex {
         }
     }
 
+    /// Returns `true` if there are no axes
     #[must_use]
     pub const fn is_empty(&self) -> bool {
         match self {
@@ -68,6 +79,15 @@ impl Deref for DynIndex {
     }
 }
 
+impl DerefMut for DynIndex {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Stack(len, stack) => &mut stack[0..(*len as usize)],
+            Self::Heap(heap) => &mut heap[..],
+        }
+    }
+}
+
 macro_rules! dyn_index_index {
     ($t: ty) => {
         impl std::ops::Index<$t> for DynIndex {
@@ -159,11 +179,19 @@ impl DimDyn {
 impl Dimension for DimDyn {
     type IndexScalar = UDim;
     type Index = DynIndex;
+    type Larger = Self;
+    type Smaller = Self;
+
+    const NDIM: Option<usize> = None;
 
     fn zero() -> Self {
         Self::new(DynIndex::zero())
     }
 
+    fn from_slice(values: &[UDim]) -> Option<Self> {
+        Some(Self::new_from(values))
+    }
+
     fn ndim(&self) -> DimLen {
         #[allow(clippy::cast_possible_truncation)]
         match self.get() {
@@ -181,6 +209,14 @@ impl Dimension for DimDyn {
         }
     }
 
+    fn as_slice(&self) -> &[UDim] {
+        &self.index
+    }
+
+    unsafe fn as_slice_mut(&mut self) -> &mut [UDim] {
+        &mut self.index
+    }
+
     unsafe fn get_mut(&mut self) -> &mut Self::Index {
         &mut self.index
     }
@@ -194,7 +230,7 @@ impl std::fmt::Debug for DimDyn {
         match self.get() {
             DynIndex::Stack(l, stack) => {
                 for i in 0..*l {
-                    s.push_str(&format!("{}", stack[i as usize]));
+                    let _ = write!(s, "{}", stack[i as usize]);
                     if i + 1 < *l {
                         s.push_str(", ");
                     }
@@ -202,7 +238,7 @@ impl std::fmt::Debug for DimDyn {
             }
             DynIndex::Heap(b) => {
                 for (i, v) in b.iter().enumerate() {
-                    s.push_str(&format!("{v}"));
+                    let _ = write!(s, "{v}");
                     if i + 1 < b.len() {
                         s.push_str(", ");
                     }
@@ -1 +1,118 @@
+//! Multi-indices for accessing the elements of an array.
 
+use crate::{
+    dimension::{
+        dim::{Dim, Dim1, Dimension},
+        dyn_dim::DimDyn,
+    },
+    types::UDim,
+};
+
+/// Advance `index` to the next position in row-major order for an array with
+/// the given `shape`, so the last axis changes fastest.
+///
+/// Returns `false` (leaving `index` as all zeros) once every position has been
+/// visited.
+#[inline(always)]
+pub(crate) fn next_index(index: &mut [UDim], shape: &[UDim]) -> bool {
+    for axis in (0..shape.len()).rev() {
+        index[axis] += 1;
+
+        if index[axis] < shape[axis] {
+            return true;
+        }
+
+        index[axis] = 0;
+    }
+
+    false
+}
+
+/// Move `index` back to the previous position in row-major order for an
+/// array with the given `shape`, the reverse of [`next_index`].
+///
+/// Returns `false` (leaving `index` at the last position) if `index` was the
+/// first position.
+#[inline(always)]
+pub(crate) fn prev_index(index: &mut [UDim], shape: &[UDim]) -> bool {
+    for axis in (0..shape.len()).rev() {
+        if index[axis] > 0 {
+            index[axis] -= 1;
+            return true;
+        }
+
+        index[axis] = shape[axis] - 1;
+    }
+
+    false
+}
+
+/// Convert the row-major `flat` index into a multi-dimensional index for an
+/// array with the given `shape`, writing the result into `index`.
+#[inline(always)]
+pub(crate) fn unravel_index(
+    mut flat: usize,
+    shape: &[UDim],
+    index: &mut [UDim],
+) {
+    for axis in (0..shape.len()).rev() {
+        index[axis] = flat % shape[axis];
+        flat /= shape[axis];
+    }
+}
+
+/// A type which can be used to index a single element of an array with
+/// dimension `D`.
+///
+/// Fixed-dimension arrays are indexed with arrays of the correct length, such
+/// as `[usize; 2]` for a [`Dim2`](crate::dimension::dim::Dim2), so the number
+/// of indices is checked at compile time. Dynamically-dimensioned arrays can be
+/// indexed with slices or arrays of any length, and the number of indices is
+/// checked at runtime.
+pub trait NdIndex<D: Dimension> {
+    /// Return the index along each axis
+    fn index_slice(&self) -> &[UDim];
+}
+
+macro_rules! nd_index_impl {
+    ($($n: literal),*) => {
+        $(
+            impl NdIndex<Dim<[UDim; $n]>> for [UDim; $n] {
+                #[inline(always)]
+                fn index_slice(&self) -> &[UDim] {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+nd_index_impl!(0, 1, 2, 3, 4, 5, 6, 7, 8);
+
+impl NdIndex<Dim1> for UDim {
+    #[inline(always)]
+    fn index_slice(&self) -> &[UDim] {
+        std::slice::from_ref(self)
+    }
+}
+
+impl<const N: usize> NdIndex<DimDyn> for [UDim; N] {
+    #[inline(always)]
+    fn index_slice(&self) -> &[UDim] {
+        self
+    }
+}
+
+impl NdIndex<DimDyn> for &[UDim] {
+    #[inline(always)]
+    fn index_slice(&self) -> &[UDim] {
+        self
+    }
+}
+
+impl NdIndex<DimDyn> for Vec<UDim> {
+    #[inline(always)]
+    fn index_slice(&self) -> &[UDim] {
+        self
+    }
+}
@@ -0,0 +1,238 @@
+//! Shapes of the results of matrix multiplication.
+//!
+//! Following `NumPy`'s `matmul`, a one-dimensional left-hand operand is treated
+//! as a row vector and a one-dimensional right-hand operand as a column
+//! vector, and the corresponding axis is removed from the result. For
+//! example, multiplying shapes `[3, 4]` and `[4]` gives shape `[3]`.
+//!
+//! Operands with more than two axes are stacks of matrices, indexed by their
+//! leading (batch) axes. The batch axes of the operands are broadcast
+//! together, so shapes `[8, 3, 4]` and `[4, 5]` give shape `[8, 3, 5]`.
+
+use crate::{
+    dimension::{
+        broadcast::broadcast_shapes,
+        dim::{
+            Dim0, Dim1, Dim2, Dim3, Dim4, Dim5, Dim6, Dim7, Dim8, Dimension,
+        },
+        dyn_dim::DimDyn,
+    },
+    error::ShapeError,
+    types::UDim,
+};
+
+/// Computes the dimension type of the result of multiplying matrices (or
+/// vectors, or stacks of matrices) with dimension types `Self` and `Rhs`.
+pub trait MatMulShape<Rhs: Dimension>: Dimension {
+    /// The dimension type of the product
+    type Output: Dimension;
+}
+
+/// Implement [`MatMulShape`] for a pair of dimension types.
+macro_rules! matmul_shape_impl {
+    ($lhs: ty, $rhs: ty => $out: ty) => {
+        impl MatMulShape<$rhs> for $lhs {
+            type Output = $out;
+        }
+    };
+}
+
+/// Implement [`MatMulShape`] between stacks of matrices with `$n` axes and
+/// themselves, vectors, [`DimDyn`] and each smaller stack `Dim$m`, in both
+/// directions. The batch axes are broadcast, so the result has `$n` axes,
+/// less one if the other operand is a vector.
+macro_rules! matmul_stack_impl {
+    ($n: literal: $($m: literal),*) => {
+        paste::paste! {
+            matmul_shape_impl!([< Dim $n >], [< Dim $n >] => [< Dim $n >]);
+            matmul_shape_impl!(
+                [< Dim $n >], Dim1 => <[< Dim $n >] as Dimension>::Smaller
+            );
+            matmul_shape_impl!(
+                Dim1, [< Dim $n >] => <[< Dim $n >] as Dimension>::Smaller
+            );
+            matmul_shape_impl!([< Dim $n >], DimDyn => DimDyn);
+
+            $(
+                matmul_shape_impl!([< Dim $n >], [< Dim $m >] => [< Dim $n >]);
+                matmul_shape_impl!([< Dim $m >], [< Dim $n >] => [< Dim $n >]);
+            )*
+        }
+    };
+}
+
+matmul_shape_impl!(Dim1, Dim1 => Dim0);
+matmul_shape_impl!(Dim1, DimDyn => DimDyn);
+matmul_stack_impl!(2:);
+matmul_stack_impl!(3: 2);
+matmul_stack_impl!(4: 2, 3);
+matmul_stack_impl!(5: 2, 3, 4);
+matmul_stack_impl!(6: 2, 3, 4, 5);
+matmul_stack_impl!(7: 2, 3, 4, 5, 6);
+matmul_stack_impl!(8: 2, 3, 4, 5, 6, 7);
+
+impl<Other: Dimension> MatMulShape<Other> for DimDyn {
+    type Output = Self;
+}
+
+/// The sizes of a (batched) matrix multiplication: each matrix of the
+/// left-hand operand has `m` rows and `k` columns, and each matrix of the
+/// right-hand operand has `k` rows and `n` columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatMulSizes {
+    /// The broadcast shape of the batch axes of the operands, which is empty
+    /// if neither operand is a stack of matrices
+    pub batch: Vec<UDim>,
+
+    /// The number of rows of the left-hand operand and the result
+    pub m: usize,
+
+    /// The number of columns of the left-hand operand and rows of the
+    /// right-hand operand
+    pub k: usize,
+
+    /// The number of columns of the right-hand operand and the result
+    pub n: usize,
+}
+
+/// Compute the shape of the product of matrices (or vectors, or stacks of
+/// matrices) with shapes `lhs` and `rhs`, along with the sizes of the
+/// multiplication.
+///
+/// # Errors
+/// Returns [`ShapeError::MatMulMismatch`] if the number of columns of `lhs`
+/// does not match the number of rows of `rhs`,
+/// [`ShapeError::BroadcastMismatch`] if the batch axes cannot be broadcast
+/// together, or [`ShapeError::DimensionMismatch`] if either operand has no
+/// axes or the result cannot be represented by `Out`.
+///
+/// # Example
+/// ```rust
+/// use tensr::dimension::dim::{Dim1, Dim3, Dimension};
+/// use tensr::dimension::matmul::matmul_shape;
+///
+/// let (shape, sizes) = matmul_shape::<Dim1>(&[3, 4], &[4]).unwrap();
+/// assert_eq!(shape.as_slice(), &[3]);
+/// assert_eq!((sizes.m, sizes.k, sizes.n), (3, 4, 1));
+///
+/// let (shape, sizes) = matmul_shape::<Dim3>(&[8, 3, 4], &[4, 5]).unwrap();
+/// assert_eq!(shape.as_slice(), &[8, 3, 5]);
+/// assert_eq!(sizes.batch, vec![8]);
+///
+/// assert!(matmul_shape::<Dim1>(&[3, 4], &[3]).is_err());
+/// ```
+pub fn matmul_shape<Out: Dimension>(
+    lhs: &[UDim],
+    rhs: &[UDim],
+) -> Result<(Out, MatMulSizes), ShapeError> {
+    if lhs.is_empty() || rhs.is_empty() {
+        return Err(ShapeError::DimensionMismatch { expected: 1, found: 0 });
+    }
+
+    // Every axis before the last two is a batch axis
+    let (lhs_batch, lhs_matrix) = lhs.split_at(lhs.len().saturating_sub(2));
+    let (rhs_batch, rhs_matrix) = rhs.split_at(rhs.len().saturating_sub(2));
+
+    // A vector on the left is a single row, and on the right a single column
+    let (m, k) = match *lhs_matrix {
+        [k] => (None, k),
+        [m, k] => (Some(m), k),
+        _ => unreachable!(),
+    };
+    let (rhs_k, n) = match *rhs_matrix {
+        [k] => (k, None),
+        [k, n] => (k, Some(n)),
+        _ => unreachable!(),
+    };
+
+    if k != rhs_k {
+        return Err(ShapeError::MatMulMismatch {
+            lhs: lhs.to_vec(),
+            rhs: rhs.to_vec(),
+        });
+    }
+
+    let batch: DimDyn =
+        broadcast_shapes(lhs_batch, rhs_batch).map_err(|_| {
+            ShapeError::BroadcastMismatch {
+                lhs: lhs.to_vec(),
+                rhs: rhs.to_vec(),
+            }
+        })?;
+    let batch = batch.as_slice().to_vec();
+
+    let shape: Vec<UDim> = batch.iter().copied().chain(m).chain(n).collect();
+    let out = Out::from_slice(&shape).ok_or_else(|| {
+        ShapeError::DimensionMismatch {
+            expected: Out::NDIM.unwrap_or_default(),
+            found: shape.len(),
+        }
+    })?;
+
+    Ok((out, MatMulSizes { batch, m: m.unwrap_or(1), k, n: n.unwrap_or(1) }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_matmul_shape() {
+        let (shape, sizes) = matmul_shape::<Dim2>(&[2, 3], &[3, 5]).unwrap();
+        assert_eq!(shape.as_slice(), &[2, 5]);
+        assert_eq!(sizes, MatMulSizes { batch: vec![], m: 2, k: 3, n: 5 });
+
+        let (shape, sizes) = matmul_shape::<Dim1>(&[3], &[3, 5]).unwrap();
+        assert_eq!(shape.as_slice(), &[5]);
+        assert_eq!(sizes, MatMulSizes { batch: vec![], m: 1, k: 3, n: 5 });
+
+        let (shape, sizes) = matmul_shape::<Dim0>(&[4], &[4]).unwrap();
+        assert_eq!(shape.as_slice(), &[] as &[UDim]);
+        assert_eq!(sizes, MatMulSizes { batch: vec![], m: 1, k: 4, n: 1 });
+    }
+
+    #[test]
+    fn test_batched_matmul_shape() {
+        let (shape, sizes) =
+            matmul_shape::<Dim3>(&[2, 3, 4], &[2, 4, 5]).unwrap();
+        assert_eq!(shape.as_slice(), &[2, 3, 5]);
+        assert_eq!(sizes, MatMulSizes { batch: vec![2], m: 3, k: 4, n: 5 });
+
+        let (shape, _) =
+            matmul_shape::<Dim4>(&[6, 1, 3, 4], &[5, 4, 2]).unwrap();
+        assert_eq!(shape.as_slice(), &[6, 5, 3, 2]);
+
+        let (shape, sizes) = matmul_shape::<Dim2>(&[4], &[7, 4, 2]).unwrap();
+        assert_eq!(shape.as_slice(), &[7, 2]);
+        assert_eq!(sizes, MatMulSizes { batch: vec![7], m: 1, k: 4, n: 2 });
+
+        let (shape, _) = matmul_shape::<DimDyn>(&[2, 1, 3, 4], &[4]).unwrap();
+        assert_eq!(shape.as_slice(), &[2, 1, 3]);
+    }
+
+    #[test]
+    fn test_matmul_shape_errors() {
+        assert_eq!(
+            matmul_shape::<Dim2>(&[2, 3], &[2, 3]).err(),
+            Some(ShapeError::MatMulMismatch {
+                lhs: vec![2, 3],
+                rhs: vec![2, 3]
+            })
+        );
+        assert_eq!(
+            matmul_shape::<Dim1>(&[2, 3], &[3, 4]).err(),
+            Some(ShapeError::DimensionMismatch { expected: 1, found: 2 })
+        );
+        assert_eq!(
+            matmul_shape::<Dim3>(&[2, 3, 4], &[3, 4, 5]).err(),
+            Some(ShapeError::BroadcastMismatch {
+                lhs: vec![2, 3, 4],
+                rhs: vec![3, 4, 5]
+            })
+        );
+        assert_eq!(
+            matmul_shape::<Dim1>(&[], &[3]).err(),
+            Some(ShapeError::DimensionMismatch { expected: 1, found: 0 })
+        );
+    }
+}
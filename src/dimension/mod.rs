@@ -1,5 +1,10 @@
+//! Shapes, strides and indices of arrays.
+
 pub mod axes;
+pub mod broadcast;
 pub mod dim;
 pub mod dyn_dim;
 pub mod index;
+pub mod matmul;
+pub mod slice;
 pub mod stride;
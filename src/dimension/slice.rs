@@ -0,0 +1,723 @@
+//! Types for describing multi-dimensional slices of arrays. Slices are most
+//! easily created with the [`s!`](crate::s) macro.
+
+use std::{
+    marker::PhantomData,
+    ops::{
+        Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive,
+    },
+};
+
+use crate::{
+    dimension::{
+        axes::Axes,
+        dim::{
+            Dim0, Dim1, Dim2, Dim3, Dim4, Dim5, Dim6, Dim7, Dim8, Dimension,
+        },
+        dyn_dim::DimDyn,
+        stride::{advance, Stride},
+    },
+    error::ShapeError,
+    types::{IDim, UDim},
+};
+
+/// A single element of a slice, describing what happens to one axis of the
+/// array being sliced.
+///
+/// Negative indices count backwards from the end of the axis, so `-1` refers
+/// to the last element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SliceElem {
+    /// Select the elements in `start..end`, taking every `step`'th element.
+    /// If `end` is `None`, the range extends to the end of the axis. A
+    /// negative step selects elements in reverse order, starting from the end
+    /// of the range.
+    Slice {
+        /// The first index in the range
+        start: IDim,
+
+        /// One past the last index in the range
+        end: Option<IDim>,
+
+        /// The distance between selected elements
+        step: IDim,
+    },
+
+    /// Select a single index, removing the axis from the result.
+    Index(IDim),
+
+    /// Insert a new axis of length one. This does not consume an axis of the
+    /// array being sliced.
+    NewAxis,
+}
+
+/// A marker which inserts a new axis of length one when used in [`s!`].
+///
+/// [`s!`]: crate::s
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NewAxis;
+
+impl SliceElem {
+    /// Return a copy of this slice element with the given step.
+    ///
+    /// # Panics
+    /// Panics if this element is not a [`SliceElem::Slice`], since only
+    /// ranges may have a step.
+    #[must_use]
+    pub fn step_by(self, step: IDim) -> Self {
+        match self {
+            Self::Slice { start, end, .. } => Self::Slice { start, end, step },
+            _ => panic!("only ranges may have a step (received {self:?})"),
+        }
+    }
+}
+
+impl From<RangeFull> for SliceElem {
+    fn from(_: RangeFull) -> Self {
+        Self::Slice { start: 0, end: None, step: 1 }
+    }
+}
+
+impl From<NewAxis> for SliceElem {
+    fn from(_: NewAxis) -> Self {
+        Self::NewAxis
+    }
+}
+
+macro_rules! slice_elem_from {
+    ($($t: ty),*) => {
+        $(
+            #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+            impl From<$t> for SliceElem {
+                fn from(index: $t) -> Self {
+                    Self::Index(index as IDim)
+                }
+            }
+
+            #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+            impl From<Range<$t>> for SliceElem {
+                fn from(r: Range<$t>) -> Self {
+                    Self::Slice {
+                        start: r.start as IDim,
+                        end: Some(r.end as IDim),
+                        step: 1,
+                    }
+                }
+            }
+
+            #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+            impl From<RangeInclusive<$t>> for SliceElem {
+                fn from(r: RangeInclusive<$t>) -> Self {
+                    let end = *r.end() as IDim;
+                    Self::Slice {
+                        start: *r.start() as IDim,
+                        // An inclusive range ending at -1 covers the rest of
+                        // the axis
+                        end: if end == -1 { None } else { Some(end + 1) },
+                        step: 1,
+                    }
+                }
+            }
+
+            #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+            impl From<RangeFrom<$t>> for SliceElem {
+                fn from(r: RangeFrom<$t>) -> Self {
+                    Self::Slice { start: r.start as IDim, end: None, step: 1 }
+                }
+            }
+
+            #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+            impl From<RangeTo<$t>> for SliceElem {
+                fn from(r: RangeTo<$t>) -> Self {
+                    Self::Slice { start: 0, end: Some(r.end as IDim), step: 1 }
+                }
+            }
+
+            #[allow(clippy::cast_possible_wrap, clippy::cast_lossless)]
+            impl From<RangeToInclusive<$t>> for SliceElem {
+                fn from(r: RangeToInclusive<$t>) -> Self {
+                    let end = r.end as IDim;
+                    Self::Slice {
+                        start: 0,
+                        end: if end == -1 { None } else { Some(end + 1) },
+                        step: 1,
+                    }
+                }
+            }
+
+            impl SliceNextDim for $t {
+                type NextIn<D: Dimension> = D::Larger;
+                type NextOut<D: Dimension> = D;
+            }
+
+            impl SliceNextDim for Range<$t> {
+                type NextIn<D: Dimension> = D::Larger;
+                type NextOut<D: Dimension> = D::Larger;
+            }
+
+            impl SliceNextDim for RangeInclusive<$t> {
+                type NextIn<D: Dimension> = D::Larger;
+                type NextOut<D: Dimension> = D::Larger;
+            }
+
+            impl SliceNextDim for RangeFrom<$t> {
+                type NextIn<D: Dimension> = D::Larger;
+                type NextOut<D: Dimension> = D::Larger;
+            }
+
+            impl SliceNextDim for RangeTo<$t> {
+                type NextIn<D: Dimension> = D::Larger;
+                type NextOut<D: Dimension> = D::Larger;
+            }
+
+            impl SliceNextDim for RangeToInclusive<$t> {
+                type NextIn<D: Dimension> = D::Larger;
+                type NextOut<D: Dimension> = D::Larger;
+            }
+        )*
+    };
+}
+
+slice_elem_from!(usize, isize, i32);
+
+/// Computes, at compile time, how a slice element changes the number of
+/// dimensions of the array being sliced and of the result. This is used by
+/// the [`s!`](crate::s) macro.
+pub trait SliceNextDim {
+    /// The number of input dimensions after this element, given `D` input
+    /// dimensions before it
+    type NextIn<D: Dimension>: Dimension;
+
+    /// The number of output dimensions after this element, given `D` output
+    /// dimensions before it
+    type NextOut<D: Dimension>: Dimension;
+
+    /// Advance the input dimension type past this element
+    fn next_in<D: Dimension>(
+        &self,
+        _: PhantomData<D>,
+    ) -> PhantomData<Self::NextIn<D>> {
+        PhantomData
+    }
+
+    /// Advance the output dimension type past this element
+    fn next_out<D: Dimension>(
+        &self,
+        _: PhantomData<D>,
+    ) -> PhantomData<Self::NextOut<D>> {
+        PhantomData
+    }
+}
+
+impl SliceNextDim for RangeFull {
+    type NextIn<D: Dimension> = D::Larger;
+    type NextOut<D: Dimension> = D::Larger;
+}
+
+impl SliceNextDim for NewAxis {
+    type NextIn<D: Dimension> = D;
+    type NextOut<D: Dimension> = D::Larger;
+}
+
+/// A complete slice specification for an array with dimension `In`, producing
+/// a view with dimension `Out`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SliceInfo<In, Out> {
+    elems: Vec<SliceElem>,
+    phantom: PhantomData<(In, Out)>,
+}
+
+impl<In, Out> SliceInfo<In, Out>
+where
+    In: Dimension,
+    Out: Dimension,
+{
+    /// Create a new [`SliceInfo`] from a list of slice elements.
+    ///
+    /// # Errors
+    /// Returns [`ShapeError::DimensionMismatch`] if the elements do not
+    /// consume `In` axes, or do not produce `Out` axes.
+    pub fn new(elems: Vec<SliceElem>) -> Result<Self, ShapeError> {
+        let consumed =
+            elems.iter().filter(|e| !matches!(e, SliceElem::NewAxis)).count();
+        let produced =
+            elems.iter().filter(|e| !matches!(e, SliceElem::Index(_))).count();
+
+        for (ndim, count) in [(In::NDIM, consumed), (Out::NDIM, produced)] {
+            if let Some(ndim) = ndim {
+                if ndim != count {
+                    return Err(ShapeError::DimensionMismatch {
+                        expected: ndim,
+                        found: count,
+                    });
+                }
+            }
+        }
+
+        Ok(Self { elems, phantom: PhantomData })
+    }
+
+    /// Create a new [`SliceInfo`] where the dimension types have been computed
+    /// by [`SliceNextDim`]. This is used by the [`s!`](crate::s) macro, and
+    /// should not be called directly.
+    #[doc(hidden)]
+    #[must_use]
+    pub const fn from_parts(
+        elems: Vec<SliceElem>,
+        _: PhantomData<In>,
+        _: PhantomData<Out>,
+    ) -> Self {
+        Self { elems, phantom: PhantomData }
+    }
+
+    /// The elements of this slice
+    #[must_use]
+    pub fn elems(&self) -> &[SliceElem] {
+        &self.elems
+    }
+}
+
+/// A type which can be used to slice an array with dimension `D`.
+///
+/// For fixed-dimension arrays, the slice must consume exactly `D` axes, which
+/// is checked at compile time. Slicing a [`DimDyn`] array always produces a
+/// [`DimDyn`] result, and the number of axes is checked at runtime.
+pub trait SliceArg<D: Dimension> {
+    /// The dimension of the sliced view
+    type OutDim: Dimension;
+
+    /// The elements of this slice
+    fn slice_elems(&self) -> &[SliceElem];
+}
+
+impl<D, T> SliceArg<D> for &T
+where
+    D: Dimension,
+    T: SliceArg<D> + ?Sized,
+{
+    type OutDim = T::OutDim;
+
+    fn slice_elems(&self) -> &[SliceElem] {
+        (**self).slice_elems()
+    }
+}
+
+macro_rules! slice_arg_impl {
+    ($($dim: ty),*) => {
+        $(
+            impl<Out> SliceArg<$dim> for SliceInfo<$dim, Out>
+            where
+                Out: Dimension,
+            {
+                type OutDim = Out;
+
+                fn slice_elems(&self) -> &[SliceElem] {
+                    &self.elems
+                }
+            }
+        )*
+    };
+}
+
+slice_arg_impl!(Dim0, Dim1, Dim2, Dim3, Dim4, Dim5, Dim6, Dim7, Dim8);
+
+impl<In, Out> SliceArg<DimDyn> for SliceInfo<In, Out>
+where
+    In: Dimension,
+    Out: Dimension,
+{
+    type OutDim = DimDyn;
+
+    fn slice_elems(&self) -> &[SliceElem] {
+        &self.elems
+    }
+}
+
+impl SliceArg<DimDyn> for [SliceElem] {
+    type OutDim = DimDyn;
+
+    fn slice_elems(&self) -> &[SliceElem] {
+        self
+    }
+}
+
+impl SliceArg<DimDyn> for Vec<SliceElem> {
+    type OutDim = DimDyn;
+
+    fn slice_elems(&self) -> &[SliceElem] {
+        self
+    }
+}
+
+/// Convert a possibly-negative index into an unsigned one, where `inclusive`
+/// allows the index to be equal to the length of the axis.
+#[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+const fn normalize_index(
+    index: IDim,
+    len: UDim,
+    axis: usize,
+    inclusive: bool,
+) -> Result<UDim, ShapeError> {
+    let normalized = if index < 0 { index + len as IDim } else { index };
+    let limit = if inclusive { len as IDim } else { len as IDim - 1 };
+
+    if normalized < 0 || normalized > limit {
+        Err(ShapeError::IndexOutOfBounds { axis, index, len })
+    } else {
+        Ok(normalized as UDim)
+    }
+}
+
+impl<D: Dimension> Axes<D> {
+    /// Apply a slice to these axes, returning the axes of the resulting view.
+    /// The storage is unaffected, so the result refers to the same data.
+    ///
+    /// # Errors
+    /// Returns an error if the number of slice elements is invalid for the
+    /// input or output dimensions, or if any index or range is out of bounds
+    /// for its axis.
+    #[allow(clippy::cast_possible_wrap, clippy::cast_sign_loss)]
+    pub fn slice<Out: Dimension>(
+        &self,
+        elems: &[SliceElem],
+    ) -> Result<Axes<Out>, ShapeError> {
+        let ndim = self.shape.ndim() as usize;
+        let consumed =
+            elems.iter().filter(|e| !matches!(e, SliceElem::NewAxis)).count();
+
+        if consumed != ndim {
+            return Err(ShapeError::DimensionMismatch {
+                expected: ndim,
+                found: consumed,
+            });
+        }
+
+        let shape = self.shape.as_slice();
+        let strides = self.stride.strides.as_slice();
+
+        let mut offset = self.stride.offset;
+        let mut out_shape = Vec::with_capacity(elems.len());
+        let mut out_strides = Vec::with_capacity(elems.len());
+
+        let mut axis = 0;
+        for elem in elems {
+            match *elem {
+                SliceElem::Index(index) => {
+                    let index =
+                        normalize_index(index, shape[axis], axis, false)?;
+                    offset = advance(offset, index, strides[axis]);
+                    axis += 1;
+                }
+                SliceElem::Slice { start, end, step } => {
+                    let len = shape[axis];
+                    let start = normalize_index(start, len, axis, true)?;
+                    let end = match end {
+                        Some(end) => normalize_index(end, len, axis, true)?,
+                        None => len,
+                    };
+
+                    if step == 0 || start > end {
+                        return Err(ShapeError::InvalidRange {
+                            axis,
+                            start,
+                            end,
+                            step,
+                        });
+                    }
+
+                    let abs_step = step.unsigned_abs();
+                    let count = (end - start).div_ceil(abs_step);
+
+                    // A negative step starts from the end of the range
+                    if count > 0 {
+                        let first = if step > 0 { start } else { end - 1 };
+                        offset = advance(offset, first, strides[axis]);
+                    }
+
+                    out_shape.push(count);
+                    out_strides.push(strides[axis].wrapping_mul(step as UDim));
+                    axis += 1;
+                }
+                SliceElem::NewAxis => {
+                    out_shape.push(1);
+                    out_strides.push(0);
+                }
+            }
+        }
+
+        match (Out::from_slice(&out_shape), Out::from_slice(&out_strides)) {
+            (Some(shape), Some(strides)) => {
+                Ok(Axes::new(shape, Stride::new(offset, strides)))
+            }
+            _ => Err(ShapeError::DimensionMismatch {
+                expected: Out::NDIM.unwrap_or_default(),
+                found: out_shape.len(),
+            }),
+        }
+    }
+}
+
+/// Create a [`SliceInfo`] for slicing an array, with NumPy-like syntax.
+///
+/// Each comma-separated element describes one axis:
+///
+/// * A range (`a..b`, `a..`, `..b`, `..`, `a..=b`) selects part of an axis. An
+///   optional step can be given after a semicolon, such as `..;2` for every
+///   other element, or `..;-1` to reverse the axis.
+/// * A single index (`3`, `-1`) selects one element, removing the axis.
+/// * [`NewAxis`] inserts a new axis of length one.
+///
+/// Negative indices count from the end of the axis. The number of dimensions
+/// of the result is computed at compile time.
+///
+/// Each element expression (and step) is evaluated exactly once, from left to
+/// right.
+///
+/// # Example
+/// ```rust
+/// use tensr::s;
+/// use tensr::array::type_remap::{Array3, ArrayView2};
+/// use tensr::backend::traits::ScalarAccessor;
+/// use tensr::dimension::dim::{Dim3, Dimension};
+///
+/// let array = Array3::from_shape_fn(Dim3::new([4, 5, 6]), |i| i[0] * 100 + i[1] * 10 + i[2]);
+///
+/// // Integer literals cannot be inferred until the end of the function, so
+/// // the view type is given explicitly
+/// let view: ArrayView2<'_, usize> = array.slice(s![1..3, .., -1]);
+/// assert_eq!(view.shape().as_slice(), &[2, 5]);
+/// assert_eq!(view.get_scalar_nd(&[1, 4]), 245);
+///
+/// let stepped: ArrayView2<'_, usize> = array.slice(s![..;2, 1, ..;-3]);
+/// assert_eq!(stepped.shape().as_slice(), &[2, 2]);
+/// assert_eq!(stepped.get_scalar_nd(&[1, 0]), 215);
+/// ```
+#[macro_export]
+macro_rules! s {
+    // All elements have been parsed
+    (@parse $in_dim: expr, $out_dim: expr, [$($stack: tt)*]) => {
+        $crate::dimension::slice::SliceInfo::from_parts(
+            vec![$($stack)*],
+            $in_dim,
+            $out_dim,
+        )
+    };
+
+    // Final element, with a step
+    (@parse $in_dim: expr, $out_dim: expr, [$($stack: tt)*] $r: expr; $s: expr $(,)?) => {
+        $crate::s![@bind $in_dim, $out_dim, [$($stack)*] ($r; $s) []]
+    };
+
+    // Final element, without a step
+    (@parse $in_dim: expr, $out_dim: expr, [$($stack: tt)*] $r: expr $(,)?) => {
+        $crate::s![@bind $in_dim, $out_dim, [$($stack)*] ($r) []]
+    };
+
+    // An element with a step, followed by more elements
+    (@parse $in_dim: expr, $out_dim: expr, [$($stack: tt)*] $r: expr; $s: expr, $($t: tt)*) => {
+        $crate::s![@bind $in_dim, $out_dim, [$($stack)*] ($r; $s) [$($t)*]]
+    };
+
+    // An element without a step, followed by more elements
+    (@parse $in_dim: expr, $out_dim: expr, [$($stack: tt)*] $r: expr, $($t: tt)*) => {
+        $crate::s![@bind $in_dim, $out_dim, [$($stack)*] ($r) [$($t)*]]
+    };
+
+    // Evaluate the element once, binding it to a local which is used both to
+    // compute the dimensions and to build the element, then parse the rest.
+    // Each expansion has its own hygiene context, so the locals of different
+    // elements do not clash
+    (
+        @bind $in_dim: expr, $out_dim: expr, [$($stack: tt)*]
+        ($r: expr $(; $s: expr)?) [$($t: tt)*]
+    ) => {{
+        let range = $r;
+        let in_dim = $crate::dimension::slice::SliceNextDim::next_in(&range, $in_dim);
+        let out_dim = $crate::dimension::slice::SliceNextDim::next_out(&range, $out_dim);
+        let elem = $crate::s![@elem range $(; $s)?];
+        $crate::s![@parse in_dim, out_dim, [$($stack)* elem,] $($t)*]
+    }};
+
+    (@elem $r: expr; $s: expr) => {
+        <$crate::dimension::slice::SliceElem as ::std::convert::From<_>>::from($r)
+            .step_by(($s) as $crate::types::IDim)
+    };
+
+    (@elem $r: expr) => {
+        <$crate::dimension::slice::SliceElem as ::std::convert::From<_>>::from($r)
+    };
+
+    ($($t: tt)*) => {
+        $crate::s![@parse
+            ::std::marker::PhantomData::<$crate::dimension::dim::Dim0>,
+            ::std::marker::PhantomData::<$crate::dimension::dim::Dim0>,
+            []
+            $($t)*
+        ]
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn axes_3d() -> Axes<Dim3> {
+        Axes::new_with_default_stride(Dim3::new([4, 5, 6]))
+    }
+
+    #[test]
+    fn test_slice_types() {
+        let info: SliceInfo<Dim3, Dim2> = crate::s![1..3, .., 2];
+        assert_eq!(
+            info.elems(),
+            &[
+                SliceElem::Slice { start: 1, end: Some(3), step: 1 },
+                SliceElem::Slice { start: 0, end: None, step: 1 },
+                SliceElem::Index(2),
+            ]
+        );
+
+        let info: SliceInfo<Dim1, Dim3> = crate::s![NewAxis, ..;-2, NewAxis];
+        assert_eq!(
+            info.elems()[1],
+            SliceElem::Slice { start: 0, end: None, step: -2 }
+        );
+
+        let info: SliceInfo<Dim2, Dim0> = crate::s![-1, 0,];
+        assert_eq!(info.elems(), &[SliceElem::Index(-1), SliceElem::Index(0)]);
+    }
+
+    #[test]
+    fn test_slice_side_effects() {
+        let mut calls = Vec::new();
+        let mut next = |name, value: isize| {
+            calls.push(name);
+            value
+        };
+
+        let info: SliceInfo<Dim3, Dim2> = crate::s![
+            next("a", 1)..next("b", 3),
+            next("c", 2),
+            ..;next("d", -1)
+        ];
+        assert_eq!(calls, ["a", "b", "c", "d"]);
+        assert_eq!(
+            info.elems(),
+            &[
+                SliceElem::Slice { start: 1, end: Some(3), step: 1 },
+                SliceElem::Index(2),
+                SliceElem::Slice { start: 0, end: None, step: -1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_slice_info_new() {
+        let elems = vec![SliceElem::Index(0), SliceElem::NewAxis];
+        assert!(SliceInfo::<Dim1, Dim1>::new(elems.clone()).is_ok());
+        assert!(SliceInfo::<DimDyn, DimDyn>::new(elems.clone()).is_ok());
+        assert_eq!(
+            SliceInfo::<Dim2, Dim1>::new(elems).err(),
+            Some(ShapeError::DimensionMismatch { expected: 2, found: 1 })
+        );
+    }
+
+    #[test]
+    fn test_slice_axes() {
+        let axes = axes_3d();
+        let sliced: Axes<Dim2> =
+            axes.slice(crate::s![1..3, .., -1].elems()).unwrap();
+
+        assert_eq!(sliced.shape.as_slice(), &[2, 5]);
+        assert_eq!(sliced.stride.strides.as_slice(), &[30, 6]);
+        assert_eq!(sliced.stride.offset, 30 + 5);
+    }
+
+    #[test]
+    fn test_slice_axes_step() {
+        let axes = axes_3d();
+        let sliced: Axes<Dim3> =
+            axes.slice(crate::s![..;3, 1..;2, ..;-4].elems()).unwrap();
+
+        assert_eq!(sliced.shape.as_slice(), &[2, 2, 2]);
+        assert_eq!(sliced.stride.stride(0), 90);
+        assert_eq!(sliced.stride.stride(1), 12);
+        assert_eq!(sliced.stride.stride(2), -4);
+
+        // Reversed axes start from the last element of the range
+        assert_eq!(sliced.offset_of(&[0, 0, 0]), 6 + 5);
+        assert_eq!(sliced.offset_of(&[1, 1, 1]), 90 + 18 + 1);
+    }
+
+    #[test]
+    fn test_slice_axes_new_axis() {
+        let axes = axes_3d();
+        let sliced: Axes<Dim4> = axes
+            .slice(crate::s![0, NewAxis, .., NewAxis, 2..4].elems())
+            .unwrap();
+
+        assert_eq!(sliced.shape.as_slice(), &[1, 5, 1, 2]);
+        assert_eq!(sliced.stride.strides.as_slice(), &[0, 6, 0, 1]);
+        assert_eq!(sliced.stride.offset, 2);
+    }
+
+    #[test]
+    fn test_slice_axes_errors() {
+        let axes = axes_3d();
+
+        assert_eq!(
+            axes.slice::<Dim2>(crate::s![4, .., ..].elems()).err(),
+            Some(ShapeError::IndexOutOfBounds { axis: 0, index: 4, len: 4 })
+        );
+        assert_eq!(
+            axes.slice::<Dim2>(crate::s![-5, .., ..].elems()).err(),
+            Some(ShapeError::IndexOutOfBounds { axis: 0, index: -5, len: 4 })
+        );
+        assert_eq!(
+            axes.slice::<Dim3>(&[
+                SliceElem::from(..),
+                SliceElem::Slice { start: 3, end: Some(-4), step: 1 },
+                SliceElem::from(..),
+            ])
+            .err(),
+            Some(ShapeError::InvalidRange {
+                axis: 1,
+                start: 3,
+                end: 1,
+                step: 1
+            })
+        );
+        assert_eq!(
+            axes.slice::<Dim3>(crate::s![.., ..;0, ..].elems()).err(),
+            Some(ShapeError::InvalidRange {
+                axis: 1,
+                start: 0,
+                end: 5,
+                step: 0
+            })
+        );
+        assert_eq!(
+            axes.slice::<Dim2>(crate::s![.., ..].elems()).err(),
+            Some(ShapeError::DimensionMismatch { expected: 3, found: 2 })
+        );
+    }
+
+    #[test]
+    fn test_slice_axes_dyn() {
+        let axes = Axes::new_with_default_stride(DimDyn::new_from(vec![
+            2usize, 3, 4, 5, 6,
+        ]));
+        let sliced: Axes<DimDyn> = axes
+            .slice(&[
+                SliceElem::Index(1),
+                SliceElem::Slice { start: -2, end: None, step: 1 },
+                SliceElem::from(..),
+                SliceElem::from(1..=2),
+                SliceElem::from(..-1),
+            ])
+            .unwrap();
+
+        assert_eq!(sliced.shape.as_slice(), &[2, 4, 2, 5]);
+        assert_eq!(sliced.stride.offset, 360 + 120 + 6);
+    }
+}
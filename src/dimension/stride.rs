@@ -1,6 +1,72 @@
-use crate::dimension::dim::Dimension;
+//! Strides, which give the distance in storage between consecutive elements
+//! along each axis.
 
+use crate::{
+    dimension::dim::Dimension,
+    types::{IDim, UDim},
+};
+
+/// Return the storage location `steps` elements after `base` along an axis
+/// with the raw stride `stride`.
+///
+/// Strides are stored as [`UDim`] values but interpreted as signed, and
+/// wrapping arithmetic on the unsigned values is equivalent to signed
+/// arithmetic, which is what allows negative strides to work. Every offset
+/// calculation goes through this function.
+#[inline(always)]
+pub(crate) const fn advance(base: usize, steps: UDim, stride: UDim) -> usize {
+    base.wrapping_add(steps.wrapping_mul(stride))
+}
+
+/// Describes where the elements of an array are located in its underlying
+/// storage: the offset of the first element, and the distance (in elements)
+/// between consecutive elements along each axis.
+///
+/// Strides are stored as [`UDim`] values, but are interpreted as signed
+/// ([`IDim`]) values, so an axis may be traversed backwards.
+#[derive(Debug, Clone)]
 pub struct Stride<DimType: Dimension> {
-    offset: usize, // Offset cannot be negative
-    strides: DimType,
+    pub(crate) offset: usize, // Offset cannot be negative
+    pub(crate) strides: DimType,
+}
+
+impl<DimType: Dimension> Stride<DimType> {
+    /// Create a new [`Stride`] with the given offset and strides.
+    pub const fn new(offset: usize, strides: DimType) -> Self {
+        Self { offset, strides }
+    }
+
+    /// The index of the first element within the underlying storage
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The raw strides along each axis. See [`Stride::stride`] for a signed
+    /// version.
+    pub const fn strides(&self) -> &DimType {
+        &self.strides
+    }
+
+    /// The signed stride along `axis`
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn stride(&self, axis: usize) -> IDim {
+        self.strides.as_slice()[axis] as IDim
+    }
+
+    /// Return the location, within the underlying storage, of the element at
+    /// the multi-dimensional `index`.
+    #[inline(always)]
+    pub fn offset_of(&self, index: &[UDim]) -> usize {
+        index
+            .iter()
+            .zip(self.strides.as_slice())
+            .fold(self.offset, |acc, (&i, &s)| advance(acc, i, s))
+    }
+
+    /// Return the location, within the underlying storage, of the element
+    /// `index` steps along `axis` from the first element.
+    #[inline(always)]
+    pub fn offset_along(&self, axis: usize, index: UDim) -> usize {
+        advance(self.offset, index, self.strides.as_slice()[axis])
+    }
 }
@@ -0,0 +1,380 @@
+//! Error types returned by fallible Tensr operations.
+
+use crate::types::{IDim, UDim};
+
+/// An error produced when the shape of an array, or the data provided for it,
+/// is invalid for the requested operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShapeError {
+    /// The number of elements provided does not match the number of elements
+    /// required by the shape.
+    LengthMismatch {
+        /// The number of elements required by the shape
+        expected: usize,
+
+        /// The number of elements which were provided
+        found: usize,
+    },
+
+    /// The number of dimensions provided does not match the number of
+    /// dimensions required.
+    DimensionMismatch {
+        /// The number of dimensions required
+        expected: usize,
+
+        /// The number of dimensions which were provided
+        found: usize,
+    },
+
+    /// An element addressed by the given shape, strides and offset lies
+    /// outside of the available data.
+    OutOfBounds {
+        /// The (possibly negative) index of the element
+        index: IDim,
+
+        /// The number of elements in the available data
+        len: usize,
+    },
+
+    /// An index along an axis is outside of the bounds of that axis.
+    IndexOutOfBounds {
+        /// The axis being indexed
+        axis: usize,
+
+        /// The (possibly negative) index
+        index: IDim,
+
+        /// The length of the axis
+        len: usize,
+    },
+
+    /// A range along an axis is invalid, either because its start is after
+    /// its end, or because its step is zero.
+    InvalidRange {
+        /// The axis being sliced
+        axis: usize,
+
+        /// The (normalised) start of the range
+        start: usize,
+
+        /// The (normalised) end of the range
+        end: usize,
+
+        /// The step of the range
+        step: IDim,
+    },
+
+    /// Two shapes are not compatible under broadcasting rules, since an axis
+    /// has different lengths in each and neither length is one.
+    BroadcastMismatch {
+        /// The shape of the left-hand operand
+        lhs: Vec<UDim>,
+
+        /// The shape of the right-hand operand
+        rhs: Vec<UDim>,
+    },
+
+    /// A shape cannot be broadcast to a fixed target shape, such as the shape
+    /// of the left-hand operand of an in-place operation.
+    BroadcastTo {
+        /// The shape being broadcast
+        from: Vec<UDim>,
+
+        /// The target shape
+        to: Vec<UDim>,
+    },
+
+    /// An axis does not exist in an array with the given number of
+    /// dimensions.
+    AxisOutOfBounds {
+        /// The requested axis
+        axis: usize,
+
+        /// The number of dimensions of the array
+        ndim: usize,
+    },
+
+    /// The shapes of two operands of a matrix multiplication are not aligned,
+    /// since the number of columns of the left-hand operand does not match
+    /// the number of rows of the right-hand operand.
+    MatMulMismatch {
+        /// The shape of the left-hand operand
+        lhs: Vec<UDim>,
+
+        /// The shape of the right-hand operand
+        rhs: Vec<UDim>,
+    },
+
+    /// The subscripts of an `einsum` contraction are malformed, or do not
+    /// match the operands.
+    InvalidSubscripts {
+        /// The subscripts, as given
+        subscripts: String,
+
+        /// A description of the problem
+        reason: String,
+    },
+
+    /// An array cannot be reshaped, since the new shape has a different
+    /// number of elements.
+    ReshapeMismatch {
+        /// The shape of the array
+        from: Vec<UDim>,
+
+        /// The requested shape, with any inferred length filled in
+        to: Vec<UDim>,
+    },
+
+    /// A requested shape is malformed, such as a shape with more than one
+    /// length to infer.
+    InvalidShape {
+        /// The shape, as given
+        shape: Vec<IDim>,
+
+        /// A description of the problem
+        reason: String,
+    },
+
+    /// The elements of an array cannot be viewed with a new shape without
+    /// copying them, because of the strides of the array.
+    IncompatibleLayout {
+        /// The shape of the array
+        shape: Vec<UDim>,
+
+        /// The (signed) strides of the array
+        strides: Vec<IDim>,
+
+        /// The requested shape
+        to: Vec<UDim>,
+    },
+
+    /// An axis which must have length one, such as an axis being removed,
+    /// does not.
+    NotSingleton {
+        /// The axis
+        axis: usize,
+
+        /// The length of the axis
+        len: usize,
+    },
+
+    /// A list of axes, such as the new order of the axes of an array, is
+    /// not a permutation of the axes of the array.
+    InvalidPermutation {
+        /// The axes, as given
+        axes: Vec<usize>,
+
+        /// The number of dimensions of the array
+        ndim: usize,
+    },
+
+    /// No arrays were given to an operation which joins arrays, such as
+    /// `concatenate`.
+    NoArrays,
+
+    /// Arrays cannot be joined, since their shapes differ along an axis other
+    /// than the one they are joined along (or, when stacking, along any
+    /// axis).
+    JoinMismatch {
+        /// The axis along which the arrays are joined
+        axis: usize,
+
+        /// The shape of the first array
+        expected: Vec<UDim>,
+
+        /// The shape of the array which does not match
+        found: Vec<UDim>,
+    },
+
+    /// An axis cannot be split into the requested number of sections.
+    InvalidSplit {
+        /// The axis being split
+        axis: usize,
+
+        /// The length of the axis
+        len: usize,
+
+        /// The requested number of sections
+        sections: usize,
+    },
+
+    /// An axis of length zero cannot be padded by copying its elements, as
+    /// the edge, reflect and wrap modes do.
+    PadEmptyAxis {
+        /// The axis being padded
+        axis: usize,
+    },
+}
+
+impl std::fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LengthMismatch { expected, found } => write!(
+                f,
+                "shape requires {expected} elements, but {found} were provided"
+            ),
+            Self::DimensionMismatch { expected, found } => write!(
+                f,
+                "expected {expected} dimensions, but {found} were provided"
+            ),
+            Self::OutOfBounds { index, len } => write!(
+                f,
+                "element {index} is out of bounds for data with {len} elements"
+            ),
+            Self::IndexOutOfBounds { axis, index, len } => write!(
+                f,
+                "index {index} is out of bounds for axis {axis} with length \
+                 {len}"
+            ),
+            Self::InvalidRange { axis, start, end, step } => write!(
+                f,
+                "invalid range {start}..{end} with step {step} for axis {axis}"
+            ),
+            Self::BroadcastMismatch { lhs, rhs } => write!(
+                f,
+                "shapes {lhs:?} and {rhs:?} cannot be broadcast together"
+            ),
+            Self::BroadcastTo { from, to } => {
+                write!(f, "shape {from:?} cannot be broadcast to shape {to:?}")
+            }
+            Self::AxisOutOfBounds { axis, ndim } => write!(
+                f,
+                "axis {axis} is out of bounds for an array with {ndim} \
+                 dimensions"
+            ),
+            Self::MatMulMismatch { lhs, rhs } => write!(
+                f,
+                "shapes {lhs:?} and {rhs:?} are not aligned for matrix \
+                 multiplication"
+            ),
+            Self::InvalidSubscripts { subscripts, reason } => {
+                write!(
+                    f,
+                    "invalid einsum subscripts \"{subscripts}\": {reason}"
+                )
+            }
+            Self::ReshapeMismatch { from, to } => write!(
+                f,
+                "cannot reshape an array of shape {from:?} with {} elements \
+                 into shape {to:?} with {} elements",
+                from.iter().product::<usize>(),
+                to.iter().product::<usize>()
+            ),
+            Self::InvalidShape { shape, reason } => {
+                write!(f, "invalid shape {shape:?}: {reason}")
+            }
+            Self::IncompatibleLayout { shape, strides, to } => write!(
+                f,
+                "an array of shape {shape:?} with strides {strides:?} cannot \
+                 be viewed with shape {to:?} without copying"
+            ),
+            Self::NotSingleton { axis, len } => write!(
+                f,
+                "axis {axis} has length {len}, but only an axis of length one \
+                 can be removed"
+            ),
+            Self::InvalidPermutation { axes, ndim } => write!(
+                f,
+                "axes {axes:?} are not a permutation of the axes of an array \
+                 with {ndim} dimensions"
+            ),
+            Self::NoArrays => write!(f, "at least one array is required"),
+            Self::JoinMismatch { axis, expected, found } => write!(
+                f,
+                "an array of shape {found:?} cannot be joined along axis \
+                 {axis} with an array of shape {expected:?}"
+            ),
+            Self::InvalidSplit { axis, len, sections } => write!(
+                f,
+                "axis {axis} with length {len} cannot be split into \
+                 {sections} equal sections"
+            ),
+            Self::PadEmptyAxis { axis } => write!(
+                f,
+                "axis {axis} has length zero, so it can only be padded with a \
+                 constant"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// An error produced by a linear algebra routine, when its operands have
+/// invalid shapes or a matrix does not have the properties required by the
+/// routine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinalgError {
+    /// The shapes of the operands are invalid.
+    Shape(ShapeError),
+
+    /// A matrix which must be square is not.
+    NotSquare {
+        /// The number of rows of the matrix
+        rows: usize,
+
+        /// The number of columns of the matrix
+        cols: usize,
+    },
+
+    /// The right-hand side of a system of equations does not have one row for
+    /// each row of the matrix.
+    RowMismatch {
+        /// The number of rows of the matrix
+        expected: usize,
+
+        /// The number of rows of the right-hand side
+        found: usize,
+    },
+
+    /// The matrix is singular (or, for least squares problems, does not have
+    /// full rank), so the system of equations has no unique solution.
+    Singular,
+
+    /// The matrix is not positive definite, so it has no Cholesky
+    /// decomposition.
+    NotPositiveDefinite,
+
+    /// An iterative algorithm, such as the QR algorithm for eigenvalues, did
+    /// not converge, which can happen if the matrix contains NaN or infinite
+    /// values.
+    NoConvergence,
+}
+
+impl From<ShapeError> for LinalgError {
+    fn from(err: ShapeError) -> Self {
+        Self::Shape(err)
+    }
+}
+
+impl std::fmt::Display for LinalgError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Shape(err) => write!(f, "{err}"),
+            Self::NotSquare { rows, cols } => write!(
+                f,
+                "expected a square matrix, but the matrix has shape [{rows}, \
+                 {cols}]"
+            ),
+            Self::RowMismatch { expected, found } => write!(
+                f,
+                "the right-hand side has {found} rows, but the matrix has \
+                 {expected} rows"
+            ),
+            Self::Singular => write!(f, "the matrix is singular"),
+            Self::NotPositiveDefinite => {
+                write!(f, "the matrix is not positive definite")
+            }
+            Self::NoConvergence => write!(f, "the algorithm did not converge"),
+        }
+    }
+}
+
+impl std::error::Error for LinalgError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Shape(err) => Some(err),
+            _ => None,
+        }
+    }
+}
@@ -1,4 +1,18 @@
+//! N-dimensional arrays with lazily evaluated, element-wise expressions.
+//!
+//! Arrays are created through the aliases in [`array::type_remap`], such as
+//! [`Array2`](array::type_remap::Array2). Arithmetic on arrays and views builds
+//! expressions which are only computed, in a single pass and without temporary
+//! arrays, when they are evaluated.
+
 pub mod array;
 pub mod backend;
 pub mod dimension;
+pub mod error;
+pub mod linalg;
 pub mod types;
+
+pub use array::{
+    einsum::{einsum, einsum_typed},
+    join::{concatenate, hstack, stack, vstack},
+};
@@ -0,0 +1,142 @@
+//! The Cholesky decomposition of symmetric, positive definite matrices.
+
+use crate::{
+    array::{base::ArrayBase, type_remap::Array2},
+    backend::{host::host_backend::HostBackend, traits::Storage},
+    dimension::dim::Dim2,
+    error::LinalgError,
+    linalg::{check_square, for_each_row, LinalgScalar, Matrix},
+};
+
+/// Compute the Cholesky decomposition of the symmetric, positive definite
+/// matrix `a`.
+///
+/// The decomposition is `A = L L^T`, where `L` is lower triangular with a
+/// positive diagonal. Only the lower triangle of `a` is read. Each step
+/// computes a column of `L`, and then updates the rows of the remaining lower
+/// triangle in parallel.
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square, or
+/// [`LinalgError::NotPositiveDefinite`] if it is not positive definite.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![4.0_f64, 2.0, 2.0, 5.0],
+/// )
+/// .unwrap();
+/// let l = linalg::cholesky(&a).unwrap();
+/// assert_eq!(l[[0, 0]], 2.0);
+/// assert_eq!(l[[1, 0]], 1.0);
+/// assert_eq!(l[[1, 1]], 2.0);
+/// assert_eq!(l[[0, 1]], 0.0);
+///
+/// let indefinite = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![1.0_f64, 2.0, 2.0, 1.0],
+/// )
+/// .unwrap();
+/// assert!(linalg::cholesky(&indefinite).is_err());
+/// ```
+pub fn cholesky<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<Array2<T>, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    let mut l = Matrix::from_array(a);
+    check_square(&l)?;
+    let n = l.rows;
+
+    for j in 0..n {
+        let pivot = l[(j, j)];
+        if pivot <= T::zero() || pivot.is_nan() {
+            return Err(LinalgError::NotPositiveDefinite);
+        }
+        let diagonal = pivot.sqrt();
+        l[(j, j)] = diagonal;
+
+        let column: Vec<T> = (j + 1..n).map(|i| l[(i, j)] / diagonal).collect();
+
+        // Subtract the outer product of the column from the lower triangle of
+        // the remaining rows and columns
+        let tail = &mut l.data[(j + 1) * n..];
+        for_each_row(tail, n, |i, row| {
+            row[j] = column[i];
+            for (value, &other) in
+                row[j + 1..=j + 1 + i].iter_mut().zip(&column)
+            {
+                *value = *value - column[i] * other;
+            }
+        });
+    }
+
+    for i in 0..n {
+        for j in i + 1..n {
+            l[(i, j)] = T::zero();
+        }
+    }
+
+    Ok(l.into_array())
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use super::*;
+    use crate::linalg::test_util::assert_close;
+
+    #[test]
+    fn test_cholesky() {
+        for n in [1, 5, 140] {
+            // B B^T + n I is symmetric and positive definite
+            let b = Array2::from_shape_fn(Dim2::new([n, n]), |i| {
+                f64::from(u32::try_from((i[0] * 3 + i[1] * 7) % 11).unwrap())
+                    - 5.0
+            });
+            let bt =
+                Array2::from_shape_fn(Dim2::new([n, n]), |i| b[[i[1], i[0]]]);
+            let mut a = b.matmul(&bt);
+            for i in 0..n {
+                a[[i, i]] += f64::from(u32::try_from(n).unwrap());
+            }
+
+            let l = cholesky(&a).unwrap();
+            let lt =
+                Array2::from_shape_fn(Dim2::new([n, n]), |i| l[[i[1], i[0]]]);
+            assert_close(&l.matmul(&lt), &a, 1e-9);
+            for i in 0..n {
+                assert!(l[[i, i]] > 0.0);
+                assert!((i + 1..n).all(|j| l[[i, j]] == 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn test_not_positive_definite() {
+        let a = Array2::from_vec(
+            Dim2::new([3, 3]),
+            vec![2.0_f32, 1.0, 0.0, 1.0, 2.0, 3.0, 0.0, 3.0, 1.0],
+        )
+        .unwrap();
+        assert_eq!(cholesky(&a).err(), Some(LinalgError::NotPositiveDefinite));
+
+        let nan = Array2::from_vec(Dim2::new([1, 1]), vec![f64::NAN]).unwrap();
+        assert_eq!(
+            cholesky(&nan).err(),
+            Some(LinalgError::NotPositiveDefinite)
+        );
+
+        assert_eq!(
+            cholesky(&Array2::<f64>::zeros(Dim2::new([3, 2]))).err(),
+            Some(LinalgError::NotSquare { rows: 3, cols: 2 })
+        );
+    }
+}
@@ -0,0 +1,1077 @@
+//! Eigenvalues and eigenvectors of symmetric and general matrices.
+
+use std::cmp::Ordering;
+
+use num_complex::Complex;
+
+use crate::{
+    array::{
+        base::ArrayBase,
+        type_remap::{Array1, Array2},
+    },
+    backend::{
+        host::{host_backend::HostBackend, host_storage::HostStorage},
+        traits::Storage,
+    },
+    dimension::dim::{Dim2, Dimension},
+    error::LinalgError,
+    linalg::{check_finite, stack_results, Batch, LinalgScalar, Matrix},
+};
+
+/// The maximum number of QR iterations spent on a single eigenvalue before
+/// giving up
+const MAX_ITERATIONS: usize = 100;
+
+/// Convert a constant used by the algorithms below to `T`
+fn constant<T: LinalgScalar>(value: f64) -> T {
+    T::from(value).expect("the constant is representable")
+}
+
+/// Reduce the symmetric matrix `v` to tridiagonal form by Householder
+/// reflections, replacing it with the accumulated transformation. Returns the
+/// diagonal and the subdiagonal, whose first element is zero.
+///
+/// This is a port of `tred2` from EISPACK, by way of JAMA.
+#[allow(clippy::many_single_char_names, clippy::needless_range_loop)]
+fn tridiagonalise<T: LinalgScalar>(v: &mut Matrix<T>) -> (Vec<T>, Vec<T>) {
+    let n = v.rows;
+    let zero = T::zero();
+    let mut d: Vec<T> = (0..n).map(|j| v[(n - 1, j)]).collect();
+    let mut e = vec![zero; n];
+
+    for i in (1..n).rev() {
+        let scale = d[..i].iter().fold(zero, |acc, x| acc + x.abs());
+        let mut h = zero;
+
+        if scale == zero {
+            e[i] = d[i - 1];
+            for j in 0..i {
+                d[j] = v[(i - 1, j)];
+                v[(i, j)] = zero;
+                v[(j, i)] = zero;
+            }
+        } else {
+            // Generate the Householder vector
+            for k in 0..i {
+                d[k] = d[k] / scale;
+                h = h + d[k] * d[k];
+            }
+            let mut f = d[i - 1];
+            let mut g = if f > zero { -h.sqrt() } else { h.sqrt() };
+            e[i] = scale * g;
+            h = h - f * g;
+            d[i - 1] = f - g;
+            e[..i].fill(zero);
+
+            // Apply the similarity transformation to the remaining columns
+            for j in 0..i {
+                f = d[j];
+                v[(j, i)] = f;
+                g = e[j] + v[(j, j)] * f;
+                for k in j + 1..i {
+                    g = g + v[(k, j)] * d[k];
+                    e[k] = e[k] + v[(k, j)] * f;
+                }
+                e[j] = g;
+            }
+            f = zero;
+            for j in 0..i {
+                e[j] = e[j] / h;
+                f = f + e[j] * d[j];
+            }
+            let hh = f / (h + h);
+            for j in 0..i {
+                e[j] = e[j] - hh * d[j];
+            }
+            for j in 0..i {
+                f = d[j];
+                g = e[j];
+                for k in j..i {
+                    let update = f * e[k] + g * d[k];
+                    v[(k, j)] = v[(k, j)] - update;
+                }
+                d[j] = v[(i - 1, j)];
+                v[(i, j)] = zero;
+            }
+        }
+        d[i] = h;
+    }
+
+    // Accumulate the transformations
+    for i in 0..n.saturating_sub(1) {
+        v[(n - 1, i)] = v[(i, i)];
+        v[(i, i)] = T::one();
+        let h = d[i + 1];
+        if h != zero {
+            for k in 0..=i {
+                d[k] = v[(k, i + 1)] / h;
+            }
+            for j in 0..=i {
+                let g = (0..=i)
+                    .fold(zero, |acc, k| acc + v[(k, i + 1)] * v[(k, j)]);
+                for k in 0..=i {
+                    v[(k, j)] = v[(k, j)] - g * d[k];
+                }
+            }
+        }
+        for k in 0..=i {
+            v[(k, i + 1)] = zero;
+        }
+    }
+    if n > 0 {
+        for j in 0..n {
+            d[j] = v[(n - 1, j)];
+            v[(n - 1, j)] = zero;
+        }
+        v[(n - 1, n - 1)] = T::one();
+    }
+
+    (d, e)
+}
+
+/// Diagonalise the symmetric tridiagonal matrix with diagonal `d` and
+/// subdiagonal `e[1..]` by the implicit QL algorithm. On return, `d` holds the
+/// eigenvalues, and the rows of `vt`, which holds the transpose of the
+/// transformation from [`tridiagonalise`] on entry, hold the eigenvectors.
+///
+/// This is a port of `tql2` from EISPACK, by way of JAMA.
+#[allow(clippy::many_single_char_names)]
+fn tridiagonal_ql<T: LinalgScalar>(
+    d: &mut [T],
+    e: &mut [T],
+    vt: &mut Matrix<T>,
+) -> Result<(), LinalgError> {
+    let n = d.len();
+    let zero = T::zero();
+    let one = T::one();
+    let two = one + one;
+    let eps = T::epsilon();
+
+    if n == 0 {
+        return Ok(());
+    }
+    e.copy_within(1.., 0);
+    e[n - 1] = zero;
+
+    let mut f = zero;
+    let mut tst1 = zero;
+    for l in 0..n {
+        // Find a small subdiagonal element, which splits the matrix
+        tst1 = tst1.max(d[l].abs() + e[l].abs());
+        let m = (l..n).find(|&m| e[m].abs() <= eps * tst1).unwrap_or(n - 1);
+
+        let mut iterations = 0;
+        while m > l && e[l].abs() > eps * tst1 {
+            iterations += 1;
+            if iterations > MAX_ITERATIONS {
+                return Err(LinalgError::NoConvergence);
+            }
+
+            // Compute the implicit shift
+            let g = d[l];
+            let p = (d[l + 1] - g) / (two * e[l]);
+            let r = if p < zero { -p.hypot(one) } else { p.hypot(one) };
+            d[l] = e[l] / (p + r);
+            d[l + 1] = e[l] * (p + r);
+            let dl1 = d[l + 1];
+            let h = g - d[l];
+            for x in &mut d[l + 2..] {
+                *x = *x - h;
+            }
+            f = f + h;
+
+            // Implicit QL transformation
+            let mut p = d[m];
+            let (mut c, mut c2, mut c3) = (one, one, one);
+            let el1 = e[l + 1];
+            let (mut s, mut s2) = (zero, zero);
+            for i in (l..m).rev() {
+                c3 = c2;
+                c2 = c;
+                s2 = s;
+                let g = c * e[i];
+                let h = c * p;
+                let r = p.hypot(e[i]);
+                e[i + 1] = s * r;
+                s = e[i] / r;
+                c = p / r;
+                p = c * d[i] - s * g;
+                let rotated = c * g + s * d[i];
+                d[i + 1] = h + s * rotated;
+
+                // Accumulate the transformation
+                let (head, tail) = vt.data.split_at_mut((i + 1) * n);
+                let lower = &mut head[i * n..];
+                for (a, b) in lower.iter_mut().zip(&mut tail[..n]) {
+                    let h = *b;
+                    *b = s * *a + c * h;
+                    *a = c * *a - s * h;
+                }
+            }
+            p = -s * s2 * c3 * el1 * e[l] / dl1;
+            e[l] = s * p;
+            d[l] = c * p;
+        }
+        d[l] = d[l] + f;
+        e[l] = zero;
+    }
+
+    Ok(())
+}
+
+/// Compute the eigenvalues, in ascending order, and the unit eigenvectors,
+/// as rows, of the symmetric matrix whose lower triangle is in `matrix`
+fn symmetric_eigen<T: LinalgScalar>(
+    mut matrix: Matrix<T>,
+) -> Result<(Vec<T>, Matrix<T>), LinalgError> {
+    check_finite(&matrix)?;
+    let n = matrix.rows;
+    for i in 0..n {
+        for j in i + 1..n {
+            matrix[(i, j)] = matrix[(j, i)];
+        }
+    }
+
+    let (mut d, mut e) = tridiagonalise(&mut matrix);
+    let mut vt = matrix.transpose();
+    tridiagonal_ql(&mut d, &mut e, &mut vt)?;
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| d[i].partial_cmp(&d[j]).unwrap_or(Ordering::Equal));
+    let values = order.iter().map(|&i| d[i]).collect();
+    let mut vectors = Matrix::zeros(n, n);
+    for (row, &i) in order.iter().enumerate() {
+        vectors.data[row * n..(row + 1) * n]
+            .copy_from_slice(&vt.data[i * n..(i + 1) * n]);
+    }
+
+    Ok((values, vectors))
+}
+
+/// Reduce `h` to upper Hessenberg form by orthogonal similarity
+/// transformations, returning the accumulated transformation.
+///
+/// This is a port of `orthes` and `ortran` from EISPACK, by way of JAMA.
+#[allow(clippy::many_single_char_names, clippy::needless_range_loop)]
+fn hessenberg<T: LinalgScalar>(h: &mut Matrix<T>) -> Matrix<T> {
+    let n = h.rows;
+    let zero = T::zero();
+    let mut ort = vec![zero; n];
+
+    for m in 1..n.saturating_sub(1) {
+        let scale = (m..n).fold(zero, |acc, i| acc + h[(i, m - 1)].abs());
+        if scale == zero {
+            continue;
+        }
+
+        // Compute the Householder transformation
+        let mut norm = zero;
+        for i in (m..n).rev() {
+            ort[i] = h[(i, m - 1)] / scale;
+            norm = norm + ort[i] * ort[i];
+        }
+        let g = if ort[m] > zero { -norm.sqrt() } else { norm.sqrt() };
+        norm = norm - ort[m] * g;
+        ort[m] = ort[m] - g;
+
+        // Apply it from the left and from the right
+        for j in m..n {
+            let f = (m..n).rev().fold(zero, |acc, i| acc + ort[i] * h[(i, j)]);
+            let f = f / norm;
+            for i in m..n {
+                h[(i, j)] = h[(i, j)] - f * ort[i];
+            }
+        }
+        for i in 0..n {
+            let f = (m..n).rev().fold(zero, |acc, j| acc + ort[j] * h[(i, j)]);
+            let f = f / norm;
+            for j in m..n {
+                h[(i, j)] = h[(i, j)] - f * ort[j];
+            }
+        }
+        ort[m] = scale * ort[m];
+        h[(m, m - 1)] = scale * g;
+    }
+
+    // Accumulate the transformations
+    let mut v = Matrix::eye(n, n);
+    for m in (1..n.saturating_sub(1)).rev() {
+        if h[(m, m - 1)] == zero {
+            continue;
+        }
+        for i in m + 1..n {
+            ort[i] = h[(i, m - 1)];
+        }
+        for j in m..n {
+            let g = (m..n).fold(zero, |acc, i| acc + ort[i] * v[(i, j)]);
+            let g = (g / ort[m]) / h[(m, m - 1)];
+            for i in m..n {
+                v[(i, j)] = v[(i, j)] + g * ort[i];
+            }
+        }
+    }
+
+    v
+}
+
+/// Divide the complex number `a + b i` by `c + d i`
+#[allow(clippy::many_single_char_names)]
+fn cdiv<T: LinalgScalar>(a: T, b: T, c: T, d: T) -> (T, T) {
+    if c.abs() > d.abs() {
+        let r = d / c;
+        let d = r.mul_add(d, c);
+        (r.mul_add(b, a) / d, r.mul_add(-a, b) / d)
+    } else {
+        let r = c / d;
+        let d = r.mul_add(c, d);
+        (r.mul_add(a, b) / d, r.mul_add(b, -a) / d)
+    }
+}
+
+/// Reduce the upper Hessenberg matrix `h` to real Schur form by the shifted
+/// double QR algorithm, and compute the eigenvectors by back substitution.
+///
+/// Returns the real and imaginary parts of the eigenvalues. A complex
+/// conjugate pair occupies adjacent elements, with the positive imaginary
+/// part first. On return, the columns of `v`, which holds the transformation
+/// from [`hessenberg`] on entry, hold the real eigenvectors, and the real and
+/// imaginary parts of the complex eigenvectors, in the same order.
+///
+/// This is a port of `hqr2` from EISPACK, by way of JAMA.
+#[allow(
+    clippy::many_single_char_names,
+    clippy::needless_range_loop,
+    clippy::too_many_lines,
+    clippy::cognitive_complexity
+)]
+fn schur_eigen<T: LinalgScalar>(
+    h: &mut Matrix<T>,
+    v: &mut Matrix<T>,
+) -> Result<(Vec<T>, Vec<T>), LinalgError> {
+    let nn = h.rows;
+    let zero = T::zero();
+    let one = T::one();
+    let two = one + one;
+    let eps = T::epsilon();
+    let mut d = vec![zero; nn];
+    let mut e = vec![zero; nn];
+
+    let mut exshift = zero;
+    let (mut r, mut s, mut z) = (zero, zero, zero);
+    let (mut p, mut q, mut t, mut w, mut x, mut y);
+
+    let mut norm = zero;
+    for i in 0..nn {
+        for j in i.saturating_sub(1)..nn {
+            norm = norm + h[(i, j)].abs();
+        }
+    }
+
+    // `top` is one more than the last row of the active submatrix
+    let mut top = nn;
+    let mut iterations = 0;
+    while top > 0 {
+        let n = top - 1;
+
+        // Look for a single small subdiagonal element
+        let mut l = n;
+        while l > 0 {
+            s = h[(l - 1, l - 1)].abs() + h[(l, l)].abs();
+            if s == zero {
+                s = norm;
+            }
+            if h[(l, l - 1)].abs() < eps * s {
+                break;
+            }
+            l -= 1;
+        }
+
+        if l == n {
+            // One root has converged
+            h[(n, n)] = h[(n, n)] + exshift;
+            d[n] = h[(n, n)];
+            e[n] = zero;
+            top -= 1;
+            iterations = 0;
+        } else if l + 1 == n {
+            // Two roots have converged
+            w = h[(n, n - 1)] * h[(n - 1, n)];
+            p = (h[(n - 1, n - 1)] - h[(n, n)]) / two;
+            q = p * p + w;
+            z = q.abs().sqrt();
+            h[(n, n)] = h[(n, n)] + exshift;
+            h[(n - 1, n - 1)] = h[(n - 1, n - 1)] + exshift;
+            x = h[(n, n)];
+
+            if q >= zero {
+                // A real pair
+                z = if p >= zero { p + z } else { p - z };
+                d[n - 1] = x + z;
+                d[n] = if z == zero { d[n - 1] } else { x - w / z };
+                e[n - 1] = zero;
+                e[n] = zero;
+                x = h[(n, n - 1)];
+                s = x.abs() + z.abs();
+                p = x / s;
+                q = z / s;
+                r = p.hypot(q);
+                p = p / r;
+                q = q / r;
+
+                // Row modification
+                for j in n - 1..nn {
+                    z = h[(n - 1, j)];
+                    h[(n - 1, j)] = q * z + p * h[(n, j)];
+                    h[(n, j)] = q * h[(n, j)] - p * z;
+                }
+                // Column modification
+                for i in 0..=n {
+                    z = h[(i, n - 1)];
+                    h[(i, n - 1)] = q * z + p * h[(i, n)];
+                    h[(i, n)] = q * h[(i, n)] - p * z;
+                }
+                // Accumulate the transformations
+                for i in 0..nn {
+                    z = v[(i, n - 1)];
+                    v[(i, n - 1)] = q * z + p * v[(i, n)];
+                    v[(i, n)] = q * v[(i, n)] - p * z;
+                }
+            } else {
+                // A complex pair
+                d[n - 1] = x + p;
+                d[n] = x + p;
+                e[n - 1] = z;
+                e[n] = -z;
+            }
+            top -= 2;
+            iterations = 0;
+        } else {
+            iterations += 1;
+            if iterations > MAX_ITERATIONS {
+                return Err(LinalgError::NoConvergence);
+            }
+
+            // Form the shift
+            x = h[(n, n)];
+            y = h[(n - 1, n - 1)];
+            w = h[(n, n - 1)] * h[(n - 1, n)];
+
+            // Wilkinson's original ad hoc shift
+            if iterations == 11 {
+                exshift = exshift + x;
+                for i in 0..=n {
+                    h[(i, i)] = h[(i, i)] - x;
+                }
+                s = h[(n, n - 1)].abs() + h[(n - 1, n - 2)].abs();
+                x = constant::<T>(0.75) * s;
+                y = x;
+                w = constant::<T>(-0.4375) * s * s;
+            }
+
+            // MATLAB's new ad hoc shift
+            if iterations == 31 {
+                s = (y - x) / two;
+                s = s * s + w;
+                if s > zero {
+                    s = s.sqrt();
+                    if y < x {
+                        s = -s;
+                    }
+                    s = x - w / ((y - x) / two + s);
+                    for i in 0..=n {
+                        h[(i, i)] = h[(i, i)] - s;
+                    }
+                    exshift = exshift + s;
+                    x = constant(0.964);
+                    y = x;
+                    w = x;
+                }
+            }
+
+            // Look for two consecutive small subdiagonal elements
+            let mut m = n - 2;
+            loop {
+                z = h[(m, m)];
+                r = x - z;
+                s = y - z;
+                p = (r * s - w) / h[(m + 1, m)] + h[(m, m + 1)];
+                q = h[(m + 1, m + 1)] - z - r - s;
+                r = h[(m + 2, m + 1)];
+                s = p.abs() + q.abs() + r.abs();
+                p = p / s;
+                q = q / s;
+                r = r / s;
+                if m == l {
+                    break;
+                }
+                let lhs = h[(m, m - 1)].abs() * (q.abs() + r.abs());
+                let rhs = eps
+                    * (p.abs()
+                        * (h[(m - 1, m - 1)].abs()
+                            + z.abs()
+                            + h[(m + 1, m + 1)].abs()));
+                if lhs < rhs {
+                    break;
+                }
+                m -= 1;
+            }
+            for i in m + 2..=n {
+                h[(i, i - 2)] = zero;
+                if i > m + 2 {
+                    h[(i, i - 3)] = zero;
+                }
+            }
+
+            // Double QR step involving rows l..=n and columns m..=n
+            for k in m..n {
+                let not_last = k != n - 1;
+                if k != m {
+                    p = h[(k, k - 1)];
+                    q = h[(k + 1, k - 1)];
+                    r = if not_last { h[(k + 2, k - 1)] } else { zero };
+                    x = p.abs() + q.abs() + r.abs();
+                    if x == zero {
+                        continue;
+                    }
+                    p = p / x;
+                    q = q / x;
+                    r = r / x;
+                }
+
+                s = (p * p + q * q + r * r).sqrt();
+                if p < zero {
+                    s = -s;
+                }
+                if s == zero {
+                    continue;
+                }
+                if k != m {
+                    h[(k, k - 1)] = -s * x;
+                } else if l != m {
+                    h[(k, k - 1)] = -h[(k, k - 1)];
+                }
+                p = p + s;
+                x = p / s;
+                y = q / s;
+                z = r / s;
+                q = q / p;
+                r = r / p;
+
+                // Row modification
+                for j in k..nn {
+                    p = h[(k, j)] + q * h[(k + 1, j)];
+                    if not_last {
+                        p = p + r * h[(k + 2, j)];
+                        h[(k + 2, j)] = h[(k + 2, j)] - p * z;
+                    }
+                    h[(k, j)] = h[(k, j)] - p * x;
+                    h[(k + 1, j)] = h[(k + 1, j)] - p * y;
+                }
+                // Column modification
+                for i in 0..=n.min(k + 3) {
+                    p = x * h[(i, k)] + y * h[(i, k + 1)];
+                    if not_last {
+                        p = p + z * h[(i, k + 2)];
+                        h[(i, k + 2)] = h[(i, k + 2)] - p * r;
+                    }
+                    h[(i, k)] = h[(i, k)] - p;
+                    h[(i, k + 1)] = h[(i, k + 1)] - p * q;
+                }
+                // Accumulate the transformations
+                for i in 0..nn {
+                    p = x * v[(i, k)] + y * v[(i, k + 1)];
+                    if not_last {
+                        p = p + z * v[(i, k + 2)];
+                        v[(i, k + 2)] = v[(i, k + 2)] - p * r;
+                    }
+                    v[(i, k)] = v[(i, k)] - p;
+                    v[(i, k + 1)] = v[(i, k + 1)] - p * q;
+                }
+            }
+        }
+    }
+
+    if norm == zero {
+        return Ok((d, e));
+    }
+
+    // Back substitute to find the vectors of the upper triangular form
+    for n in (0..nn).rev() {
+        p = d[n];
+        q = e[n];
+
+        if q == zero {
+            // A real vector
+            let mut l = n;
+            h[(n, n)] = one;
+            for i in (0..n).rev() {
+                w = h[(i, i)] - p;
+                r = (l..=n).fold(zero, |acc, j| acc + h[(i, j)] * h[(j, n)]);
+                if e[i] < zero {
+                    z = w;
+                    s = r;
+                    continue;
+                }
+
+                l = i;
+                if e[i] == zero {
+                    h[(i, n)] =
+                        if w == zero { -r / (eps * norm) } else { -r / w };
+                } else {
+                    // Solve the real equations
+                    x = h[(i, i + 1)];
+                    y = h[(i + 1, i)];
+                    q = (d[i] - p) * (d[i] - p) + e[i] * e[i];
+                    t = (x * s - z * r) / q;
+                    h[(i, n)] = t;
+                    h[(i + 1, n)] = if x.abs() > z.abs() {
+                        (-r - w * t) / x
+                    } else {
+                        (-s - y * t) / z
+                    };
+                }
+
+                // Overflow control
+                t = h[(i, n)].abs();
+                if eps * t * t > one {
+                    for j in i..=n {
+                        h[(j, n)] = h[(j, n)] / t;
+                    }
+                }
+            }
+        } else if q < zero {
+            // A complex vector, whose real part is in column n - 1
+            let mut l = n - 1;
+            if h[(n, n - 1)].abs() > h[(n - 1, n)].abs() {
+                h[(n - 1, n - 1)] = q / h[(n, n - 1)];
+                h[(n - 1, n)] = -(h[(n, n)] - p) / h[(n, n - 1)];
+            } else {
+                (h[(n - 1, n - 1)], h[(n - 1, n)]) =
+                    cdiv(zero, -h[(n - 1, n)], h[(n - 1, n - 1)] - p, q);
+            }
+            h[(n, n - 1)] = zero;
+            h[(n, n)] = one;
+
+            for i in (0..n - 1).rev() {
+                let (mut ra, mut sa) = (zero, zero);
+                for j in l..=n {
+                    ra = ra + h[(i, j)] * h[(j, n - 1)];
+                    sa = sa + h[(i, j)] * h[(j, n)];
+                }
+                w = h[(i, i)] - p;
+
+                if e[i] < zero {
+                    z = w;
+                    r = ra;
+                    s = sa;
+                    continue;
+                }
+
+                l = i;
+                if e[i] == zero {
+                    (h[(i, n - 1)], h[(i, n)]) = cdiv(-ra, -sa, w, q);
+                } else {
+                    // Solve the complex equations
+                    x = h[(i, i + 1)];
+                    y = h[(i + 1, i)];
+                    let mut vr = (d[i] - p) * (d[i] - p) + e[i] * e[i] - q * q;
+                    let vi = (d[i] - p) * two * q;
+                    if vr == zero && vi == zero {
+                        vr = eps
+                            * norm
+                            * (w.abs() + q.abs() + x.abs() + y.abs() + z.abs());
+                    }
+                    (h[(i, n - 1)], h[(i, n)]) = cdiv(
+                        x * r - z * ra + q * sa,
+                        x * s - z * sa - q * ra,
+                        vr,
+                        vi,
+                    );
+                    if x.abs() > z.abs() + q.abs() {
+                        h[(i + 1, n - 1)] =
+                            (-ra - w * h[(i, n - 1)] + q * h[(i, n)]) / x;
+                        h[(i + 1, n)] =
+                            (-sa - w * h[(i, n)] - q * h[(i, n - 1)]) / x;
+                    } else {
+                        (h[(i + 1, n - 1)], h[(i + 1, n)]) = cdiv(
+                            -r - y * h[(i, n - 1)],
+                            -s - y * h[(i, n)],
+                            z,
+                            q,
+                        );
+                    }
+                }
+
+                // Overflow control
+                t = h[(i, n - 1)].abs().max(h[(i, n)].abs());
+                if eps * t * t > one {
+                    for j in i..=n {
+                        h[(j, n - 1)] = h[(j, n - 1)] / t;
+                        h[(j, n)] = h[(j, n)] / t;
+                    }
+                }
+            }
+        }
+    }
+
+    // Back transform to find the vectors of the original matrix
+    for j in (0..nn).rev() {
+        for i in 0..nn {
+            z = (0..=j).fold(zero, |acc, k| acc + v[(i, k)] * h[(k, j)]);
+            v[(i, j)] = z;
+        }
+    }
+
+    Ok((d, e))
+}
+
+/// Compute the eigenvalues and the unit eigenvectors, as rows, of a general
+/// square matrix
+#[allow(clippy::many_single_char_names, clippy::type_complexity)]
+fn general_eigen<T: LinalgScalar>(
+    mut matrix: Matrix<T>,
+) -> Result<(Vec<Complex<T>>, Vec<Complex<T>>), LinalgError> {
+    check_finite(&matrix)?;
+    let n = matrix.rows;
+    let mut v = hessenberg(&mut matrix);
+    let (d, e) = schur_eigen(&mut matrix, &mut v)?;
+
+    let values =
+        d.iter().zip(&e).map(|(&re, &im)| Complex::new(re, im)).collect();
+    let mut vectors = Vec::with_capacity(n * n);
+    let mut j = 0;
+    while j < n {
+        let mut push = |vector: &mut dyn Iterator<Item = Complex<T>>| {
+            let start = vectors.len();
+            vectors.extend(vector);
+            let norm = vectors[start..]
+                .iter()
+                .fold(T::zero(), |acc, x: &Complex<T>| acc.hypot(x.norm()));
+            if norm > T::zero() {
+                for x in &mut vectors[start..] {
+                    *x = *x / norm;
+                }
+            }
+        };
+
+        if e[j] > T::zero() && j + 1 < n {
+            // The columns of a complex pair hold the real and imaginary parts
+            // of the first vector, and the second is its conjugate
+            push(&mut (0..n).map(|i| Complex::new(v[(i, j)], v[(i, j + 1)])));
+            push(&mut (0..n).map(|i| Complex::new(v[(i, j)], -v[(i, j + 1)])));
+            j += 2;
+        } else {
+            push(&mut (0..n).map(|i| Complex::new(v[(i, j)], T::zero())));
+            j += 1;
+        }
+    }
+
+    Ok((values, vectors))
+}
+
+/// Transpose the square matrices in `data`, each with `n` rows, so that the
+/// vectors stored in their rows are stored in their columns
+fn rows_to_columns<T: Copy>(data: &[T], n: usize) -> Vec<T> {
+    (0..n * n).map(|k| data[(k % n) * n + k / n]).collect()
+}
+
+/// Compute the eigenvalues and eigenvectors of the symmetric matrix `a`.
+///
+/// Only the lower triangle of `a` is read. Returns the eigenvalues in
+/// ascending order, and a matrix whose columns are the corresponding unit
+/// eigenvectors, so that `A V = V diag(w)`. The matrix is reduced to
+/// tridiagonal form by Householder reflections, which is then diagonalised by
+/// the implicit QL algorithm.
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square, or
+/// [`LinalgError::NoConvergence`] if it contains a NaN or an infinity, or the
+/// algorithm fails to converge.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![2.0_f64, 1.0, 1.0, 2.0],
+/// )
+/// .unwrap();
+/// let (w, v) = linalg::eigh(&a).unwrap();
+/// assert!((w[[0]] - 1.0).abs() < 1e-12);
+/// assert!((w[[1]] - 3.0).abs() < 1e-12);
+/// assert!((v[[0, 1]].abs() - 0.5_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn eigh<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<(Array1<T>, Array2<T>), LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    eigh_batched(a)
+}
+
+/// Compute the eigenvalues and eigenvectors of each symmetric matrix of the
+/// stack `a`, whose matrices are indexed by all but its last two axes. See
+/// [`eigh`].
+///
+/// # Errors
+/// Returns [`LinalgError::Shape`] if `a` has fewer than two axes, or any
+/// error of [`eigh`] for one of its matrices.
+#[allow(clippy::type_complexity)]
+pub fn eigh_batched<T, StorageType, NDims>(
+    a: &ArrayBase<HostBackend, StorageType, NDims>,
+) -> Result<
+    (
+        ArrayBase<HostBackend, HostStorage<T>, NDims::Smaller>,
+        ArrayBase<HostBackend, HostStorage<T>, NDims>,
+    ),
+    LinalgError,
+>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    NDims: Dimension,
+{
+    let batch = Batch::new(a)?;
+    batch.check_square()?;
+    let (shape, n) = (batch.shape.clone(), batch.rows);
+
+    let results = batch.map(symmetric_eigen)?;
+    let (values, vectors): (Vec<_>, Vec<_>) = results
+        .into_iter()
+        .map(|(values, vectors)| (values, rows_to_columns(&vectors.data, n)))
+        .unzip();
+
+    Ok((
+        stack_results(&shape, &[n], values),
+        stack_results(&shape, &[n, n], vectors),
+    ))
+}
+
+/// Compute the eigenvalues and eigenvectors of the square matrix `a`, which
+/// are complex in general.
+///
+/// Returns the eigenvalues, and a matrix whose columns are the corresponding
+/// unit eigenvectors, so that `A V = V diag(w)`. The eigenvalues are not
+/// sorted, but the complex eigenvalues of a real matrix come in conjugate
+/// pairs, which are adjacent with the positive imaginary part first. The
+/// matrix is reduced to Hessenberg form, and then to real Schur form by the
+/// shifted double QR algorithm. For symmetric matrices, [`eigh`] is faster
+/// and more accurate.
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square, or
+/// [`LinalgError::NoConvergence`] if it contains a NaN or an infinity, or the
+/// algorithm fails to converge.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// // A rotation by a quarter turn has eigenvalues `i` and `-i`
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![0.0_f64, -1.0, 1.0, 0.0],
+/// )
+/// .unwrap();
+/// let (w, _) = linalg::eig(&a).unwrap();
+/// assert!((w[[0]] - linalg::Complex::new(0.0, 1.0)).norm() < 1e-12);
+/// assert!((w[[1]] - linalg::Complex::new(0.0, -1.0)).norm() < 1e-12);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn eig<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<(Array1<Complex<T>>, Array2<Complex<T>>), LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    eig_batched(a)
+}
+
+/// Compute the eigenvalues and eigenvectors of each square matrix of the
+/// stack `a`, whose matrices are indexed by all but its last two axes. See
+/// [`eig`].
+///
+/// # Errors
+/// Returns [`LinalgError::Shape`] if `a` has fewer than two axes, or any
+/// error of [`eig`] for one of its matrices.
+#[allow(clippy::type_complexity)]
+pub fn eig_batched<T, StorageType, NDims>(
+    a: &ArrayBase<HostBackend, StorageType, NDims>,
+) -> Result<
+    (
+        ArrayBase<HostBackend, HostStorage<Complex<T>>, NDims::Smaller>,
+        ArrayBase<HostBackend, HostStorage<Complex<T>>, NDims>,
+    ),
+    LinalgError,
+>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    NDims: Dimension,
+{
+    let batch = Batch::new(a)?;
+    batch.check_square()?;
+    let (shape, n) = (batch.shape.clone(), batch.rows);
+
+    let results = batch.map(general_eigen)?;
+    let (values, vectors): (Vec<_>, Vec<_>) = results
+        .into_iter()
+        .map(|(values, vectors)| (values, rows_to_columns(&vectors, n)))
+        .unzip();
+
+    Ok((
+        stack_results(&shape, &[n], values),
+        stack_results(&shape, &[n, n], vectors),
+    ))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use super::*;
+    use crate::{
+        array::type_remap::{Array3, ArrayDyn},
+        dimension::{dim::Dim3, dyn_dim::DimDyn},
+    };
+
+    /// A matrix with pseudo-random elements between -5 and 5
+    fn random(n: usize, seed: usize) -> Array2<f64> {
+        Array2::from_shape_fn(Dim2::new([n, n]), |i| {
+            let hash =
+                (i[0] * 7919 + i[1] * 104_729 + i[0] * i[1] * 31 + seed) % 1000;
+            f64::from(u32::try_from(hash).unwrap()) / 100.0 - 5.0
+        })
+    }
+
+    /// Assert that `A v = w v` for each eigenpair
+    #[track_caller]
+    fn assert_eigenpairs(
+        a: &Array2<f64>,
+        w: &Array1<Complex<f64>>,
+        v: &Array2<Complex<f64>>,
+        tolerance: f64,
+    ) {
+        let n = a.axes.shape[0];
+        for k in 0..n {
+            let norm = (0..n).map(|i| v[[i, k]].norm_sqr()).sum::<f64>();
+            assert!((norm - 1.0).abs() < 1e-9);
+            for i in 0..n {
+                let av = (0..n).fold(Complex::new(0.0, 0.0), |acc, j| {
+                    acc + v[[j, k]] * a[[i, j]]
+                });
+                let error = (av - w[[k]] * v[[i, k]]).norm();
+                assert!(error < tolerance, "{error} at [{i}, {k}]");
+            }
+        }
+    }
+
+    #[test]
+    fn test_eigh() {
+        for n in [0, 1, 2, 7, 60] {
+            let b = random(n, 3);
+            let a = Array2::from_shape_fn(Dim2::new([n, n]), |i| {
+                b[[i[0], i[1]]] + b[[i[1], i[0]]]
+            });
+            let (w, v) = eigh(&a).unwrap();
+            assert_eq!(w.axes.shape.as_slice(), &[n]);
+            assert!((1..n).all(|i| w[[i - 1]] <= w[[i]]));
+
+            let complex_w = Array1::from_shape_fn(w.axes.shape.clone(), |i| {
+                Complex::new(w[[i[0]]], 0.0)
+            });
+            let complex_v = Array2::from_shape_fn(v.axes.shape.clone(), |i| {
+                Complex::new(v[[i[0], i[1]]], 0.0)
+            });
+            assert_eigenpairs(&a, &complex_w, &complex_v, 1e-9);
+        }
+
+        // Only the lower triangle is read
+        let a = Array2::from_vec(Dim2::new([2, 2]), vec![2.0, 100.0, 0.0, 3.0])
+            .unwrap();
+        let (w, _) = eigh(&a).unwrap();
+        assert_eq!([w[[0]], w[[1]]], [2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_eig() {
+        for n in [0, 1, 2, 5, 40] {
+            let a = random(n, 11);
+            let (w, v) = eig(&a).unwrap();
+            assert_eigenpairs(&a, &w, &v, 1e-8);
+
+            // Complex eigenvalues come in adjacent conjugate pairs
+            let mut i = 0;
+            while i < n {
+                if w[[i]].im == 0.0 {
+                    i += 1;
+                } else {
+                    assert!(w[[i]].im > 0.0);
+                    assert_eq!(w[[i + 1]], w[[i]].conj());
+                    i += 2;
+                }
+            }
+        }
+
+        // A rotation and a shear
+        let a = Array2::from_vec(
+            Dim2::new([3, 3]),
+            vec![0.0, -2.0, 0.0, 2.0, 0.0, 0.0, 0.0, 0.0, 1.0],
+        )
+        .unwrap();
+        let (w, v) = eig(&a).unwrap();
+        assert_eigenpairs(&a, &w, &v, 1e-12);
+        let mut im: Vec<f64> = (0..3).map(|i| w[[i]].im).collect();
+        im.sort_by(f64::total_cmp);
+        assert_eq!(im, vec![-2.0, 0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_batched() {
+        let a = Array3::from_shape_fn(Dim3::new([3, 4, 4]), |i| {
+            random(4, i[0])[[i[1], i[2]]] + random(4, i[0])[[i[2], i[1]]]
+        });
+        let (w, v) = eigh_batched(&a).unwrap();
+        assert_eq!(w.axes.shape.as_slice(), &[3, 4]);
+        assert_eq!(v.axes.shape.as_slice(), &[3, 4, 4]);
+
+        let dynamic = ArrayDyn::from_shape_fn(
+            DimDyn::from_slice(&[3, 4, 4]).unwrap(),
+            |i| a[[i[0], i[1], i[2]]],
+        );
+        let (w_dyn, _) = eig_batched(&dynamic).unwrap();
+        assert_eq!(w_dyn.axes.shape.as_slice(), &[3, 4]);
+
+        for k in 0..3 {
+            let (expected, _) =
+                eigh(&Array2::from_shape_fn(Dim2::new([4, 4]), |i| {
+                    a[[k, i[0], i[1]]]
+                }))
+                .unwrap();
+            for i in 0..4 {
+                assert_eq!(w[[k, i]], expected[[i]]);
+            }
+        }
+
+        assert_eq!(
+            eigh_batched(&Array3::<f64>::zeros(Dim3::new([2, 3, 2]))).err(),
+            Some(LinalgError::NotSquare { rows: 3, cols: 2 })
+        );
+    }
+
+    #[test]
+    fn test_no_convergence() {
+        let mut a = random(3, 0);
+        a[[1, 2]] = f64::NAN;
+        assert_eq!(eig(&a).err(), Some(LinalgError::NoConvergence));
+        a[[2, 1]] = f64::INFINITY;
+        assert_eq!(eigh(&a).err(), Some(LinalgError::NoConvergence));
+    }
+}
@@ -0,0 +1,480 @@
+//! The LU decomposition, and the solutions, inverses and determinants
+//! computed from it.
+
+use crate::{
+    array::{base::ArrayBase, type_remap::Array2},
+    backend::{
+        host::{host_backend::HostBackend, host_storage::HostStorage},
+        traits::Storage,
+    },
+    dimension::dim::{Dim2, Dimension},
+    error::LinalgError,
+    linalg::{
+        back_substitute, check_square, for_each_column, for_each_row,
+        forward_substitute, LinalgScalar, Matrix,
+    },
+};
+
+/// The LU decomposition of a square matrix `A` with partial pivoting.
+///
+/// The decomposition is `P A = L U`, where `P` is a permutation matrix, `L`
+/// is lower triangular with ones on its diagonal and `U` is upper triangular.
+/// A decomposition can be reused to solve several systems of equations with
+/// the same matrix, and exists even if the matrix is singular, in which case
+/// `U` has a zero on its diagonal.
+#[derive(Debug, Clone)]
+pub struct Lu<T> {
+    /// `L` below the diagonal, and `U` on and above it
+    factors: Matrix<T>,
+
+    /// Row `i` of `P A` is row `permutation[i]` of `A`
+    permutation: Vec<usize>,
+
+    /// Whether `P` is an odd permutation, so has determinant `-1`
+    odd: bool,
+}
+
+/// Compute the LU decomposition of the square matrix `a` with partial
+/// pivoting. See [`Lu`].
+///
+/// At each step, the row with the largest element in the pivot column is
+/// swapped into place, and the rows below it are updated in parallel.
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![1.0_f64, 2.0, 3.0, 4.0],
+/// )
+/// .unwrap();
+/// let lu = linalg::lu(&a).unwrap();
+///
+/// // The larger element of the first column is chosen as the pivot
+/// assert_eq!(lu.permutation(), &[1, 0]);
+/// assert_eq!(lu.u()[[0, 0]], 3.0);
+/// assert!((lu.det() + 2.0).abs() < 1e-12);
+/// ```
+pub fn lu<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<Lu<T>, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    let mut factors = Matrix::from_array(a);
+    check_square(&factors)?;
+
+    let n = factors.rows;
+    let mut permutation: Vec<usize> = (0..n).collect();
+    let mut odd = false;
+
+    for k in 0..n {
+        // Choosing the largest pivot keeps every multiplier at most one in
+        // magnitude
+        let pivot = (k + 1..n).fold(k, |best, i| {
+            if factors[(i, k)].abs() > factors[(best, k)].abs() {
+                i
+            } else {
+                best
+            }
+        });
+        if pivot != k {
+            for j in 0..n {
+                factors.data.swap(k * n + j, pivot * n + j);
+            }
+            permutation.swap(k, pivot);
+            odd = !odd;
+        }
+
+        // If the pivot is zero, so is the rest of the column, and there is
+        // nothing to eliminate
+        let diagonal = factors[(k, k)];
+        if diagonal.is_zero() {
+            continue;
+        }
+
+        let (head, tail) = factors.data.split_at_mut((k + 1) * n);
+        let pivot_row = &head[k * n + k + 1..];
+        for_each_row(tail, n, |_, row| {
+            let multiplier = row[k] / diagonal;
+            row[k] = multiplier;
+            if !multiplier.is_zero() {
+                for (value, &pivot) in row[k + 1..].iter_mut().zip(pivot_row) {
+                    *value = *value - multiplier * pivot;
+                }
+            }
+        });
+    }
+
+    Ok(Lu { factors, permutation, odd })
+}
+
+impl<T: LinalgScalar> Lu<T> {
+    /// The lower triangular factor `L`, with ones on its diagonal
+    #[must_use]
+    pub fn l(&self) -> Array2<T> {
+        let mut l = Matrix::eye(self.factors.rows, self.factors.cols);
+        for i in 0..l.rows {
+            for j in 0..i {
+                l[(i, j)] = self.factors[(i, j)];
+            }
+        }
+        l.into_array()
+    }
+
+    /// The upper triangular factor `U`
+    #[must_use]
+    pub fn u(&self) -> Array2<T> {
+        let mut u = Matrix::zeros(self.factors.rows, self.factors.cols);
+        for i in 0..u.rows {
+            for j in i..u.cols {
+                u[(i, j)] = self.factors[(i, j)];
+            }
+        }
+        u.into_array()
+    }
+
+    /// The permutation matrix `P`
+    #[must_use]
+    pub fn p(&self) -> Array2<T> {
+        let n = self.permutation.len();
+        let mut p = Matrix::zeros(n, n);
+        for (i, &row) in self.permutation.iter().enumerate() {
+            p[(i, row)] = T::one();
+        }
+        p.into_array()
+    }
+
+    /// The permutation of the rows of `A`, such that row `i` of `P A` is row
+    /// `permutation()[i]` of `A`
+    #[must_use]
+    pub fn permutation(&self) -> &[usize] {
+        &self.permutation
+    }
+
+    /// Returns `true` if the matrix is singular, meaning `U` has a zero on
+    /// its diagonal
+    #[must_use]
+    pub fn is_singular(&self) -> bool {
+        (0..self.factors.rows).any(|i| self.factors[(i, i)].is_zero())
+    }
+
+    /// The determinant of the matrix
+    #[must_use]
+    pub fn det(&self) -> T {
+        let sign = if self.odd { -T::one() } else { T::one() };
+        (0..self.factors.rows).fold(sign, |acc, i| acc * self.factors[(i, i)])
+    }
+
+    /// The sign and the natural logarithm of the absolute value of the
+    /// determinant of the matrix, which do not overflow or underflow for
+    /// large matrices when the determinant itself would. The sign is zero
+    /// and the logarithm is negative infinity if the matrix is singular.
+    #[must_use]
+    pub fn slogdet(&self) -> (T, T) {
+        if self.is_singular() {
+            return (T::zero(), T::neg_infinity());
+        }
+
+        let sign = if self.odd { -T::one() } else { T::one() };
+        (0..self.factors.rows).fold((sign, T::zero()), |(sign, log), i| {
+            let diagonal = self.factors[(i, i)];
+            (sign * diagonal.signum(), log + diagonal.abs().ln())
+        })
+    }
+
+    /// Solve `A X = B` for each column of `b`, reusing the decomposition
+    fn solve_matrix(&self, b: &Matrix<T>) -> Result<Matrix<T>, LinalgError> {
+        let n = self.factors.rows;
+        if b.rows != n {
+            return Err(LinalgError::RowMismatch {
+                expected: n,
+                found: b.rows,
+            });
+        }
+        if self.is_singular() {
+            return Err(LinalgError::Singular);
+        }
+
+        // Solve `L U X = P B`
+        let mut x = Matrix::zeros(n, b.cols);
+        for (i, &row) in self.permutation.iter().enumerate() {
+            x.data[i * b.cols..(i + 1) * b.cols]
+                .copy_from_slice(&b.data[row * b.cols..(row + 1) * b.cols]);
+        }
+        for_each_column(&mut x, |column| {
+            forward_substitute(&self.factors, true, column);
+            back_substitute(&self.factors, column);
+        });
+
+        Ok(x)
+    }
+
+    /// Solve `A x = b`, where `b` is a vector or a matrix with a right-hand
+    /// side in each column. See [`solve`].
+    ///
+    /// # Errors
+    /// Returns [`LinalgError::Singular`] if the matrix is singular,
+    /// [`LinalgError::RowMismatch`] if `b` does not have a row for each row of
+    /// the matrix, or [`LinalgError::Shape`] if `b` is not a vector or a
+    /// matrix.
+    pub fn solve<RhsStorage, RhsDims>(
+        &self,
+        b: &ArrayBase<HostBackend, RhsStorage, RhsDims>,
+    ) -> Result<ArrayBase<HostBackend, HostStorage<T>, RhsDims>, LinalgError>
+    where
+        RhsStorage: Storage<Scalar = T>,
+        RhsDims: Dimension,
+    {
+        let vector = b.axes.shape.as_slice().len() == 1;
+        let x = self.solve_matrix(&Matrix::from_rhs(b)?)?;
+        Ok(x.into_rhs_array(vector))
+    }
+
+    /// The inverse of the matrix. See [`inv`].
+    ///
+    /// # Errors
+    /// Returns [`LinalgError::Singular`] if the matrix is singular.
+    pub fn inv(&self) -> Result<Array2<T>, LinalgError> {
+        let n = self.factors.rows;
+        Ok(self.solve_matrix(&Matrix::eye(n, n))?.into_array())
+    }
+}
+
+/// Solve the system of linear equations `A x = b` for the square matrix `a`,
+/// where `b` is a vector or a matrix with a right-hand side in each column.
+/// The solution has the same shape as `b`.
+///
+/// The system is solved using the LU decomposition of `a` (see [`lu`]). To
+/// solve several systems with the same matrix, compute the decomposition once
+/// and use [`Lu::solve`].
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square,
+/// [`LinalgError::Singular`] if it is singular, [`LinalgError::RowMismatch`]
+/// if `b` does not have a row for each row of `a`, or [`LinalgError::Shape`]
+/// if `b` is not a vector or a matrix.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::{Array1, Array2};
+/// use tensr::dimension::dim::{Dim1, Dim2};
+/// use tensr::linalg;
+///
+/// // 2x + y = 5, x + 3y = 10
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![2.0_f64, 1.0, 1.0, 3.0],
+/// )
+/// .unwrap();
+/// let b = Array1::from_vec(Dim1::new([2]), vec![5.0_f64, 10.0]).unwrap();
+///
+/// let x = linalg::solve(&a, &b).unwrap();
+/// assert!((x[0] - 1.0).abs() < 1e-12);
+/// assert!((x[1] - 3.0).abs() < 1e-12);
+/// ```
+pub fn solve<T, StorageType, RhsStorage, RhsDims>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+    b: &ArrayBase<HostBackend, RhsStorage, RhsDims>,
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, RhsDims>, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    RhsStorage: Storage<Scalar = T>,
+    RhsDims: Dimension,
+{
+    lu(a)?.solve(b)
+}
+
+/// Compute the inverse of the square matrix `a`, using its LU decomposition.
+///
+/// Solving a system of equations with [`solve`] is faster and more accurate
+/// than multiplying by the inverse.
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square, or
+/// [`LinalgError::Singular`] if it is singular.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![4.0_f64, 7.0, 2.0, 6.0],
+/// )
+/// .unwrap();
+/// let inverse = linalg::inv(&a).unwrap();
+/// assert!((inverse[[0, 0]] - 0.6).abs() < 1e-12);
+/// assert!((inverse[[0, 1]] + 0.7).abs() < 1e-12);
+///
+/// let singular = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![1.0_f64, 2.0, 2.0, 4.0],
+/// )
+/// .unwrap();
+/// assert!(linalg::inv(&singular).is_err());
+/// ```
+pub fn inv<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<Array2<T>, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    lu(a)?.inv()
+}
+
+/// Compute the determinant of the square matrix `a`, using its LU
+/// decomposition. The determinant of a singular matrix is zero.
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square.
+pub fn det<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<T, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    Ok(lu(a)?.det())
+}
+
+/// Compute the sign and the natural logarithm of the absolute value of the
+/// determinant of the square matrix `a`. See [`Lu::slogdet`].
+///
+/// # Errors
+/// Returns [`LinalgError::NotSquare`] if `a` is not square.
+pub fn slogdet<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<(T, T), LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    Ok(lu(a)?.slogdet())
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use super::*;
+    use crate::{
+        array::type_remap::{Array1, ArrayView2},
+        dimension::dim::Dim1,
+        linalg::test_util::assert_close,
+        s,
+    };
+
+    /// A matrix of pseudo-random values, which is almost certainly not
+    /// singular, and needs row swaps to factorise
+    fn matrix(n: usize) -> Array2<f64> {
+        Array2::from_shape_fn(Dim2::new([n, n]), |i| {
+            let hash = (i[0] * 7919 + i[1] * 104_729 + i[0] * i[1] * 31) % 1000;
+            f64::from(u32::try_from(hash).unwrap()) / 100.0 - 5.0
+        })
+    }
+
+    #[test]
+    fn test_lu() {
+        for n in [1, 3, 8, 150] {
+            let a = matrix(n);
+            let lu = lu(&a).unwrap();
+            let pa = lu.p().matmul(&a);
+            let product = lu.l().matmul(&lu.u());
+            assert_close(&pa, &product, 1e-9);
+
+            for i in 0..n {
+                for j in 0..i {
+                    assert!(lu.l()[[i, j]].abs() <= 1.0);
+                }
+            }
+        }
+
+        assert_eq!(
+            lu(&Array2::<f32>::zeros(Dim2::new([2, 3]))).err(),
+            Some(LinalgError::NotSquare { rows: 2, cols: 3 })
+        );
+    }
+
+    #[test]
+    fn test_solve_and_inv() {
+        let a = matrix(40);
+        let x = Array2::from_shape_fn(Dim2::new([40, 3]), |i| {
+            f64::from(u32::try_from(i[0] + 2 * i[1]).unwrap())
+        });
+        let b = a.matmul(&x);
+        assert_close(&solve(&a, &b).unwrap(), &x, 1e-9);
+
+        let column = Array1::from_shape_fn(Dim1::new([40]), |i| b[[i[0], 1]]);
+        let solved = solve(&a, &column).unwrap();
+        assert_eq!(solved.axes.shape.as_slice(), &[40]);
+        assert!((0..40).all(|i| (solved[i] - x[[i, 1]]).abs() < 1e-9));
+
+        let inverse = inv(&a).unwrap();
+        let eye = Matrix::eye(40, 40).into_array();
+        assert_close(&inverse.matmul(&a), &eye, 1e-9);
+
+        // A reversed view is read through its strides
+        let view: ArrayView2<'_, f64> = a.slice(s![..;-1, ..]);
+        let reversed =
+            Array2::from_shape_fn(Dim2::new([40, 3]), |i| b[[39 - i[0], i[1]]]);
+        assert_close(&solve(&view, &reversed).unwrap(), &x, 1e-9);
+
+        assert_eq!(
+            solve(&a, &Array1::<f64>::zeros(Dim1::new([3]))).err(),
+            Some(LinalgError::RowMismatch { expected: 40, found: 3 })
+        );
+    }
+
+    #[test]
+    fn test_singular() {
+        let a = Array2::from_vec(
+            Dim2::new([3, 3]),
+            vec![1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 1.0, 0.0, 1.0],
+        )
+        .unwrap();
+        assert!(lu(&a).unwrap().is_singular());
+        assert_eq!(inv(&a).err(), Some(LinalgError::Singular));
+        assert_eq!(det(&a).unwrap(), 0.0);
+        assert_eq!(slogdet(&a).unwrap(), (0.0, f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn test_det() {
+        let a = Array2::from_vec(
+            Dim2::new([3, 3]),
+            vec![2.0_f32, -3.0, 1.0, 2.0, 0.0, -1.0, 1.0, 4.0, 5.0],
+        )
+        .unwrap();
+        assert!((det(&a).unwrap() - 49.0).abs() < 1e-4);
+
+        let (sign, log) = slogdet(&a).unwrap();
+        assert_eq!(sign, 1.0);
+        assert!((log - 49.0_f32.ln()).abs() < 1e-5);
+
+        // The determinant of a large diagonal matrix underflows, but its
+        // logarithm does not
+        let small = Array2::from_shape_fn(Dim2::new([400, 400]), |i| {
+            if i[0] == i[1] {
+                -0.01
+            } else {
+                0.0
+            }
+        });
+        assert_eq!(det(&small).unwrap(), 0.0);
+        let (sign, log) = slogdet(&small).unwrap();
+        assert_eq!(sign, 1.0);
+        let expected = 400.0 * 0.01_f64.ln();
+        assert!((log - expected).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,424 @@
+//! Dense linear algebra on the host.
+//!
+//! The routines in this module operate on matrices of `f32` or `f64`, which
+//! may be owned arrays or views with any strides. Each operand is first copied
+//! into a contiguous working matrix, which is then factorised in place, and
+//! the larger steps of each factorisation are split between threads.
+//!
+//! Routines with a `_batched` suffix accept stacks of matrices, indexed by
+//! any number of leading axes, and process the matrices of a stack in
+//! parallel.
+//!
+//! Routines which can fail, such as solving a singular system, return a
+//! [`LinalgError`](crate::error::LinalgError) rather than panicking.
+
+pub mod cholesky;
+pub mod eigen;
+pub mod lu;
+pub mod qr;
+pub mod svd;
+
+pub use cholesky::cholesky;
+pub use eigen::{eig, eig_batched, eigh, eigh_batched};
+pub use lu::{det, inv, lu, slogdet, solve, Lu};
+pub use num_complex::Complex;
+use num_traits::Float;
+pub use qr::{lstsq, qr};
+use rayon::prelude::*;
+pub use svd::{
+    matrix_rank, matrix_rank_batched, norm, norm_batched, pinv, pinv_batched,
+    svd, svd_batched, NormOrd,
+};
+
+use crate::{
+    array::{base::ArrayBase, type_remap::Array2},
+    backend::{
+        host::{
+            host_backend::HostBackend, host_gemm::GemmScalar,
+            host_storage::HostStorage,
+        },
+        traits::Storage,
+    },
+    dimension::{
+        axes::Axes,
+        dim::{Dim2, Dimension},
+        index::next_index,
+        stride::advance,
+    },
+    error::{LinalgError, ShapeError},
+    types::UDim,
+};
+
+/// A floating point type supported by the routines in [`linalg`](self)
+pub trait LinalgScalar: Float + GemmScalar + std::fmt::Debug {}
+
+/// Implement [`LinalgScalar`] for each of the given types
+macro_rules! linalg_scalar_impl {
+    ($($t: ty),*) => {
+        $(impl LinalgScalar for $t {})*
+    };
+}
+
+linalg_scalar_impl!(f32, f64);
+
+/// The minimum number of elements updated by a step of a factorisation
+/// before the update is split between threads
+const PARALLEL_THRESHOLD: usize = 1 << 14;
+
+/// Call `f` with the index and contents of each row of the row-major matrix
+/// `data`, which has `cols` columns. Rows are processed in parallel if there
+/// are enough elements to make it worthwhile.
+pub(crate) fn for_each_row<T, F>(data: &mut [T], cols: usize, f: F)
+where
+    T: Send,
+    F: Fn(usize, &mut [T]) + Sync,
+{
+    if cols == 0 {
+        return;
+    }
+
+    if data.len() >= PARALLEL_THRESHOLD {
+        data.par_chunks_mut(cols).enumerate().for_each(|(i, row)| f(i, row));
+    } else {
+        data.chunks_mut(cols).enumerate().for_each(|(i, row)| f(i, row));
+    }
+}
+
+/// A working copy of a matrix, stored contiguously in row-major order
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Matrix<T> {
+    pub(crate) rows: usize,
+    pub(crate) cols: usize,
+    pub(crate) data: Vec<T>,
+}
+
+impl<T: LinalgScalar> Matrix<T> {
+    /// A matrix of zeros
+    pub(crate) fn zeros(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![T::zero(); rows * cols] }
+    }
+
+    /// The `rows` by `cols` matrix with ones on its main diagonal and zeros
+    /// elsewhere
+    pub(crate) fn eye(rows: usize, cols: usize) -> Self {
+        let mut matrix = Self::zeros(rows, cols);
+        for i in 0..rows.min(cols) {
+            matrix[(i, i)] = T::one();
+        }
+        matrix
+    }
+
+    /// Copy the `rows` by `cols` matrix whose first element is at `offset` in
+    /// `storage`, with the given strides between rows and columns
+    fn from_strided<StorageType>(
+        storage: &StorageType,
+        offset: usize,
+        [rows, cols]: [usize; 2],
+        [row_stride, col_stride]: [usize; 2],
+    ) -> Self
+    where
+        StorageType: Storage<Scalar = T>,
+    {
+        let mut data = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            let start = advance(offset, i, row_stride);
+            data.extend(
+                (0..cols).map(|j| storage[advance(start, j, col_stride)]),
+            );
+        }
+
+        Self { rows, cols, data }
+    }
+
+    /// Copy a matrix, which may have any strides
+    pub(crate) fn from_array<StorageType>(
+        array: &ArrayBase<HostBackend, StorageType, Dim2>,
+    ) -> Self
+    where
+        StorageType: Storage<Scalar = T>,
+    {
+        let strides = &array.axes.stride.strides;
+        Self::from_strided(
+            &array.storage,
+            array.axes.stride.offset,
+            [array.axes.shape[0], array.axes.shape[1]],
+            [strides[0], strides[1]],
+        )
+    }
+
+    /// Copy the right-hand side of a system of equations, which is a vector
+    /// (a single column) or a matrix
+    pub(crate) fn from_rhs<StorageType, NDims>(
+        array: &ArrayBase<HostBackend, StorageType, NDims>,
+    ) -> Result<Self, LinalgError>
+    where
+        StorageType: Storage<Scalar = T>,
+        NDims: Dimension,
+    {
+        let strides = array.axes.stride.strides.as_slice();
+        let (shape, strides) = match *array.axes.shape.as_slice() {
+            [rows] => ([rows, 1], [strides[0], 0]),
+            [rows, cols] => ([rows, cols], [strides[0], strides[1]]),
+            ref shape => {
+                return Err(ShapeError::DimensionMismatch {
+                    expected: 2,
+                    found: shape.len(),
+                }
+                .into())
+            }
+        };
+
+        Ok(Self::from_strided(
+            &array.storage,
+            array.axes.stride.offset,
+            shape,
+            strides,
+        ))
+    }
+
+    /// Move the matrix into a new array
+    pub(crate) fn into_array(self) -> Array2<T> {
+        let shape = Dim2::new([self.rows, self.cols]);
+        ArrayBase::new(
+            Axes::new_with_default_stride(shape),
+            HostStorage::from_vec(self.data),
+        )
+    }
+
+    /// Move the matrix into a new array, which is a vector if `vector` is
+    /// `true`, matching a right-hand side copied by [`Matrix::from_rhs`]
+    pub(crate) fn into_rhs_array<NDims: Dimension>(
+        self,
+        vector: bool,
+    ) -> ArrayBase<HostBackend, HostStorage<T>, NDims> {
+        let shape = if vector {
+            NDims::from_slice(&[self.rows])
+        } else {
+            NDims::from_slice(&[self.rows, self.cols])
+        };
+        let shape = shape.expect("the right-hand side has the same axes");
+
+        ArrayBase::new(
+            Axes::new_with_default_stride(shape),
+            HostStorage::from_vec(self.data),
+        )
+    }
+
+    /// The transpose of the matrix
+    pub(crate) fn transpose(&self) -> Self {
+        let mut transposed = Self::zeros(self.cols, self.rows);
+        for i in 0..self.rows {
+            for j in 0..self.cols {
+                transposed[(j, i)] = self[(i, j)];
+            }
+        }
+        transposed
+    }
+}
+
+impl<T> std::ops::Index<(usize, usize)> for Matrix<T> {
+    type Output = T;
+
+    #[inline(always)]
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.data[i * self.cols + j]
+    }
+}
+
+impl<T> std::ops::IndexMut<(usize, usize)> for Matrix<T> {
+    #[inline(always)]
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.data[i * self.cols + j]
+    }
+}
+
+/// Working copies of the matrices of a stack, whose matrices are indexed by
+/// all but its last two axes
+pub(crate) struct Batch<T> {
+    /// The shape of the leading (batch) axes of the stack
+    pub(crate) shape: Vec<UDim>,
+
+    /// The number of rows of each matrix
+    pub(crate) rows: usize,
+
+    /// The number of columns of each matrix
+    pub(crate) cols: usize,
+
+    /// The matrices, in row-major order of their batch indices
+    pub(crate) matrices: Vec<Matrix<T>>,
+}
+
+impl<T: LinalgScalar> Batch<T> {
+    /// Copy each matrix of the stack `a`
+    pub(crate) fn new<StorageType, NDims>(
+        a: &ArrayBase<HostBackend, StorageType, NDims>,
+    ) -> Result<Self, LinalgError>
+    where
+        StorageType: Storage<Scalar = T>,
+        NDims: Dimension,
+    {
+        let shape = a.axes.shape.as_slice();
+        let strides = a.axes.stride.strides.as_slice();
+        let Some(split) = shape.len().checked_sub(2) else {
+            return Err(ShapeError::DimensionMismatch {
+                expected: 2,
+                found: shape.len(),
+            }
+            .into());
+        };
+        let (batch, [rows, cols]) =
+            (&shape[..split], [shape[split], shape[split + 1]]);
+
+        let count: usize = batch.iter().product();
+        let mut index: Vec<UDim> = vec![0; split];
+        let mut matrices = Vec::with_capacity(count);
+        for _ in 0..count {
+            let offset = index
+                .iter()
+                .zip(strides)
+                .fold(a.axes.stride.offset, |acc, (&i, &stride)| {
+                    advance(acc, i, stride)
+                });
+            matrices.push(Matrix::from_strided(
+                &a.storage,
+                offset,
+                [rows, cols],
+                [strides[split], strides[split + 1]],
+            ));
+            next_index(&mut index, batch);
+        }
+
+        Ok(Self { shape: batch.to_vec(), rows, cols, matrices })
+    }
+
+    /// Return an error unless the matrices are square
+    pub(crate) const fn check_square(&self) -> Result<(), LinalgError> {
+        if self.rows == self.cols {
+            Ok(())
+        } else {
+            Err(LinalgError::NotSquare { rows: self.rows, cols: self.cols })
+        }
+    }
+
+    /// Apply `f` to each matrix in parallel, returning the results in order,
+    /// or the first error
+    pub(crate) fn map<U, F>(self, f: F) -> Result<Vec<U>, LinalgError>
+    where
+        U: Send,
+        F: Fn(Matrix<T>) -> Result<U, LinalgError> + Send + Sync,
+    {
+        self.matrices.into_par_iter().map(f).collect()
+    }
+}
+
+/// Concatenate the results of a routine for each matrix of a stack, each
+/// with shape `shape`, into an array whose leading axes have shape `batch`
+pub(crate) fn stack_results<T, NDims>(
+    batch: &[UDim],
+    shape: &[UDim],
+    results: impl IntoIterator<Item = Vec<T>>,
+) -> ArrayBase<HostBackend, HostStorage<T>, NDims>
+where
+    T: Copy,
+    NDims: Dimension,
+{
+    let full: Vec<UDim> = batch.iter().chain(shape).copied().collect();
+    let data: Vec<T> = results.into_iter().flatten().collect();
+    let full = NDims::from_slice(&full)
+        .expect("results have an axis for each batch axis and result axis");
+
+    ArrayBase::new(
+        Axes::new_with_default_stride(full),
+        HostStorage::from_vec(data),
+    )
+}
+
+/// Return an error unless `matrix` is square
+pub(crate) const fn check_square<T>(
+    matrix: &Matrix<T>,
+) -> Result<(), LinalgError> {
+    if matrix.rows == matrix.cols {
+        Ok(())
+    } else {
+        Err(LinalgError::NotSquare { rows: matrix.rows, cols: matrix.cols })
+    }
+}
+
+/// Return an error if `matrix` contains an infinity or a NaN, on which the
+/// iterative algorithms below cannot converge
+pub(crate) fn check_finite<T: LinalgScalar>(
+    matrix: &Matrix<T>,
+) -> Result<(), LinalgError> {
+    if matrix.data.iter().all(|x| x.is_finite()) {
+        Ok(())
+    } else {
+        Err(LinalgError::NoConvergence)
+    }
+}
+
+/// Call `f` with each column of `matrix` as a contiguous slice, in parallel
+/// if there are enough elements to make it worthwhile
+pub(crate) fn for_each_column<T, F>(matrix: &mut Matrix<T>, f: F)
+where
+    T: LinalgScalar,
+    F: Fn(&mut [T]) + Sync,
+{
+    let mut columns = matrix.transpose();
+    for_each_row(&mut columns.data, columns.cols, |_, column| f(column));
+    *matrix = columns.transpose();
+}
+
+/// Solve `L x = b` in place, where `L` is the leading lower triangle of
+/// `lower` with as many rows as `x`. If `unit_diagonal` is `true`, the
+/// diagonal of `L` is taken to be one and is not read.
+pub(crate) fn forward_substitute<T: LinalgScalar>(
+    lower: &Matrix<T>,
+    unit_diagonal: bool,
+    x: &mut [T],
+) {
+    for i in 0..x.len() {
+        let row = &lower.data[i * lower.cols..i * lower.cols + i];
+        let sum =
+            row.iter().zip(&x[..i]).fold(x[i], |acc, (&l, &x)| acc - l * x);
+        x[i] = if unit_diagonal { sum } else { sum / lower[(i, i)] };
+    }
+}
+
+/// Solve `U x = b` in place, where `U` is the leading upper triangle of
+/// `upper` with as many rows as `x`
+pub(crate) fn back_substitute<T: LinalgScalar>(upper: &Matrix<T>, x: &mut [T]) {
+    let n = x.len();
+    for i in (0..n).rev() {
+        let row = &upper.data[i * upper.cols + i + 1..i * upper.cols + n];
+        let sum =
+            row.iter().zip(&x[i + 1..]).fold(x[i], |acc, (&u, &x)| acc - u * x);
+        x[i] = sum / upper[(i, i)];
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_util {
+    use super::LinalgScalar;
+    use crate::array::type_remap::Array2;
+
+    /// Assert that `a` and `b` have the same shape and that their elements
+    /// differ by at most `tolerance`
+    #[track_caller]
+    pub fn assert_close<T: LinalgScalar>(
+        a: &Array2<T>,
+        b: &Array2<T>,
+        tolerance: T,
+    ) {
+        let shape = [a.axes.shape[0], a.axes.shape[1]];
+        assert_eq!(shape, [b.axes.shape[0], b.axes.shape[1]]);
+        for i in 0..shape[0] {
+            for j in 0..shape[1] {
+                let (x, y) = (a[[i, j]], b[[i, j]]);
+                assert!(
+                    (x - y).abs() <= tolerance,
+                    "{x:?} != {y:?} at [{i}, {j}]"
+                );
+            }
+        }
+    }
+}
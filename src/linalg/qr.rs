@@ -0,0 +1,507 @@
+//! The QR decomposition and linear least squares.
+
+use crate::{
+    array::{base::ArrayBase, type_remap::Array2},
+    backend::{
+        host::{host_backend::HostBackend, host_storage::HostStorage},
+        traits::Storage,
+    },
+    dimension::dim::{Dim2, Dimension},
+    error::LinalgError,
+    linalg::{
+        back_substitute, for_each_column, for_each_row, forward_substitute,
+        LinalgScalar, Matrix,
+    },
+};
+
+/// Apply the Householder reflection `I - 2 v v^T` to the rows of `matrix`
+/// starting at `first_row`, and the columns starting at `first_col`. The
+/// reflector `v` has unit length, and one element for each row from
+/// `first_row` to the end of the matrix.
+fn apply_reflector<T: LinalgScalar>(
+    matrix: &mut Matrix<T>,
+    v: &[T],
+    first_row: usize,
+    first_col: usize,
+) {
+    let cols = matrix.cols;
+    if first_col == cols {
+        return;
+    }
+    let rows = &mut matrix.data[first_row * cols..];
+
+    // w = v^T A, accumulated a row at a time to read the matrix in order
+    let mut w = vec![T::zero(); cols - first_col];
+    for (row, &v) in rows.chunks_exact(cols).zip(v) {
+        for (w, &a) in w.iter_mut().zip(&row[first_col..]) {
+            *w = *w + v * a;
+        }
+    }
+
+    for_each_row(rows, cols, |i, row| {
+        let scale = v[i] + v[i];
+        for (a, &w) in row[first_col..].iter_mut().zip(&w) {
+            *a = *a - scale * w;
+        }
+    });
+}
+
+/// Zero the elements of column `j` of `r` below the diagonal by a Householder
+/// reflection, which is applied to the rest of the rows and columns from `j`
+/// onwards. Returns the reflector, which is zero if the column was already
+/// zero below the diagonal.
+fn householder_step<T: LinalgScalar>(r: &mut Matrix<T>, j: usize) -> Vec<T> {
+    let m = r.rows;
+    let mut v: Vec<T> = (j..m).map(|i| r[(i, j)]).collect();
+    let norm = v.iter().fold(T::zero(), |acc, &x| acc.hypot(x));
+
+    // Reflecting onto the opposite sign of the leading element avoids
+    // cancellation when computing `v`
+    let alpha = if v[0].is_sign_negative() { norm } else { -norm };
+    v[0] = v[0] - alpha;
+    let length = v.iter().fold(T::zero(), |acc, &x| acc.hypot(x));
+
+    if !length.is_zero() {
+        for x in &mut v {
+            *x = *x / length;
+        }
+        apply_reflector(r, &v, j, j);
+
+        r[(j, j)] = alpha;
+        for i in j + 1..m {
+            r[(i, j)] = T::zero();
+        }
+    }
+
+    v
+}
+
+/// Reduce `r` to upper triangular form in place by Householder reflections,
+/// returning the reflectors, such that `A = H_0 H_1 ... H_{k-1} R`.
+fn householder<T: LinalgScalar>(r: &mut Matrix<T>) -> Vec<Vec<T>> {
+    (0..r.rows.min(r.cols)).map(|j| householder_step(r, j)).collect()
+}
+
+/// Reduce `r` to upper triangular form in place by Householder reflections
+/// with column pivoting, such that `A P = H_0 H_1 ... H_{k-1} R`. Returns the
+/// reflectors, and the column of `A` moved to each column of `R`.
+///
+/// Each step moves the remaining column with the largest norm below the
+/// diagonal into place, so the diagonal of `R` does not increase in
+/// magnitude, and the rank of `A` is the number of leading diagonal elements
+/// which are not negligible.
+fn householder_pivoted<T: LinalgScalar>(
+    r: &mut Matrix<T>,
+) -> (Vec<Vec<T>>, Vec<usize>) {
+    let (m, n) = (r.rows, r.cols);
+    let mut permutation: Vec<usize> = (0..n).collect();
+    let mut reflectors = Vec::with_capacity(m.min(n));
+
+    for j in 0..m.min(n) {
+        let norm =
+            |c: usize| (j..m).fold(T::zero(), |acc, i| acc.hypot(r[(i, c)]));
+        let pivot =
+            (j + 1..n).fold(
+                j,
+                |best, c| {
+                    if norm(c) > norm(best) {
+                        c
+                    } else {
+                        best
+                    }
+                },
+            );
+        if pivot != j {
+            for i in 0..m {
+                r.data.swap(i * n + j, i * n + pivot);
+            }
+            permutation.swap(j, pivot);
+        }
+
+        reflectors.push(householder_step(r, j));
+    }
+
+    (reflectors, permutation)
+}
+
+/// Returns `true` if no diagonal element of `r` is negligible relative to the
+/// largest, meaning the matrix it was computed from has full rank
+fn has_full_rank<T: LinalgScalar>(r: &Matrix<T>) -> bool {
+    let k = r.rows.min(r.cols);
+    let largest = (0..k).fold(T::zero(), |acc, i| acc.max(r[(i, i)].abs()));
+    let size = T::from(r.rows.max(r.cols)).unwrap_or_else(T::one);
+    let tolerance = largest * T::epsilon() * size;
+
+    (0..k).all(|i| r[(i, i)].abs() > tolerance)
+}
+
+/// Solve a least squares problem whose matrix `a` does not have full rank,
+/// for the right-hand sides in the columns of `b`. Of all the solutions which
+/// minimise the residual, this gives the one with the smallest norm.
+///
+/// The solution uses a complete orthogonal decomposition. With `A P = Q R`
+/// from [`householder_pivoted`], the rows of `R` after the rank are
+/// negligible, so the problem reduces to `T y = c`, where `T` holds the
+/// leading rows of `R`, `c` the leading rows of `Q^T b`, and `y = P^T x`.
+/// Then with `T^T = Z S`, the smallest solution is `y = Z w`, where
+/// `S^T w = c`.
+#[allow(clippy::many_single_char_names)]
+fn lstsq_rank_deficient<T: LinalgScalar>(
+    a: Matrix<T>,
+    mut b: Matrix<T>,
+) -> Matrix<T> {
+    let (m, n) = (a.rows, a.cols);
+    let cols = b.cols;
+
+    let mut r = a;
+    let (reflectors, permutation) = householder_pivoted(&mut r);
+    let largest = if m.min(n) == 0 { T::zero() } else { r[(0, 0)].abs() };
+    let size = T::from(m.max(n)).unwrap_or_else(T::one);
+    let tolerance = largest * T::epsilon() * size;
+    let rank =
+        (0..m.min(n)).take_while(|&i| r[(i, i)].abs() > tolerance).count();
+
+    for (j, v) in reflectors.iter().enumerate() {
+        apply_reflector(&mut b, v, j, 0);
+    }
+    b.data.truncate(rank * cols);
+    b.rows = rank;
+
+    r.data.truncate(rank * n);
+    r.rows = rank;
+    let mut s = r.transpose();
+    let z = householder(&mut s);
+    let lower = s.transpose();
+    for_each_column(&mut b, |column| {
+        forward_substitute(&lower, false, column);
+    });
+
+    let mut y = Matrix::zeros(n, cols);
+    y.data[..rank * cols].copy_from_slice(&b.data);
+    for (j, v) in z.iter().enumerate().rev() {
+        apply_reflector(&mut y, v, j, 0);
+    }
+
+    // Undo the column pivoting, which permuted the rows of the solution
+    let mut x = Matrix::zeros(n, cols);
+    for (j, &column) in permutation.iter().enumerate() {
+        x.data[column * cols..(column + 1) * cols]
+            .copy_from_slice(&y.data[j * cols..(j + 1) * cols]);
+    }
+    x
+}
+
+/// Compute the reduced QR decomposition of the `m` by `n` matrix `a`.
+///
+/// The decomposition is `A = Q R`, where `Q` is an `m` by `k` matrix with
+/// orthonormal columns, `R` is a `k` by `n` upper triangular matrix, and `k` is
+/// the smaller of `m` and `n`.
+///
+/// The decomposition is computed with Householder reflections, which are
+/// applied to the rows of the matrix in parallel. The diagonal of `R` may
+/// have either sign.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::{Dim2, Dimension};
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([3, 2]),
+///     vec![3.0_f64, 1.0, 4.0, 2.0, 0.0, 5.0],
+/// )
+/// .unwrap();
+/// let (q, r) = linalg::qr(&a);
+///
+/// assert_eq!(q.shape().as_slice(), &[3, 2]);
+/// assert_eq!(r[[1, 0]], 0.0);
+/// assert!((r[[0, 0]].abs() - 5.0).abs() < 1e-12);
+///
+/// let product = q.matmul(&r);
+/// assert!((product[[2, 1]] - 5.0).abs() < 1e-12);
+/// ```
+#[allow(clippy::many_single_char_names)]
+pub fn qr<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> (Array2<T>, Array2<T>)
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    let mut r = Matrix::from_array(a);
+    let reflectors = householder(&mut r);
+    let (m, n) = (r.rows, r.cols);
+    let k = m.min(n);
+
+    // Accumulating the reflectors from the last to the first means each only
+    // touches the rows and columns which are not yet part of the identity
+    let mut q = Matrix::eye(m, k);
+    for (j, v) in reflectors.iter().enumerate().rev() {
+        apply_reflector(&mut q, v, j, j);
+    }
+
+    r.data.truncate(k * n);
+    r.rows = k;
+    (q.into_array(), r.into_array())
+}
+
+/// Solve the linear least squares problem `A x = b` for the `m` by `n` matrix
+/// `a`, where `b` is a vector or a matrix with a right-hand side in each
+/// column.
+///
+/// If `a` has at least as many rows as columns, the solution minimises the
+/// Euclidean norm of `b - A x`. Otherwise the system has many exact
+/// solutions, and the one with the smallest norm is returned. In either case
+/// the solution has `n` rows, and has the same number of axes as `b`.
+///
+/// The problem is solved using the QR decomposition of `a` (or of its
+/// transpose, if it has more columns than rows), which is more accurate than
+/// solving the normal equations. If `a` does not have full rank, meaning a
+/// diagonal element of `R` is negligible relative to the largest, the
+/// residual has many minimisers, and as in `NumPy` the one with the smallest
+/// norm is found using a complete orthogonal decomposition of `a` instead,
+/// which is computed from its QR decomposition with column pivoting.
+///
+/// # Errors
+/// Returns [`LinalgError::RowMismatch`] if `b` does not have a row for each
+/// row of `a`, or [`LinalgError::Shape`] if `b` is not a vector or a matrix.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::{Array1, Array2};
+/// use tensr::dimension::dim::{Dim1, Dim2};
+/// use tensr::linalg;
+///
+/// // Fit a line `c + m t` through the points (0, 1), (1, 3) and (2, 4)
+/// let a = Array2::from_vec(
+///     Dim2::new([3, 2]),
+///     vec![1.0_f64, 0.0, 1.0, 1.0, 1.0, 2.0],
+/// )
+/// .unwrap();
+/// let b = Array1::from_vec(Dim1::new([3]), vec![1.0_f64, 3.0, 4.0]).unwrap();
+///
+/// let x = linalg::lstsq(&a, &b).unwrap();
+/// assert!((x[0] - 7.0 / 6.0).abs() < 1e-12);
+/// assert!((x[1] - 1.5).abs() < 1e-12);
+/// ```
+#[allow(clippy::many_single_char_names)]
+pub fn lstsq<T, StorageType, RhsStorage, RhsDims>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+    b: &ArrayBase<HostBackend, RhsStorage, RhsDims>,
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, RhsDims>, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    RhsStorage: Storage<Scalar = T>,
+    RhsDims: Dimension,
+{
+    let matrix = Matrix::from_array(a);
+    let (m, n) = (matrix.rows, matrix.cols);
+
+    let vector = b.axes.shape.as_slice().len() == 1;
+    let mut b = Matrix::from_rhs(b)?;
+    if b.rows != m {
+        return Err(LinalgError::RowMismatch { expected: m, found: b.rows });
+    }
+    let cols = b.cols;
+
+    let x = if m >= n {
+        // Minimise |Q R x - b|, which is |R x - Q^T b|
+        let mut r = matrix;
+        let reflectors = householder(&mut r);
+        if !has_full_rank(&r) {
+            let x = lstsq_rank_deficient(Matrix::from_array(a), b);
+            return Ok(x.into_rhs_array(vector));
+        }
+
+        for (j, v) in reflectors.iter().enumerate() {
+            apply_reflector(&mut b, v, j, 0);
+        }
+        b.data.truncate(n * cols);
+        b.rows = n;
+
+        for_each_column(&mut b, |column| back_substitute(&r, column));
+        b
+    } else {
+        // With A^T = Q R, the solutions of R^T Q^T x = b with the smallest
+        // norm are x = Q z, where R^T z = b
+        let mut r = matrix.transpose();
+        let reflectors = householder(&mut r);
+        if !has_full_rank(&r) {
+            let x = lstsq_rank_deficient(matrix, b);
+            return Ok(x.into_rhs_array(vector));
+        }
+
+        let lower = r.transpose();
+        for_each_column(&mut b, |column| {
+            forward_substitute(&lower, false, column);
+        });
+
+        let mut x = Matrix::zeros(n, cols);
+        x.data[..m * cols].copy_from_slice(&b.data);
+        for (j, v) in reflectors.iter().enumerate().rev() {
+            apply_reflector(&mut x, v, j, 0);
+        }
+        x
+    };
+
+    Ok(x.into_rhs_array(vector))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use super::*;
+    use crate::{
+        array::type_remap::Array1,
+        dimension::dim::Dim1,
+        linalg::{solve, test_util::assert_close},
+    };
+
+    fn matrix(m: usize, n: usize) -> Array2<f64> {
+        Array2::from_shape_fn(Dim2::new([m, n]), |i| {
+            f64::from(
+                u32::try_from((i[0] * 5 + i[1] * 11 + i[0] * i[1]) % 13)
+                    .unwrap(),
+            ) - 6.0
+        })
+    }
+
+    #[test]
+    fn test_qr() {
+        for (m, n) in [(1, 1), (4, 4), (9, 5), (5, 9), (120, 80)] {
+            let a = matrix(m, n);
+            let (q, r) = qr(&a);
+            let k = m.min(n);
+            assert_eq!(q.shape().as_slice(), &[m, k]);
+            assert_eq!(r.shape().as_slice(), &[k, n]);
+
+            assert_close(&q.matmul(&r), &a, 1e-9);
+            let eye = Matrix::eye(k, k).into_array();
+            let qt: Array2<f64> =
+                Array2::from_shape_fn(Dim2::new([k, m]), |i| q[[i[1], i[0]]]);
+            assert_close(&qt.matmul(&q), &eye, 1e-9);
+            for i in 0..k {
+                for j in 0..i.min(n) {
+                    assert_eq!(r[[i, j]], 0.0);
+                }
+            }
+        }
+
+        // A matrix with a zero column
+        let mut a = matrix(4, 3);
+        for i in 0..4 {
+            a[[i, 0]] = 0.0;
+        }
+        let (q, r) = qr(&a);
+        assert_close(&q.matmul(&r), &a, 1e-9);
+    }
+
+    #[test]
+    fn test_lstsq() {
+        // Overdetermined: the residual is orthogonal to the columns of `a`
+        let a = matrix(30, 6);
+        let b = Array2::from_shape_fn(Dim2::new([30, 2]), |i| {
+            f64::from(u32::try_from(i[0] * i[0] % 7 + i[1]).unwrap())
+        });
+        let x = lstsq(&a, &b).unwrap();
+        assert_eq!(x.shape().as_slice(), &[6, 2]);
+        let ax = a.matmul(&x);
+        let residual = Array2::from_shape_fn(Dim2::new([30, 2]), |i| {
+            b[[i[0], i[1]]] - ax[[i[0], i[1]]]
+        });
+        let at = Array2::from_shape_fn(Dim2::new([6, 30]), |i| a[[i[1], i[0]]]);
+        assert_close(
+            &at.matmul(&residual),
+            &Matrix::zeros(6, 2).into_array(),
+            1e-9,
+        );
+
+        // Underdetermined: the solution is exact, and lies in the row space
+        // of `a`, so x = A^T y with A A^T y = b
+        let a = matrix(4, 10);
+        let b = Array1::from_vec(Dim1::new([4]), vec![1.0, -2.0, 0.5, 3.0])
+            .unwrap();
+        let x = lstsq(&a, &b).unwrap();
+        assert_eq!(x.shape().as_slice(), &[10]);
+        let at = Array2::from_shape_fn(Dim2::new([10, 4]), |i| a[[i[1], i[0]]]);
+        let y = solve(&a.matmul(&at), &b).unwrap();
+        let expected = at.matmul(&y);
+        assert!((0..10).all(|i| (x[i] - expected[i]).abs() < 1e-9));
+
+        assert_eq!(
+            lstsq(&a, &Array1::<f64>::ones(Dim1::new([5]))).err(),
+            Some(LinalgError::RowMismatch { expected: 4, found: 5 })
+        );
+    }
+
+    #[test]
+    fn test_lstsq_rank_deficient() {
+        // Every column is the same, so `a x` only depends on the sum of `x`,
+        // and the smallest solution shares that sum equally
+        let a = Array2::from_shape_fn(Dim2::new([5, 3]), |i| {
+            f64::from(u32::try_from(i[0] + 1).unwrap())
+        });
+        let b = Array1::from_shape_fn(Dim1::new([5]), |i| {
+            f64::from(u32::try_from(3 * (i[0] + 1)).unwrap())
+        });
+        let x = lstsq(&a, &b).unwrap();
+        assert!((0..3).all(|i| (x[i] - 1.0).abs() < 1e-9));
+
+        // The same is true with more columns than rows
+        let wide = Array2::from_shape_fn(Dim2::new([2, 4]), |i| {
+            f64::from(u32::try_from(i[0] + 1).unwrap())
+        });
+        let b = Array2::from_vec(Dim2::new([2, 2]), vec![4.0, 8.0, 8.0, 16.0])
+            .unwrap();
+        let x = lstsq(&wide, &b).unwrap();
+        assert_eq!(x.shape().as_slice(), &[4, 2]);
+        assert!((0..4).all(|i| (x[[i, 0]] - 1.0).abs() < 1e-9));
+        assert!((0..4).all(|i| (x[[i, 1]] - 2.0).abs() < 1e-9));
+
+        // A product of rank three, whose null space is spanned by the
+        // columns of `null`. The residual is orthogonal to the columns of `a`,
+        // and the smallest solution is orthogonal to its null space.
+        let c = Array2::from_vec(
+            Dim2::new([3, 5]),
+            vec![
+                1.0, 0.0, 1.0, 0.0, 2.0, 0.0, 1.0, 1.0, 0.0, -1.0, 0.0, 0.0,
+                0.0, 1.0, 1.0,
+            ],
+        )
+        .unwrap();
+        let null = Array2::from_vec(
+            Dim2::new([5, 2]),
+            vec![-1.0, -2.0, -1.0, 1.0, 1.0, 0.0, 0.0, -1.0, 0.0, 1.0],
+        )
+        .unwrap();
+        let a = matrix(7, 3).matmul(&c);
+        let b = Array2::from_shape_fn(Dim2::new([7, 2]), |i| {
+            f64::from(u32::try_from(i[0] * i[0] % 5 + i[1]).unwrap())
+        });
+        let x = lstsq(&a, &b).unwrap();
+        let ax = a.matmul(&x);
+        let residual = Array2::from_shape_fn(Dim2::new([7, 2]), |i| {
+            b[[i[0], i[1]]] - ax[[i[0], i[1]]]
+        });
+        let at = Array2::from_shape_fn(Dim2::new([5, 7]), |i| a[[i[1], i[0]]]);
+        assert_close(
+            &at.matmul(&residual),
+            &Matrix::zeros(5, 2).into_array(),
+            1e-9,
+        );
+        let xt = Array2::from_shape_fn(Dim2::new([2, 5]), |i| x[[i[1], i[0]]]);
+        assert_close(
+            &xt.matmul(&null),
+            &Matrix::zeros(2, 2).into_array(),
+            1e-9,
+        );
+
+        // A zero matrix has the zero vector as its smallest solution
+        let zero = Array2::<f64>::zeros(Dim2::new([3, 2]));
+        let x = lstsq(&zero, &Array1::<f64>::ones(Dim1::new([3]))).unwrap();
+        assert_eq!(x[0], 0.0);
+        assert_eq!(x[1], 0.0);
+    }
+}
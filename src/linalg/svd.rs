@@ -0,0 +1,775 @@
+//! The singular value decomposition, and the pseudo-inverse, rank and norms
+//! computed from it.
+
+use crate::{
+    array::{
+        base::ArrayBase,
+        type_remap::{Array0, Array1, Array2},
+    },
+    backend::{
+        host::{host_backend::HostBackend, host_storage::HostStorage},
+        traits::Storage,
+    },
+    dimension::dim::{Dim2, Dimension},
+    error::LinalgError,
+    linalg::{
+        check_finite, for_each_row, stack_results, Batch, LinalgScalar, Matrix,
+    },
+};
+
+/// The maximum number of sweeps over every pair of columns before the Jacobi
+/// iteration gives up
+const MAX_SWEEPS: usize = 60;
+
+/// The singular value decomposition of a working matrix
+struct Decomposition<T> {
+    /// The left singular vectors, as columns
+    u: Matrix<T>,
+
+    /// The singular values, in descending order
+    s: Vec<T>,
+
+    /// The right singular vectors, as rows
+    vt: Matrix<T>,
+}
+
+/// Borrow rows `p` and `q` of the row-major matrix `data` with `cols`
+/// columns, where `p < q`
+fn row_pair<T>(
+    data: &mut [T],
+    cols: usize,
+    p: usize,
+    q: usize,
+) -> (&mut [T], &mut [T]) {
+    let (head, tail) = data.split_at_mut(q * cols);
+    (&mut head[p * cols..(p + 1) * cols], &mut tail[..cols])
+}
+
+/// The dot product of two vectors
+fn dot<T: LinalgScalar>(a: &[T], b: &[T]) -> T {
+    a.iter().zip(b).fold(T::zero(), |acc, (&x, &y)| acc + x * y)
+}
+
+/// Replace the rows of `rows` from `start` onwards with unit vectors
+/// orthogonal to every earlier row, where the rows before `start` are
+/// orthonormal
+fn complete_basis<T: LinalgScalar>(rows: &mut Matrix<T>, start: usize) {
+    let len = rows.cols;
+    for j in start..rows.rows {
+        // The standard basis vector with the largest component outside the
+        // span of the earlier rows is the best conditioned to start from
+        let best = (0..len)
+            .map(|i| {
+                let inside = (0..j).fold(T::zero(), |acc, k| {
+                    acc + rows[(k, i)] * rows[(k, i)]
+                });
+                (i, T::one() - inside)
+            })
+            .fold((0, T::neg_infinity()), |best, next| {
+                if next.1 > best.1 {
+                    next
+                } else {
+                    best
+                }
+            })
+            .0;
+
+        let (done, rest) = rows.data.split_at_mut(j * len);
+        let row = &mut rest[..len];
+        row.fill(T::zero());
+        row[best] = T::one();
+
+        // Orthogonalise twice, for numerical stability
+        for _ in 0..2 {
+            for earlier in done.chunks(len) {
+                let projection = dot(earlier, row);
+                for (x, &e) in row.iter_mut().zip(earlier) {
+                    *x = *x - projection * e;
+                }
+            }
+        }
+        let norm = dot(row, row).sqrt();
+        for x in row.iter_mut() {
+            *x = *x / norm;
+        }
+    }
+}
+
+/// Compute the reduced singular value decomposition of `matrix` by one-sided
+/// Jacobi rotations.
+///
+/// Pairs of rows of the matrix, or of its transpose if it is tall, are
+/// rotated until they are orthogonal, and the rotations are accumulated to
+/// give the singular vectors on the other side.
+#[allow(clippy::many_single_char_names)]
+fn jacobi_svd<T: LinalgScalar>(
+    matrix: Matrix<T>,
+) -> Result<Decomposition<T>, LinalgError> {
+    check_finite(&matrix)?;
+    let (m, n) = (matrix.rows, matrix.cols);
+    let tall = m >= n;
+    let mut w = if tall { matrix.transpose() } else { matrix };
+    let (k, len) = (w.rows, w.cols);
+    let mut v = Matrix::eye(k, k);
+
+    let eps = T::epsilon();
+    let one = T::one();
+    let two = one + one;
+    // An inner product at the level of the rounding errors in the matrix is
+    // treated as zero, so that rows which are numerically zero cannot stall
+    // the iteration
+    let negligible = eps * dot(&w.data, &w.data).sqrt();
+    let negligible = negligible * negligible;
+
+    let mut converged = false;
+    for _ in 0..MAX_SWEEPS {
+        let mut rotated = false;
+        for p in 0..k {
+            for q in p + 1..k {
+                let (wp, wq) = row_pair(&mut w.data, len, p, q);
+                let alpha = dot(wp, wp);
+                let beta = dot(wq, wq);
+                let gamma = dot(wp, wq);
+                if gamma.abs()
+                    <= (eps * alpha.sqrt() * beta.sqrt()).max(negligible)
+                {
+                    continue;
+                }
+                rotated = true;
+
+                // The rotation which makes the two rows orthogonal
+                let zeta = (beta - alpha) / (two * gamma);
+                let t = zeta.signum() / (zeta.abs() + zeta.hypot(one));
+                let c = one / t.hypot(one);
+                let s = c * t;
+
+                for (x, y) in wp.iter_mut().zip(wq.iter_mut()) {
+                    (*x, *y) = (c * *x - s * *y, s * *x + c * *y);
+                }
+                let (vp, vq) = row_pair(&mut v.data, k, p, q);
+                for (x, y) in vp.iter_mut().zip(vq.iter_mut()) {
+                    (*x, *y) = (c * *x - s * *y, s * *x + c * *y);
+                }
+            }
+        }
+        if !rotated {
+            converged = true;
+            break;
+        }
+    }
+    if !converged {
+        return Err(LinalgError::NoConvergence);
+    }
+
+    // The norms of the rows are the singular values
+    let norms: Vec<T> =
+        w.data.chunks(len.max(1)).map(|row| dot(row, row).sqrt()).collect();
+    let mut order: Vec<usize> = (0..k).collect();
+    order.sort_by(|&i, &j| {
+        norms[j].partial_cmp(&norms[i]).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    let s: Vec<T> = order.iter().map(|&i| norms[i]).collect();
+
+    let tolerance = tolerance(&s, m.max(n));
+    let mut left = Matrix::zeros(k, len);
+    let mut right = Matrix::zeros(k, k);
+    let mut rank = 0;
+    for (j, &i) in order.iter().enumerate() {
+        if s[j] > tolerance {
+            rank += 1;
+            for (x, &y) in left.data[j * len..(j + 1) * len]
+                .iter_mut()
+                .zip(&w.data[i * len..(i + 1) * len])
+            {
+                *x = y / s[j];
+            }
+        }
+        right.data[j * k..(j + 1) * k]
+            .copy_from_slice(&v.data[i * k..(i + 1) * k]);
+    }
+    complete_basis(&mut left, rank);
+
+    let (u, vt) = if tall {
+        (left.transpose(), right)
+    } else {
+        (right.transpose(), left)
+    };
+    Ok(Decomposition { u, s, vt })
+}
+
+/// The tolerance below which singular values, in descending order, of a
+/// matrix whose longer side is `len` are treated as zero
+fn tolerance<T: LinalgScalar>(s: &[T], len: usize) -> T {
+    let len = T::from(len).unwrap_or_else(T::max_value);
+    s.first().map_or_else(T::zero, |&s_max| s_max * len * T::epsilon())
+}
+
+/// Compute the pseudo-inverse of a working matrix
+fn matrix_pinv<T: LinalgScalar>(
+    matrix: Matrix<T>,
+) -> Result<Matrix<T>, LinalgError> {
+    let (m, n) = (matrix.rows, matrix.cols);
+    let Decomposition { u, s, vt } = jacobi_svd(matrix)?;
+    let tolerance = tolerance(&s, m.max(n));
+    let rank = s.iter().take_while(|&&s| s > tolerance).count();
+
+    // A+ = V S+ U^T, using only the nonzero singular values
+    let mut pinv = Matrix::zeros(n, m);
+    for_each_row(&mut pinv.data, m, |i, row| {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..rank)
+                .fold(T::zero(), |acc, r| acc + vt[(r, i)] / s[r] * u[(j, r)]);
+        }
+    });
+
+    Ok(pinv)
+}
+
+/// Compute the rank of a working matrix
+fn rank<T: LinalgScalar>(matrix: Matrix<T>) -> Result<usize, LinalgError> {
+    let len = matrix.rows.max(matrix.cols);
+    let s = jacobi_svd(matrix)?.s;
+    let tolerance = tolerance(&s, len);
+    Ok(s.iter().filter(|&&s| s > tolerance).count())
+}
+
+/// The order of a matrix norm computed by [`norm`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormOrd {
+    /// The Frobenius norm, the square root of the sum of the squares of the
+    /// elements
+    Fro,
+
+    /// The nuclear norm, the sum of the singular values
+    Nuc,
+
+    /// The largest sum of the absolute values of a column
+    One,
+
+    /// The smallest sum of the absolute values of a column
+    NegOne,
+
+    /// The spectral norm, the largest singular value
+    Two,
+
+    /// The smallest singular value
+    NegTwo,
+
+    /// The largest sum of the absolute values of a row
+    Inf,
+
+    /// The smallest sum of the absolute values of a row
+    NegInf,
+}
+
+/// Compute a norm of a working matrix
+fn matrix_norm<T: LinalgScalar>(
+    matrix: Matrix<T>,
+    ord: NormOrd,
+) -> Result<T, LinalgError> {
+    let zero = T::zero();
+    let sums = |lines: &Matrix<T>| -> Vec<T> {
+        lines
+            .data
+            .chunks(lines.cols.max(1))
+            .take(lines.rows)
+            .map(|line| line.iter().fold(zero, |acc, x| acc + x.abs()))
+            .collect()
+    };
+    // Unlike `T::max` and `T::min`, these propagate NaNs
+    let max = |values: Vec<T>| {
+        if values.iter().any(|x| x.is_nan()) {
+            T::nan()
+        } else {
+            values.into_iter().fold(zero, T::max)
+        }
+    };
+    let min = |values: Vec<T>| {
+        if values.iter().any(|x| x.is_nan()) {
+            T::nan()
+        } else {
+            values.into_iter().reduce(T::min).unwrap_or(zero)
+        }
+    };
+
+    Ok(match ord {
+        NormOrd::Fro => dot(&matrix.data, &matrix.data).sqrt(),
+        NormOrd::Nuc => {
+            jacobi_svd(matrix)?.s.into_iter().fold(zero, |acc, s| acc + s)
+        }
+        NormOrd::One => max(sums(&matrix.transpose())),
+        NormOrd::NegOne => min(sums(&matrix.transpose())),
+        NormOrd::Two => max(jacobi_svd(matrix)?.s),
+        NormOrd::NegTwo => min(jacobi_svd(matrix)?.s),
+        NormOrd::Inf => max(sums(&matrix)),
+        NormOrd::NegInf => min(sums(&matrix)),
+    })
+}
+
+/// Compute the reduced singular value decomposition of the matrix `a`.
+///
+/// Returns `(U, s, Vt)`, where `s` holds the `k = min(m, n)` singular values
+/// in descending order, and the columns of the `m` by `k` matrix `U` and the
+/// rows of the `k` by `n` matrix `Vt` are the corresponding orthonormal
+/// singular vectors, so that `A = U diag(s) Vt`. The decomposition is
+/// computed by one-sided Jacobi rotations, which find even small singular
+/// values to high relative accuracy.
+///
+/// # Errors
+/// Returns [`LinalgError::NoConvergence`] if `a` contains a NaN or an
+/// infinity, or the algorithm fails to converge.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([3, 2]),
+///     vec![3.0_f64, 0.0, 0.0, -4.0, 0.0, 0.0],
+/// )
+/// .unwrap();
+/// let (u, s, vt) = linalg::svd(&a).unwrap();
+/// assert!((s[[0]] - 4.0).abs() < 1e-12);
+/// assert!((s[[1]] - 3.0).abs() < 1e-12);
+///
+/// let usvt = Array2::from_shape_fn(Dim2::new([3, 2]), |i| {
+///     (0..2).map(|r| u[[i[0], r]] * s[[r]] * vt[[r, i[1]]]).sum::<f64>()
+/// });
+/// assert!((usvt[[1, 1]] + 4.0).abs() < 1e-12);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn svd<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<(Array2<T>, Array1<T>, Array2<T>), LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    svd_batched(a)
+}
+
+/// Compute the reduced singular value decomposition of each matrix of the
+/// stack `a`, whose matrices are indexed by all but its last two axes. See
+/// [`svd`].
+///
+/// # Errors
+/// Returns [`LinalgError::Shape`] if `a` has fewer than two axes, or any
+/// error of [`svd`] for one of its matrices.
+#[allow(clippy::type_complexity, clippy::many_single_char_names)]
+pub fn svd_batched<T, StorageType, NDims>(
+    a: &ArrayBase<HostBackend, StorageType, NDims>,
+) -> Result<
+    (
+        ArrayBase<HostBackend, HostStorage<T>, NDims>,
+        ArrayBase<HostBackend, HostStorage<T>, NDims::Smaller>,
+        ArrayBase<HostBackend, HostStorage<T>, NDims>,
+    ),
+    LinalgError,
+>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    NDims: Dimension,
+{
+    let batch = Batch::new(a)?;
+    let (shape, m, n) = (batch.shape.clone(), batch.rows, batch.cols);
+    let k = m.min(n);
+
+    let results = batch.map(jacobi_svd)?;
+    let mut u = Vec::with_capacity(results.len());
+    let mut s = Vec::with_capacity(results.len());
+    let mut vt = Vec::with_capacity(results.len());
+    for decomposition in results {
+        u.push(decomposition.u.data);
+        s.push(decomposition.s);
+        vt.push(decomposition.vt.data);
+    }
+
+    Ok((
+        stack_results(&shape, &[m, k], u),
+        stack_results(&shape, &[k], s),
+        stack_results(&shape, &[k, n], vt),
+    ))
+}
+
+/// Compute the Moore-Penrose pseudo-inverse of the matrix `a`.
+///
+/// The pseudo-inverse is computed from the singular value decomposition of
+/// `a` (see [`svd`]), treating singular values at most
+/// `max(m, n) * eps * s_max` as zero. If `a` has full column rank, it is the
+/// left inverse of `a`, and gives the least squares solution of `A x = b` as
+/// `pinv(A) b`.
+///
+/// # Errors
+/// Returns [`LinalgError::NoConvergence`] if `a` contains a NaN or an
+/// infinity, or the singular value decomposition fails to converge.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![1.0_f64, 2.0, 2.0, 4.0],
+/// )
+/// .unwrap();
+/// let pinv = linalg::pinv(&a).unwrap();
+/// assert!((pinv[[0, 0]] - 0.04).abs() < 1e-12);
+/// assert!((pinv[[1, 0]] - 0.08).abs() < 1e-12);
+/// ```
+pub fn pinv<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<Array2<T>, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    pinv_batched(a)
+}
+
+/// Compute the pseudo-inverse of each matrix of the stack `a`, whose
+/// matrices are indexed by all but its last two axes. See [`pinv`].
+///
+/// # Errors
+/// Returns [`LinalgError::Shape`] if `a` has fewer than two axes, or any
+/// error of [`pinv`] for one of its matrices.
+pub fn pinv_batched<T, StorageType, NDims>(
+    a: &ArrayBase<HostBackend, StorageType, NDims>,
+) -> Result<ArrayBase<HostBackend, HostStorage<T>, NDims>, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    NDims: Dimension,
+{
+    let batch = Batch::new(a)?;
+    let (shape, m, n) = (batch.shape.clone(), batch.rows, batch.cols);
+
+    let results = batch.map(matrix_pinv)?;
+    Ok(stack_results(
+        &shape,
+        &[n, m],
+        results.into_iter().map(|pinv| pinv.data),
+    ))
+}
+
+/// Compute the rank of the matrix `a`, the number of its singular values
+/// greater than `max(m, n) * eps * s_max`.
+///
+/// # Errors
+/// Returns [`LinalgError::NoConvergence`] if `a` contains a NaN or an
+/// infinity, or the singular value decomposition fails to converge.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg;
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([3, 3]),
+///     vec![1.0_f64, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0],
+/// )
+/// .unwrap();
+/// assert_eq!(linalg::matrix_rank(&a).unwrap(), 2);
+/// ```
+pub fn matrix_rank<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+) -> Result<usize, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    let rank: Array0<usize> = matrix_rank_batched(a)?;
+    Ok(rank[[]])
+}
+
+/// Compute the rank of each matrix of the stack `a`, whose matrices are
+/// indexed by all but its last two axes. See [`matrix_rank`].
+///
+/// # Errors
+/// Returns [`LinalgError::Shape`] if `a` has fewer than two axes, or any
+/// error of [`matrix_rank`] for one of its matrices.
+#[allow(clippy::type_complexity)]
+pub fn matrix_rank_batched<T, StorageType, NDims>(
+    a: &ArrayBase<HostBackend, StorageType, NDims>,
+) -> Result<
+    ArrayBase<
+        HostBackend,
+        HostStorage<usize>,
+        <NDims::Smaller as Dimension>::Smaller,
+    >,
+    LinalgError,
+>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    NDims: Dimension,
+{
+    let batch = Batch::new(a)?;
+    let shape = batch.shape.clone();
+
+    let results = batch.map(rank)?;
+    Ok(stack_results(&shape, &[], results.into_iter().map(|rank| vec![rank])))
+}
+
+/// Compute a norm of the matrix `a`. See [`NormOrd`] for the norms
+/// available.
+///
+/// The norms of orders [`NormOrd::Nuc`], [`NormOrd::Two`] and
+/// [`NormOrd::NegTwo`] are computed from the singular values of `a` (see
+/// [`svd`]), and the others directly from its elements. The norm of a matrix
+/// with no elements is zero.
+///
+/// # Errors
+/// Returns [`LinalgError::NoConvergence`] if the norm is computed from the
+/// singular values and `a` contains a NaN or an infinity, or the singular
+/// value decomposition fails to converge.
+///
+/// # Example
+/// ```rust
+/// use tensr::array::type_remap::Array2;
+/// use tensr::dimension::dim::Dim2;
+/// use tensr::linalg::{self, NormOrd};
+///
+/// let a = Array2::from_vec(
+///     Dim2::new([2, 2]),
+///     vec![1.0_f64, -2.0, 3.0, 4.0],
+/// )
+/// .unwrap();
+/// assert_eq!(linalg::norm(&a, NormOrd::One).unwrap(), 6.0);
+/// assert_eq!(linalg::norm(&a, NormOrd::Inf).unwrap(), 7.0);
+/// let fro = linalg::norm(&a, NormOrd::Fro).unwrap();
+/// assert!((fro - 30.0_f64.sqrt()).abs() < 1e-12);
+/// ```
+pub fn norm<T, StorageType>(
+    a: &ArrayBase<HostBackend, StorageType, Dim2>,
+    ord: NormOrd,
+) -> Result<T, LinalgError>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+{
+    let norm: Array0<T> = norm_batched(a, ord)?;
+    Ok(norm[[]])
+}
+
+/// Compute a norm of each matrix of the stack `a`, whose matrices are
+/// indexed by all but its last two axes. See [`norm`].
+///
+/// # Errors
+/// Returns [`LinalgError::Shape`] if `a` has fewer than two axes, or any
+/// error of [`norm`] for one of its matrices.
+#[allow(clippy::type_complexity)]
+pub fn norm_batched<T, StorageType, NDims>(
+    a: &ArrayBase<HostBackend, StorageType, NDims>,
+    ord: NormOrd,
+) -> Result<
+    ArrayBase<
+        HostBackend,
+        HostStorage<T>,
+        <NDims::Smaller as Dimension>::Smaller,
+    >,
+    LinalgError,
+>
+where
+    T: LinalgScalar,
+    StorageType: Storage<Scalar = T>,
+    NDims: Dimension,
+{
+    let batch = Batch::new(a)?;
+    let shape = batch.shape.clone();
+
+    let results = batch.map(|matrix| matrix_norm(matrix, ord))?;
+    Ok(stack_results(&shape, &[], results.into_iter().map(|norm| vec![norm])))
+}
+
+#[cfg(test)]
+#[allow(clippy::float_cmp)]
+mod test {
+    use super::*;
+    use crate::{
+        array::type_remap::{Array3, ArrayDyn},
+        dimension::{dim::Dim3, dyn_dim::DimDyn},
+        linalg::test_util::assert_close,
+    };
+
+    /// A matrix with pseudo-random elements between -5 and 5
+    fn random(shape: [usize; 2], seed: usize) -> Array2<f64> {
+        Array2::from_shape_fn(Dim2::new(shape), |i| {
+            let index = i[0] * shape[1] + i[1] + seed * 7919;
+            let mut hash = u64::try_from(index).unwrap();
+            hash = hash.wrapping_mul(0x9e37_79b9_7f4a_7c15);
+            hash ^= hash >> 29;
+            f64::from(u32::try_from(hash % 1000).unwrap()) / 100.0 - 5.0
+        })
+    }
+
+    /// The transpose of `a`
+    fn transpose(a: &Array2<f64>) -> Array2<f64> {
+        let shape = [a.axes.shape[1], a.axes.shape[0]];
+        Array2::from_shape_fn(Dim2::new(shape), |i| a[[i[1], i[0]]])
+    }
+
+    /// Assert that `(u, s, vt)` is a reduced singular value decomposition of
+    /// `a`
+    #[track_caller]
+    #[allow(clippy::many_single_char_names)]
+    fn assert_svd(a: &Array2<f64>) {
+        let (m, n) = (a.axes.shape[0], a.axes.shape[1]);
+        let k = m.min(n);
+        let (u, s, vt) = svd(a).unwrap();
+        assert_eq!(u.axes.shape.as_slice(), &[m, k]);
+        assert_eq!(s.axes.shape.as_slice(), &[k]);
+        assert_eq!(vt.axes.shape.as_slice(), &[k, n]);
+        assert!((1..k).all(|i| s[[i - 1]] >= s[[i]]));
+
+        let eye = Array2::from_shape_fn(Dim2::new([k, k]), |i| {
+            if i[0] == i[1] {
+                1.0
+            } else {
+                0.0
+            }
+        });
+        assert_close(&transpose(&u).matmul(&u), &eye, 1e-10);
+        assert_close(&vt.matmul(&transpose(&vt)), &eye, 1e-10);
+
+        let us = Array2::from_shape_fn(Dim2::new([m, k]), |i| {
+            u[[i[0], i[1]]] * s[[i[1]]]
+        });
+        if k > 0 {
+            assert_close(&us.matmul(&vt), a, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_svd() {
+        for shape in [[0, 0], [0, 3], [1, 1], [4, 4], [9, 5], [5, 9], [50, 30]]
+        {
+            assert_svd(&random(shape, 1));
+        }
+
+        // Rank deficient, with repeated rows
+        let a = Array2::from_shape_fn(Dim2::new([6, 4]), |i| {
+            f64::from(u32::try_from((i[0] % 2) * 3 + i[1]).unwrap())
+        });
+        assert_svd(&a);
+        assert_svd(&transpose(&a));
+        assert_svd(&Array2::zeros(Dim2::new([3, 2])));
+    }
+
+    #[test]
+    fn test_pinv() {
+        for shape in [[5, 5], [7, 3], [3, 7]] {
+            let a = random(shape, 5);
+            let pinv = pinv(&a).unwrap();
+            assert_eq!(pinv.axes.shape.as_slice(), &[shape[1], shape[0]]);
+            assert_close(&a.matmul(&pinv).matmul(&a), &a, 1e-10);
+            assert_close(&pinv.matmul(&a).matmul(&pinv), &pinv, 1e-10);
+        }
+
+        let a = random([4, 4], 2);
+        let inverse = crate::linalg::inv(&a).unwrap();
+        assert_close(&pinv(&a).unwrap(), &inverse, 1e-10);
+
+        let zeros = Array2::<f64>::zeros(Dim2::new([2, 3]));
+        assert_close(
+            &pinv(&zeros).unwrap(),
+            &Array2::zeros(Dim2::new([3, 2])),
+            0.0,
+        );
+    }
+
+    #[test]
+    fn test_matrix_rank() {
+        assert_eq!(matrix_rank(&random([6, 4], 0)).unwrap(), 4);
+        assert_eq!(
+            matrix_rank(&Array2::<f32>::zeros(Dim2::new([3, 3]))).unwrap(),
+            0
+        );
+
+        let b = random([8, 3], 4);
+        let product = b.matmul(&transpose(&b));
+        assert_eq!(matrix_rank(&product).unwrap(), 3);
+
+        let stack = Array3::from_shape_fn(Dim3::new([2, 3, 3]), |i| {
+            if i[0] == 0 {
+                f64::from(u32::try_from(i[1] + i[2]).unwrap())
+            } else {
+                f64::from(u32::from(i[1] == i[2]))
+            }
+        });
+        let ranks = matrix_rank_batched(&stack).unwrap();
+        assert_eq!([ranks[[0]], ranks[[1]]], [2, 3]);
+    }
+
+    #[test]
+    fn test_norm() {
+        let a = Array2::from_vec(
+            Dim2::new([2, 3]),
+            vec![1.0, -2.0, 3.0, -4.0, 5.0, -6.0],
+        )
+        .unwrap();
+        assert_eq!(norm(&a, NormOrd::One).unwrap(), 9.0);
+        assert_eq!(norm(&a, NormOrd::NegOne).unwrap(), 5.0);
+        assert_eq!(norm(&a, NormOrd::Inf).unwrap(), 15.0);
+        assert_eq!(norm(&a, NormOrd::NegInf).unwrap(), 6.0);
+        assert!(
+            (norm(&a, NormOrd::Fro).unwrap() - 91.0_f64.sqrt()).abs() < 1e-12
+        );
+
+        let (_, s, _) = svd(&a).unwrap();
+        assert!((norm(&a, NormOrd::Two).unwrap() - s[[0]]).abs() < 1e-12);
+        assert!((norm(&a, NormOrd::NegTwo).unwrap() - s[[1]]).abs() < 1e-12);
+        assert!(
+            (norm(&a, NormOrd::Nuc).unwrap() - s[[0]] - s[[1]]).abs() < 1e-12
+        );
+
+        // The Frobenius norm is also the root of the sum of squared singular
+        // values
+        let fro = s[[0]].hypot(s[[1]]);
+        assert!((norm(&a, NormOrd::Fro).unwrap() - fro).abs() < 1e-12);
+
+        let empty = Array2::<f64>::zeros(Dim2::new([0, 3]));
+        assert_eq!(norm(&empty, NormOrd::Two).unwrap(), 0.0);
+        assert_eq!(norm(&empty, NormOrd::Inf).unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_batched() {
+        let a = ArrayDyn::from_shape_fn(
+            DimDyn::from_slice(&[2, 3, 5, 4]).unwrap(),
+            |i| random([5, 4], i[0] * 3 + i[1])[[i[2], i[3]]],
+        );
+        let (u, s, vt) = svd_batched(&a).unwrap();
+        assert_eq!(u.axes.shape.as_slice(), &[2, 3, 5, 4]);
+        assert_eq!(s.axes.shape.as_slice(), &[2, 3, 4]);
+        assert_eq!(vt.axes.shape.as_slice(), &[2, 3, 4, 4]);
+
+        let pinv = pinv_batched(&a).unwrap();
+        assert_eq!(pinv.axes.shape.as_slice(), &[2, 3, 4, 5]);
+
+        let norms = norm_batched(&a, NormOrd::Two).unwrap();
+        assert_eq!(norms.axes.shape.as_slice(), &[2, 3]);
+        for b in 0..2 {
+            for c in 0..3 {
+                assert_eq!(norms[[b, c].as_slice()], s[[b, c, 0].as_slice()]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_no_convergence() {
+        let mut a = random([3, 2], 0);
+        a[[1, 1]] = f64::NAN;
+        assert_eq!(svd(&a).err(), Some(LinalgError::NoConvergence));
+        assert_eq!(
+            norm(&a, NormOrd::Two).err(),
+            Some(LinalgError::NoConvergence)
+        );
+        assert!(norm(&a, NormOrd::One).unwrap().is_nan());
+    }
+}
@@ -1,3 +1,5 @@
+//! Integer types used for lengths, indices and numbers of axes.
+
 /// Signed axis dimension type
 pub type IDim = isize;
 